@@ -0,0 +1,1267 @@
+//! `savings-cli`: operate savings goals and their MXE computations.
+//!
+//! Every command prints a `key: value` summary, or a single JSON object per
+//! command with `--json` for scripting.
+
+mod output;
+mod rpc;
+
+use std::path::PathBuf;
+use std::time::Duration;
+
+use anyhow::{anyhow, bail, Context as _, Result};
+use clap::{Args, Parser, Subcommand};
+use savings_client::accounts::{
+    decode_audit, decode_computation_record, decode_contribution, decode_goal, decode_group_stats,
+    decode_member_progress, decode_period_contribution, decode_recipient_proposal,
+    decode_transfer_request, Contribution, Milestone, Schedule, TransferPolicy, YieldAdapter,
+};
+use savings_client::cipher::{encrypt_u64, random_nonce, ClientKeypair};
+use savings_client::events::SavingsEvent;
+use savings_client::mxe::{Circuit, OffChainSource, QueueParams};
+use savings_client::{goal, mxe, pda, Pubkey};
+use serde_json::{json, Value};
+use sha2::{Digest, Sha256};
+use solana_sdk::signature::{read_keypair_file, Signature};
+
+use crate::rpc::Context;
+
+#[derive(Parser)]
+#[command(
+    name = "savings-cli",
+    about = "Operate savings goals and MXE computations"
+)]
+struct Cli {
+    /// RPC endpoint
+    #[arg(
+        long,
+        env = "SAVINGS_RPC_URL",
+        default_value = "http://127.0.0.1:8899",
+        global = true
+    )]
+    url: String,
+    /// Fee payer / signer keypair
+    #[arg(long, env = "SAVINGS_KEYPAIR", global = true)]
+    keypair: Option<PathBuf>,
+    /// Print one JSON object per command
+    #[arg(long, global = true)]
+    json: bool,
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Create, join, contribute to and finalize goals
+    #[command(subcommand)]
+    Goal(GoalCommand),
+    /// Request and approve payouts from a finalized goal
+    #[command(subcommand)]
+    Transfer(TransferCommand),
+    /// Stake a goal's idle funds and claim the yield they earned
+    #[command(subcommand)]
+    Yield(YieldCommand),
+    /// Configure the MXE and queue / await computations
+    #[command(subcommand)]
+    Mxe(MxeCommand),
+}
+
+#[derive(Subcommand)]
+enum GoalCommand {
+    Create {
+        #[arg(long)]
+        goal_id: u64,
+        #[arg(long)]
+        name: String,
+        #[arg(long)]
+        target: u64,
+        /// Unix timestamp after which the goal can be finalized
+        #[arg(long)]
+        deadline: Option<i64>,
+        #[command(flatten)]
+        schedule: ScheduleArgs,
+        #[command(flatten)]
+        transfer_policy: TransferPolicyArgs,
+        /// Hex-encoded x25519 key of an auditor who may read every
+        /// contribution, see `mxe auditor-key`
+        #[arg(long)]
+        auditor: Option<String>,
+        /// Staged payout as `PERCENT[:RECIPIENT]`, repeated in increasing
+        /// order (up to 4)
+        #[arg(long)]
+        milestone: Vec<String>,
+        /// Restrict transfers to these recipients, repeated (up to 8);
+        /// members can allow more later
+        #[arg(long)]
+        allow_recipient: Vec<Pubkey>,
+    },
+    Invite {
+        #[arg(long)]
+        goal: Pubkey,
+        #[arg(long)]
+        member: Pubkey,
+    },
+    /// Propose adding a recipient to the goal's allow-list; it is allowed
+    /// once a majority of the members approved, the proposer included
+    ProposeRecipient {
+        #[arg(long)]
+        goal: Pubkey,
+        #[arg(long)]
+        recipient: Pubkey,
+    },
+    ApproveRecipient {
+        #[arg(long)]
+        goal: Pubkey,
+        #[arg(long)]
+        recipient: Pubkey,
+    },
+    /// Show who approved a proposed recipient
+    ShowRecipient {
+        #[arg(long)]
+        goal: Pubkey,
+        #[arg(long)]
+        recipient: Pubkey,
+    },
+    /// Encrypt `amount` locally to the MXE and store it as a contribution
+    Contribute {
+        #[arg(long)]
+        goal: Pubkey,
+        #[arg(long)]
+        amount: u64,
+        #[command(flatten)]
+        mxe_key: MxeKeyArgs,
+    },
+    /// Let the goal owner reveal your contribution with `mxe reveal-to-owner`
+    OwnerReveal {
+        #[arg(long)]
+        goal: Pubkey,
+        /// Withdraw a previous opt-in instead
+        #[arg(long)]
+        revoke: bool,
+    },
+    /// Pay `amount` lamports into the goal's vault, also after finalization;
+    /// vault checks compare the contributions with what was deposited this way
+    Deposit {
+        #[arg(long)]
+        goal: Pubkey,
+        #[arg(long)]
+        amount: u64,
+    },
+    /// Encrypt `amount` locally to the MXE and contribute it for the current
+    /// period of the goal's schedule
+    ContributePeriod {
+        #[arg(long)]
+        goal: Pubkey,
+        #[arg(long)]
+        period: u32,
+        #[arg(long)]
+        amount: u64,
+        #[command(flatten)]
+        mxe_key: MxeKeyArgs,
+    },
+    /// Crank: mark members who did not contribute in an ended period as missed
+    ClosePeriod {
+        #[arg(long)]
+        goal: Pubkey,
+        #[arg(long)]
+        period: u32,
+        /// Members to close the period for (defaults to every member without
+        /// a record for it)
+        #[arg(long)]
+        member: Vec<Pubkey>,
+    },
+    ShowPeriod {
+        #[arg(long)]
+        goal: Pubkey,
+        #[arg(long)]
+        member: Pubkey,
+        #[arg(long)]
+        period: u32,
+    },
+    Finalize {
+        #[arg(long)]
+        goal: Pubkey,
+    },
+    Show {
+        #[arg(long)]
+        goal: Pubkey,
+    },
+    ShowContribution {
+        #[arg(long)]
+        goal: Pubkey,
+        #[arg(long)]
+        contributor: Pubkey,
+    },
+}
+
+#[derive(Subcommand)]
+enum TransferCommand {
+    Request {
+        #[arg(long)]
+        goal: Pubkey,
+        #[arg(long)]
+        recipient: Pubkey,
+        #[arg(long)]
+        amount: u64,
+    },
+    Approve {
+        #[arg(long)]
+        goal: Pubkey,
+    },
+    Show {
+        #[arg(long)]
+        goal: Pubkey,
+        /// Show the request for this milestone's payout instead
+        #[arg(long)]
+        milestone: Option<u8>,
+    },
+    /// Request the payout unlocked by a reached milestone, before the goal
+    /// is finalized
+    RequestMilestone {
+        #[arg(long)]
+        goal: Pubkey,
+        /// Milestone index, from 0
+        #[arg(long)]
+        milestone: u8,
+        /// Defaults to the milestone's recipient
+        #[arg(long)]
+        recipient: Option<Pubkey>,
+        #[arg(long)]
+        amount: u64,
+    },
+    ApproveMilestone {
+        #[arg(long)]
+        goal: Pubkey,
+        #[arg(long)]
+        milestone: u8,
+    },
+    /// Object to a pending transfer while its timelock runs; the owner has
+    /// to request it again. Goals without a timelock can't veto transfers
+    Veto {
+        #[arg(long)]
+        goal: Pubkey,
+        /// Veto this milestone's payout instead of the final transfer
+        #[arg(long)]
+        milestone: Option<u8>,
+    },
+}
+
+#[derive(Subcommand)]
+enum YieldCommand {
+    /// Choose where the goal's idle funds are staked, while nothing is
+    /// deposited
+    SetAdapter {
+        #[arg(long)]
+        goal: Pubkey,
+        /// Delegate native stake to this vote account
+        #[arg(long, required_unless_present = "none")]
+        vote_account: Option<Pubkey>,
+        /// Remove the adapter
+        #[arg(long, conflicts_with = "vote_account")]
+        none: bool,
+    },
+    /// Stake everything in the vault above its rent
+    Deposit {
+        #[arg(long)]
+        goal: Pubkey,
+    },
+    /// Start unstaking; the owner at any time, anyone once the goal is
+    /// finalized
+    Unstake {
+        #[arg(long)]
+        goal: Pubkey,
+    },
+    /// Move unstaked funds and their yield back into the vault
+    Withdraw {
+        #[arg(long)]
+        goal: Pubkey,
+    },
+    /// Claim your share of the yield, once `mxe distribute-yield` has run
+    Claim {
+        #[arg(long)]
+        goal: Pubkey,
+    },
+}
+
+#[derive(Subcommand)]
+enum MxeCommand {
+    /// Create the program config with the signer, who must be the program's
+    /// upgrade authority, as admin
+    InitConfig {
+        /// Finalize authority for comp defs (defaults to the signer)
+        #[arg(long)]
+        mxe_authority: Option<Pubkey>,
+    },
+    /// Initialize every comp def that does not exist yet
+    InitCompDefs {
+        /// Reference circuits hosted under this URL instead of uploading them
+        #[arg(long)]
+        circuit_base_url: Option<String>,
+        /// Directory with the compiled `.arcis` files, used to hash them
+        #[arg(long, default_value = "build")]
+        build_dir: PathBuf,
+    },
+    /// Pin the active circuit versions for a goal
+    Pin {
+        #[arg(long)]
+        goal: Pubkey,
+    },
+    /// Queue add_two_contributions for two (locally encrypted) amounts
+    Aggregate {
+        #[arg(long)]
+        goal: Pubkey,
+        #[arg(long)]
+        amount_a: u64,
+        #[arg(long)]
+        amount_b: u64,
+        #[command(flatten)]
+        mxe_key: MxeKeyArgs,
+        #[command(flatten)]
+        queue: QueueArgs,
+    },
+    /// Queue check_goal_reached for a (locally encrypted) total
+    CheckGoal {
+        #[arg(long)]
+        goal: Pubkey,
+        #[arg(long)]
+        total: u64,
+        #[arg(long)]
+        target: u64,
+        #[command(flatten)]
+        mxe_key: MxeKeyArgs,
+        #[command(flatten)]
+        queue: QueueArgs,
+    },
+    /// Queue check_period_contribution for a member's contribution in one
+    /// period; the callback marks it met or short
+    CheckPeriod {
+        #[arg(long)]
+        goal: Pubkey,
+        #[arg(long)]
+        member: Pubkey,
+        #[arg(long)]
+        period: u32,
+        #[command(flatten)]
+        queue: QueueArgs,
+    },
+    /// Queue member_progress: privately compare your own contribution with
+    /// your personal commitment. Only you can decrypt the result.
+    Progress {
+        #[arg(long)]
+        goal: Pubkey,
+        #[arg(long)]
+        commitment: u64,
+        #[command(flatten)]
+        mxe_key: MxeKeyArgs,
+        #[command(flatten)]
+        queue: QueueArgs,
+    },
+    /// Decrypt your latest progress result for a goal
+    ShowProgress {
+        #[arg(long)]
+        goal: Pubkey,
+        #[command(flatten)]
+        mxe_key: MxeKeyArgs,
+    },
+    /// Queue group_stats_10 over the goal's stored contributions; only the
+    /// average, minimum, maximum and number of active contributors are revealed
+    Stats {
+        #[arg(long)]
+        goal: Pubkey,
+        #[command(flatten)]
+        queue: QueueArgs,
+    },
+    /// Show the latest group statistics of a goal
+    ShowStats {
+        #[arg(long)]
+        goal: Pubkey,
+    },
+    /// Queue reveal_own_contributions_5: each contribution is re-encrypted
+    /// to its own contributor; prints your own amount
+    RevealOwn {
+        #[arg(long)]
+        goal: Pubkey,
+        /// Contributors to include, up to 5 (defaults to every member with a
+        /// stored contribution)
+        #[arg(long)]
+        contributor: Vec<Pubkey>,
+        #[command(flatten)]
+        mxe_key: MxeKeyArgs,
+        #[command(flatten)]
+        queue: QueueArgs,
+    },
+    /// Goal owner: reveal opted-in contributions to yourself
+    RevealToOwner {
+        #[arg(long)]
+        goal: Pubkey,
+        /// Contributors to include, up to 10 (defaults to every member who
+        /// opted in)
+        #[arg(long)]
+        contributor: Vec<Pubkey>,
+        #[command(flatten)]
+        mxe_key: MxeKeyArgs,
+        #[command(flatten)]
+        queue: QueueArgs,
+    },
+    /// Goal owner: queue audit_contributions_10, re-encrypting contributions
+    /// and their total to the goal's auditor
+    Audit {
+        #[arg(long)]
+        goal: Pubkey,
+        /// Contributors to include, up to 10 (defaults to every member with a
+        /// stored contribution)
+        #[arg(long)]
+        contributor: Vec<Pubkey>,
+        #[command(flatten)]
+        queue: QueueArgs,
+    },
+    /// Show the latest audit of a goal, decrypted if this wallet is its auditor
+    ShowAudit {
+        #[arg(long)]
+        goal: Pubkey,
+        #[command(flatten)]
+        mxe_key: MxeKeyArgs,
+    },
+    /// Print this wallet's x25519 key, to be passed to `goal create --auditor`
+    AuditorKey,
+    /// Queue add_and_check_goal_10 over every member's contribution: only
+    /// whether the total reaches the target is revealed, and if it does the
+    /// goal is marked reached
+    CheckTarget {
+        #[arg(long)]
+        goal: Pubkey,
+        #[command(flatten)]
+        queue: QueueArgs,
+    },
+    /// Finalize a goal and queue reveal_final_total_10 over every member's
+    /// contribution; the total is revealed only if it reached the target
+    Finalize {
+        #[arg(long)]
+        goal: Pubkey,
+        #[command(flatten)]
+        queue: QueueArgs,
+    },
+    /// Queue check_milestones_10: reveal how many of the goal's milestones
+    /// the members' contributions have crossed, unlocking their payouts
+    CheckMilestones {
+        #[arg(long)]
+        goal: Pubkey,
+        #[command(flatten)]
+        queue: QueueArgs,
+    },
+    /// Queue check_vault_balance_10: compare the vault balance with every
+    /// member's contribution, revealing only whether it matches, holds more
+    /// or holds less
+    CheckVault {
+        #[arg(long)]
+        goal: Pubkey,
+        #[command(flatten)]
+        queue: QueueArgs,
+    },
+    /// Queue distribute_yield_10: split a finalized goal's yield over its
+    /// members in proportion to their contributions. The shares are public.
+    /// Needs a `check-vault` since finalization that found no shortfall.
+    DistributeYield {
+        #[arg(long)]
+        goal: Pubkey,
+        #[command(flatten)]
+        queue: QueueArgs,
+    },
+    /// Re-queue a failed computation with the inputs it was queued with
+    Retry {
+        /// Offset of the failed computation
+        #[arg(long)]
+        offset: u64,
+        #[command(flatten)]
+        queue: QueueArgs,
+    },
+    /// Wait for a queued computation's callback
+    Await {
+        #[arg(long)]
+        offset: u64,
+        #[arg(long, default_value_t = 180)]
+        timeout_secs: u64,
+    },
+    /// Show the record of a queued computation
+    ShowComputation {
+        #[arg(long)]
+        offset: u64,
+    },
+    /// Save the Arcium accounts the MXE program reads as test fixtures
+    DumpAccounts {
+        #[arg(long, env = "SAVINGS_CLUSTER_OFFSET")]
+        cluster_offset: u32,
+        #[arg(long)]
+        out: PathBuf,
+    },
+}
+
+#[derive(Args)]
+struct MxeKeyArgs {
+    /// Hex-encoded x25519 public key of the MXE
+    #[arg(long, env = "SAVINGS_MXE_PUBKEY")]
+    mxe_pubkey: String,
+}
+
+/// Recurring contribution schedule; all three are given together or not at all
+#[derive(Args)]
+struct ScheduleArgs {
+    /// Period length in seconds
+    #[arg(long, requires_all = ["expected_amount", "start_time"])]
+    interval: Option<i64>,
+    /// Amount each member is expected to contribute per period
+    #[arg(long, requires = "interval")]
+    expected_amount: Option<u64>,
+    /// Unix timestamp at which period 0 starts
+    #[arg(long, requires = "interval")]
+    start_time: Option<i64>,
+}
+
+/// Limits on payouts from the goal's vault
+#[derive(Args)]
+struct TransferPolicyArgs {
+    /// Most a single transfer may move
+    #[arg(long)]
+    max_per_transfer: Option<u64>,
+    /// Most that may be paid out per spending period
+    #[arg(long, requires = "spending_period")]
+    max_per_period: Option<u64>,
+    /// Spending period length in seconds
+    #[arg(long, requires = "max_per_period")]
+    spending_period: Option<i64>,
+    /// Seconds between a transfer request and its approval, during which
+    /// members can veto it (no timelock, no veto)
+    #[arg(long)]
+    timelock: Option<i64>,
+}
+
+#[derive(Args)]
+struct QueueArgs {
+    /// Arcium cluster the MXE is attached to
+    #[arg(long, env = "SAVINGS_CLUSTER_OFFSET")]
+    cluster_offset: u32,
+    /// Return right after queueing instead of awaiting the callback
+    #[arg(long)]
+    no_wait: bool,
+    #[arg(long, default_value_t = 180)]
+    timeout_secs: u64,
+}
+
+fn main() -> Result<()> {
+    let cli = Cli::parse();
+    let keypair_path = match cli.keypair {
+        Some(path) => path,
+        None => dirs_home()?.join(".config/solana/id.json"),
+    };
+    let payer = read_keypair_file(&keypair_path)
+        .map_err(|e| anyhow!("failed to read keypair {}: {e}", keypair_path.display()))?;
+    let ctx = Context::new(&cli.url, payer);
+
+    let value = match cli.command {
+        Command::Goal(cmd) => run_goal(&ctx, cmd)?,
+        Command::Transfer(cmd) => run_transfer(&ctx, cmd)?,
+        Command::Yield(cmd) => run_yield(&ctx, cmd)?,
+        Command::Mxe(cmd) => run_mxe(&ctx, cmd)?,
+    };
+    output::print(&value, cli.json);
+    Ok(())
+}
+
+fn dirs_home() -> Result<PathBuf> {
+    std::env::var_os("HOME")
+        .map(PathBuf::from)
+        .ok_or_else(|| anyhow!("HOME is not set; pass --keypair"))
+}
+
+fn run_goal(ctx: &Context, cmd: GoalCommand) -> Result<Value> {
+    let owner = ctx.payer();
+    Ok(match cmd {
+        GoalCommand::Create {
+            goal_id,
+            name,
+            target,
+            deadline,
+            schedule,
+            transfer_policy,
+            auditor,
+            milestone,
+            allow_recipient,
+        } => {
+            let auditor = auditor
+                .map(|key| x25519_key(&key, "--auditor"))
+                .transpose()?;
+            let milestones = milestone
+                .iter()
+                .map(|arg| parse_milestone(arg))
+                .collect::<Result<_>>()?;
+            let ix = goal::create_goal(
+                &owner,
+                goal_id,
+                name,
+                target,
+                deadline,
+                schedule.parse()?,
+                auditor,
+                milestones,
+                transfer_policy.parse(),
+                allow_recipient,
+            );
+            let signature = ctx.send(&[ix])?;
+            json!({ "signature": signature.to_string(), "goal": pda::goal(&owner, goal_id).to_string() })
+        }
+        GoalCommand::Invite { goal, member } => {
+            let signature = ctx.send(&[goal::invite_member(&owner, &goal, &member)])?;
+            json!({ "signature": signature.to_string() })
+        }
+        GoalCommand::ProposeRecipient { goal, recipient } => {
+            let signature = ctx.send(&[goal::propose_recipient(&owner, &goal, recipient)])?;
+            json!({ "signature": signature.to_string() })
+        }
+        GoalCommand::ApproveRecipient { goal, recipient } => {
+            let signature = ctx.send(&[goal::approve_recipient(&owner, &goal, recipient)])?;
+            json!({ "signature": signature.to_string() })
+        }
+        GoalCommand::ShowRecipient { goal, recipient } => {
+            let address = pda::recipient_proposal(&goal, &recipient);
+            let proposal = decode_recipient_proposal(&ctx.account_data(&address)?)?;
+            let allowed = decode_goal(&ctx.account_data(&goal)?)?
+                .allowed_recipients
+                .contains(&recipient);
+            json!({
+                "recipient": recipient.to_string(),
+                "approvals": proposal.approvals.iter().map(|m| m.to_string()).collect::<Vec<_>>(),
+                "allowed": allowed,
+            })
+        }
+        GoalCommand::Contribute {
+            goal,
+            amount,
+            mxe_key,
+        } => {
+            let encrypted = encrypt_u64(&member_keypair(ctx), &mxe_key.parse()?, amount);
+            let signature = ctx.send(&[goal::add_contribution(&owner, &goal, &encrypted)])?;
+            json!({
+                "signature": signature.to_string(),
+                "contribution": pda::contribution(&goal, &owner).to_string(),
+                "encryption_pubkey": hex::encode(encrypted.pub_key),
+            })
+        }
+        GoalCommand::OwnerReveal { goal, revoke } => {
+            let signature = ctx.send(&[goal::set_owner_reveal(&owner, &goal, !revoke)])?;
+            json!({ "signature": signature.to_string(), "owner_reveal": !revoke })
+        }
+        GoalCommand::Deposit { goal, amount } => {
+            let signature = ctx.send(&[goal::deposit(&owner, &goal, amount)])?;
+            json!({ "signature": signature.to_string(), "vault": pda::vault(&goal).to_string() })
+        }
+        GoalCommand::ContributePeriod {
+            goal,
+            period,
+            amount,
+            mxe_key,
+        } => {
+            let encrypted = encrypt_u64(&member_keypair(ctx), &mxe_key.parse()?, amount);
+            let signature = ctx.send(&[goal::contribute_for_period(
+                &owner, &goal, period, &encrypted,
+            )])?;
+            json!({
+                "signature": signature.to_string(),
+                "period_contribution": pda::period_contribution(&goal, &owner, period).to_string(),
+            })
+        }
+        GoalCommand::ClosePeriod {
+            goal,
+            period,
+            member,
+        } => {
+            let members = if member.is_empty() {
+                decode_goal(&ctx.account_data(&goal)?)?.members
+            } else {
+                member
+            };
+            let mut closed = serde_json::Map::new();
+            for member in members {
+                if ctx.exists(&pda::period_contribution(&goal, &member, period))? {
+                    continue;
+                }
+                let signature = ctx.send(&[goal::close_period(&owner, &goal, &member, period)])?;
+                closed.insert(member.to_string(), json!(signature.to_string()));
+            }
+            Value::Object(closed)
+        }
+        GoalCommand::ShowPeriod {
+            goal,
+            member,
+            period,
+        } => {
+            let address = pda::period_contribution(&goal, &member, period);
+            output::period_contribution(&decode_period_contribution(&ctx.account_data(&address)?)?)
+        }
+        GoalCommand::Finalize { goal } => {
+            let signature = ctx.send(&[goal::finalize_and_reveal(&owner, &goal)])?;
+            json!({ "signature": signature.to_string() })
+        }
+        GoalCommand::Show { goal } => output::goal(&decode_goal(&ctx.account_data(&goal)?)?),
+        GoalCommand::ShowContribution { goal, contributor } => {
+            let address = pda::contribution(&goal, &contributor);
+            output::contribution(&decode_contribution(&ctx.account_data(&address)?)?)
+        }
+    })
+}
+
+fn run_transfer(ctx: &Context, cmd: TransferCommand) -> Result<Value> {
+    let owner = ctx.payer();
+    Ok(match cmd {
+        TransferCommand::Request {
+            goal,
+            recipient,
+            amount,
+        } => {
+            let signature =
+                ctx.send(&[goal::request_transfer(&owner, &goal, recipient, amount)])?;
+            json!({ "signature": signature.to_string(), "transfer_request": pda::transfer(&goal).to_string() })
+        }
+        TransferCommand::Approve { goal } => {
+            let request = decode_transfer_request(&ctx.account_data(&pda::transfer(&goal))?)?;
+            let signature =
+                ctx.send(&[goal::approve_transfer(&owner, &goal, &request.recipient)])?;
+            json!({
+                "signature": signature.to_string(),
+                "recipient": request.recipient.to_string(),
+                "amount": request.amount,
+            })
+        }
+        TransferCommand::Show { goal, milestone } => {
+            let address = match milestone {
+                Some(milestone) => pda::milestone_transfer(&goal, milestone),
+                None => pda::transfer(&goal),
+            };
+            output::transfer_request(&decode_transfer_request(&ctx.account_data(&address)?)?)
+        }
+        TransferCommand::RequestMilestone {
+            goal,
+            milestone,
+            recipient,
+            amount,
+        } => {
+            let recipient = match recipient {
+                Some(recipient) => recipient,
+                None => decode_goal(&ctx.account_data(&goal)?)?
+                    .milestones
+                    .get(usize::from(milestone))
+                    .and_then(|m| m.recipient)
+                    .ok_or_else(|| {
+                        anyhow!("milestone {milestone} has no recipient; pass --recipient")
+                    })?,
+            };
+            let signature = ctx.send(&[goal::request_milestone_transfer(
+                &owner, &goal, milestone, recipient, amount,
+            )])?;
+            json!({
+                "signature": signature.to_string(),
+                "transfer_request": pda::milestone_transfer(&goal, milestone).to_string(),
+            })
+        }
+        TransferCommand::ApproveMilestone { goal, milestone } => {
+            let request = decode_transfer_request(
+                &ctx.account_data(&pda::milestone_transfer(&goal, milestone))?,
+            )?;
+            let signature = ctx.send(&[goal::approve_milestone_transfer(
+                &owner,
+                &goal,
+                milestone,
+                &request.recipient,
+            )])?;
+            json!({
+                "signature": signature.to_string(),
+                "recipient": request.recipient.to_string(),
+                "amount": request.amount,
+            })
+        }
+        TransferCommand::Veto { goal, milestone } => {
+            let goal_owner = decode_goal(&ctx.account_data(&goal)?)?.owner;
+            let signature =
+                ctx.send(&[goal::veto_transfer(&owner, &goal, &goal_owner, milestone)])?;
+            json!({ "signature": signature.to_string() })
+        }
+    })
+}
+
+fn run_yield(ctx: &Context, cmd: YieldCommand) -> Result<Value> {
+    let signer = ctx.payer();
+    Ok(match cmd {
+        YieldCommand::SetAdapter {
+            goal,
+            vote_account,
+            none: _,
+        } => {
+            let adapter =
+                vote_account.map(|vote_account| YieldAdapter::NativeStake { vote_account });
+            let signature = ctx.send(&[goal::set_yield_adapter(&signer, &goal, adapter)])?;
+            json!({ "signature": signature.to_string() })
+        }
+        YieldCommand::Deposit { goal } => {
+            let adapter = decode_goal(&ctx.account_data(&goal)?)?.yield_adapter;
+            let Some(YieldAdapter::NativeStake { vote_account }) = adapter else {
+                bail!("goal has no yield adapter; run `yield set-adapter` first");
+            };
+            let signature = ctx.send(&[goal::deposit_to_yield(&signer, &goal, &vote_account)])?;
+            json!({ "signature": signature.to_string(), "stake_account": pda::stake(&goal).to_string() })
+        }
+        YieldCommand::Unstake { goal } => {
+            let signature = ctx.send(&[goal::begin_yield_withdrawal(&signer, &goal)])?;
+            json!({ "signature": signature.to_string() })
+        }
+        YieldCommand::Withdraw { goal } => {
+            let goal_owner = decode_goal(&ctx.account_data(&goal)?)?.owner;
+            let signature = ctx.send(&[goal::withdraw_from_yield(&goal, &goal_owner)])?;
+            let earned = decode_goal(&ctx.account_data(&goal)?)?.yield_earned;
+            json!({ "signature": signature.to_string(), "yield_earned": earned })
+        }
+        YieldCommand::Claim { goal } => {
+            let signature = ctx.send(&[goal::claim_yield(&signer, &goal)])?;
+            json!({ "signature": signature.to_string() })
+        }
+    })
+}
+
+fn run_mxe(ctx: &Context, cmd: MxeCommand) -> Result<Value> {
+    let payer = ctx.payer();
+    Ok(match cmd {
+        MxeCommand::InitConfig { mxe_authority } => {
+            let authority = mxe_authority.unwrap_or(payer);
+            let signature = ctx.send(&[mxe::initialize_config(&payer, authority)])?;
+            json!({ "signature": signature.to_string(), "config": pda::config().to_string() })
+        }
+        MxeCommand::InitCompDefs {
+            circuit_base_url,
+            build_dir,
+        } => {
+            let mut initialized = serde_json::Map::new();
+            for compiled in Circuit::compiled_versions() {
+                let circuit = compiled.circuit;
+                let ix_name = circuit.ix_name(compiled.version);
+                if ctx.exists(&pda::comp_def(circuit, compiled.version))? {
+                    initialized.insert(ix_name, json!("already initialized"));
+                    continue;
+                }
+                let source = match &circuit_base_url {
+                    Some(base) => Some(off_chain_source(base, &build_dir, &ix_name)?),
+                    None => None,
+                };
+                let signature = ctx.send(&[mxe::init_comp_def(&payer, circuit, source)])?;
+                initialized.insert(ix_name, json!(signature.to_string()));
+            }
+            Value::Object(initialized)
+        }
+        MxeCommand::Pin { goal } => {
+            let signature = ctx.send(&[mxe::pin_goal_circuits(&payer, &goal)])?;
+            json!({ "signature": signature.to_string(), "goal_circuits": pda::goal_circuits(&goal).to_string() })
+        }
+        MxeCommand::Aggregate {
+            goal,
+            amount_a,
+            amount_b,
+            mxe_key,
+            queue,
+        } => {
+            let params = queue.params(payer, goal);
+            let keypair = member_keypair(ctx);
+            let cipher = keypair.shared_cipher(&mxe_key.parse()?);
+            let nonce = random_nonce();
+            let ix = mxe::add_two_contributions(
+                &params,
+                cipher.encrypt(&[amount_a], nonce)[0],
+                cipher.encrypt(&[amount_b], nonce)[0],
+                keypair.public_key(),
+                nonce,
+            );
+            queue_and_await(ctx, &params, ix, &queue)?
+        }
+        MxeCommand::CheckGoal {
+            goal,
+            total,
+            target,
+            mxe_key,
+            queue,
+        } => {
+            let params = queue.params(payer, goal);
+            let encrypted = encrypt_u64(&member_keypair(ctx), &mxe_key.parse()?, total);
+            let ix = mxe::check_goal_reached(
+                &params,
+                encrypted.ciphertext,
+                target,
+                encrypted.pub_key,
+                encrypted.nonce,
+            );
+            queue_and_await(ctx, &params, ix, &queue)?
+        }
+        MxeCommand::CheckPeriod {
+            goal,
+            member,
+            period,
+            queue,
+        } => {
+            let params = queue.params(payer, goal);
+            let ix = mxe::check_period_contribution(&params, &member, period);
+            queue_and_await(ctx, &params, ix, &queue)?
+        }
+        MxeCommand::Progress {
+            goal,
+            commitment,
+            mxe_key,
+            queue,
+        } => {
+            let params = queue.params(payer, goal);
+            let encrypted = encrypt_u64(&member_keypair(ctx), &mxe_key.parse()?, commitment);
+            let ix = mxe::check_member_progress(&params, &encrypted, random_nonce());
+            let mut value = queue_and_await(ctx, &params, ix, &queue)?;
+            if !queue.no_wait {
+                value["progress"] = show_progress(ctx, &goal, &mxe_key.parse()?)?;
+            }
+            value
+        }
+        MxeCommand::ShowProgress { goal, mxe_key } => show_progress(ctx, &goal, &mxe_key.parse()?)?,
+        MxeCommand::Stats { goal, queue } => {
+            let members = decode_goal(&ctx.account_data(&goal)?)?.members;
+            let params = queue.params(payer, goal);
+            let ix = mxe::compute_group_stats(&params, &members);
+            queue_and_await(ctx, &params, ix, &queue)?
+        }
+        MxeCommand::ShowStats { goal } => output::group_stats(&decode_group_stats(
+            &ctx.account_data(&pda::group_stats(&goal))?,
+        )?),
+        MxeCommand::RevealOwn {
+            goal,
+            contributor,
+            mxe_key,
+            queue,
+        } => {
+            let contributors = contributors_or_members(ctx, &goal, contributor, |_| true)?;
+            let params = queue.params(payer, goal);
+            let ix = mxe::reveal_own_contributions(&params, &contributors);
+            let (mut value, events) = queue_and_collect(ctx, &params, ix, &queue)?;
+            let keypair = member_keypair(ctx);
+            let cipher = keypair.shared_cipher(&mxe_key.parse()?);
+            for event in &events {
+                if let SavingsEvent::ContributionRevealed(e) = event {
+                    if e.encryption_key == keypair.public_key() {
+                        value["amount"] = json!(cipher.decrypt(&[e.encrypted_amount], e.nonce)?[0]);
+                    }
+                }
+            }
+            value
+        }
+        MxeCommand::RevealToOwner {
+            goal,
+            contributor,
+            mxe_key,
+            queue,
+        } => {
+            let contributors =
+                contributors_or_members(ctx, &goal, contributor, |c| c.owner_reveal)?;
+            let params = queue.params(payer, goal);
+            let ix = mxe::reveal_to_owner(
+                &params,
+                &contributors,
+                member_keypair(ctx).public_key(),
+                random_nonce(),
+            );
+            let (mut value, events) = queue_and_collect(ctx, &params, ix, &queue)?;
+            let cipher = member_keypair(ctx).shared_cipher(&mxe_key.parse()?);
+            for event in &events {
+                if let SavingsEvent::OwnerReveal(e) = event {
+                    let amounts = cipher.decrypt(&e.encrypted_amounts, e.nonce)?;
+                    let revealed: serde_json::Map<_, _> = contributors
+                        .iter()
+                        .zip(amounts)
+                        .map(|(contributor, amount)| (contributor.to_string(), json!(amount)))
+                        .collect();
+                    value["amounts"] = Value::Object(revealed);
+                }
+            }
+            value
+        }
+        MxeCommand::Audit {
+            goal,
+            contributor,
+            queue,
+        } => {
+            let contributors = contributors_or_members(ctx, &goal, contributor, |_| true)?;
+            let params = queue.params(payer, goal);
+            let ix = mxe::audit_contributions(&params, &contributors, random_nonce());
+            queue_and_await(ctx, &params, ix, &queue)?
+        }
+        MxeCommand::ShowAudit { goal, mxe_key } => {
+            let audit = decode_audit(&ctx.account_data(&pda::audit(&goal))?)?;
+            let mut value = output::audit(&audit);
+            let keypair = member_keypair(ctx);
+            if audit.updated_at.is_some() && audit.encryption_key == keypair.public_key() {
+                let mut ciphertexts = audit.encrypted_amounts.clone();
+                ciphertexts.push(audit.encrypted_total);
+                let mut amounts = keypair
+                    .shared_cipher(&mxe_key.parse()?)
+                    .decrypt(&ciphertexts, audit.nonce)?;
+                let total = amounts.pop();
+                let amounts: serde_json::Map<_, _> = audit
+                    .contributors
+                    .iter()
+                    .zip(amounts)
+                    .map(|(contributor, amount)| (contributor.to_string(), json!(amount)))
+                    .collect();
+                value["amounts"] = Value::Object(amounts);
+                value["total"] = json!(total);
+            }
+            value
+        }
+        MxeCommand::AuditorKey => {
+            json!({ "auditor_key": hex::encode(member_keypair(ctx).public_key()) })
+        }
+        MxeCommand::CheckTarget { goal, queue } => {
+            let members = decode_goal(&ctx.account_data(&goal)?)?.members;
+            let params = queue.params(payer, goal);
+            let ix = mxe::add_and_check_goal(&params, &members);
+            queue_and_await(ctx, &params, ix, &queue)?
+        }
+        MxeCommand::Finalize { goal, queue } => {
+            let members = decode_goal(&ctx.account_data(&goal)?)?.members;
+            let params = queue.params(payer, goal);
+            let ix = mxe::finalize_and_reveal_total(&params, &members);
+            queue_and_await(ctx, &params, ix, &queue)?
+        }
+        MxeCommand::CheckMilestones { goal, queue } => {
+            let members = decode_goal(&ctx.account_data(&goal)?)?.members;
+            let params = queue.params(payer, goal);
+            let ix = mxe::check_milestones(&params, &members);
+            queue_and_await(ctx, &params, ix, &queue)?
+        }
+        MxeCommand::CheckVault { goal, queue } => {
+            let members = decode_goal(&ctx.account_data(&goal)?)?.members;
+            let params = queue.params(payer, goal);
+            let ix = mxe::check_vault_balance(&params, &members);
+            queue_and_await(ctx, &params, ix, &queue)?
+        }
+        MxeCommand::DistributeYield { goal, queue } => {
+            let members = decode_goal(&ctx.account_data(&goal)?)?.members;
+            let params = queue.params(payer, goal);
+            let ix = mxe::distribute_yield(&params, &members);
+            queue_and_await(ctx, &params, ix, &queue)?
+        }
+        MxeCommand::Retry { offset, queue } => {
+            let failed =
+                decode_computation_record(&ctx.account_data(&pda::computation_record(offset))?)?;
+            let params = queue.params(payer, failed.goal);
+            let ix = mxe::retry_computation(&params, offset, failed.kind);
+            queue_and_await(ctx, &params, ix, &queue)?
+        }
+        MxeCommand::Await {
+            offset,
+            timeout_secs,
+        } => await_result(ctx, offset, timeout_secs)?,
+        MxeCommand::ShowComputation { offset } => output::computation_record(
+            &decode_computation_record(&ctx.account_data(&pda::computation_record(offset))?)?,
+        ),
+        MxeCommand::DumpAccounts {
+            cluster_offset,
+            out,
+        } => {
+            std::fs::create_dir_all(&out)?;
+            std::fs::write(
+                out.join("manifest.json"),
+                json!({ "cluster_offset": cluster_offset }).to_string(),
+            )?;
+            let mut written = Vec::new();
+            for address in pda::arcium_accounts(cluster_offset) {
+                let account = ctx.rpc.get_account(&address)?;
+                let path = out.join(format!("{address}.json"));
+                std::fs::write(&path, output::account_json(&address, &account).to_string())?;
+                written.push(path.display().to_string());
+            }
+            json!({ "written": written })
+        }
+    })
+}
+
+fn queue_and_await(
+    ctx: &Context,
+    params: &QueueParams,
+    ix: savings_client::Instruction,
+    queue: &QueueArgs,
+) -> Result<Value> {
+    Ok(queue_and_collect(ctx, params, ix, queue)?.0)
+}
+
+/// Like [`queue_and_await`], also returning the callback's events (none with
+/// `--no-wait`)
+fn queue_and_collect(
+    ctx: &Context,
+    params: &QueueParams,
+    ix: savings_client::Instruction,
+    queue: &QueueArgs,
+) -> Result<(Value, Vec<SavingsEvent>)> {
+    let signature = ctx.send(&[ix])?;
+    let mut value = json!({
+        "signature": signature.to_string(),
+        "computation_offset": params.computation_offset,
+    });
+    let mut events = Vec::new();
+    if !queue.no_wait {
+        let (callback, result) = ctx.await_computation(
+            params.computation_offset,
+            Duration::from_secs(queue.timeout_secs),
+        )?;
+        value["result"] = result_json(&callback, &result);
+        events = result;
+    }
+    Ok((value, events))
+}
+
+fn await_result(ctx: &Context, offset: u64, timeout_secs: u64) -> Result<Value> {
+    let (signature, events) = ctx.await_computation(offset, Duration::from_secs(timeout_secs))?;
+    Ok(result_json(&signature, &events))
+}
+
+fn result_json(callback: &Signature, events: &[SavingsEvent]) -> Value {
+    json!({
+        "callback_signature": callback.to_string(),
+        "events": events.iter().map(output::event).collect::<Vec<_>>(),
+    })
+}
+
+/// `given`, or else every member of `goal` with a stored contribution that
+/// `keep` accepts
+fn contributors_or_members(
+    ctx: &Context,
+    goal: &Pubkey,
+    given: Vec<Pubkey>,
+    keep: impl Fn(&Contribution) -> bool,
+) -> Result<Vec<Pubkey>> {
+    if !given.is_empty() {
+        return Ok(given);
+    }
+    let mut contributors = Vec::new();
+    for member in decode_goal(&ctx.account_data(goal)?)?.members {
+        let address = pda::contribution(goal, &member);
+        if ctx.exists(&address)? && keep(&decode_contribution(&ctx.account_data(&address)?)?) {
+            contributors.push(member);
+        }
+    }
+    Ok(contributors)
+}
+
+/// The signer's `MemberProgress` on `goal`, with the shortfall decrypted
+fn show_progress(ctx: &Context, goal: &Pubkey, mxe_pubkey: &[u8; 32]) -> Result<Value> {
+    let address = pda::member_progress(goal, &ctx.payer());
+    let progress = decode_member_progress(&ctx.account_data(&address)?)?;
+    let mut value = output::member_progress(&progress);
+    if progress.updated_at.is_none() {
+        return Ok(value);
+    }
+    let keypair = member_keypair(ctx);
+    if progress.encryption_key != keypair.public_key() {
+        bail!("progress is encrypted to a key other than this wallet's");
+    }
+    let shortfall = keypair
+        .shared_cipher(mxe_pubkey)
+        .decrypt(&[progress.encrypted_shortfall], progress.nonce)?[0];
+    value["shortfall"] = json!(shortfall);
+    value["on_track"] = json!(shortfall == 0);
+    Ok(value)
+}
+
+/// x25519 key of the signer, derived from their wallet so it can be recreated
+/// later to decrypt values re-encrypted to them
+fn member_keypair(ctx: &Context) -> ClientKeypair {
+    let seed = Sha256::new()
+        .chain_update(b"savings-cli x25519")
+        .chain_update(&ctx.payer.to_bytes()[..32])
+        .finalize();
+    ClientKeypair::from_secret(seed.into())
+}
+
+fn off_chain_source(
+    base_url: &str,
+    build_dir: &std::path::Path,
+    ix_name: &str,
+) -> Result<OffChainSource> {
+    let path = build_dir.join(format!("{ix_name}.arcis"));
+    let circuit =
+        std::fs::read(&path).with_context(|| format!("failed to read {}", path.display()))?;
+    Ok(OffChainSource {
+        url: format!("{}/{ix_name}.arcis", base_url.trim_end_matches('/')),
+        hash: Sha256::digest(&circuit).into(),
+    })
+}
+
+/// Parse the hex-encoded x25519 key given to `flag`
+fn x25519_key(key: &str, flag: &str) -> Result<[u8; 32]> {
+    let bytes = hex::decode(key).with_context(|| format!("{flag} is not hex"))?;
+    bytes
+        .try_into()
+        .map_err(|_| anyhow!("{flag} must be 32 bytes"))
+}
+
+/// `PERCENT[:RECIPIENT]`
+fn parse_milestone(arg: &str) -> Result<Milestone> {
+    let (percent, recipient) = match arg.split_once(':') {
+        Some((percent, recipient)) => (percent, Some(recipient)),
+        None => (arg, None),
+    };
+    Ok(Milestone {
+        percent: percent
+            .parse()
+            .with_context(|| format!("--milestone {arg}: bad percentage"))?,
+        recipient: recipient
+            .map(|recipient| recipient.parse())
+            .transpose()
+            .with_context(|| format!("--milestone {arg}: bad recipient"))?,
+    })
+}
+
+impl MxeKeyArgs {
+    fn parse(&self) -> Result<[u8; 32]> {
+        x25519_key(&self.mxe_pubkey, "--mxe-pubkey")
+    }
+}
+
+impl ScheduleArgs {
+    fn parse(&self) -> Result<Option<Schedule>> {
+        match (self.interval, self.expected_amount, self.start_time) {
+            (Some(interval), Some(expected_amount), Some(start_time)) => Ok(Some(Schedule {
+                interval,
+                expected_amount,
+                start_time,
+            })),
+            (None, None, None) => Ok(None),
+            _ => bail!("--interval, --expected-amount and --start-time go together"),
+        }
+    }
+}
+
+impl TransferPolicyArgs {
+    fn parse(&self) -> Option<TransferPolicy> {
+        if self.max_per_transfer.is_none()
+            && self.max_per_period.is_none()
+            && self.timelock.is_none()
+        {
+            return None;
+        }
+        Some(TransferPolicy {
+            max_per_transfer: self.max_per_transfer,
+            max_per_period: self.max_per_period,
+            period: self.spending_period.unwrap_or(0),
+            timelock: self.timelock.unwrap_or(0),
+        })
+    }
+}
+
+impl QueueArgs {
+    fn params(&self, payer: Pubkey, goal: Pubkey) -> QueueParams {
+        QueueParams {
+            payer,
+            goal,
+            computation_offset: rand::random(),
+            cluster_offset: self.cluster_offset,
+        }
+    }
+}
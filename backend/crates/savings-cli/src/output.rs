@@ -0,0 +1,453 @@
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use savings_client::accounts::{
+    Audit, ComputationRecord, ComputationStatus, Contribution, FailureReason, GoalStatus,
+    GroupStats, MemberProgress, PeriodContribution, PeriodStatus, SavingsGoal, Schedule,
+    TransferPolicy, TransferRequest, YieldAdapter,
+};
+use savings_client::events::{SavingsEvent, VaultBalance};
+use savings_client::Pubkey;
+use serde_json::{json, Value};
+use solana_sdk::account::Account;
+
+pub fn print(value: &Value, as_json: bool) {
+    if as_json {
+        println!("{value}");
+        return;
+    }
+    match value {
+        Value::Object(map) => {
+            for (key, value) in map {
+                match value {
+                    Value::String(s) => println!("{key}: {s}"),
+                    other => println!("{key}: {other}"),
+                }
+            }
+        }
+        other => println!("{other}"),
+    }
+}
+
+pub fn goal_status(status: &GoalStatus) -> &'static str {
+    match status {
+        GoalStatus::Active => "active",
+        GoalStatus::Finalized => "finalized",
+        GoalStatus::Reached => "reached",
+    }
+}
+
+pub fn goal(goal: &SavingsGoal) -> Value {
+    json!({
+        "goal_id": goal.goal_id,
+        "owner": goal.owner.to_string(),
+        "name": goal.name,
+        "target_amount": goal.target_amount,
+        "current_total": goal.current_total,
+        "deadline": goal.deadline,
+        "members": goal.members.iter().map(|m| m.to_string()).collect::<Vec<_>>(),
+        "status": goal_status(&goal.status),
+        "created_at": goal.created_at,
+        "finalized_at": goal.finalized_at,
+        "schedule": goal.schedule.as_ref().map(schedule),
+        "auditor": goal.auditor.map(hex::encode),
+        "revealed_total": goal.revealed_total,
+        "deposited": goal.deposited,
+        "inconsistent": goal.inconsistent,
+        "vault_checked_at": goal.vault_checked_at,
+        "milestones": goal.milestones.iter().map(|m| json!({
+            "percent": m.percent,
+            "recipient": m.recipient.map(|r| r.to_string()),
+        })).collect::<Vec<_>>(),
+        "milestones_reached": goal.milestones_reached,
+        "released": goal.released,
+        "transfer_policy": goal.transfer_policy.as_ref().map(transfer_policy),
+        "period_spent": goal.period_spent,
+        "allowed_recipients": goal
+            .allowed_recipients
+            .iter()
+            .map(|r| r.to_string())
+            .collect::<Vec<_>>(),
+        "yield_adapter": goal.yield_adapter.as_ref().map(yield_adapter),
+        "yield_deposited": goal.yield_deposited,
+        "yield_withdrawing": goal.yield_withdrawing,
+        "yield_earned": goal.yield_earned,
+        "yield_shares": goal.yield_shares,
+    })
+}
+
+pub fn yield_adapter(adapter: &YieldAdapter) -> Value {
+    match adapter {
+        YieldAdapter::NativeStake { vote_account } => json!({
+            "kind": "native_stake",
+            "vote_account": vote_account.to_string(),
+        }),
+    }
+}
+
+pub fn transfer_policy(policy: &TransferPolicy) -> Value {
+    json!({
+        "max_per_transfer": policy.max_per_transfer,
+        "max_per_period": policy.max_per_period,
+        "period": policy.period,
+        "timelock": policy.timelock,
+    })
+}
+
+pub fn schedule(schedule: &Schedule) -> Value {
+    json!({
+        "interval": schedule.interval,
+        "expected_amount": schedule.expected_amount,
+        "start_time": schedule.start_time,
+    })
+}
+
+pub fn period_status(status: &PeriodStatus) -> &'static str {
+    match status {
+        PeriodStatus::Contributed => "contributed",
+        PeriodStatus::Met => "met",
+        PeriodStatus::Short => "short",
+        PeriodStatus::Missed => "missed",
+    }
+}
+
+pub fn period_contribution(record: &PeriodContribution) -> Value {
+    json!({
+        "goal": record.goal.to_string(),
+        "member": record.member.to_string(),
+        "period": record.period,
+        "status": period_status(&record.status),
+        "encrypted_amount": hex::encode(record.encrypted_amount),
+        "encryption_pubkey": hex::encode(record.encryption_pubkey),
+        "nonce": record.nonce.to_string(),
+    })
+}
+
+pub fn contribution(contribution: &Contribution) -> Value {
+    json!({
+        "goal_id": contribution.goal_id,
+        "contributor": contribution.contributor.to_string(),
+        "encrypted_amount": hex::encode(contribution.encrypted_amount),
+        "encryption_pubkey": hex::encode(contribution.encryption_pubkey),
+        "nonce": contribution.nonce.to_string(),
+        "timestamp": contribution.timestamp,
+        "owner_reveal": contribution.owner_reveal,
+    })
+}
+
+pub fn transfer_request(request: &TransferRequest) -> Value {
+    json!({
+        "goal_id": request.goal_id,
+        "recipient": request.recipient.to_string(),
+        "amount": request.amount,
+        "requested_at": request.requested_at,
+        "approved": request.approved,
+    })
+}
+
+pub fn computation_status(status: &ComputationStatus) -> &'static str {
+    match status {
+        ComputationStatus::Queued => "queued",
+        ComputationStatus::Completed => "completed",
+        ComputationStatus::Failed => "failed",
+    }
+}
+
+pub fn failure_reason(reason: &FailureReason) -> &'static str {
+    match reason {
+        FailureReason::Aborted => "aborted",
+    }
+}
+
+pub fn vault_balance(balance: &VaultBalance) -> &'static str {
+    match balance {
+        VaultBalance::Matches => "matches",
+        VaultBalance::Surplus => "surplus",
+        VaultBalance::Shortfall => "shortfall",
+    }
+}
+
+pub fn computation_record(record: &ComputationRecord) -> Value {
+    json!({
+        "computation_offset": record.computation_offset,
+        "goal": record.goal.to_string(),
+        "kind": record.kind.name(),
+        "status": computation_status(&record.status),
+        "requester": record.requester.to_string(),
+        "queued_at": record.queued_at,
+        "completed_at": record.completed_at,
+        "failure": record.failure.as_ref().map(failure_reason),
+        "retried_by": record.retried_by,
+    })
+}
+
+pub fn member_progress(progress: &MemberProgress) -> Value {
+    json!({
+        "goal": progress.goal.to_string(),
+        "member": progress.member.to_string(),
+        "computation_offset": progress.computation_offset,
+        "encryption_key": hex::encode(progress.encryption_key),
+        "nonce": progress.nonce.to_string(),
+        "encrypted_shortfall": hex::encode(progress.encrypted_shortfall),
+        "updated_at": progress.updated_at,
+    })
+}
+
+pub fn group_stats(stats: &GroupStats) -> Value {
+    json!({
+        "goal": stats.goal.to_string(),
+        "computation_offset": stats.computation_offset,
+        "contributors": stats.contributors,
+        "average": stats.average,
+        "min": stats.min,
+        "max": stats.max,
+        "active": stats.active,
+        "updated_at": stats.updated_at,
+    })
+}
+
+pub fn audit(audit: &Audit) -> Value {
+    json!({
+        "goal": audit.goal.to_string(),
+        "computation_offset": audit.computation_offset,
+        "contributors": audit.contributors.iter().map(|c| c.to_string()).collect::<Vec<_>>(),
+        "encryption_key": hex::encode(audit.encryption_key),
+        "nonce": audit.nonce.to_string(),
+        "encrypted_amounts": audit.encrypted_amounts.iter().map(hex::encode).collect::<Vec<_>>(),
+        "encrypted_total": hex::encode(audit.encrypted_total),
+        "updated_at": audit.updated_at,
+    })
+}
+
+pub fn event(event: &SavingsEvent) -> Value {
+    let fields = match event {
+        SavingsEvent::GoalCreated(e) => json!({
+            "goal_id": e.goal_id,
+            "owner": e.owner.to_string(),
+            "name": e.name,
+            "target_amount": e.target_amount,
+            "deadline": e.deadline,
+        }),
+        SavingsEvent::MemberInvited(e) => json!({
+            "goal_id": e.goal_id,
+            "member": e.member.to_string(),
+        }),
+        SavingsEvent::ContributionAdded(e) => json!({
+            "goal_id": e.goal_id,
+            "contributor": e.contributor.to_string(),
+            "timestamp": e.timestamp,
+        }),
+        SavingsEvent::OwnerRevealChanged(e) => json!({
+            "goal_id": e.goal_id,
+            "contributor": e.contributor.to_string(),
+            "allowed": e.allowed,
+        }),
+        SavingsEvent::Deposited(e) => json!({
+            "goal_id": e.goal_id,
+            "member": e.member.to_string(),
+            "amount": e.amount,
+        }),
+        SavingsEvent::PeriodContributionAdded(e) => json!({
+            "goal_id": e.goal_id,
+            "member": e.member.to_string(),
+            "period": e.period,
+            "timestamp": e.timestamp,
+        }),
+        SavingsEvent::ContributionMissed(e) => json!({
+            "goal_id": e.goal_id,
+            "member": e.member.to_string(),
+            "period": e.period,
+            "contributed": e.contributed,
+        }),
+        SavingsEvent::GoalReached(e) => json!({
+            "goal_id": e.goal_id,
+            "reached_at": e.reached_at,
+        }),
+        SavingsEvent::GoalFinalized(e) => json!({
+            "goal_id": e.goal_id,
+            "finalized_at": e.finalized_at,
+            "goal_reached": e.goal_reached,
+        }),
+        SavingsEvent::TotalRevealed(e) => json!({
+            "goal_id": e.goal_id,
+            "total": e.total,
+        }),
+        SavingsEvent::VaultChecked(e) => json!({
+            "goal_id": e.goal_id,
+            "inconsistent": e.inconsistent,
+        }),
+        SavingsEvent::MilestoneReached(e) => json!({
+            "goal_id": e.goal_id,
+            "milestone": e.milestone,
+            "percent": e.percent,
+        }),
+        SavingsEvent::MilestoneTransferRequested(e) => json!({
+            "goal_id": e.goal_id,
+            "milestone": e.milestone,
+            "recipient": e.recipient.to_string(),
+            "amount": e.amount,
+        }),
+        SavingsEvent::MilestoneReleased(e) => json!({
+            "goal_id": e.goal_id,
+            "milestone": e.milestone,
+            "recipient": e.recipient.to_string(),
+            "amount": e.amount,
+        }),
+        SavingsEvent::RecipientApproved(e) => json!({
+            "goal_id": e.goal_id,
+            "recipient": e.recipient.to_string(),
+            "member": e.member.to_string(),
+            "approvals": e.approvals,
+        }),
+        SavingsEvent::RecipientAllowed(e) => json!({
+            "goal_id": e.goal_id,
+            "recipient": e.recipient.to_string(),
+        }),
+        SavingsEvent::TransferRequested(e) => json!({
+            "goal_id": e.goal_id,
+            "recipient": e.recipient.to_string(),
+            "amount": e.amount,
+        }),
+        SavingsEvent::TransferCompleted(e) => json!({
+            "goal_id": e.goal_id,
+            "recipient": e.recipient.to_string(),
+            "amount": e.amount,
+        }),
+        SavingsEvent::TransferVetoed(e) => json!({
+            "goal_id": e.goal_id,
+            "milestone": e.milestone,
+            "member": e.member.to_string(),
+            "recipient": e.recipient.to_string(),
+            "amount": e.amount,
+        }),
+        SavingsEvent::YieldAdapterChanged(e) => json!({
+            "goal_id": e.goal_id,
+            "adapter": e.adapter.as_ref().map(yield_adapter),
+        }),
+        SavingsEvent::YieldDeposited(e) => json!({
+            "goal_id": e.goal_id,
+            "amount": e.amount,
+        }),
+        SavingsEvent::YieldWithdrawalStarted(e) => json!({
+            "goal_id": e.goal_id,
+        }),
+        SavingsEvent::YieldWithdrawn(e) => json!({
+            "goal_id": e.goal_id,
+            "deposited": e.deposited,
+            "earned": e.earned,
+        }),
+        SavingsEvent::YieldDistributed(e) => json!({
+            "goal_id": e.goal_id,
+            "earned": e.earned,
+            "distributed": e.distributed,
+        }),
+        SavingsEvent::YieldClaimed(e) => json!({
+            "goal_id": e.goal_id,
+            "member": e.member.to_string(),
+            "amount": e.amount,
+        }),
+        SavingsEvent::ComputationQueued(e) => json!({
+            "goal": e.goal.to_string(),
+            "computation_offset": e.computation_offset,
+            "kind": e.kind.name(),
+            "requester": e.requester.to_string(),
+        }),
+        SavingsEvent::ComputationFailed(e) => json!({
+            "goal": e.goal.to_string(),
+            "computation_offset": e.computation_offset,
+            "kind": e.kind.name(),
+            "reason": failure_reason(&e.reason),
+        }),
+        SavingsEvent::ComputationRetried(e) => json!({
+            "goal": e.goal.to_string(),
+            "failed_offset": e.failed_offset,
+            "computation_offset": e.computation_offset,
+        }),
+        SavingsEvent::Aggregation(e) => json!({
+            "goal": e.goal.to_string(),
+            "computation_offset": e.computation_offset,
+            "total": e.total,
+        }),
+        SavingsEvent::GoalCheck(e) => json!({
+            "goal": e.goal.to_string(),
+            "computation_offset": e.computation_offset,
+            "reached": e.reached,
+        }),
+        SavingsEvent::PeriodCheck(e) => json!({
+            "goal": e.goal.to_string(),
+            "computation_offset": e.computation_offset,
+            "member": e.member.to_string(),
+            "period": e.period,
+            "met": e.met,
+        }),
+        SavingsEvent::MemberProgress(e) => json!({
+            "goal": e.goal.to_string(),
+            "computation_offset": e.computation_offset,
+            "member": e.member.to_string(),
+        }),
+        SavingsEvent::ContributionRevealed(e) => json!({
+            "goal": e.goal.to_string(),
+            "computation_offset": e.computation_offset,
+            "encryption_key": hex::encode(e.encryption_key),
+            "nonce": e.nonce.to_string(),
+            "encrypted_amount": hex::encode(e.encrypted_amount),
+        }),
+        SavingsEvent::OwnerReveal(e) => json!({
+            "goal": e.goal.to_string(),
+            "computation_offset": e.computation_offset,
+            "encryption_key": hex::encode(e.encryption_key),
+            "nonce": e.nonce.to_string(),
+            "encrypted_amounts": e.encrypted_amounts.iter().map(hex::encode).collect::<Vec<_>>(),
+        }),
+        SavingsEvent::GroupStats(e) => json!({
+            "goal": e.goal.to_string(),
+            "computation_offset": e.computation_offset,
+            "contributors": e.contributors,
+            "average": e.average,
+            "min": e.min,
+            "max": e.max,
+            "active": e.active,
+        }),
+        SavingsEvent::Audit(e) => json!({
+            "goal": e.goal.to_string(),
+            "computation_offset": e.computation_offset,
+            "contributors": e.contributors,
+        }),
+        SavingsEvent::FinalTotal(e) => json!({
+            "goal": e.goal.to_string(),
+            "computation_offset": e.computation_offset,
+            "total": e.total,
+        }),
+        SavingsEvent::VaultCheck(e) => json!({
+            "goal": e.goal.to_string(),
+            "computation_offset": e.computation_offset,
+            "vault_balance": e.vault_balance,
+            "balance": vault_balance(&e.balance),
+        }),
+        SavingsEvent::MilestoneCheck(e) => json!({
+            "goal": e.goal.to_string(),
+            "computation_offset": e.computation_offset,
+            "reached": e.reached,
+        }),
+        SavingsEvent::YieldDistribution(e) => json!({
+            "goal": e.goal.to_string(),
+            "computation_offset": e.computation_offset,
+            "shares": e.shares,
+        }),
+    };
+    json!({ "event": event.name(), "data": fields })
+}
+
+/// An account in the `solana account --output json` format
+pub fn account_json(address: &Pubkey, account: &Account) -> Value {
+    json!({
+        "pubkey": address.to_string(),
+        "account": {
+            "lamports": account.lamports,
+            "data": [STANDARD.encode(&account.data), "base64"],
+            "owner": account.owner.to_string(),
+            "executable": account.executable,
+            "rentEpoch": account.rent_epoch,
+            "space": account.data.len(),
+        },
+    })
+}
@@ -0,0 +1,110 @@
+use std::thread::sleep;
+use std::time::{Duration, Instant};
+
+use anyhow::{anyhow, bail, Context as _, Result};
+use savings_client::events::{events_from_logs, SavingsEvent};
+use savings_client::{pda, Instruction, Pubkey};
+use solana_client::rpc_client::RpcClient;
+use solana_client::rpc_config::RpcTransactionConfig;
+use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::signature::{Keypair, Signature, Signer};
+use solana_sdk::transaction::Transaction;
+use solana_transaction_status::UiTransactionEncoding;
+
+const POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+pub struct Context {
+    pub rpc: RpcClient,
+    pub payer: Keypair,
+}
+
+impl Context {
+    pub fn new(url: &str, payer: Keypair) -> Self {
+        Self {
+            rpc: RpcClient::new_with_commitment(url.to_string(), CommitmentConfig::confirmed()),
+            payer,
+        }
+    }
+
+    pub fn payer(&self) -> Pubkey {
+        self.payer.pubkey()
+    }
+
+    /// Sign with the payer and send, waiting for confirmation
+    pub fn send(&self, instructions: &[Instruction]) -> Result<Signature> {
+        let blockhash = self.rpc.get_latest_blockhash()?;
+        let tx = Transaction::new_signed_with_payer(
+            instructions,
+            Some(&self.payer.pubkey()),
+            &[&self.payer],
+            blockhash,
+        );
+        self.rpc
+            .send_and_confirm_transaction(&tx)
+            .context("transaction failed")
+    }
+
+    pub fn account_data(&self, address: &Pubkey) -> Result<Vec<u8>> {
+        self.rpc
+            .get_account_data(address)
+            .with_context(|| format!("failed to fetch account {address}"))
+    }
+
+    pub fn exists(&self, address: &Pubkey) -> Result<bool> {
+        Ok(self
+            .rpc
+            .get_account_with_commitment(address, CommitmentConfig::confirmed())?
+            .value
+            .is_some())
+    }
+
+    fn logs(&self, signature: &Signature) -> Result<Vec<String>> {
+        let tx = self.rpc.get_transaction_with_config(
+            signature,
+            RpcTransactionConfig {
+                encoding: Some(UiTransactionEncoding::Json),
+                commitment: Some(CommitmentConfig::confirmed()),
+                max_supported_transaction_version: Some(0),
+            },
+        )?;
+        let meta = tx
+            .transaction
+            .meta
+            .ok_or_else(|| anyhow!("transaction {signature} has no status meta"))?;
+        Ok(Option::<Vec<String>>::from(meta.log_messages).unwrap_or_default())
+    }
+
+    /// Wait until the callback of the computation at `computation_offset` has
+    /// landed and return the events it emitted
+    pub fn await_computation(
+        &self,
+        computation_offset: u64,
+        timeout: Duration,
+    ) -> Result<(Signature, Vec<SavingsEvent>)> {
+        // The callback writes the computation record, so its history holds the result
+        let record = pda::computation_record(computation_offset);
+        let started = Instant::now();
+        let mut seen = Vec::new();
+        while started.elapsed() < timeout {
+            for status in self.rpc.get_signatures_for_address(&record)? {
+                if status.err.is_some() || seen.contains(&status.signature) {
+                    continue;
+                }
+                seen.push(status.signature.clone());
+                let signature: Signature = status.signature.parse()?;
+                let events: Vec<SavingsEvent> = events_from_logs(&self.logs(&signature)?)
+                    .into_iter()
+                    .filter(|e| {
+                        e.is_computation_result()
+                            && e.computation_offset() == Some(computation_offset)
+                    })
+                    .collect();
+                if !events.is_empty() {
+                    return Ok((signature, events));
+                }
+            }
+            sleep(POLL_INTERVAL);
+        }
+        bail!("computation {computation_offset} did not finalize within {timeout:?}")
+    }
+}
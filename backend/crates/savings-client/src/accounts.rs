@@ -0,0 +1,61 @@
+//! Decoders for raw account data fetched over RPC.
+
+use anchor_lang::{AccountDeserialize, Result};
+
+pub use savings_goal::{
+    Contribution, GoalStatus, Milestone, PeriodContribution, PeriodStatus, RecipientProposal,
+    SavingsGoal, Schedule, TransferPolicy, TransferRequest, YieldAdapter,
+};
+pub use savings_mxe::{
+    Audit, ComputationInputs, ComputationRecord, ComputationStatus, FailureReason, GoalCircuits,
+    GroupStats, MemberProgress, MxeConfig, RegisteredCircuitVersion, StoredContributionInput,
+};
+
+/// Decode any Anchor account, checking its discriminator
+pub fn decode<T: AccountDeserialize>(data: &[u8]) -> Result<T> {
+    T::try_deserialize(&mut &data[..])
+}
+
+pub fn decode_goal(data: &[u8]) -> Result<SavingsGoal> {
+    decode(data)
+}
+
+pub fn decode_contribution(data: &[u8]) -> Result<Contribution> {
+    decode(data)
+}
+
+pub fn decode_period_contribution(data: &[u8]) -> Result<PeriodContribution> {
+    decode(data)
+}
+
+pub fn decode_transfer_request(data: &[u8]) -> Result<TransferRequest> {
+    decode(data)
+}
+
+pub fn decode_recipient_proposal(data: &[u8]) -> Result<RecipientProposal> {
+    decode(data)
+}
+
+pub fn decode_mxe_config(data: &[u8]) -> Result<MxeConfig> {
+    decode(data)
+}
+
+pub fn decode_goal_circuits(data: &[u8]) -> Result<GoalCircuits> {
+    decode(data)
+}
+
+pub fn decode_computation_record(data: &[u8]) -> Result<ComputationRecord> {
+    decode(data)
+}
+
+pub fn decode_group_stats(data: &[u8]) -> Result<GroupStats> {
+    decode(data)
+}
+
+pub fn decode_member_progress(data: &[u8]) -> Result<MemberProgress> {
+    decode(data)
+}
+
+pub fn decode_audit(data: &[u8]) -> Result<Audit> {
+    decode(data)
+}
@@ -0,0 +1,457 @@
+//! Client-side encryption for `Enc<Shared, _>` circuit inputs and outputs.
+//!
+//! Mirrors `x25519` + `RescueCipher` from `@arcium-hq/client`:
+//!
+//! 1. x25519 key exchange between a client keypair and the MXE public key
+//! 2. the shared secret is hashed with Rescue-Prime into a 5-element key
+//! 3. values are encrypted with the Rescue block cipher in counter mode,
+//!    using the counter blocks `[nonce, i, 0, 0, 0]`
+//!
+//! Everything is computed over the Curve25519 base field `2^255 - 19`.
+//! Ciphertexts are 32-byte little-endian field elements, which is the form
+//! `Argument::EncryptedU64` and the `ciphertexts` of callback outputs use.
+
+use std::fmt;
+
+use num_bigint::BigUint;
+use num_traits::{One, ToPrimitive, Zero};
+use rand_core::{OsRng, RngCore};
+use sha3::digest::{ExtendableOutput, Update, XofReader};
+use sha3::Shake256;
+use x25519_dalek::{PublicKey, StaticSecret};
+
+/// Cipher state width (and key length) of the Rescue block cipher
+const CIPHER_M: usize = 5;
+/// Rescue-Prime hash state width, rate and output length
+const HASH_M: usize = 12;
+const HASH_CAPACITY: usize = 5;
+const HASH_RATE: usize = HASH_M - HASH_CAPACITY;
+const HASH_DIGEST_LEN: usize = CIPHER_M;
+const SECURITY_LEVEL: u32 = 128;
+/// Seed of the block cipher round constant generator
+const CIPHER_SEED: &[u8] = b"encrypt everything, compute anything";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CipherError {
+    /// A ciphertext is not a canonical field element
+    NonCanonical,
+    /// A decrypted value does not fit the requested integer type
+    OutOfRange,
+}
+
+impl fmt::Display for CipherError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CipherError::NonCanonical => write!(f, "ciphertext is not a canonical field element"),
+            CipherError::OutOfRange => write!(f, "decrypted value is out of range"),
+        }
+    }
+}
+
+impl std::error::Error for CipherError {}
+
+// ============================================================================
+// Key exchange
+// ============================================================================
+
+/// A client x25519 keypair used to talk to the MXE
+pub struct ClientKeypair {
+    secret: StaticSecret,
+    public: PublicKey,
+}
+
+impl ClientKeypair {
+    pub fn generate() -> Self {
+        Self::from_secret(StaticSecret::random_from_rng(OsRng).to_bytes())
+    }
+
+    pub fn from_secret(secret: [u8; 32]) -> Self {
+        let secret = StaticSecret::from(secret);
+        let public = PublicKey::from(&secret);
+        Self { secret, public }
+    }
+
+    /// The `pub_key` argument passed alongside ciphertexts
+    pub fn public_key(&self) -> [u8; 32] {
+        self.public.to_bytes()
+    }
+
+    pub fn secret_bytes(&self) -> [u8; 32] {
+        self.secret.to_bytes()
+    }
+
+    /// Cipher shared with the MXE whose x25519 public key is `mxe_public_key`
+    pub fn shared_cipher(&self, mxe_public_key: &[u8; 32]) -> RescueCipher {
+        let shared = self
+            .secret
+            .diffie_hellman(&PublicKey::from(*mxe_public_key));
+        RescueCipher::new(shared.as_bytes())
+    }
+}
+
+/// A fresh random 128-bit nonce, as passed in `nonce: u128` arguments
+pub fn random_nonce() -> u128 {
+    let mut bytes = [0u8; 16];
+    OsRng.fill_bytes(&mut bytes);
+    u128::from_le_bytes(bytes)
+}
+
+/// One encrypted value together with what the MXE needs to decrypt it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SharedCiphertext {
+    pub pub_key: [u8; 32],
+    pub nonce: u128,
+    pub ciphertext: [u8; 32],
+}
+
+/// Encrypt a single `u64` (e.g. a contribution amount) to the MXE
+pub fn encrypt_u64(
+    keypair: &ClientKeypair,
+    mxe_public_key: &[u8; 32],
+    value: u64,
+) -> SharedCiphertext {
+    let nonce = random_nonce();
+    let ciphertext = keypair
+        .shared_cipher(mxe_public_key)
+        .encrypt(&[value], nonce)[0];
+    SharedCiphertext {
+        pub_key: keypair.public_key(),
+        nonce,
+        ciphertext,
+    }
+}
+
+// ============================================================================
+// Rescue cipher
+// ============================================================================
+
+/// Rescue in counter mode, keyed by an x25519 shared secret
+pub struct RescueCipher {
+    field: Field,
+    round_keys: Vec<Vec<BigUint>>,
+}
+
+impl RescueCipher {
+    pub fn new(shared_secret: &[u8; 32]) -> Self {
+        let field = Field::new();
+        let secret = field.reduce(BigUint::from_bytes_le(shared_secret));
+        let key = RescuePrimeHash::new(&field).digest(&field, &[secret]);
+        let round_keys = key_schedule(&field, &key);
+        Self { field, round_keys }
+    }
+
+    /// Encrypt `plaintext` under `nonce`; one 32-byte ciphertext per value
+    pub fn encrypt(&self, plaintext: &[u64], nonce: u128) -> Vec<[u8; 32]> {
+        let values: Vec<BigUint> = plaintext.iter().map(|&v| BigUint::from(v)).collect();
+        self.apply_keystream(&values, nonce, false)
+            .iter()
+            .map(to_bytes)
+            .collect()
+    }
+
+    /// Decrypt ciphertexts produced under `nonce`, e.g. the `ciphertexts` and
+    /// `nonce` of an `Enc<Shared, [u64; N]>` callback output
+    pub fn decrypt(&self, ciphertexts: &[[u8; 32]], nonce: u128) -> Result<Vec<u64>, CipherError> {
+        let values = ciphertexts
+            .iter()
+            .map(|c| {
+                let v = BigUint::from_bytes_le(c);
+                if v < self.field.p {
+                    Ok(v)
+                } else {
+                    Err(CipherError::NonCanonical)
+                }
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        self.apply_keystream(&values, nonce, true)
+            .iter()
+            .map(|v| v.to_u64().ok_or(CipherError::OutOfRange))
+            .collect()
+    }
+
+    /// Decrypt a revealed boolean (`Enc<Shared, bool>`)
+    pub fn decrypt_bool(&self, ciphertext: &[u8; 32], nonce: u128) -> Result<bool, CipherError> {
+        match self.decrypt(&[*ciphertext], nonce)?[0] {
+            0 => Ok(false),
+            1 => Ok(true),
+            _ => Err(CipherError::OutOfRange),
+        }
+    }
+
+    fn apply_keystream(&self, values: &[BigUint], nonce: u128, subtract: bool) -> Vec<BigUint> {
+        let f = &self.field;
+        let mut out = Vec::with_capacity(values.len());
+        for (block, chunk) in values.chunks(CIPHER_M).enumerate() {
+            let mut counter = vec![BigUint::zero(); CIPHER_M];
+            counter[0] = BigUint::from(nonce);
+            counter[1] = BigUint::from(block as u64);
+            let keystream = encrypt_block(f, &self.round_keys, counter);
+            for (v, k) in chunk.iter().zip(keystream) {
+                out.push(if subtract { f.sub(v, &k) } else { f.add(v, &k) });
+            }
+        }
+        out
+    }
+}
+
+fn to_bytes(v: &BigUint) -> [u8; 32] {
+    let mut out = [0u8; 32];
+    let bytes = v.to_bytes_le();
+    out[..bytes.len()].copy_from_slice(&bytes);
+    out
+}
+
+/// Number of (double) rounds of the Rescue block cipher, per the Rescue paper
+fn cipher_rounds(field: &Field) -> usize {
+    let m = CIPHER_M as f64;
+    let s = SECURITY_LEVEL as f64;
+    let log_p = field.p.bits() as f64;
+    let l0 = (2.0 * s / ((m + 1.0) * (log_p - ((field.alpha - 1) as f64).log2()))).ceil();
+    let l1 = ((s + 3.0) / (5.5 * m)).ceil();
+    2 * l0.max(l1).max(5.0) as usize
+}
+
+/// Derive the `2 * rounds + 1` round keys by running the permutation on the key
+fn key_schedule(field: &Field, key: &[BigUint]) -> Vec<Vec<BigUint>> {
+    let rounds = cipher_rounds(field);
+    let mds = cauchy_mds(field, CIPHER_M);
+    let mut shake = Shake256::default();
+    shake.update(CIPHER_SEED);
+    let mut reader = shake.finalize_xof();
+
+    // Round constants follow an affine recurrence c_{i+1} = A * c_i + b
+    let a: Vec<Vec<BigUint>> = (0..CIPHER_M)
+        .map(|_| (0..CIPHER_M).map(|_| field.sample(&mut reader)).collect())
+        .collect();
+    let mut constant: Vec<BigUint> = (0..CIPHER_M).map(|_| field.sample(&mut reader)).collect();
+    let b: Vec<BigUint> = (0..CIPHER_M).map(|_| field.sample(&mut reader)).collect();
+
+    let mut state: Vec<BigUint> = key
+        .iter()
+        .zip(&constant)
+        .map(|(k, c)| field.add(k, c))
+        .collect();
+    let mut round_keys = vec![state.clone()];
+    for step in 0..2 * rounds {
+        constant = field
+            .mat_vec(&a, &constant)
+            .iter()
+            .zip(&b)
+            .map(|(x, y)| field.add(x, y))
+            .collect();
+        state = field.sbox(&state, step % 2 == 1);
+        state = field
+            .mat_vec(&mds, &state)
+            .iter()
+            .zip(&constant)
+            .map(|(x, c)| field.add(x, c))
+            .collect();
+        round_keys.push(state.clone());
+    }
+    round_keys
+}
+
+fn encrypt_block(field: &Field, round_keys: &[Vec<BigUint>], block: Vec<BigUint>) -> Vec<BigUint> {
+    let mds = cauchy_mds(field, CIPHER_M);
+    let mut state: Vec<BigUint> = block
+        .iter()
+        .zip(&round_keys[0])
+        .map(|(x, k)| field.add(x, k))
+        .collect();
+    for (step, key) in round_keys[1..].iter().enumerate() {
+        state = field.sbox(&state, step % 2 == 1);
+        state = field
+            .mat_vec(&mds, &state)
+            .iter()
+            .zip(key)
+            .map(|(x, k)| field.add(x, k))
+            .collect();
+    }
+    state
+}
+
+/// Cauchy matrix `1 / (x_i + y_j)` with `x_i = i`, `y_j = m + j`, which is MDS
+fn cauchy_mds(field: &Field, m: usize) -> Vec<Vec<BigUint>> {
+    (0..m)
+        .map(|i| {
+            (0..m)
+                .map(|j| field.inv(&BigUint::from((i + m + j) as u64)))
+                .collect()
+        })
+        .collect()
+}
+
+// ============================================================================
+// Rescue-Prime hash
+// ============================================================================
+
+struct RescuePrimeHash {
+    rounds: usize,
+    constants: Vec<BigUint>,
+    mds: Vec<Vec<BigUint>>,
+}
+
+impl RescuePrimeHash {
+    fn new(field: &Field) -> Self {
+        let rounds = hash_rounds(field);
+        let seed = format!(
+            "Rescue-XLIX({},{},{},{})",
+            field.p, HASH_M, HASH_CAPACITY, SECURITY_LEVEL
+        );
+        let mut shake = Shake256::default();
+        shake.update(seed.as_bytes());
+        let mut reader = shake.finalize_xof();
+        let constants = (0..2 * HASH_M * rounds)
+            .map(|_| field.sample(&mut reader))
+            .collect();
+        Self {
+            rounds,
+            constants,
+            mds: cauchy_mds(field, HASH_M),
+        }
+    }
+
+    fn permute(&self, field: &Field, mut state: Vec<BigUint>) -> Vec<BigUint> {
+        for round in 0..self.rounds {
+            for half in 0..2 {
+                state = field.sbox(&state, half == 1);
+                let offset = (2 * round + half) * HASH_M;
+                state = field
+                    .mat_vec(&self.mds, &state)
+                    .iter()
+                    .zip(&self.constants[offset..offset + HASH_M])
+                    .map(|(x, c)| field.add(x, c))
+                    .collect();
+            }
+        }
+        state
+    }
+
+    /// Sponge: pad with a one and zeros to a multiple of the rate, absorb, squeeze
+    fn digest(&self, field: &Field, input: &[BigUint]) -> Vec<BigUint> {
+        let mut padded = input.to_vec();
+        padded.push(BigUint::one());
+        while padded.len() % HASH_RATE != 0 {
+            padded.push(BigUint::zero());
+        }
+        let mut state = vec![BigUint::zero(); HASH_M];
+        for chunk in padded.chunks(HASH_RATE) {
+            for (s, x) in state.iter_mut().zip(chunk) {
+                *s = field.add(s, x);
+            }
+            state = self.permute(field, state);
+        }
+        state.truncate(HASH_DIGEST_LEN);
+        state
+    }
+}
+
+/// Number of Rescue-Prime rounds: Gröbner basis bound plus a 50% margin
+fn hash_rounds(field: &Field) -> usize {
+    let m = HASH_M as f64;
+    let alpha = field.alpha as f64;
+    let log2_binomial = |n: f64, k: f64| {
+        (1..=k as u64)
+            .map(|i| ((n - k + i as f64) / i as f64).log2())
+            .sum::<f64>()
+    };
+    let mut l1 = 1;
+    while l1 < 25 {
+        let n = l1 as f64;
+        let dcon = (0.5 * (alpha - 1.0) * m * (n - 1.0) + 2.0).floor();
+        let v = m * (n - 1.0) + HASH_RATE as f64;
+        if 2.0 * log2_binomial(v + dcon, v) > SECURITY_LEVEL as f64 {
+            break;
+        }
+        l1 += 1;
+    }
+    (1.5 * l1.max(5) as f64).ceil() as usize
+}
+
+// ============================================================================
+// Field arithmetic
+// ============================================================================
+
+struct Field {
+    p: BigUint,
+    alpha: u64,
+    alpha_inverse: BigUint,
+    /// Bytes drawn from the XOF per sampled element
+    sample_bytes: usize,
+}
+
+impl Field {
+    fn new() -> Self {
+        let p = (BigUint::one() << 255u32) - BigUint::from(19u32);
+        let p_minus_one = &p - BigUint::one();
+        // Smallest alpha coprime to p - 1, so x^alpha is a permutation
+        let alpha = (3u64..)
+            .find(|a| (&p_minus_one % a) != BigUint::zero())
+            .expect("an alpha exists");
+        let alpha_inverse = mod_inverse(&BigUint::from(alpha), &p_minus_one);
+        let sample_bytes = (p.bits() as usize + SECURITY_LEVEL as usize).div_ceil(8);
+        Self {
+            p,
+            alpha,
+            alpha_inverse,
+            sample_bytes,
+        }
+    }
+
+    fn reduce(&self, v: BigUint) -> BigUint {
+        v % &self.p
+    }
+
+    fn add(&self, a: &BigUint, b: &BigUint) -> BigUint {
+        (a + b) % &self.p
+    }
+
+    fn sub(&self, a: &BigUint, b: &BigUint) -> BigUint {
+        (a + &self.p - b) % &self.p
+    }
+
+    fn inv(&self, a: &BigUint) -> BigUint {
+        a.modpow(&(&self.p - BigUint::from(2u32)), &self.p)
+    }
+
+    /// `x^alpha` (or `x^(1/alpha)` when `inverse`) applied elementwise
+    fn sbox(&self, state: &[BigUint], inverse: bool) -> Vec<BigUint> {
+        let exponent = if inverse {
+            self.alpha_inverse.clone()
+        } else {
+            BigUint::from(self.alpha)
+        };
+        state.iter().map(|x| x.modpow(&exponent, &self.p)).collect()
+    }
+
+    fn mat_vec(&self, mat: &[Vec<BigUint>], v: &[BigUint]) -> Vec<BigUint> {
+        mat.iter()
+            .map(|row| {
+                row.iter()
+                    .zip(v)
+                    .fold(BigUint::zero(), |acc, (a, b)| (acc + a * b) % &self.p)
+            })
+            .collect()
+    }
+
+    fn sample(&self, reader: &mut impl XofReader) -> BigUint {
+        let mut bytes = vec![0u8; self.sample_bytes];
+        reader.read(&mut bytes);
+        self.reduce(BigUint::from_bytes_le(&bytes))
+    }
+}
+
+/// Inverse of `a` modulo `m` (extended Euclid); `a` and `m` must be coprime
+fn mod_inverse(a: &BigUint, m: &BigUint) -> BigUint {
+    let (mut old_r, mut r) = (a.clone(), m.clone());
+    // Coefficients are tracked modulo m to stay unsigned
+    let (mut old_s, mut s) = (BigUint::one(), BigUint::zero());
+    while !r.is_zero() {
+        let q = &old_r / &r;
+        let next_r = &old_r - &q * &r;
+        old_r = std::mem::replace(&mut r, next_r);
+        let next_s = (&old_s + m - (&q * &s) % m) % m;
+        old_s = std::mem::replace(&mut s, next_s);
+    }
+    old_s
+}
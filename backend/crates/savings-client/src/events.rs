@@ -0,0 +1,253 @@
+//! Decoding of Anchor events emitted by `savings_goal` and `savings_mxe`.
+//!
+//! Events are logged as `Program data: <base64>` where the payload is the
+//! 8-byte event discriminator followed by the Borsh-encoded event.
+
+use anchor_lang::{AnchorDeserialize, Discriminator};
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+
+pub use savings_goal::{
+    ContributionAdded, ContributionMissed, Deposited, GoalCreated, GoalFinalized, GoalReached,
+    MemberInvited, MilestoneReached, MilestoneReleased, MilestoneTransferRequested,
+    OwnerRevealChanged, PeriodContributionAdded, RecipientAllowed, RecipientApproved,
+    TotalRevealed, TransferCompleted, TransferRequested, TransferVetoed, VaultChecked,
+    YieldAdapterChanged, YieldClaimed, YieldDeposited, YieldDistributed, YieldWithdrawalStarted,
+    YieldWithdrawn,
+};
+pub use savings_mxe::{
+    AggregationEvent, AuditEvent, ComputationFailed, ComputationQueued, ComputationRetried,
+    ContributionRevealed, FinalTotalEvent, GoalCheckEvent, GroupStatsEvent, MemberProgressEvent,
+    MilestoneCheckEvent, OwnerRevealEvent, PeriodCheckEvent, VaultBalance, VaultCheckEvent,
+    YieldDistributionEvent,
+};
+
+use crate::{Pubkey, SAVINGS_GOAL_ID, SAVINGS_MXE_ID};
+
+const PROGRAM_DATA: &str = "Program data: ";
+
+pub enum SavingsEvent {
+    GoalCreated(GoalCreated),
+    MemberInvited(MemberInvited),
+    ContributionAdded(ContributionAdded),
+    PeriodContributionAdded(PeriodContributionAdded),
+    ContributionMissed(ContributionMissed),
+    OwnerRevealChanged(OwnerRevealChanged),
+    Deposited(Deposited),
+    GoalReached(GoalReached),
+    GoalFinalized(GoalFinalized),
+    TotalRevealed(TotalRevealed),
+    VaultChecked(VaultChecked),
+    MilestoneReached(MilestoneReached),
+    MilestoneTransferRequested(MilestoneTransferRequested),
+    MilestoneReleased(MilestoneReleased),
+    RecipientApproved(RecipientApproved),
+    RecipientAllowed(RecipientAllowed),
+    TransferRequested(TransferRequested),
+    TransferCompleted(TransferCompleted),
+    TransferVetoed(TransferVetoed),
+    YieldAdapterChanged(YieldAdapterChanged),
+    YieldDeposited(YieldDeposited),
+    YieldWithdrawalStarted(YieldWithdrawalStarted),
+    YieldWithdrawn(YieldWithdrawn),
+    YieldDistributed(YieldDistributed),
+    YieldClaimed(YieldClaimed),
+    ComputationQueued(ComputationQueued),
+    ComputationFailed(ComputationFailed),
+    ComputationRetried(ComputationRetried),
+    Aggregation(AggregationEvent),
+    GoalCheck(GoalCheckEvent),
+    PeriodCheck(PeriodCheckEvent),
+    MemberProgress(MemberProgressEvent),
+    GroupStats(GroupStatsEvent),
+    ContributionRevealed(ContributionRevealed),
+    OwnerReveal(OwnerRevealEvent),
+    Audit(AuditEvent),
+    FinalTotal(FinalTotalEvent),
+    VaultCheck(VaultCheckEvent),
+    MilestoneCheck(MilestoneCheckEvent),
+    YieldDistribution(YieldDistributionEvent),
+}
+
+impl SavingsEvent {
+    pub fn name(&self) -> &'static str {
+        match self {
+            SavingsEvent::GoalCreated(_) => "GoalCreated",
+            SavingsEvent::MemberInvited(_) => "MemberInvited",
+            SavingsEvent::ContributionAdded(_) => "ContributionAdded",
+            SavingsEvent::PeriodContributionAdded(_) => "PeriodContributionAdded",
+            SavingsEvent::ContributionMissed(_) => "ContributionMissed",
+            SavingsEvent::OwnerRevealChanged(_) => "OwnerRevealChanged",
+            SavingsEvent::Deposited(_) => "Deposited",
+            SavingsEvent::GoalReached(_) => "GoalReached",
+            SavingsEvent::GoalFinalized(_) => "GoalFinalized",
+            SavingsEvent::TotalRevealed(_) => "TotalRevealed",
+            SavingsEvent::VaultChecked(_) => "VaultChecked",
+            SavingsEvent::MilestoneReached(_) => "MilestoneReached",
+            SavingsEvent::MilestoneTransferRequested(_) => "MilestoneTransferRequested",
+            SavingsEvent::MilestoneReleased(_) => "MilestoneReleased",
+            SavingsEvent::RecipientApproved(_) => "RecipientApproved",
+            SavingsEvent::RecipientAllowed(_) => "RecipientAllowed",
+            SavingsEvent::TransferRequested(_) => "TransferRequested",
+            SavingsEvent::TransferCompleted(_) => "TransferCompleted",
+            SavingsEvent::TransferVetoed(_) => "TransferVetoed",
+            SavingsEvent::YieldAdapterChanged(_) => "YieldAdapterChanged",
+            SavingsEvent::YieldDeposited(_) => "YieldDeposited",
+            SavingsEvent::YieldWithdrawalStarted(_) => "YieldWithdrawalStarted",
+            SavingsEvent::YieldWithdrawn(_) => "YieldWithdrawn",
+            SavingsEvent::YieldDistributed(_) => "YieldDistributed",
+            SavingsEvent::YieldClaimed(_) => "YieldClaimed",
+            SavingsEvent::ComputationQueued(_) => "ComputationQueued",
+            SavingsEvent::ComputationFailed(_) => "ComputationFailed",
+            SavingsEvent::ComputationRetried(_) => "ComputationRetried",
+            SavingsEvent::Aggregation(_) => "AggregationEvent",
+            SavingsEvent::GoalCheck(_) => "GoalCheckEvent",
+            SavingsEvent::PeriodCheck(_) => "PeriodCheckEvent",
+            SavingsEvent::MemberProgress(_) => "MemberProgressEvent",
+            SavingsEvent::GroupStats(_) => "GroupStatsEvent",
+            SavingsEvent::ContributionRevealed(_) => "ContributionRevealed",
+            SavingsEvent::OwnerReveal(_) => "OwnerRevealEvent",
+            SavingsEvent::Audit(_) => "AuditEvent",
+            SavingsEvent::FinalTotal(_) => "FinalTotalEvent",
+            SavingsEvent::VaultCheck(_) => "VaultCheckEvent",
+            SavingsEvent::MilestoneCheck(_) => "MilestoneCheckEvent",
+            SavingsEvent::YieldDistribution(_) => "YieldDistributionEvent",
+        }
+    }
+
+    /// Whether this event is emitted by an MXE computation callback, successful
+    /// or not
+    pub fn is_computation_result(&self) -> bool {
+        matches!(
+            self,
+            SavingsEvent::Aggregation(_)
+                | SavingsEvent::GoalCheck(_)
+                | SavingsEvent::PeriodCheck(_)
+                | SavingsEvent::MemberProgress(_)
+                | SavingsEvent::GroupStats(_)
+                | SavingsEvent::ContributionRevealed(_)
+                | SavingsEvent::OwnerReveal(_)
+                | SavingsEvent::Audit(_)
+                | SavingsEvent::FinalTotal(_)
+                | SavingsEvent::VaultCheck(_)
+                | SavingsEvent::MilestoneCheck(_)
+                | SavingsEvent::YieldDistribution(_)
+                | SavingsEvent::ComputationFailed(_)
+        )
+    }
+
+    /// Offset of the computation a `savings_mxe` event belongs to
+    pub fn computation_offset(&self) -> Option<u64> {
+        match self {
+            SavingsEvent::ComputationQueued(e) => Some(e.computation_offset),
+            SavingsEvent::ComputationFailed(e) => Some(e.computation_offset),
+            SavingsEvent::ComputationRetried(e) => Some(e.computation_offset),
+            SavingsEvent::Aggregation(e) => Some(e.computation_offset),
+            SavingsEvent::GoalCheck(e) => Some(e.computation_offset),
+            SavingsEvent::PeriodCheck(e) => Some(e.computation_offset),
+            SavingsEvent::MemberProgress(e) => Some(e.computation_offset),
+            SavingsEvent::GroupStats(e) => Some(e.computation_offset),
+            SavingsEvent::ContributionRevealed(e) => Some(e.computation_offset),
+            SavingsEvent::OwnerReveal(e) => Some(e.computation_offset),
+            SavingsEvent::Audit(e) => Some(e.computation_offset),
+            SavingsEvent::FinalTotal(e) => Some(e.computation_offset),
+            SavingsEvent::VaultCheck(e) => Some(e.computation_offset),
+            SavingsEvent::MilestoneCheck(e) => Some(e.computation_offset),
+            SavingsEvent::YieldDistribution(e) => Some(e.computation_offset),
+            _ => None,
+        }
+    }
+}
+
+fn parse<T: AnchorDeserialize + Discriminator>(data: &[u8]) -> Option<T> {
+    let body = data.strip_prefix(T::DISCRIMINATOR)?;
+    T::deserialize(&mut &body[..]).ok()
+}
+
+/// Decode one event payload (discriminator + Borsh body) emitted by `program_id`
+pub fn decode_event(program_id: &Pubkey, data: &[u8]) -> Option<SavingsEvent> {
+    if *program_id == SAVINGS_GOAL_ID {
+        parse(data)
+            .map(SavingsEvent::GoalCreated)
+            .or_else(|| parse(data).map(SavingsEvent::MemberInvited))
+            .or_else(|| parse(data).map(SavingsEvent::ContributionAdded))
+            .or_else(|| parse(data).map(SavingsEvent::PeriodContributionAdded))
+            .or_else(|| parse(data).map(SavingsEvent::ContributionMissed))
+            .or_else(|| parse(data).map(SavingsEvent::OwnerRevealChanged))
+            .or_else(|| parse(data).map(SavingsEvent::Deposited))
+            .or_else(|| parse(data).map(SavingsEvent::GoalReached))
+            .or_else(|| parse(data).map(SavingsEvent::GoalFinalized))
+            .or_else(|| parse(data).map(SavingsEvent::TotalRevealed))
+            .or_else(|| parse(data).map(SavingsEvent::VaultChecked))
+            .or_else(|| parse(data).map(SavingsEvent::MilestoneReached))
+            .or_else(|| parse(data).map(SavingsEvent::MilestoneTransferRequested))
+            .or_else(|| parse(data).map(SavingsEvent::MilestoneReleased))
+            .or_else(|| parse(data).map(SavingsEvent::RecipientApproved))
+            .or_else(|| parse(data).map(SavingsEvent::RecipientAllowed))
+            .or_else(|| parse(data).map(SavingsEvent::TransferRequested))
+            .or_else(|| parse(data).map(SavingsEvent::TransferCompleted))
+            .or_else(|| parse(data).map(SavingsEvent::TransferVetoed))
+            .or_else(|| parse(data).map(SavingsEvent::YieldAdapterChanged))
+            .or_else(|| parse(data).map(SavingsEvent::YieldDeposited))
+            .or_else(|| parse(data).map(SavingsEvent::YieldWithdrawalStarted))
+            .or_else(|| parse(data).map(SavingsEvent::YieldWithdrawn))
+            .or_else(|| parse(data).map(SavingsEvent::YieldDistributed))
+            .or_else(|| parse(data).map(SavingsEvent::YieldClaimed))
+    } else if *program_id == SAVINGS_MXE_ID {
+        parse(data)
+            .map(SavingsEvent::ComputationQueued)
+            .or_else(|| parse(data).map(SavingsEvent::ComputationFailed))
+            .or_else(|| parse(data).map(SavingsEvent::ComputationRetried))
+            .or_else(|| parse(data).map(SavingsEvent::Aggregation))
+            .or_else(|| parse(data).map(SavingsEvent::GoalCheck))
+            .or_else(|| parse(data).map(SavingsEvent::PeriodCheck))
+            .or_else(|| parse(data).map(SavingsEvent::MemberProgress))
+            .or_else(|| parse(data).map(SavingsEvent::GroupStats))
+            .or_else(|| parse(data).map(SavingsEvent::ContributionRevealed))
+            .or_else(|| parse(data).map(SavingsEvent::OwnerReveal))
+            .or_else(|| parse(data).map(SavingsEvent::Audit))
+            .or_else(|| parse(data).map(SavingsEvent::FinalTotal))
+            .or_else(|| parse(data).map(SavingsEvent::VaultCheck))
+            .or_else(|| parse(data).map(SavingsEvent::MilestoneCheck))
+            .or_else(|| parse(data).map(SavingsEvent::YieldDistribution))
+    } else {
+        None
+    }
+}
+
+/// Extract every savings event from a transaction's log messages.
+///
+/// `Program data:` lines are attributed to the program currently executing,
+/// tracked through the `invoke` / `success` / `failed` lines, so events from
+/// other programs with colliding discriminators are ignored.
+pub fn events_from_logs(logs: &[String]) -> Vec<SavingsEvent> {
+    let mut stack: Vec<Pubkey> = Vec::new();
+    let mut events = Vec::new();
+    for line in logs {
+        if let Some(data) = line.strip_prefix(PROGRAM_DATA) {
+            let Some(program_id) = stack.last() else {
+                continue;
+            };
+            if let Ok(bytes) = STANDARD.decode(data) {
+                events.extend(decode_event(program_id, &bytes));
+            }
+        } else if let Some(rest) = line.strip_prefix("Program ") {
+            let mut parts = rest.split_whitespace();
+            let (Some(id), Some(action)) = (parts.next(), parts.next()) else {
+                continue;
+            };
+            match action {
+                "invoke" => {
+                    if let Ok(id) = id.parse() {
+                        stack.push(id);
+                    }
+                }
+                "success" | "failed:" => {
+                    stack.pop();
+                }
+                _ => {}
+            }
+        }
+    }
+    events
+}
@@ -0,0 +1,352 @@
+//! Instruction builders for `savings_goal`.
+
+use anchor_lang::solana_program::{system_program, sysvar};
+use anchor_lang::{InstructionData, ToAccountMetas};
+use savings_goal::{accounts, instruction, native_stake};
+
+use crate::accounts::{Milestone, Schedule, TransferPolicy, YieldAdapter};
+use crate::cipher::SharedCiphertext;
+use crate::{pda, Instruction, Pubkey, SAVINGS_GOAL_ID};
+
+fn build(accounts: impl ToAccountMetas, data: impl InstructionData) -> Instruction {
+    Instruction {
+        program_id: SAVINGS_GOAL_ID,
+        accounts: accounts.to_account_metas(None),
+        data: data.data(),
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn create_goal(
+    owner: &Pubkey,
+    goal_id: u64,
+    name: String,
+    target_amount: u64,
+    deadline: Option<i64>,
+    schedule: Option<Schedule>,
+    auditor: Option<[u8; 32]>,
+    milestones: Vec<Milestone>,
+    transfer_policy: Option<TransferPolicy>,
+    allowed_recipients: Vec<Pubkey>,
+) -> Instruction {
+    build(
+        accounts::CreateGoal {
+            owner: *owner,
+            goal: pda::goal(owner, goal_id),
+            system_program: system_program::ID,
+        },
+        instruction::CreateGoal {
+            goal_id,
+            name,
+            target_amount,
+            deadline,
+            schedule,
+            auditor,
+            milestones,
+            transfer_policy,
+            allowed_recipients,
+        },
+    )
+}
+
+pub fn invite_member(owner: &Pubkey, goal: &Pubkey, member_wallet: &Pubkey) -> Instruction {
+    build(
+        accounts::InviteMember {
+            owner: *owner,
+            goal: *goal,
+            member_wallet: *member_wallet,
+        },
+        instruction::InviteMember {},
+    )
+}
+
+/// Propose adding `recipient` to the goal's allow-list, counting the
+/// member's own approval
+pub fn propose_recipient(member: &Pubkey, goal: &Pubkey, recipient: Pubkey) -> Instruction {
+    build(
+        accounts::ProposeRecipient {
+            member: *member,
+            goal: *goal,
+            proposal: pda::recipient_proposal(goal, &recipient),
+            system_program: system_program::ID,
+        },
+        instruction::ProposeRecipient { recipient },
+    )
+}
+
+pub fn approve_recipient(member: &Pubkey, goal: &Pubkey, recipient: Pubkey) -> Instruction {
+    build(
+        accounts::ApproveRecipient {
+            member: *member,
+            goal: *goal,
+            proposal: pda::recipient_proposal(goal, &recipient),
+        },
+        instruction::ApproveRecipient {
+            _recipient: recipient,
+        },
+    )
+}
+
+/// Store a contribution encrypted with [`crate::cipher::encrypt_u64`]
+pub fn add_contribution(
+    contributor: &Pubkey,
+    goal: &Pubkey,
+    encrypted: &SharedCiphertext,
+) -> Instruction {
+    build(
+        accounts::AddContribution {
+            contributor: *contributor,
+            goal: *goal,
+            contribution: pda::contribution(goal, contributor),
+            system_program: system_program::ID,
+        },
+        instruction::AddContribution {
+            encrypted_amount: encrypted.ciphertext,
+            encryption_pubkey: encrypted.pub_key,
+            nonce: encrypted.nonce,
+        },
+    )
+}
+
+/// Allow (or stop allowing) the goal owner to reveal `contributor`'s amount
+pub fn set_owner_reveal(contributor: &Pubkey, goal: &Pubkey, allowed: bool) -> Instruction {
+    build(
+        accounts::SetOwnerReveal {
+            contributor: *contributor,
+            goal: *goal,
+            contribution: pda::contribution(goal, contributor),
+        },
+        instruction::SetOwnerReveal { allowed },
+    )
+}
+
+/// Pay `amount` lamports into the goal's vault as `member`
+pub fn deposit(member: &Pubkey, goal: &Pubkey, amount: u64) -> Instruction {
+    build(
+        accounts::Deposit {
+            member: *member,
+            goal: *goal,
+            vault: pda::vault(goal),
+            system_program: system_program::ID,
+        },
+        instruction::Deposit { amount },
+    )
+}
+
+/// Contribute for `period` of the goal's schedule; it must be the current one
+pub fn contribute_for_period(
+    member: &Pubkey,
+    goal: &Pubkey,
+    period: u32,
+    encrypted: &SharedCiphertext,
+) -> Instruction {
+    build(
+        accounts::ContributeForPeriod {
+            member: *member,
+            goal: *goal,
+            period_contribution: pda::period_contribution(goal, member, period),
+            system_program: system_program::ID,
+        },
+        instruction::ContributeForPeriod {
+            period,
+            encrypted_amount: encrypted.ciphertext,
+            encryption_pubkey: encrypted.pub_key,
+            nonce: encrypted.nonce,
+        },
+    )
+}
+
+/// Crank: mark `member` as having missed `period`, paid for by `payer`
+pub fn close_period(payer: &Pubkey, goal: &Pubkey, member: &Pubkey, period: u32) -> Instruction {
+    build(
+        accounts::ClosePeriod {
+            payer: *payer,
+            goal: *goal,
+            member: *member,
+            period_contribution: pda::period_contribution(goal, member, period),
+            system_program: system_program::ID,
+        },
+        instruction::ClosePeriod { period },
+    )
+}
+
+/// `caller` must be the owner unless the deadline has passed or the goal
+/// has been marked reached
+pub fn finalize_and_reveal(caller: &Pubkey, goal: &Pubkey) -> Instruction {
+    build(
+        accounts::FinalizeAndReveal {
+            caller: *caller,
+            goal: *goal,
+        },
+        instruction::FinalizeAndReveal {},
+    )
+}
+
+pub fn request_transfer(
+    owner: &Pubkey,
+    goal: &Pubkey,
+    recipient: Pubkey,
+    amount: u64,
+) -> Instruction {
+    build(
+        accounts::RequestTransfer {
+            owner: *owner,
+            goal: *goal,
+            transfer_request: pda::transfer(goal),
+            system_program: system_program::ID,
+        },
+        instruction::RequestTransfer { recipient, amount },
+    )
+}
+
+pub fn approve_transfer(owner: &Pubkey, goal: &Pubkey, recipient: &Pubkey) -> Instruction {
+    build(
+        accounts::ApproveTransfer {
+            owner: *owner,
+            goal: *goal,
+            transfer_request: pda::transfer(goal),
+            vault: pda::vault(goal),
+            recipient: *recipient,
+        },
+        instruction::ApproveTransfer {},
+    )
+}
+
+/// Request the payout unlocked by a reached `milestone`
+pub fn request_milestone_transfer(
+    owner: &Pubkey,
+    goal: &Pubkey,
+    milestone: u8,
+    recipient: Pubkey,
+    amount: u64,
+) -> Instruction {
+    build(
+        accounts::RequestMilestoneTransfer {
+            owner: *owner,
+            goal: *goal,
+            transfer_request: pda::milestone_transfer(goal, milestone),
+            system_program: system_program::ID,
+        },
+        instruction::RequestMilestoneTransfer {
+            milestone,
+            recipient,
+            amount,
+        },
+    )
+}
+
+pub fn approve_milestone_transfer(
+    owner: &Pubkey,
+    goal: &Pubkey,
+    milestone: u8,
+    recipient: &Pubkey,
+) -> Instruction {
+    build(
+        accounts::ApproveMilestoneTransfer {
+            owner: *owner,
+            goal: *goal,
+            transfer_request: pda::milestone_transfer(goal, milestone),
+            vault: pda::vault(goal),
+            recipient: *recipient,
+        },
+        instruction::ApproveMilestoneTransfer { milestone },
+    )
+}
+
+/// Veto the goal's pending final transfer, or `milestone`'s payout. Only
+/// possible while the goal's transfer timelock runs
+pub fn veto_transfer(
+    member: &Pubkey,
+    goal: &Pubkey,
+    goal_owner: &Pubkey,
+    milestone: Option<u8>,
+) -> Instruction {
+    let transfer_request = match milestone {
+        Some(milestone) => pda::milestone_transfer(goal, milestone),
+        None => pda::transfer(goal),
+    };
+    build(
+        accounts::VetoTransfer {
+            member: *member,
+            goal: *goal,
+            transfer_request,
+            owner: *goal_owner,
+        },
+        instruction::VetoTransfer { milestone },
+    )
+}
+
+pub fn set_yield_adapter(
+    owner: &Pubkey,
+    goal: &Pubkey,
+    adapter: Option<YieldAdapter>,
+) -> Instruction {
+    build(
+        accounts::SetYieldAdapter {
+            owner: *owner,
+            goal: *goal,
+        },
+        instruction::SetYieldAdapter { adapter },
+    )
+}
+
+/// Stake the vault's balance with the vote account of the goal's
+/// `YieldAdapter::NativeStake`
+pub fn deposit_to_yield(owner: &Pubkey, goal: &Pubkey, vote_account: &Pubkey) -> Instruction {
+    build(
+        accounts::DepositToYield {
+            owner: *owner,
+            goal: *goal,
+            vault: pda::vault(goal),
+            stake_account: pda::stake(goal),
+            vote_account: *vote_account,
+            clock: sysvar::clock::ID,
+            rent: sysvar::rent::ID,
+            stake_history: sysvar::stake_history::ID,
+            stake_config: native_stake::CONFIG_ID,
+            stake_program: native_stake::PROGRAM_ID,
+            system_program: system_program::ID,
+        },
+        instruction::DepositToYield {},
+    )
+}
+
+pub fn begin_yield_withdrawal(caller: &Pubkey, goal: &Pubkey) -> Instruction {
+    build(
+        accounts::BeginYieldWithdrawal {
+            caller: *caller,
+            goal: *goal,
+            vault: pda::vault(goal),
+            stake_account: pda::stake(goal),
+            clock: sysvar::clock::ID,
+            stake_program: native_stake::PROGRAM_ID,
+        },
+        instruction::BeginYieldWithdrawal {},
+    )
+}
+
+pub fn withdraw_from_yield(goal: &Pubkey, goal_owner: &Pubkey) -> Instruction {
+    build(
+        accounts::WithdrawFromYield {
+            goal: *goal,
+            vault: pda::vault(goal),
+            stake_account: pda::stake(goal),
+            owner: *goal_owner,
+            clock: sysvar::clock::ID,
+            stake_history: sysvar::stake_history::ID,
+            stake_program: native_stake::PROGRAM_ID,
+        },
+        instruction::WithdrawFromYield {},
+    )
+}
+
+pub fn claim_yield(member: &Pubkey, goal: &Pubkey) -> Instruction {
+    build(
+        accounts::ClaimYield {
+            member: *member,
+            goal: *goal,
+            vault: pda::vault(goal),
+        },
+        instruction::ClaimYield {},
+    )
+}
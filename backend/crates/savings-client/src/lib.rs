@@ -0,0 +1,23 @@
+//! Rust client for the `savings_goal` and `savings_mxe` programs.
+//!
+//! - [`pda`]: addresses of every program-derived account the programs use
+//! - [`goal`] / [`mxe`]: one instruction builder per program instruction
+//! - [`accounts`]: decoders for the on-chain account types
+//! - [`cipher`]: x25519 + Rescue encryption of `Enc<Shared, _>` values
+//! - [`events`]: decoding of program events from transaction logs
+//!
+//! Builders only assemble `Instruction`s; signing and sending is left to the
+//! caller's RPC stack.
+
+pub mod accounts;
+pub mod cipher;
+pub mod events;
+pub mod goal;
+pub mod mxe;
+pub mod pda;
+
+pub use anchor_lang::solana_program::instruction::Instruction;
+pub use anchor_lang::solana_program::pubkey::Pubkey;
+
+pub use savings_goal::ID as SAVINGS_GOAL_ID;
+pub use savings_mxe::ID as SAVINGS_MXE_ID;
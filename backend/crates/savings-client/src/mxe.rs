@@ -0,0 +1,807 @@
+//! Instruction builders for `savings_mxe`.
+//!
+//! Callback instructions are invoked by the Arcium program when a computation
+//! finishes and have no builder here.
+
+use anchor_lang::solana_program::instruction::AccountMeta;
+use anchor_lang::solana_program::system_program;
+use anchor_lang::{Id, InstructionData, ToAccountMetas};
+use arcium_anchor::prelude::{
+    Arcium, ARCIUM_CLOCK_ACCOUNT_ADDRESS, ARCIUM_FEE_POOL_ACCOUNT_ADDRESS,
+};
+use savings_mxe::{accounts, instruction};
+
+pub use savings_mxe::{Circuit, CircuitVersion, OffChainSource};
+
+use crate::cipher::SharedCiphertext;
+use crate::{pda, Instruction, Pubkey, SAVINGS_GOAL_ID, SAVINGS_MXE_ID};
+
+fn build(accounts: impl ToAccountMetas, data: impl InstructionData) -> Instruction {
+    Instruction {
+        program_id: SAVINGS_MXE_ID,
+        accounts: accounts.to_account_metas(None),
+        data: data.data(),
+    }
+}
+
+/// Version of `circuit` whose queue/callback instructions this program build contains
+pub fn compiled_version(circuit: Circuit) -> u16 {
+    circuit.compiled_version()
+}
+
+// ============================================================================
+// Config and circuit registry
+// ============================================================================
+
+/// Only the program's upgrade authority may initialize the config
+pub fn initialize_config(admin: &Pubkey, mxe_authority: Pubkey) -> Instruction {
+    build(
+        accounts::InitializeConfig {
+            admin: *admin,
+            program: SAVINGS_MXE_ID,
+            program_data: pda::mxe_program_data(),
+            config: pda::config(),
+            system_program: system_program::ID,
+        },
+        instruction::InitializeConfig { mxe_authority },
+    )
+}
+
+pub fn propose_admin(admin: &Pubkey, new_admin: Pubkey) -> Instruction {
+    build(
+        accounts::UpdateConfig {
+            admin: *admin,
+            config: pda::config(),
+        },
+        instruction::ProposeAdmin { new_admin },
+    )
+}
+
+pub fn accept_admin(new_admin: &Pubkey) -> Instruction {
+    build(
+        accounts::AcceptAdmin {
+            new_admin: *new_admin,
+            config: pda::config(),
+        },
+        instruction::AcceptAdmin {},
+    )
+}
+
+pub fn set_mxe_authority(admin: &Pubkey, mxe_authority: Pubkey) -> Instruction {
+    build(
+        accounts::UpdateConfig {
+            admin: *admin,
+            config: pda::config(),
+        },
+        instruction::SetMxeAuthority { mxe_authority },
+    )
+}
+
+pub fn set_comp_def_settings(
+    admin: &Pubkey,
+    offset: u32,
+    finalize_during_callback: bool,
+    cu_amount: u64,
+) -> Instruction {
+    build(
+        accounts::UpdateConfig {
+            admin: *admin,
+            config: pda::config(),
+        },
+        instruction::SetCompDefSettings {
+            offset,
+            finalize_during_callback,
+            cu_amount,
+        },
+    )
+}
+
+pub fn register_circuit_version(admin: &Pubkey, circuit: Circuit, version: u16) -> Instruction {
+    build(
+        accounts::RegisterCircuitVersion {
+            admin: *admin,
+            config: pda::config(),
+            circuit_version: pda::circuit_version(circuit, version),
+            system_program: system_program::ID,
+        },
+        instruction::RegisterCircuitVersion { circuit, version },
+    )
+}
+
+pub fn set_active_version(admin: &Pubkey, circuit: Circuit, version: u16) -> Instruction {
+    build(
+        accounts::SetActiveVersion {
+            admin: *admin,
+            config: pda::config(),
+            circuit_version: pda::circuit_version(circuit, version),
+        },
+        instruction::SetActiveVersion { circuit, version },
+    )
+}
+
+pub fn pin_goal_circuits(owner: &Pubkey, goal: &Pubkey) -> Instruction {
+    build(
+        accounts::PinGoalCircuits {
+            owner: *owner,
+            goal: *goal,
+            config: pda::config(),
+            goal_circuits: pda::goal_circuits(goal),
+            system_program: system_program::ID,
+        },
+        instruction::PinGoalCircuits {},
+    )
+}
+
+/// Simulate this instruction and read the `u16` return data to learn which
+/// version of `circuit` a goal is pinned to
+pub fn get_goal_circuit_version(goal: &Pubkey, circuit: Circuit) -> Instruction {
+    build(
+        accounts::GetGoalCircuitVersion {
+            goal_circuits: pda::goal_circuits(goal),
+        },
+        instruction::GetGoalCircuitVersion { circuit },
+    )
+}
+
+// ============================================================================
+// Computation definitions
+// ============================================================================
+
+/// Initialize the comp def of the compiled version of `circuit`
+pub fn init_comp_def(
+    payer: &Pubkey,
+    circuit: Circuit,
+    circuit_source: Option<OffChainSource>,
+) -> Instruction {
+    let comp_def_account = pda::comp_def(circuit, compiled_version(circuit));
+    match circuit {
+        Circuit::AddTwoContributions => build(
+            accounts::InitAddTwoCompDef {
+                payer: *payer,
+                mxe_account: pda::mxe(),
+                comp_def_account,
+                config: pda::config(),
+                arcium_program: Arcium::id(),
+                system_program: system_program::ID,
+            },
+            instruction::InitAddTwoContributionsCompDef { circuit_source },
+        ),
+        Circuit::CheckGoalReached => build(
+            accounts::InitCheckGoalCompDef {
+                payer: *payer,
+                mxe_account: pda::mxe(),
+                comp_def_account,
+                config: pda::config(),
+                arcium_program: Arcium::id(),
+                system_program: system_program::ID,
+            },
+            instruction::InitCheckGoalReachedCompDef { circuit_source },
+        ),
+        Circuit::RevealContributions10 => build(
+            accounts::InitReveal10CompDef {
+                payer: *payer,
+                mxe_account: pda::mxe(),
+                comp_def_account,
+                config: pda::config(),
+                arcium_program: Arcium::id(),
+                system_program: system_program::ID,
+            },
+            instruction::InitRevealContributions10CompDef { circuit_source },
+        ),
+        Circuit::AddAndCheckGoal => build(
+            accounts::InitAddAndCheckGoalCompDef {
+                payer: *payer,
+                mxe_account: pda::mxe(),
+                comp_def_account,
+                config: pda::config(),
+                arcium_program: Arcium::id(),
+                system_program: system_program::ID,
+            },
+            instruction::InitAddAndCheckGoalCompDef { circuit_source },
+        ),
+        Circuit::CheckPeriodContribution => build(
+            accounts::InitCheckPeriodCompDef {
+                payer: *payer,
+                mxe_account: pda::mxe(),
+                comp_def_account,
+                config: pda::config(),
+                arcium_program: Arcium::id(),
+                system_program: system_program::ID,
+            },
+            instruction::InitCheckPeriodContributionCompDef { circuit_source },
+        ),
+        Circuit::MemberProgress => build(
+            accounts::InitMemberProgressCompDef {
+                payer: *payer,
+                mxe_account: pda::mxe(),
+                comp_def_account,
+                config: pda::config(),
+                arcium_program: Arcium::id(),
+                system_program: system_program::ID,
+            },
+            instruction::InitMemberProgressCompDef { circuit_source },
+        ),
+        Circuit::GroupStats => build(
+            accounts::InitGroupStatsCompDef {
+                payer: *payer,
+                mxe_account: pda::mxe(),
+                comp_def_account,
+                config: pda::config(),
+                arcium_program: Arcium::id(),
+                system_program: system_program::ID,
+            },
+            instruction::InitGroupStatsCompDef { circuit_source },
+        ),
+        Circuit::RevealOwnContributions => build(
+            accounts::InitRevealOwnCompDef {
+                payer: *payer,
+                mxe_account: pda::mxe(),
+                comp_def_account,
+                config: pda::config(),
+                arcium_program: Arcium::id(),
+                system_program: system_program::ID,
+            },
+            instruction::InitRevealOwnContributionsCompDef { circuit_source },
+        ),
+        Circuit::AuditContributions => build(
+            accounts::InitAuditCompDef {
+                payer: *payer,
+                mxe_account: pda::mxe(),
+                comp_def_account,
+                config: pda::config(),
+                arcium_program: Arcium::id(),
+                system_program: system_program::ID,
+            },
+            instruction::InitAuditContributionsCompDef { circuit_source },
+        ),
+        Circuit::RevealFinalTotal => build(
+            accounts::InitRevealFinalTotalCompDef {
+                payer: *payer,
+                mxe_account: pda::mxe(),
+                comp_def_account,
+                config: pda::config(),
+                arcium_program: Arcium::id(),
+                system_program: system_program::ID,
+            },
+            instruction::InitRevealFinalTotalCompDef { circuit_source },
+        ),
+        Circuit::CheckVaultBalance => build(
+            accounts::InitCheckVaultBalanceCompDef {
+                payer: *payer,
+                mxe_account: pda::mxe(),
+                comp_def_account,
+                config: pda::config(),
+                arcium_program: Arcium::id(),
+                system_program: system_program::ID,
+            },
+            instruction::InitCheckVaultBalanceCompDef { circuit_source },
+        ),
+        Circuit::CheckMilestones => build(
+            accounts::InitCheckMilestonesCompDef {
+                payer: *payer,
+                mxe_account: pda::mxe(),
+                comp_def_account,
+                config: pda::config(),
+                arcium_program: Arcium::id(),
+                system_program: system_program::ID,
+            },
+            instruction::InitCheckMilestonesCompDef { circuit_source },
+        ),
+        Circuit::DistributeYield => build(
+            accounts::InitDistributeYieldCompDef {
+                payer: *payer,
+                mxe_account: pda::mxe(),
+                comp_def_account,
+                config: pda::config(),
+                arcium_program: Arcium::id(),
+                system_program: system_program::ID,
+            },
+            instruction::InitDistributeYieldCompDef { circuit_source },
+        ),
+    }
+}
+
+// ============================================================================
+// Computations
+// ============================================================================
+
+/// Where and how a computation is queued
+#[derive(Clone, Copy, Debug)]
+pub struct QueueParams {
+    pub payer: Pubkey,
+    /// Goal whose pinned circuit versions the computation runs against
+    pub goal: Pubkey,
+    pub computation_offset: u64,
+    /// Offset of the Arcium cluster the MXE is attached to
+    pub cluster_offset: u32,
+}
+
+pub fn add_two_contributions(
+    params: &QueueParams,
+    ciphertext_0: [u8; 32],
+    ciphertext_1: [u8; 32],
+    pub_key: [u8; 32],
+    nonce: u128,
+) -> Instruction {
+    let circuit = Circuit::AddTwoContributions;
+    build(
+        accounts::AddTwoContributions {
+            payer: params.payer,
+            sign_pda_account: pda::sign_pda(),
+            mxe_account: pda::mxe(),
+            mempool_account: pda::mempool(),
+            executing_pool: pda::execpool(),
+            computation_account: pda::computation(params.computation_offset),
+            comp_def_account: pda::comp_def(circuit, compiled_version(circuit)),
+            goal: params.goal,
+            goal_circuits: pda::goal_circuits(&params.goal),
+            computation_record: pda::computation_record(params.computation_offset),
+            cluster_account: pda::cluster(params.cluster_offset),
+            pool_account: ARCIUM_FEE_POOL_ACCOUNT_ADDRESS,
+            clock_account: ARCIUM_CLOCK_ACCOUNT_ADDRESS,
+            system_program: system_program::ID,
+            arcium_program: Arcium::id(),
+        },
+        instruction::AddTwoContributions {
+            computation_offset: params.computation_offset,
+            ciphertext_0,
+            ciphertext_1,
+            pub_key,
+            nonce,
+        },
+    )
+}
+
+/// Add up every member's contribution to `params.goal` and compare the
+/// total against the goal's target; the goal is marked reached if it is met.
+/// `members` is the goal's full member list, in order.
+pub fn add_and_check_goal(params: &QueueParams, members: &[Pubkey]) -> Instruction {
+    let circuit = Circuit::AddAndCheckGoal;
+    let mut ix = build(
+        accounts::AddAndCheckGoal {
+            payer: params.payer,
+            sign_pda_account: pda::sign_pda(),
+            mxe_account: pda::mxe(),
+            mempool_account: pda::mempool(),
+            executing_pool: pda::execpool(),
+            computation_account: pda::computation(params.computation_offset),
+            comp_def_account: pda::comp_def(circuit, compiled_version(circuit)),
+            goal: params.goal,
+            goal_circuits: pda::goal_circuits(&params.goal),
+            computation_record: pda::computation_record(params.computation_offset),
+            cluster_account: pda::cluster(params.cluster_offset),
+            pool_account: ARCIUM_FEE_POOL_ACCOUNT_ADDRESS,
+            clock_account: ARCIUM_CLOCK_ACCOUNT_ADDRESS,
+            system_program: system_program::ID,
+            arcium_program: Arcium::id(),
+        },
+        instruction::AddAndCheckGoal {
+            computation_offset: params.computation_offset,
+        },
+    );
+    ix.accounts
+        .extend(contribution_metas(&params.goal, members));
+    ix
+}
+
+/// Check `member`'s contribution for `period` of `params.goal`'s schedule
+/// against the expected amount
+pub fn check_period_contribution(
+    params: &QueueParams,
+    member: &Pubkey,
+    period: u32,
+) -> Instruction {
+    let circuit = Circuit::CheckPeriodContribution;
+    build(
+        accounts::CheckPeriodContribution {
+            payer: params.payer,
+            sign_pda_account: pda::sign_pda(),
+            mxe_account: pda::mxe(),
+            mempool_account: pda::mempool(),
+            executing_pool: pda::execpool(),
+            computation_account: pda::computation(params.computation_offset),
+            comp_def_account: pda::comp_def(circuit, compiled_version(circuit)),
+            goal: params.goal,
+            goal_circuits: pda::goal_circuits(&params.goal),
+            period_contribution: pda::period_contribution(&params.goal, member, period),
+            computation_record: pda::computation_record(params.computation_offset),
+            cluster_account: pda::cluster(params.cluster_offset),
+            pool_account: ARCIUM_FEE_POOL_ACCOUNT_ADDRESS,
+            clock_account: ARCIUM_CLOCK_ACCOUNT_ADDRESS,
+            system_program: system_program::ID,
+            arcium_program: Arcium::id(),
+        },
+        instruction::CheckPeriodContribution {
+            computation_offset: params.computation_offset,
+        },
+    )
+}
+
+/// Compute aggregate statistics over every member's stored contribution; 3
+/// to 10 members must have one. `members` is the goal's full member list, in
+/// order. Only the goal owner can queue this.
+pub fn compute_group_stats(params: &QueueParams, members: &[Pubkey]) -> Instruction {
+    let circuit = Circuit::GroupStats;
+    let mut ix = build(
+        accounts::ComputeGroupStats {
+            payer: params.payer,
+            sign_pda_account: pda::sign_pda(),
+            mxe_account: pda::mxe(),
+            mempool_account: pda::mempool(),
+            executing_pool: pda::execpool(),
+            computation_account: pda::computation(params.computation_offset),
+            comp_def_account: pda::comp_def(circuit, compiled_version(circuit)),
+            goal: params.goal,
+            goal_circuits: pda::goal_circuits(&params.goal),
+            group_stats: pda::group_stats(&params.goal),
+            computation_record: pda::computation_record(params.computation_offset),
+            cluster_account: pda::cluster(params.cluster_offset),
+            pool_account: ARCIUM_FEE_POOL_ACCOUNT_ADDRESS,
+            clock_account: ARCIUM_CLOCK_ACCOUNT_ADDRESS,
+            system_program: system_program::ID,
+            arcium_program: Arcium::id(),
+        },
+        instruction::ComputeGroupStats {
+            computation_offset: params.computation_offset,
+        },
+    );
+    ix.accounts
+        .extend(contribution_metas(&params.goal, members));
+    ix
+}
+
+/// Hand the stored contributions of `contributors` (1 to 5 of them) back to
+/// their contributors, each encrypted to the key it was made with. The payer
+/// must be a goal member.
+pub fn reveal_own_contributions(params: &QueueParams, contributors: &[Pubkey]) -> Instruction {
+    let circuit = Circuit::RevealOwnContributions;
+    let mut ix = build(
+        accounts::RevealOwnContributions {
+            payer: params.payer,
+            sign_pda_account: pda::sign_pda(),
+            mxe_account: pda::mxe(),
+            mempool_account: pda::mempool(),
+            executing_pool: pda::execpool(),
+            computation_account: pda::computation(params.computation_offset),
+            comp_def_account: pda::comp_def(circuit, compiled_version(circuit)),
+            goal: params.goal,
+            goal_circuits: pda::goal_circuits(&params.goal),
+            computation_record: pda::computation_record(params.computation_offset),
+            cluster_account: pda::cluster(params.cluster_offset),
+            pool_account: ARCIUM_FEE_POOL_ACCOUNT_ADDRESS,
+            clock_account: ARCIUM_CLOCK_ACCOUNT_ADDRESS,
+            system_program: system_program::ID,
+            arcium_program: Arcium::id(),
+        },
+        instruction::RevealOwnContributions {
+            computation_offset: params.computation_offset,
+        },
+    );
+    ix.accounts
+        .extend(contribution_metas(&params.goal, contributors));
+    ix
+}
+
+/// Reveal the stored contributions of `contributors` (1 to 10 of them, all
+/// opted in) to the goal owner, re-encrypted to `recipient_pubkey`. Only the
+/// goal owner can queue this.
+pub fn reveal_to_owner(
+    params: &QueueParams,
+    contributors: &[Pubkey],
+    recipient_pubkey: [u8; 32],
+    recipient_nonce: u128,
+) -> Instruction {
+    let circuit = Circuit::RevealContributions10;
+    let mut ix = build(
+        accounts::RevealToOwner {
+            payer: params.payer,
+            sign_pda_account: pda::sign_pda(),
+            mxe_account: pda::mxe(),
+            mempool_account: pda::mempool(),
+            executing_pool: pda::execpool(),
+            computation_account: pda::computation(params.computation_offset),
+            comp_def_account: pda::comp_def(circuit, compiled_version(circuit)),
+            goal: params.goal,
+            goal_circuits: pda::goal_circuits(&params.goal),
+            computation_record: pda::computation_record(params.computation_offset),
+            cluster_account: pda::cluster(params.cluster_offset),
+            pool_account: ARCIUM_FEE_POOL_ACCOUNT_ADDRESS,
+            clock_account: ARCIUM_CLOCK_ACCOUNT_ADDRESS,
+            system_program: system_program::ID,
+            arcium_program: Arcium::id(),
+        },
+        instruction::RevealToOwner {
+            computation_offset: params.computation_offset,
+            recipient_pubkey,
+            recipient_nonce,
+        },
+    );
+    ix.accounts
+        .extend(contribution_metas(&params.goal, contributors));
+    ix
+}
+
+/// Re-encrypt the stored contributions of `contributors` (1 to 10 of them)
+/// and their total to the goal's auditor key, using `auditor_nonce`. The
+/// result is kept in [`pda::audit`]. Only the goal owner can queue this.
+pub fn audit_contributions(
+    params: &QueueParams,
+    contributors: &[Pubkey],
+    auditor_nonce: u128,
+) -> Instruction {
+    let circuit = Circuit::AuditContributions;
+    let mut ix = build(
+        accounts::AuditContributions {
+            payer: params.payer,
+            sign_pda_account: pda::sign_pda(),
+            mxe_account: pda::mxe(),
+            mempool_account: pda::mempool(),
+            executing_pool: pda::execpool(),
+            computation_account: pda::computation(params.computation_offset),
+            comp_def_account: pda::comp_def(circuit, compiled_version(circuit)),
+            goal: params.goal,
+            goal_circuits: pda::goal_circuits(&params.goal),
+            audit: pda::audit(&params.goal),
+            computation_record: pda::computation_record(params.computation_offset),
+            cluster_account: pda::cluster(params.cluster_offset),
+            pool_account: ARCIUM_FEE_POOL_ACCOUNT_ADDRESS,
+            clock_account: ARCIUM_CLOCK_ACCOUNT_ADDRESS,
+            system_program: system_program::ID,
+            arcium_program: Arcium::id(),
+        },
+        instruction::AuditContributions {
+            computation_offset: params.computation_offset,
+            auditor_nonce,
+        },
+    );
+    ix.accounts
+        .extend(contribution_metas(&params.goal, contributors));
+    ix
+}
+
+/// Finalize the goal and queue the reveal of its total, which is recorded on
+/// the goal only if it met the target. `members` is the goal's full member
+/// list, in order; see [`crate::goal::finalize_and_reveal`] for who may call
+/// this.
+pub fn finalize_and_reveal_total(params: &QueueParams, members: &[Pubkey]) -> Instruction {
+    let circuit = Circuit::RevealFinalTotal;
+    let mut ix = build(
+        accounts::FinalizeAndRevealTotal {
+            payer: params.payer,
+            sign_pda_account: pda::sign_pda(),
+            mxe_account: pda::mxe(),
+            mempool_account: pda::mempool(),
+            executing_pool: pda::execpool(),
+            computation_account: pda::computation(params.computation_offset),
+            comp_def_account: pda::comp_def(circuit, compiled_version(circuit)),
+            goal: params.goal,
+            goal_circuits: pda::goal_circuits(&params.goal),
+            computation_record: pda::computation_record(params.computation_offset),
+            cluster_account: pda::cluster(params.cluster_offset),
+            pool_account: ARCIUM_FEE_POOL_ACCOUNT_ADDRESS,
+            clock_account: ARCIUM_CLOCK_ACCOUNT_ADDRESS,
+            savings_goal_program: SAVINGS_GOAL_ID,
+            system_program: system_program::ID,
+            arcium_program: Arcium::id(),
+        },
+        instruction::FinalizeAndRevealTotal {
+            computation_offset: params.computation_offset,
+        },
+    );
+    ix.accounts
+        .extend(contribution_metas(&params.goal, members));
+    ix
+}
+
+/// Compare what was deposited into the goal's vault with the total of its
+/// members' contributions; `members` is the goal's full member list, in order. The
+/// payer must be a member.
+pub fn check_vault_balance(params: &QueueParams, members: &[Pubkey]) -> Instruction {
+    let circuit = Circuit::CheckVaultBalance;
+    let mut ix = build(
+        accounts::CheckVaultBalance {
+            payer: params.payer,
+            sign_pda_account: pda::sign_pda(),
+            mxe_account: pda::mxe(),
+            mempool_account: pda::mempool(),
+            executing_pool: pda::execpool(),
+            computation_account: pda::computation(params.computation_offset),
+            comp_def_account: pda::comp_def(circuit, compiled_version(circuit)),
+            goal: params.goal,
+            goal_circuits: pda::goal_circuits(&params.goal),
+            computation_record: pda::computation_record(params.computation_offset),
+            cluster_account: pda::cluster(params.cluster_offset),
+            pool_account: ARCIUM_FEE_POOL_ACCOUNT_ADDRESS,
+            clock_account: ARCIUM_CLOCK_ACCOUNT_ADDRESS,
+            system_program: system_program::ID,
+            arcium_program: Arcium::id(),
+        },
+        instruction::CheckVaultBalance {
+            computation_offset: params.computation_offset,
+        },
+    );
+    ix.accounts
+        .extend(contribution_metas(&params.goal, members));
+    ix
+}
+
+/// Count the goal's milestones crossed by the total of its members'
+/// contributions; `members` is the goal's full member list, in order. The
+/// payer must be a member.
+pub fn check_milestones(params: &QueueParams, members: &[Pubkey]) -> Instruction {
+    let circuit = Circuit::CheckMilestones;
+    let mut ix = build(
+        accounts::CheckMilestones {
+            payer: params.payer,
+            sign_pda_account: pda::sign_pda(),
+            mxe_account: pda::mxe(),
+            mempool_account: pda::mempool(),
+            executing_pool: pda::execpool(),
+            computation_account: pda::computation(params.computation_offset),
+            comp_def_account: pda::comp_def(circuit, compiled_version(circuit)),
+            goal: params.goal,
+            goal_circuits: pda::goal_circuits(&params.goal),
+            computation_record: pda::computation_record(params.computation_offset),
+            cluster_account: pda::cluster(params.cluster_offset),
+            pool_account: ARCIUM_FEE_POOL_ACCOUNT_ADDRESS,
+            clock_account: ARCIUM_CLOCK_ACCOUNT_ADDRESS,
+            system_program: system_program::ID,
+            arcium_program: Arcium::id(),
+        },
+        instruction::CheckMilestones {
+            computation_offset: params.computation_offset,
+        },
+    );
+    ix.accounts
+        .extend(contribution_metas(&params.goal, members));
+    ix
+}
+
+/// Split a finalized goal's yield over its members in proportion to their
+/// contributions; `members` is the goal's full member list, in order. The
+/// payer must be a member.
+pub fn distribute_yield(params: &QueueParams, members: &[Pubkey]) -> Instruction {
+    let circuit = Circuit::DistributeYield;
+    let mut ix = build(
+        accounts::DistributeYield {
+            payer: params.payer,
+            sign_pda_account: pda::sign_pda(),
+            mxe_account: pda::mxe(),
+            mempool_account: pda::mempool(),
+            executing_pool: pda::execpool(),
+            computation_account: pda::computation(params.computation_offset),
+            comp_def_account: pda::comp_def(circuit, compiled_version(circuit)),
+            goal: params.goal,
+            goal_circuits: pda::goal_circuits(&params.goal),
+            computation_record: pda::computation_record(params.computation_offset),
+            cluster_account: pda::cluster(params.cluster_offset),
+            pool_account: ARCIUM_FEE_POOL_ACCOUNT_ADDRESS,
+            clock_account: ARCIUM_CLOCK_ACCOUNT_ADDRESS,
+            system_program: system_program::ID,
+            arcium_program: Arcium::id(),
+        },
+        instruction::DistributeYield {
+            computation_offset: params.computation_offset,
+        },
+    );
+    ix.accounts
+        .extend(contribution_metas(&params.goal, members));
+    ix
+}
+
+/// Stored contributions passed as remaining accounts, in `contributors` order
+fn contribution_metas<'a>(
+    goal: &'a Pubkey,
+    contributors: &'a [Pubkey],
+) -> impl Iterator<Item = AccountMeta> + 'a {
+    contributors
+        .iter()
+        .map(|contributor| AccountMeta::new_readonly(pda::contribution(goal, contributor), false))
+}
+
+/// Check `params.payer`'s own contribution against their encrypted
+/// `commitment`. The result is encrypted to the key the commitment was
+/// encrypted with, under `recipient_nonce`, and kept in
+/// [`pda::member_progress`].
+pub fn check_member_progress(
+    params: &QueueParams,
+    commitment: &SharedCiphertext,
+    recipient_nonce: u128,
+) -> Instruction {
+    let circuit = Circuit::MemberProgress;
+    build(
+        accounts::CheckMemberProgress {
+            payer: params.payer,
+            sign_pda_account: pda::sign_pda(),
+            mxe_account: pda::mxe(),
+            mempool_account: pda::mempool(),
+            executing_pool: pda::execpool(),
+            computation_account: pda::computation(params.computation_offset),
+            comp_def_account: pda::comp_def(circuit, compiled_version(circuit)),
+            goal: params.goal,
+            goal_circuits: pda::goal_circuits(&params.goal),
+            contribution: pda::contribution(&params.goal, &params.payer),
+            member_progress: pda::member_progress(&params.goal, &params.payer),
+            computation_record: pda::computation_record(params.computation_offset),
+            cluster_account: pda::cluster(params.cluster_offset),
+            pool_account: ARCIUM_FEE_POOL_ACCOUNT_ADDRESS,
+            clock_account: ARCIUM_CLOCK_ACCOUNT_ADDRESS,
+            system_program: system_program::ID,
+            arcium_program: Arcium::id(),
+        },
+        instruction::CheckMemberProgress {
+            computation_offset: params.computation_offset,
+            commitment: commitment.ciphertext,
+            commitment_nonce: commitment.nonce,
+            recipient_pubkey: commitment.pub_key,
+            recipient_nonce,
+        },
+    )
+}
+
+pub fn check_goal_reached(
+    params: &QueueParams,
+    ciphertext_0: [u8; 32],
+    target: u64,
+    pub_key: [u8; 32],
+    nonce: u128,
+) -> Instruction {
+    let circuit = Circuit::CheckGoalReached;
+    build(
+        accounts::CheckGoalReached {
+            payer: params.payer,
+            sign_pda_account: pda::sign_pda(),
+            mxe_account: pda::mxe(),
+            mempool_account: pda::mempool(),
+            executing_pool: pda::execpool(),
+            computation_account: pda::computation(params.computation_offset),
+            comp_def_account: pda::comp_def(circuit, compiled_version(circuit)),
+            goal: params.goal,
+            goal_circuits: pda::goal_circuits(&params.goal),
+            computation_record: pda::computation_record(params.computation_offset),
+            cluster_account: pda::cluster(params.cluster_offset),
+            pool_account: ARCIUM_FEE_POOL_ACCOUNT_ADDRESS,
+            clock_account: ARCIUM_CLOCK_ACCOUNT_ADDRESS,
+            system_program: system_program::ID,
+            arcium_program: Arcium::id(),
+        },
+        instruction::CheckGoalReached {
+            computation_offset: params.computation_offset,
+            ciphertext_0,
+            target,
+            pub_key,
+            nonce,
+        },
+    )
+}
+
+/// Re-queue the failed `circuit` computation at `failed_offset` under
+/// `params.computation_offset`; only its requester may do so
+pub fn retry_computation(
+    params: &QueueParams,
+    failed_offset: u64,
+    circuit: Circuit,
+) -> Instruction {
+    build(
+        accounts::RetryComputation {
+            payer: params.payer,
+            sign_pda_account: pda::sign_pda(),
+            mxe_account: pda::mxe(),
+            mempool_account: pda::mempool(),
+            executing_pool: pda::execpool(),
+            computation_account: pda::computation(params.computation_offset),
+            comp_def_account: pda::comp_def(circuit, compiled_version(circuit)),
+            goal_circuits: pda::goal_circuits(&params.goal),
+            failed_record: pda::computation_record(failed_offset),
+            computation_record: pda::computation_record(params.computation_offset),
+            cluster_account: pda::cluster(params.cluster_offset),
+            pool_account: ARCIUM_FEE_POOL_ACCOUNT_ADDRESS,
+            clock_account: ARCIUM_CLOCK_ACCOUNT_ADDRESS,
+            system_program: system_program::ID,
+            arcium_program: Arcium::id(),
+        },
+        instruction::RetryComputation {
+            computation_offset: params.computation_offset,
+        },
+    )
+}
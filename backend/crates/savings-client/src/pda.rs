@@ -0,0 +1,219 @@
+//! Program-derived addresses for `savings_goal`, `savings_mxe` and the Arcium
+//! accounts the MXE queue instructions reference.
+
+use anchor_lang::Id;
+use arcium_anchor::prelude::{
+    comp_def_offset, Arcium, ARCIUM_CLOCK_ACCOUNT_ADDRESS, ARCIUM_FEE_POOL_ACCOUNT_ADDRESS,
+    SIGN_PDA_SEED,
+};
+use savings_mxe::Circuit;
+
+use crate::{Pubkey, SAVINGS_GOAL_ID, SAVINGS_MXE_ID};
+
+// Seeds of the Arcium-owned accounts, mirroring `@arcium-hq/client`
+const MXE_SEED: &[u8] = b"MXEAccount";
+const MEMPOOL_SEED: &[u8] = b"Mempool";
+const EXECPOOL_SEED: &[u8] = b"Execpool";
+const COMPUTATION_SEED: &[u8] = b"ComputationAccount";
+const COMP_DEF_SEED: &[u8] = b"ComputationDefinitionAccount";
+const CLUSTER_SEED: &[u8] = b"Cluster";
+
+// ============================================================================
+// savings_goal
+// ============================================================================
+
+pub fn goal(owner: &Pubkey, goal_id: u64) -> Pubkey {
+    Pubkey::find_program_address(
+        &[b"goal", owner.as_ref(), &goal_id.to_le_bytes()],
+        &SAVINGS_GOAL_ID,
+    )
+    .0
+}
+
+pub fn contribution(goal: &Pubkey, contributor: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[b"contribution", goal.as_ref(), contributor.as_ref()],
+        &SAVINGS_GOAL_ID,
+    )
+    .0
+}
+
+/// A member's record for one period of a goal's contribution schedule
+pub fn period_contribution(goal: &Pubkey, member: &Pubkey, period: u32) -> Pubkey {
+    Pubkey::find_program_address(
+        &[
+            b"period",
+            goal.as_ref(),
+            member.as_ref(),
+            &period.to_le_bytes(),
+        ],
+        &SAVINGS_GOAL_ID,
+    )
+    .0
+}
+
+pub fn transfer(goal: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"transfer", goal.as_ref()], &SAVINGS_GOAL_ID).0
+}
+
+/// Transfer request for the payout of one of the goal's milestones
+pub fn milestone_transfer(goal: &Pubkey, milestone: u8) -> Pubkey {
+    Pubkey::find_program_address(
+        &[b"transfer", goal.as_ref(), &[milestone]],
+        &SAVINGS_GOAL_ID,
+    )
+    .0
+}
+
+pub fn vault(goal: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"vault", goal.as_ref()], &SAVINGS_GOAL_ID).0
+}
+
+/// Stake account the vault's funds are delegated from under
+/// `YieldAdapter::NativeStake`
+pub fn stake(goal: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"stake", goal.as_ref()], &SAVINGS_GOAL_ID).0
+}
+
+/// Members' approvals for adding `recipient` to the goal's allow-list
+pub fn recipient_proposal(goal: &Pubkey, recipient: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[b"recipient", goal.as_ref(), recipient.as_ref()],
+        &SAVINGS_GOAL_ID,
+    )
+    .0
+}
+
+// ============================================================================
+// savings_mxe
+// ============================================================================
+
+/// `savings_mxe`'s program data, which holds its upgrade authority
+// solana-program 2.3 deprecates its loader re-exports in favour of
+// solana-loader-v3-interface, which it re-exports unchanged
+#[allow(deprecated)]
+pub fn mxe_program_data() -> Pubkey {
+    use anchor_lang::solana_program::bpf_loader_upgradeable;
+
+    Pubkey::find_program_address(&[SAVINGS_MXE_ID.as_ref()], &bpf_loader_upgradeable::ID).0
+}
+
+pub fn config() -> Pubkey {
+    Pubkey::find_program_address(&[b"config"], &SAVINGS_MXE_ID).0
+}
+
+pub fn circuit_version(circuit: Circuit, version: u16) -> Pubkey {
+    Pubkey::find_program_address(
+        &[b"circuit", &[circuit as u8], &version.to_le_bytes()],
+        &SAVINGS_MXE_ID,
+    )
+    .0
+}
+
+pub fn goal_circuits(goal: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"goal_circuits", goal.as_ref()], &SAVINGS_MXE_ID).0
+}
+
+/// Record linking a queued computation to its goal
+pub fn computation_record(computation_offset: u64) -> Pubkey {
+    Pubkey::find_program_address(
+        &[b"computation", &computation_offset.to_le_bytes()],
+        &SAVINGS_MXE_ID,
+    )
+    .0
+}
+
+/// Latest aggregate statistics over a goal's contributions
+pub fn group_stats(goal: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"group_stats", goal.as_ref()], &SAVINGS_MXE_ID).0
+}
+
+/// Latest audit of a goal's contributions, encrypted to its auditor
+pub fn audit(goal: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"audit", goal.as_ref()], &SAVINGS_MXE_ID).0
+}
+
+/// A member's latest private progress check on a goal
+pub fn member_progress(goal: &Pubkey, member: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[b"member_progress", goal.as_ref(), member.as_ref()],
+        &SAVINGS_MXE_ID,
+    )
+    .0
+}
+
+/// PDA that signs `savings_goal`'s MXE-only instructions from callbacks
+pub fn goal_authority() -> Pubkey {
+    Pubkey::find_program_address(&[savings_goal::GOAL_AUTHORITY_SEED], &SAVINGS_MXE_ID).0
+}
+
+pub fn sign_pda() -> Pubkey {
+    Pubkey::find_program_address(&[SIGN_PDA_SEED.as_ref()], &SAVINGS_MXE_ID).0
+}
+
+// ============================================================================
+// Arcium
+// ============================================================================
+
+/// Comp def offset of a specific circuit version
+pub fn comp_def_offset_of(circuit: Circuit, version: u16) -> u32 {
+    comp_def_offset(&circuit.ix_name(version))
+}
+
+pub fn mxe() -> Pubkey {
+    Pubkey::find_program_address(&[MXE_SEED, SAVINGS_MXE_ID.as_ref()], &Arcium::id()).0
+}
+
+pub fn mempool() -> Pubkey {
+    Pubkey::find_program_address(&[MEMPOOL_SEED, SAVINGS_MXE_ID.as_ref()], &Arcium::id()).0
+}
+
+pub fn execpool() -> Pubkey {
+    Pubkey::find_program_address(&[EXECPOOL_SEED, SAVINGS_MXE_ID.as_ref()], &Arcium::id()).0
+}
+
+pub fn computation(computation_offset: u64) -> Pubkey {
+    Pubkey::find_program_address(
+        &[
+            COMPUTATION_SEED,
+            SAVINGS_MXE_ID.as_ref(),
+            &computation_offset.to_le_bytes(),
+        ],
+        &Arcium::id(),
+    )
+    .0
+}
+
+pub fn comp_def(circuit: Circuit, version: u16) -> Pubkey {
+    Pubkey::find_program_address(
+        &[
+            COMP_DEF_SEED,
+            SAVINGS_MXE_ID.as_ref(),
+            &comp_def_offset_of(circuit, version).to_le_bytes(),
+        ],
+        &Arcium::id(),
+    )
+    .0
+}
+
+pub fn cluster(cluster_offset: u32) -> Pubkey {
+    Pubkey::find_program_address(
+        &[CLUSTER_SEED, &cluster_offset.to_le_bytes()],
+        &Arcium::id(),
+    )
+    .0
+}
+
+/// Every Arcium account the MXE program deserializes: the MXE, the cluster,
+/// the comp defs of the compiled circuit versions, the fee pool and the clock
+pub fn arcium_accounts(cluster_offset: u32) -> Vec<Pubkey> {
+    let mut accounts = vec![mxe(), cluster(cluster_offset)];
+    accounts.extend(
+        Circuit::compiled_versions()
+            .into_iter()
+            .map(|v| comp_def(v.circuit, v.version)),
+    );
+    accounts.push(ARCIUM_FEE_POOL_ACCOUNT_ADDRESS);
+    accounts.push(ARCIUM_CLOCK_ACCOUNT_ADDRESS);
+    accounts
+}
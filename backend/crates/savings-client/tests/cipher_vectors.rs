@@ -0,0 +1,122 @@
+//! Known answers for the cipher: RFC 7748 for the key exchange, encryptions by
+//! a second implementation and outputs of a real MXE. See
+//! `tests/vectors/README.md` for how they are made.
+
+use std::fs;
+
+use savings_client::cipher::{ClientKeypair, RescueCipher};
+use serde_json::Value;
+
+const VECTORS: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/vectors/cipher.json");
+
+struct Case {
+    cipher: RescueCipher,
+    nonce: u128,
+    plaintext: Vec<u64>,
+    ciphertexts: Vec<[u8; 32]>,
+}
+
+fn cases(list: &str) -> Vec<Case> {
+    let vectors: Value = serde_json::from_slice(
+        &fs::read(VECTORS).expect("no cipher.json, see tests/vectors/README.md"),
+    )
+    .expect("cipher.json is not JSON");
+    let cases: Vec<Case> = vectors[list]
+        .as_array()
+        .unwrap_or_else(|| panic!("cipher.json has no {list} list"))
+        .iter()
+        .map(|case| Case {
+            cipher: ClientKeypair::from_secret(hex(&case["client_secret"]))
+                .shared_cipher(&hex(&case["mxe_public_key"])),
+            nonce: decimal(&case["nonce"]),
+            plaintext: each(&case["plaintext"], decimal),
+            ciphertexts: each(&case["ciphertexts"], hex),
+        })
+        .collect();
+    assert!(!cases.is_empty(), "cipher.json has an empty {list} list");
+    cases
+}
+
+fn each<T>(value: &Value, parse: fn(&Value) -> T) -> Vec<T> {
+    value
+        .as_array()
+        .expect("expected a list")
+        .iter()
+        .map(parse)
+        .collect()
+}
+
+fn hex(value: &Value) -> [u8; 32] {
+    bytes(value.as_str().expect("expected a hex string"))
+}
+
+fn bytes(s: &str) -> [u8; 32] {
+    let bytes: Vec<u8> = (0..s.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&s[i..i + 2], 16).expect("invalid hex"))
+        .collect();
+    bytes.try_into().expect("expected 32 bytes")
+}
+
+fn decimal<T: std::str::FromStr>(value: &Value) -> T {
+    value
+        .as_str()
+        .and_then(|s| s.parse().ok())
+        .expect("expected a decimal string")
+}
+
+#[test]
+fn key_exchange_matches_rfc_7748() {
+    let alice = ClientKeypair::from_secret(bytes(
+        "77076d0a7318a57d3c16c17251b26645df4c2f87ebc0992ab177fba51db92c2a",
+    ));
+    let bob = ClientKeypair::from_secret(bytes(
+        "5dab087e624a8a4b79e17f8b83800ee66f3bb1292618b6fd1c2f8b27ff88e0eb",
+    ));
+    assert_eq!(
+        alice.public_key(),
+        bytes("8520f0098930a754748b7ddcb43ef75a0dbf3a0d26381af4eba4a98eaa9b4e6a")
+    );
+    assert_eq!(
+        bob.public_key(),
+        bytes("de9edb7d7b7dc1b4d35b61c2ece435373f8343c85b78674dadfc7e146f882b4f")
+    );
+    // Both sides key the cipher with the RFC's shared secret
+    let shared = RescueCipher::new(&bytes(
+        "4a5d9d5ba4ce2de1728e3bf480350f25e07e21c947d19e3376f09b3c1e161742",
+    ));
+    let expected = shared.encrypt(&[42], 7);
+    assert_eq!(
+        alice.shared_cipher(&bob.public_key()).encrypt(&[42], 7),
+        expected
+    );
+    assert_eq!(
+        bob.shared_cipher(&alice.public_key()).encrypt(&[42], 7),
+        expected
+    );
+}
+
+#[test]
+fn matches_reference_vectors() {
+    for case in cases("cipher") {
+        assert_eq!(
+            case.cipher.encrypt(&case.plaintext, case.nonce),
+            case.ciphertexts
+        );
+        assert_eq!(
+            case.cipher.decrypt(&case.ciphertexts, case.nonce),
+            Ok(case.plaintext)
+        );
+    }
+}
+
+#[test]
+#[ignore = "needs captured MXE outputs, see tests/vectors/README.md"]
+fn decrypts_mxe_outputs() {
+    for case in cases("mxe_outputs") {
+        assert_eq!(
+            case.cipher.decrypt(&case.ciphertexts, case.nonce),
+            Ok(case.plaintext)
+        );
+    }
+}
@@ -0,0 +1,53 @@
+//! Stand-in for the Arcium program.
+//!
+//! Every Arcium instruction the MXE program CPIs into (comp def init, queue
+//! computation, ...) succeeds without effect. The one extra instruction,
+//! tagged with [`DELIVER_CALLBACK`], forwards a callback instruction to the
+//! MXE program so it executes as a CPI from the Arcium program id, the same
+//! way a real callback arrives.
+
+use anchor_lang::Id;
+use arcium_anchor::prelude::Arcium;
+use solana_sdk::account_info::AccountInfo;
+use solana_sdk::entrypoint::ProgramResult;
+use solana_sdk::instruction::{AccountMeta, Instruction};
+use solana_sdk::program::invoke;
+use solana_sdk::program_error::ProgramError;
+use solana_sdk::pubkey::Pubkey;
+
+const DELIVER_CALLBACK: [u8; 8] = *b"mock_cb\0";
+
+pub fn process(_program_id: &Pubkey, accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let Some(callback_data) = data.strip_prefix(&DELIVER_CALLBACK) else {
+        return Ok(());
+    };
+    let (program, accounts) = accounts
+        .split_first()
+        .ok_or(ProgramError::NotEnoughAccountKeys)?;
+    let callback = Instruction {
+        program_id: *program.key,
+        accounts: accounts
+            .iter()
+            .map(|account| AccountMeta {
+                pubkey: *account.key,
+                is_signer: account.is_signer,
+                is_writable: account.is_writable,
+            })
+            .collect(),
+        data: callback_data.to_vec(),
+    };
+    invoke(&callback, accounts)
+}
+
+/// Wrap a callback instruction so the mock Arcium program invokes it
+pub fn deliver(callback: Instruction) -> Instruction {
+    let mut accounts = vec![AccountMeta::new_readonly(callback.program_id, false)];
+    accounts.extend(callback.accounts);
+    let mut data = DELIVER_CALLBACK.to_vec();
+    data.extend(callback.data);
+    Instruction {
+        program_id: Arcium::id(),
+        accounts,
+        data,
+    }
+}
@@ -0,0 +1,281 @@
+use std::collections::HashMap;
+
+use anchor_lang::{AccountDeserialize, AccountSerialize, Id};
+use arcium_anchor::prelude::Arcium;
+use savings_client::accounts::SavingsGoal;
+use savings_client::events::{events_from_logs, SavingsEvent};
+use savings_client::mxe::QueueParams;
+use savings_client::{pda, Instruction, SAVINGS_GOAL_ID, SAVINGS_MXE_ID};
+use solana_program_test::{processor, ProgramTest, ProgramTestContext};
+use solana_sdk::account::{Account, AccountSharedData};
+use solana_sdk::clock::Clock;
+use solana_sdk::instruction::InstructionError;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Keypair, Signer};
+use solana_sdk::system_instruction;
+use solana_sdk::transaction::{Transaction, TransactionError};
+use solana_sdk::vote::instruction::{self as vote_instruction, CreateVoteAccountConfig};
+use solana_sdk::vote::state::{VoteInit, VoteState};
+
+use crate::fixtures::ArciumFixtures;
+use crate::{arcium, Computation, MockMxe};
+
+const DEPLOY_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/../../target/deploy");
+
+/// Both programs plus the mock Arcium program in one in-process bank
+pub struct TestEnv {
+    pub context: ProgramTestContext,
+    pub mxe: MockMxe,
+    cluster_offset: u32,
+    next_computation_offset: u64,
+}
+
+impl TestEnv {
+    pub async fn start() -> Self {
+        if std::env::var_os("SBF_OUT_DIR").is_none() {
+            std::env::set_var("SBF_OUT_DIR", DEPLOY_DIR);
+        }
+        let mut program_test = ProgramTest::default();
+        program_test.add_program("savings_goal", SAVINGS_GOAL_ID, None);
+        program_test.add_upgradeable_program_to_genesis("savings_mxe", &SAVINGS_MXE_ID);
+        program_test.add_program("arcium_mock", Arcium::id(), processor!(arcium::process));
+
+        let fixtures = ArciumFixtures::load_or_mock().expect("failed to load Arcium fixtures");
+        let cluster_offset = fixtures.cluster_offset;
+        for (address, account) in fixtures.accounts {
+            program_test.add_account(address, account);
+        }
+
+        let mut env = Self {
+            context: program_test.start_with_context().await,
+            mxe: MockMxe::default(),
+            cluster_offset,
+            next_computation_offset: 1,
+        };
+        env.set_mxe_upgrade_authority(Some(env.payer())).await;
+        env
+    }
+
+    /// Hand `savings_mxe`'s upgrade authority, which initializes its config,
+    /// to `authority`
+    #[allow(deprecated)]
+    pub async fn set_mxe_upgrade_authority(&mut self, authority: Option<Pubkey>) {
+        use solana_sdk::bpf_loader_upgradeable::UpgradeableLoaderState;
+
+        let program_data = pda::mxe_program_data();
+        let mut account = AccountSharedData::from(
+            self.raw_account(&program_data)
+                .await
+                .expect("savings_mxe is not upgradeable"),
+        );
+        let slot = match account.deserialize_data() {
+            Ok(UpgradeableLoaderState::ProgramData { slot, .. }) => slot,
+            _ => panic!("savings_mxe program data does not decode"),
+        };
+        account
+            .serialize_data(&UpgradeableLoaderState::ProgramData {
+                slot,
+                upgrade_authority_address: authority,
+            })
+            .expect("failed to write the upgrade authority");
+        self.context.set_account(&program_data, &account);
+    }
+
+    pub fn payer(&self) -> Pubkey {
+        self.context.payer.pubkey()
+    }
+
+    /// A new keypair funded by the payer
+    pub async fn funded_keypair(&mut self, lamports: u64) -> Keypair {
+        let keypair = Keypair::new();
+        let ix = system_instruction::transfer(&self.payer(), &keypair.pubkey(), lamports);
+        self.send(&[ix], &[])
+            .await
+            .expect("funding transfer failed");
+        keypair
+    }
+
+    /// Send `instructions` paid for by the payer and return the events they emitted
+    pub async fn send(
+        &mut self,
+        instructions: &[Instruction],
+        signers: &[&Keypair],
+    ) -> Result<Vec<SavingsEvent>, TransactionError> {
+        let blockhash = self
+            .context
+            .get_new_latest_blockhash()
+            .await
+            .expect("failed to get blockhash");
+        let mut all_signers = vec![&self.context.payer];
+        all_signers.extend(signers);
+        let tx = Transaction::new_signed_with_payer(
+            instructions,
+            Some(&self.context.payer.pubkey()),
+            &all_signers,
+            blockhash,
+        );
+        let outcome = self
+            .context
+            .banks_client
+            .process_transaction_with_metadata(tx)
+            .await
+            .expect("banks client failed");
+        outcome.result?;
+        let logs = outcome.metadata.map(|m| m.log_messages).unwrap_or_default();
+        Ok(events_from_logs(&logs))
+    }
+
+    pub async fn raw_account(&mut self, address: &Pubkey) -> Option<Account> {
+        self.context
+            .banks_client
+            .get_account(*address)
+            .await
+            .expect("banks client failed")
+    }
+
+    pub async fn account<T: AccountDeserialize>(&mut self, address: &Pubkey) -> Option<T> {
+        let account = self.raw_account(address).await?;
+        Some(T::try_deserialize(&mut account.data.as_slice()).expect("account failed to decode"))
+    }
+
+    pub async fn now(&mut self) -> i64 {
+        self.clock().await.unix_timestamp
+    }
+
+    async fn clock(&mut self) -> Clock {
+        self.context
+            .banks_client
+            .get_sysvar::<Clock>()
+            .await
+            .expect("failed to read clock")
+    }
+
+    /// Move the bank clock to `unix_timestamp`
+    pub async fn set_time(&mut self, unix_timestamp: i64) {
+        let mut clock = self.clock().await;
+        clock.unix_timestamp = unix_timestamp;
+        self.context.set_sysvar(&clock);
+    }
+
+    /// Put `lamports` in a goal's vault. `approve_transfer` debits the vault
+    /// directly, which the runtime only allows for accounts the program owns.
+    pub fn fund_vault(&mut self, goal: &Pubkey, lamports: u64) {
+        let vault = AccountSharedData::new(lamports, 0, &SAVINGS_GOAL_ID);
+        self.context.set_account(&pda::vault(goal), &vault);
+    }
+
+    /// Record `total` as the revealed total of a finalized goal, standing in
+    /// for `savings_mxe::finalize_and_reveal_total` in tests without the MXE
+    pub async fn set_revealed_total(&mut self, goal: &Pubkey, total: u64) {
+        let mut account = self.raw_account(goal).await.expect("goal does not exist");
+        let mut state =
+            SavingsGoal::try_deserialize(&mut account.data.as_slice()).expect("not a savings goal");
+        state.revealed_total = Some(total);
+        state
+            .try_serialize(&mut account.data.as_mut_slice())
+            .expect("failed to serialize the goal");
+        self.context
+            .set_account(goal, &AccountSharedData::from(account));
+    }
+
+    /// Credit `lamports` to an existing account, e.g. to stand in for the
+    /// rewards a stake account earned
+    pub async fn add_lamports(&mut self, address: &Pubkey, lamports: u64) {
+        let mut account = self
+            .raw_account(address)
+            .await
+            .expect("account does not exist");
+        account.lamports += lamports;
+        self.context
+            .set_account(address, &AccountSharedData::from(account));
+    }
+
+    /// A new vote account that stake can be delegated to
+    pub async fn create_vote_account(&mut self) -> Pubkey {
+        let vote_account = Keypair::new();
+        let node = Keypair::new();
+        let rent = self
+            .context
+            .banks_client
+            .get_rent()
+            .await
+            .expect("failed to read rent");
+        let ixs = vote_instruction::create_account_with_config(
+            &self.payer(),
+            &vote_account.pubkey(),
+            &VoteInit {
+                node_pubkey: node.pubkey(),
+                authorized_voter: node.pubkey(),
+                authorized_withdrawer: node.pubkey(),
+                commission: 0,
+            },
+            rent.minimum_balance(VoteState::size_of()),
+            CreateVoteAccountConfig::default(),
+        );
+        self.send(&ixs, &[&vote_account, &node])
+            .await
+            .expect("creating the vote account failed");
+        vote_account.pubkey()
+    }
+
+    /// Queue parameters for a new computation on `goal`
+    pub fn queue_params(&mut self, goal: Pubkey) -> QueueParams {
+        let computation_offset = self.next_computation_offset;
+        self.next_computation_offset += 1;
+        QueueParams {
+            payer: self.payer(),
+            goal,
+            computation_offset,
+            cluster_offset: self.cluster_offset,
+        }
+    }
+
+    /// Send a queue instruction, run its computation on the mock MXE and
+    /// deliver the callback. Returns the events emitted by the callback.
+    pub async fn run_computation(
+        &mut self,
+        queue_ix: Instruction,
+        signers: &[&Keypair],
+    ) -> Result<Vec<SavingsEvent>, TransactionError> {
+        let computation = self.queue_computation(queue_ix, signers).await?;
+        self.send(&[arcium::deliver(computation.callback())], &[])
+            .await
+    }
+
+    /// Like [`run_computation`](Self::run_computation), but the cluster
+    /// aborts the computation instead of returning its result
+    pub async fn abort_computation(
+        &mut self,
+        queue_ix: Instruction,
+        signers: &[&Keypair],
+    ) -> Result<Vec<SavingsEvent>, TransactionError> {
+        let computation = self.queue_computation(queue_ix, signers).await?;
+        self.send(&[arcium::deliver(computation.abort().callback())], &[])
+            .await
+    }
+
+    async fn queue_computation(
+        &mut self,
+        queue_ix: Instruction,
+        signers: &[&Keypair],
+    ) -> Result<Computation, TransactionError> {
+        self.send(&[queue_ix.clone()], signers).await?;
+
+        let mut accounts = HashMap::new();
+        for meta in &queue_ix.accounts {
+            if let Some(account) = self.raw_account(&meta.pubkey).await {
+                accounts.insert(meta.pubkey, account);
+            }
+        }
+        Ok(self.mxe.execute(&queue_ix, &accounts))
+    }
+}
+
+/// The custom error code a failed transaction returned, if any. Compare with
+/// `u32::from(ErrorCode::...)` of the program that raised it.
+pub fn custom_error(error: &TransactionError) -> Option<u32> {
+    match error {
+        TransactionError::InstructionError(_, InstructionError::Custom(code)) => Some(*code),
+        _ => None,
+    }
+}
@@ -0,0 +1,175 @@
+//! Arcium account fixtures.
+//!
+//! The queue instructions only deserialize the Arcium accounts they are
+//! passed (MXE, cluster, comp defs, fee pool, clock) and hand them to the
+//! mock Arcium program, so by default [`ArciumFixtures::mock`] makes them up:
+//! each is a zeroed account of its type, with the MXE pointing at the
+//! cluster. To run against the real layouts instead, `savings-cli mxe
+//! dump-accounts` writes, from a running localnet, one `solana account
+//! --output json` file per account plus a `manifest.json` holding the cluster
+//! offset; [`ArciumFixtures::load_default`] picks them up.
+
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use anchor_lang::{AccountDeserialize, AccountSerialize, Discriminator, Id};
+use arcium_anchor::prelude::{
+    Arcium, ClockAccount, Cluster, ComputationDefinitionAccount, FeePool, MXEAccount,
+    ARCIUM_CLOCK_ACCOUNT_ADDRESS, ARCIUM_FEE_POOL_ACCOUNT_ADDRESS,
+};
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use savings_client::mxe::Circuit;
+use savings_client::pda;
+use serde_json::Value;
+use solana_sdk::account::Account;
+use solana_sdk::native_token::LAMPORTS_PER_SOL;
+use solana_sdk::pubkey::Pubkey;
+
+pub const FIXTURE_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/fixtures/arcium");
+
+/// Cluster the mock accounts are set up for
+pub const MOCK_CLUSTER_OFFSET: u32 = 1;
+
+const MANIFEST: &str = "manifest.json";
+
+/// Data length of a mock account; zeros past the fields deserialize as
+/// empty collections and `None`s
+const MOCK_ACCOUNT_LEN: usize = 4096;
+
+pub struct ArciumFixtures {
+    pub cluster_offset: u32,
+    pub accounts: Vec<(Pubkey, Account)>,
+}
+
+impl ArciumFixtures {
+    /// Load the fixtures in [`FIXTURE_DIR`], or `None` if none were dumped
+    pub fn load_default() -> io::Result<Option<Self>> {
+        let dir = Path::new(FIXTURE_DIR);
+        if !dir.join(MANIFEST).exists() {
+            return Ok(None);
+        }
+        Self::load(dir).map(Some)
+    }
+
+    /// The dumped fixtures if there are any, otherwise [`Self::mock`]
+    pub fn load_or_mock() -> io::Result<Self> {
+        Ok(Self::load_default()?.unwrap_or_else(|| Self::mock(MOCK_CLUSTER_OFFSET)))
+    }
+
+    /// Made-up Arcium accounts for a cluster at `cluster_offset`, at the
+    /// addresses of [`pda::arcium_accounts`]
+    pub fn mock(cluster_offset: u32) -> Self {
+        let mut accounts = vec![
+            (
+                pda::mxe(),
+                mock_account(|mxe: &mut MXEAccount| mxe.cluster = Some(cluster_offset)),
+            ),
+            (
+                pda::cluster(cluster_offset),
+                mock_account(|_: &mut Cluster| {}),
+            ),
+        ];
+        accounts.extend(Circuit::compiled_versions().into_iter().map(|compiled| {
+            (
+                pda::comp_def(compiled.circuit, compiled.version),
+                mock_account(|_: &mut ComputationDefinitionAccount| {}),
+            )
+        }));
+        accounts.push((
+            ARCIUM_FEE_POOL_ACCOUNT_ADDRESS,
+            mock_account(|_: &mut FeePool| {}),
+        ));
+        accounts.push((
+            ARCIUM_CLOCK_ACCOUNT_ADDRESS,
+            mock_account(|_: &mut ClockAccount| {}),
+        ));
+        Self {
+            cluster_offset,
+            accounts,
+        }
+    }
+
+    pub fn load(dir: &Path) -> io::Result<Self> {
+        let manifest = read_json(&dir.join(MANIFEST))?;
+        let cluster_offset = manifest["cluster_offset"]
+            .as_u64()
+            .and_then(|offset| u32::try_from(offset).ok())
+            .ok_or_else(|| invalid("manifest.json has no cluster_offset"))?;
+
+        let mut accounts = Vec::new();
+        for entry in fs::read_dir(dir)? {
+            let path = entry?.path();
+            if path.extension().is_some_and(|ext| ext == "json")
+                && path.file_name().is_some_and(|name| name != MANIFEST)
+            {
+                accounts.push(parse_account(&read_json(&path)?)?);
+            }
+        }
+        Ok(Self {
+            cluster_offset,
+            accounts,
+        })
+    }
+}
+
+/// An Arcium-owned `T` with every field zeroed, then changed by `edit`
+fn mock_account<T>(edit: impl FnOnce(&mut T)) -> Account
+where
+    T: AccountDeserialize + AccountSerialize + Discriminator,
+{
+    let mut data = T::DISCRIMINATOR.to_vec();
+    data.resize(MOCK_ACCOUNT_LEN, 0);
+    let mut account =
+        T::try_deserialize(&mut data.as_slice()).expect("a zeroed account does not deserialize");
+    edit(&mut account);
+    data.clear();
+    account
+        .try_serialize(&mut data)
+        .expect("failed to serialize a mock account");
+    data.resize(MOCK_ACCOUNT_LEN, 0);
+    Account {
+        lamports: LAMPORTS_PER_SOL,
+        data,
+        owner: Arcium::id(),
+        executable: false,
+        rent_epoch: 0,
+    }
+}
+
+fn read_json(path: &Path) -> io::Result<Value> {
+    serde_json::from_slice(&fs::read(path)?)
+        .map_err(|e| invalid(&format!("{}: {e}", path.display())))
+}
+
+fn parse_account(value: &Value) -> io::Result<(Pubkey, Account)> {
+    let pubkey = parse_pubkey(&value["pubkey"])?;
+    let account = &value["account"];
+    let data = account["data"][0]
+        .as_str()
+        .ok_or_else(|| invalid("account data is not base64"))?;
+    Ok((
+        pubkey,
+        Account {
+            lamports: account["lamports"]
+                .as_u64()
+                .ok_or_else(|| invalid("account has no lamports"))?,
+            data: STANDARD.decode(data).map_err(|e| invalid(&e.to_string()))?,
+            owner: parse_pubkey(&account["owner"])?,
+            executable: account["executable"].as_bool().unwrap_or(false),
+            rent_epoch: 0,
+        },
+    ))
+}
+
+fn parse_pubkey(value: &Value) -> io::Result<Pubkey> {
+    value
+        .as_str()
+        .and_then(|s| s.parse().ok())
+        .ok_or_else(|| invalid("invalid pubkey"))
+}
+
+fn invalid(msg: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg.to_string())
+}
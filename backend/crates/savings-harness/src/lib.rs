@@ -0,0 +1,22 @@
+//! In-process test harness for `savings_goal` and `savings_mxe`.
+//!
+//! [`TestEnv`] boots both programs in `solana-program-test` from their
+//! `target/deploy` builds, with a mock program standing in for Arcium.
+//! Computations queued through it are picked up by [`MockMxe`], which
+//! decrypts the inputs, runs the circuit's plaintext twin ([`circuits`]) and
+//! delivers the callback through the mock Arcium program, so a goal's whole
+//! lifecycle runs offline and deterministically.
+//!
+//! The Arcium accounts the queue instructions deserialize (MXE, comp defs,
+//! cluster, fee pool, clock) are made up by [`fixtures`], unless real ones
+//! were dumped there. Build the programs with `arcium build` before running
+//! the tests.
+
+mod arcium;
+mod env;
+pub mod fixtures;
+mod mxe;
+
+pub use encrypted_ixs::reference as circuits;
+pub use env::{custom_error, TestEnv};
+pub use mxe::{CircuitOutput, Computation, EncryptedAmount, MockMxe};
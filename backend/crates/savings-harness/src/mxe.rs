@@ -0,0 +1,677 @@
+//! Plaintext mock of the MXE cluster.
+
+use std::collections::HashMap;
+
+use anchor_lang::solana_program::sysvar;
+use anchor_lang::{Id, InstructionData, ToAccountMetas};
+use arcium_anchor::prelude::{Arcium, ComputationOutputs, SharedEncryptedStruct};
+use savings_client::accounts::{
+    decode_computation_record, ComputationInputs, ComputationRecord, ComputationStatus,
+    StoredContributionInput,
+};
+use savings_client::cipher::ClientKeypair;
+use savings_client::mxe::{compiled_version, Circuit};
+use savings_client::{pda, Instruction, SAVINGS_GOAL_ID, SAVINGS_MXE_ID};
+use savings_mxe::{
+    accounts, instruction, AddAndCheckGoal10V1Output, AddTwoContributionsV4Output,
+    AuditContributions10V1Output, CheckGoalReachedV4Output, CheckMilestones10V1Output,
+    CheckPeriodContributionV1Output, CheckVaultBalance10V1Output, DistributeYield10V1Output,
+    GroupStats10V1Output, MemberProgressV2Output, RevealContributions10V5Output,
+    RevealFinalTotal10V1Output, RevealOwnContributions5V1Output,
+    RevealOwnContributions5V1OutputStruct0,
+};
+use solana_sdk::account::Account;
+use solana_sdk::pubkey::Pubkey;
+
+use crate::circuits;
+
+// Fixed so test runs are reproducible
+const MXE_SECRET: [u8; 32] = [7; 32];
+
+/// Result of one plaintext circuit run, ready to be delivered to its callback
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Computation {
+    pub computation_offset: u64,
+    pub goal: Pubkey,
+    /// The inputs it was queued with, as recorded on-chain
+    pub inputs: ComputationInputs,
+    pub output: CircuitOutput,
+}
+
+/// What the cluster hands back to a callback
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CircuitOutput {
+    /// add_two_contributions: the revealed sum; reveal_final_total: the
+    /// total, or zero if it missed the target
+    Total(u64),
+    /// check_goal_reached / add_and_check_goal
+    Reached(bool),
+    /// check_period_contribution
+    Met(bool),
+    /// member_progress: the shortfall, encrypted to the member
+    Shortfall {
+        encryption_key: [u8; 32],
+        nonce: u128,
+        ciphertext: [u8; 32],
+    },
+    /// group_stats: `[average, min, max, active]`
+    Stats([u64; 4]),
+    /// reveal_own_contributions: every slot, encrypted to its own contributor
+    OwnAmounts([EncryptedAmount; 5]),
+    /// reveal_contributions_10: every slot, encrypted to the goal owner
+    OwnerAmounts {
+        encryption_key: [u8; 32],
+        nonce: u128,
+        ciphertexts: [[u8; 32]; 10],
+    },
+    /// audit_contributions: every slot and the total, encrypted to the auditor
+    Audit {
+        encryption_key: [u8; 32],
+        nonce: u128,
+        ciphertexts: [[u8; 32]; 11],
+    },
+    /// check_vault_balance: 0 if the vault matches, 1 if it holds more, 2 if
+    /// it holds less
+    VaultSign(u8),
+    /// check_milestones: how many of the goal's milestones were crossed
+    Milestones(u8),
+    /// distribute_yield: each slot's share of the yield
+    YieldShares([u64; 10]),
+    /// The cluster gave up on the computation; its callback gets a failure
+    Aborted,
+}
+
+/// One `Enc<Shared, u64>` output
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EncryptedAmount {
+    pub encryption_key: [u8; 32],
+    pub nonce: u128,
+    pub ciphertext: [u8; 32],
+}
+
+impl EncryptedAmount {
+    fn shared(&self) -> SharedEncryptedStruct<1> {
+        SharedEncryptedStruct {
+            encryption_key: self.encryption_key,
+            nonce: self.nonce,
+            ciphertexts: [self.ciphertext],
+        }
+    }
+}
+
+impl Computation {
+    pub fn circuit(&self) -> Circuit {
+        self.inputs.circuit()
+    }
+
+    /// The same computation, aborted instead of completed
+    pub fn abort(&self) -> Computation {
+        Computation {
+            output: CircuitOutput::Aborted,
+            ..*self
+        }
+    }
+
+    /// The callback instruction Arcium would invoke with this result
+    pub fn callback(&self) -> Instruction {
+        let comp_def_account = pda::comp_def(self.circuit(), compiled_version(self.circuit()));
+        let computation_record = pda::computation_record(self.computation_offset);
+        match self.circuit() {
+            Circuit::AddTwoContributions => build(
+                accounts::AddTwoContributionsV4Callback {
+                    arcium_program: Arcium::id(),
+                    comp_def_account,
+                    instructions_sysvar: sysvar::instructions::ID,
+                    computation_record,
+                },
+                instruction::AddTwoContributionsV4Callback {
+                    output: match self.output {
+                        CircuitOutput::Total(total) => {
+                            ComputationOutputs::Success(AddTwoContributionsV4Output {
+                                field_0: total,
+                            })
+                        }
+                        _ => ComputationOutputs::Failure,
+                    },
+                },
+            ),
+            Circuit::CheckGoalReached => build(
+                accounts::CheckGoalReachedV4Callback {
+                    arcium_program: Arcium::id(),
+                    comp_def_account,
+                    instructions_sysvar: sysvar::instructions::ID,
+                    computation_record,
+                },
+                instruction::CheckGoalReachedV4Callback {
+                    output: match self.output {
+                        CircuitOutput::Reached(reached) => {
+                            ComputationOutputs::Success(CheckGoalReachedV4Output {
+                                field_0: reached,
+                            })
+                        }
+                        _ => ComputationOutputs::Failure,
+                    },
+                },
+            ),
+            Circuit::AddAndCheckGoal => build(
+                accounts::AddAndCheckGoal10V1Callback {
+                    arcium_program: Arcium::id(),
+                    comp_def_account,
+                    instructions_sysvar: sysvar::instructions::ID,
+                    computation_record,
+                    goal: self.goal,
+                    goal_authority: pda::goal_authority(),
+                    savings_goal_program: SAVINGS_GOAL_ID,
+                },
+                instruction::AddAndCheckGoal10V1Callback {
+                    output: match self.output {
+                        CircuitOutput::Reached(reached) => {
+                            ComputationOutputs::Success(AddAndCheckGoal10V1Output {
+                                field_0: reached,
+                            })
+                        }
+                        _ => ComputationOutputs::Failure,
+                    },
+                },
+            ),
+            Circuit::CheckPeriodContribution => {
+                let (member, period) = self
+                    .inputs
+                    .period_contribution()
+                    .expect("period checks record their member and period");
+                build(
+                    accounts::CheckPeriodContributionV1Callback {
+                        arcium_program: Arcium::id(),
+                        comp_def_account,
+                        instructions_sysvar: sysvar::instructions::ID,
+                        computation_record,
+                        goal: self.goal,
+                        period_contribution: pda::period_contribution(&self.goal, &member, period),
+                        goal_authority: pda::goal_authority(),
+                        savings_goal_program: SAVINGS_GOAL_ID,
+                    },
+                    instruction::CheckPeriodContributionV1Callback {
+                        output: match self.output {
+                            CircuitOutput::Met(met) => {
+                                ComputationOutputs::Success(CheckPeriodContributionV1Output {
+                                    field_0: met,
+                                })
+                            }
+                            _ => ComputationOutputs::Failure,
+                        },
+                    },
+                )
+            }
+            Circuit::MemberProgress => {
+                let member = self
+                    .inputs
+                    .member_progress()
+                    .expect("progress checks record their member");
+                build(
+                    accounts::MemberProgressV2Callback {
+                        arcium_program: Arcium::id(),
+                        comp_def_account,
+                        instructions_sysvar: sysvar::instructions::ID,
+                        computation_record,
+                        member_progress: pda::member_progress(&self.goal, &member),
+                    },
+                    instruction::MemberProgressV2Callback {
+                        output: match self.output {
+                            CircuitOutput::Shortfall {
+                                encryption_key,
+                                nonce,
+                                ciphertext,
+                            } => ComputationOutputs::Success(MemberProgressV2Output {
+                                field_0: SharedEncryptedStruct {
+                                    encryption_key,
+                                    nonce,
+                                    ciphertexts: [ciphertext],
+                                },
+                            }),
+                            _ => ComputationOutputs::Failure,
+                        },
+                    },
+                )
+            }
+            Circuit::GroupStats => build(
+                accounts::GroupStats10V1Callback {
+                    arcium_program: Arcium::id(),
+                    comp_def_account,
+                    instructions_sysvar: sysvar::instructions::ID,
+                    computation_record,
+                    group_stats: pda::group_stats(&self.goal),
+                },
+                instruction::GroupStats10V1Callback {
+                    output: match self.output {
+                        CircuitOutput::Stats(stats) => {
+                            ComputationOutputs::Success(GroupStats10V1Output { field_0: stats })
+                        }
+                        _ => ComputationOutputs::Failure,
+                    },
+                },
+            ),
+            Circuit::RevealOwnContributions => build(
+                accounts::RevealOwnContributions5V1Callback {
+                    arcium_program: Arcium::id(),
+                    comp_def_account,
+                    instructions_sysvar: sysvar::instructions::ID,
+                    computation_record,
+                },
+                instruction::RevealOwnContributions5V1Callback {
+                    output: match self.output {
+                        CircuitOutput::OwnAmounts(amounts) => {
+                            let [a, b, c, d, e] = amounts.map(|amount| amount.shared());
+                            ComputationOutputs::Success(RevealOwnContributions5V1Output {
+                                field_0: RevealOwnContributions5V1OutputStruct0 {
+                                    field_0: a,
+                                    field_1: b,
+                                    field_2: c,
+                                    field_3: d,
+                                    field_4: e,
+                                },
+                            })
+                        }
+                        _ => ComputationOutputs::Failure,
+                    },
+                },
+            ),
+            Circuit::RevealContributions10 => build(
+                accounts::RevealContributions10V5Callback {
+                    arcium_program: Arcium::id(),
+                    comp_def_account,
+                    instructions_sysvar: sysvar::instructions::ID,
+                    computation_record,
+                },
+                instruction::RevealContributions10V5Callback {
+                    output: match self.output {
+                        CircuitOutput::OwnerAmounts {
+                            encryption_key,
+                            nonce,
+                            ciphertexts,
+                        } => ComputationOutputs::Success(RevealContributions10V5Output {
+                            field_0: SharedEncryptedStruct {
+                                encryption_key,
+                                nonce,
+                                ciphertexts,
+                            },
+                        }),
+                        _ => ComputationOutputs::Failure,
+                    },
+                },
+            ),
+            Circuit::AuditContributions => build(
+                accounts::AuditContributions10V1Callback {
+                    arcium_program: Arcium::id(),
+                    comp_def_account,
+                    instructions_sysvar: sysvar::instructions::ID,
+                    computation_record,
+                    audit: pda::audit(&self.goal),
+                },
+                instruction::AuditContributions10V1Callback {
+                    output: match self.output {
+                        CircuitOutput::Audit {
+                            encryption_key,
+                            nonce,
+                            ciphertexts,
+                        } => ComputationOutputs::Success(AuditContributions10V1Output {
+                            field_0: SharedEncryptedStruct {
+                                encryption_key,
+                                nonce,
+                                ciphertexts,
+                            },
+                        }),
+                        _ => ComputationOutputs::Failure,
+                    },
+                },
+            ),
+            Circuit::RevealFinalTotal => build(
+                accounts::RevealFinalTotal10V1Callback {
+                    arcium_program: Arcium::id(),
+                    comp_def_account,
+                    instructions_sysvar: sysvar::instructions::ID,
+                    computation_record,
+                    goal: self.goal,
+                    goal_authority: pda::goal_authority(),
+                    savings_goal_program: SAVINGS_GOAL_ID,
+                },
+                instruction::RevealFinalTotal10V1Callback {
+                    output: match self.output {
+                        CircuitOutput::Total(total) => {
+                            ComputationOutputs::Success(RevealFinalTotal10V1Output {
+                                field_0: total,
+                            })
+                        }
+                        _ => ComputationOutputs::Failure,
+                    },
+                },
+            ),
+            Circuit::CheckVaultBalance => build(
+                accounts::CheckVaultBalance10V1Callback {
+                    arcium_program: Arcium::id(),
+                    comp_def_account,
+                    instructions_sysvar: sysvar::instructions::ID,
+                    computation_record,
+                    goal: self.goal,
+                    goal_authority: pda::goal_authority(),
+                    savings_goal_program: SAVINGS_GOAL_ID,
+                },
+                instruction::CheckVaultBalance10V1Callback {
+                    output: match self.output {
+                        CircuitOutput::VaultSign(sign) => {
+                            ComputationOutputs::Success(CheckVaultBalance10V1Output {
+                                field_0: sign,
+                            })
+                        }
+                        _ => ComputationOutputs::Failure,
+                    },
+                },
+            ),
+            Circuit::CheckMilestones => build(
+                accounts::CheckMilestones10V1Callback {
+                    arcium_program: Arcium::id(),
+                    comp_def_account,
+                    instructions_sysvar: sysvar::instructions::ID,
+                    computation_record,
+                    goal: self.goal,
+                    goal_authority: pda::goal_authority(),
+                    savings_goal_program: SAVINGS_GOAL_ID,
+                },
+                instruction::CheckMilestones10V1Callback {
+                    output: match self.output {
+                        CircuitOutput::Milestones(reached) => {
+                            ComputationOutputs::Success(CheckMilestones10V1Output {
+                                field_0: reached,
+                            })
+                        }
+                        _ => ComputationOutputs::Failure,
+                    },
+                },
+            ),
+            Circuit::DistributeYield => build(
+                accounts::DistributeYield10V1Callback {
+                    arcium_program: Arcium::id(),
+                    comp_def_account,
+                    instructions_sysvar: sysvar::instructions::ID,
+                    computation_record,
+                    goal: self.goal,
+                    goal_authority: pda::goal_authority(),
+                    savings_goal_program: SAVINGS_GOAL_ID,
+                },
+                instruction::DistributeYield10V1Callback {
+                    output: match self.output {
+                        CircuitOutput::YieldShares(shares) => {
+                            ComputationOutputs::Success(DistributeYield10V1Output {
+                                field_0: shares,
+                            })
+                        }
+                        _ => ComputationOutputs::Failure,
+                    },
+                },
+            ),
+            circuit => unreachable!("{} is never queued", circuit.name()),
+        }
+    }
+}
+
+fn build(accounts: impl ToAccountMetas, data: impl InstructionData) -> Instruction {
+    Instruction {
+        program_id: SAVINGS_MXE_ID,
+        accounts: accounts.to_account_metas(None),
+        data: data.data(),
+    }
+}
+
+/// An MXE that holds its x25519 secret in the clear and evaluates circuits
+/// on decrypted inputs
+pub struct MockMxe {
+    keypair: ClientKeypair,
+}
+
+impl Default for MockMxe {
+    fn default() -> Self {
+        Self {
+            keypair: ClientKeypair::from_secret(MXE_SECRET),
+        }
+    }
+}
+
+impl MockMxe {
+    /// The key clients encrypt to, in place of the MXE account's key
+    pub fn public_key(&self) -> [u8; 32] {
+        self.keypair.public_key()
+    }
+
+    pub fn decrypt(&self, pub_key: &[u8; 32], nonce: u128, ciphertext: [u8; 32]) -> u64 {
+        self.keypair
+            .shared_cipher(pub_key)
+            .decrypt(&[ciphertext], nonce)
+            .expect("ciphertext was not produced for the mock MXE")[0]
+    }
+
+    fn decrypt_contribution(&self, contribution: &StoredContributionInput) -> u64 {
+        self.decrypt(
+            &contribution.encryption_pubkey,
+            contribution.nonce,
+            contribution.encrypted_amount,
+        )
+    }
+
+    /// Run the computation queued by `queue_ix`. `accounts` holds the state
+    /// of the instruction's accounts after it ran; the inputs are read from
+    /// the computation record it created.
+    pub fn execute(
+        &self,
+        queue_ix: &Instruction,
+        accounts: &HashMap<Pubkey, Account>,
+    ) -> Computation {
+        assert_eq!(
+            queue_ix.program_id, SAVINGS_MXE_ID,
+            "not a savings_mxe instruction"
+        );
+        let record = queue_ix
+            .accounts
+            .iter()
+            .filter_map(|meta| accounts.get(&meta.pubkey))
+            .filter(|account| account.owner == SAVINGS_MXE_ID)
+            .filter_map(|account| decode_computation_record(&account.data).ok())
+            .find(|record| record.status == ComputationStatus::Queued)
+            .expect("instruction does not queue a computation");
+        self.evaluate(&record)
+    }
+
+    /// Run the circuit of a queued computation on its recorded inputs
+    pub fn evaluate(&self, record: &ComputationRecord) -> Computation {
+        let output = match record.inputs {
+            ComputationInputs::AddTwoContributions {
+                ciphertext_0,
+                ciphertext_1,
+                pub_key,
+                nonce,
+            } => CircuitOutput::Total(circuits::add_two_contributions(
+                self.decrypt(&pub_key, nonce, ciphertext_0),
+                self.decrypt(&pub_key, nonce, ciphertext_1),
+            )),
+            ComputationInputs::CheckGoalReached {
+                ciphertext_0,
+                target,
+                pub_key,
+                nonce,
+            } => CircuitOutput::Reached(circuits::check_goal_reached(
+                self.decrypt(&pub_key, nonce, ciphertext_0),
+                target,
+            )),
+            ComputationInputs::AddAndCheckGoal {
+                contributions,
+                count,
+                target,
+            } => {
+                let amounts: Vec<u64> = contributions[..usize::from(count)]
+                    .iter()
+                    .map(|contribution| self.decrypt_contribution(contribution))
+                    .collect();
+                CircuitOutput::Reached(circuits::add_and_check_goal(&amounts, target))
+            }
+            ComputationInputs::CheckPeriodContribution {
+                contribution,
+                expected,
+                ..
+            } => CircuitOutput::Met(circuits::check_period_contribution(
+                self.decrypt_contribution(&contribution),
+                expected,
+            )),
+            ComputationInputs::MemberProgress {
+                contribution,
+                commitment,
+                commitment_nonce,
+                recipient_pubkey,
+                recipient_nonce,
+                ..
+            } => {
+                let shortfall = circuits::member_progress(
+                    self.decrypt_contribution(&contribution),
+                    self.decrypt(&recipient_pubkey, commitment_nonce, commitment),
+                );
+                // Outputs are encrypted under the nonce after the input's
+                let nonce = recipient_nonce.wrapping_add(1);
+                CircuitOutput::Shortfall {
+                    encryption_key: recipient_pubkey,
+                    nonce,
+                    ciphertext: self
+                        .keypair
+                        .shared_cipher(&recipient_pubkey)
+                        .encrypt(&[shortfall], nonce)[0],
+                }
+            }
+            ComputationInputs::GroupStats {
+                contributions,
+                count,
+            } => {
+                let amounts: Vec<u64> = contributions[..usize::from(count)]
+                    .iter()
+                    .map(|contribution| self.decrypt_contribution(contribution))
+                    .collect();
+                CircuitOutput::Stats(circuits::group_stats(&amounts))
+            }
+            ComputationInputs::RevealOwnContributions { contributions, .. } => {
+                let amounts = circuits::reveal_own_contributions(
+                    contributions.map(|contribution| self.decrypt_contribution(&contribution)),
+                );
+                // Each output uses the nonce after its own input's
+                CircuitOutput::OwnAmounts(std::array::from_fn(|slot| {
+                    let contribution = contributions[slot];
+                    let nonce = contribution.nonce.wrapping_add(1);
+                    EncryptedAmount {
+                        encryption_key: contribution.encryption_pubkey,
+                        nonce,
+                        ciphertext: self
+                            .keypair
+                            .shared_cipher(&contribution.encryption_pubkey)
+                            .encrypt(&[amounts[slot]], nonce)[0],
+                    }
+                }))
+            }
+            ComputationInputs::RevealToOwner {
+                contributions,
+                recipient_pubkey,
+                recipient_nonce,
+                ..
+            } => {
+                let amounts = circuits::reveal_contributions(
+                    contributions.map(|contribution| self.decrypt_contribution(&contribution)),
+                );
+                let nonce = recipient_nonce.wrapping_add(1);
+                let ciphertexts = self
+                    .keypair
+                    .shared_cipher(&recipient_pubkey)
+                    .encrypt(&amounts, nonce);
+                CircuitOutput::OwnerAmounts {
+                    encryption_key: recipient_pubkey,
+                    nonce,
+                    ciphertexts: ciphertexts.try_into().expect("one ciphertext per slot"),
+                }
+            }
+            ComputationInputs::Audit {
+                contributions,
+                count,
+                auditor_pubkey,
+                auditor_nonce,
+                ..
+            } => {
+                let amounts: Vec<u64> = contributions[..usize::from(count)]
+                    .iter()
+                    .map(|contribution| self.decrypt_contribution(contribution))
+                    .collect();
+                let audited = circuits::audit_contributions(&amounts);
+                let nonce = auditor_nonce.wrapping_add(1);
+                let ciphertexts = self
+                    .keypair
+                    .shared_cipher(&auditor_pubkey)
+                    .encrypt(&audited, nonce);
+                CircuitOutput::Audit {
+                    encryption_key: auditor_pubkey,
+                    nonce,
+                    ciphertexts: ciphertexts.try_into().expect("one ciphertext per slot"),
+                }
+            }
+            ComputationInputs::RevealFinalTotal {
+                contributions,
+                count,
+                target,
+            } => {
+                let amounts: Vec<u64> = contributions[..usize::from(count)]
+                    .iter()
+                    .map(|contribution| self.decrypt_contribution(contribution))
+                    .collect();
+                CircuitOutput::Total(circuits::reveal_final_total(&amounts, target))
+            }
+            ComputationInputs::CheckVaultBalance {
+                contributions,
+                count,
+                vault_balance,
+                ..
+            } => {
+                let amounts: Vec<u64> = contributions[..usize::from(count)]
+                    .iter()
+                    .map(|contribution| self.decrypt_contribution(contribution))
+                    .collect();
+                CircuitOutput::VaultSign(circuits::check_vault_balance(&amounts, vault_balance))
+            }
+            ComputationInputs::CheckMilestones {
+                contributions,
+                count,
+                milestones,
+                thresholds,
+            } => {
+                let amounts: Vec<u64> = contributions[..usize::from(count)]
+                    .iter()
+                    .map(|contribution| self.decrypt_contribution(contribution))
+                    .collect();
+                CircuitOutput::Milestones(circuits::check_milestones(
+                    &amounts,
+                    &thresholds[..usize::from(milestones)],
+                ))
+            }
+            ComputationInputs::DistributeYield {
+                contributions,
+                count,
+                yield_amount,
+                ..
+            } => {
+                let amounts: Vec<u64> = contributions[..usize::from(count)]
+                    .iter()
+                    .map(|contribution| self.decrypt_contribution(contribution))
+                    .collect();
+                let mut shares = [0; 10];
+                shares[..amounts.len()]
+                    .copy_from_slice(&circuits::distribute_yield(&amounts, yield_amount));
+                CircuitOutput::YieldShares(shares)
+            }
+        };
+        Computation {
+            computation_offset: record.computation_offset,
+            goal: record.goal,
+            inputs: record.inputs,
+            output,
+        }
+    }
+}
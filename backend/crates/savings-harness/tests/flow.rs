@@ -0,0 +1,1909 @@
+//! The full goal lifecycle against the mock MXE.
+
+use savings_client::accounts::{
+    Audit, ComputationRecord, ComputationStatus, Contribution, FailureReason, GoalStatus,
+    GroupStats, MemberProgress, Milestone, PeriodContribution, PeriodStatus, SavingsGoal, Schedule,
+    TransferPolicy, YieldAdapter,
+};
+use savings_client::cipher::{encrypt_u64, random_nonce, ClientKeypair};
+use savings_client::events::AggregationEvent;
+use savings_client::events::{SavingsEvent, VaultBalance};
+use savings_client::mxe::Circuit;
+use savings_client::{goal, mxe, pda};
+use savings_goal::ErrorCode as GoalError;
+use savings_harness::{custom_error, TestEnv};
+use savings_mxe::ErrorCode;
+use solana_sdk::native_token::LAMPORTS_PER_SOL;
+use solana_sdk::signature::{Keypair, Signer};
+
+fn aggregation(events: &[SavingsEvent]) -> Option<&AggregationEvent> {
+    events.iter().find_map(|event| match event {
+        SavingsEvent::Aggregation(e) => Some(e),
+        _ => None,
+    })
+}
+
+fn goal_check(events: &[SavingsEvent]) -> Option<bool> {
+    events.iter().find_map(|event| match event {
+        SavingsEvent::GoalCheck(e) => Some(e.reached),
+        _ => None,
+    })
+}
+
+#[tokio::test]
+async fn contribute_aggregate_check_finalize_transfer() {
+    let mut env = TestEnv::start().await;
+    let owner = env.payer();
+    let member = env.funded_keypair(LAMPORTS_PER_SOL).await;
+    // Funded, so the small payout does not leave it below rent exemption
+    let recipient = env.funded_keypair(LAMPORTS_PER_SOL).await.pubkey();
+    let goal_address = pda::goal(&owner, 1);
+    let deadline = env.now().await + 3_600;
+
+    env.send(
+        &[
+            mxe::initialize_config(&owner, owner),
+            goal::create_goal(
+                &owner,
+                1,
+                "trip".into(),
+                1_000,
+                Some(deadline),
+                None,
+                None,
+                vec![],
+                None,
+                vec![],
+            ),
+        ],
+        &[],
+    )
+    .await
+    .unwrap();
+    env.send(
+        &[
+            goal::invite_member(&owner, &goal_address, &member.pubkey()),
+            mxe::pin_goal_circuits(&owner, &goal_address),
+        ],
+        &[],
+    )
+    .await
+    .unwrap();
+
+    // Contribute
+    let mxe_key = env.mxe.public_key();
+    let owner_amount = encrypt_u64(&ClientKeypair::generate(), &mxe_key, 400);
+    let member_amount = encrypt_u64(&ClientKeypair::generate(), &mxe_key, 700);
+    env.send(
+        &[goal::add_contribution(&owner, &goal_address, &owner_amount)],
+        &[],
+    )
+    .await
+    .unwrap();
+    env.send(
+        &[goal::add_contribution(
+            &member.pubkey(),
+            &goal_address,
+            &member_amount,
+        )],
+        &[&member],
+    )
+    .await
+    .unwrap();
+
+    // Aggregate
+    let keypair = ClientKeypair::generate();
+    let cipher = keypair.shared_cipher(&mxe_key);
+    let nonce = random_nonce();
+    let params = env.queue_params(goal_address);
+    let events = env
+        .run_computation(
+            mxe::add_two_contributions(
+                &params,
+                cipher.encrypt(&[400], nonce)[0],
+                cipher.encrypt(&[700], nonce)[0],
+                keypair.public_key(),
+                nonce,
+            ),
+            &[],
+        )
+        .await
+        .unwrap();
+    let result = aggregation(&events).unwrap();
+    assert_eq!(result.total, 1_100);
+    assert_eq!(result.goal, goal_address);
+    assert_eq!(result.computation_offset, params.computation_offset);
+    let record: ComputationRecord = env
+        .account(&pda::computation_record(params.computation_offset))
+        .await
+        .unwrap();
+    assert!(record.status == ComputationStatus::Completed);
+    assert_eq!(record.goal, goal_address);
+    assert_eq!(record.requester, owner);
+    assert!(record.completed_at.is_some());
+
+    // Check
+    let total = encrypt_u64(&ClientKeypair::generate(), &mxe_key, 1_100);
+    let params = env.queue_params(goal_address);
+    let events = env
+        .run_computation(
+            mxe::check_goal_reached(&params, total.ciphertext, 1_000, total.pub_key, total.nonce),
+            &[],
+        )
+        .await
+        .unwrap();
+    assert_eq!(goal_check(&events), Some(true));
+
+    // Finalize
+    env.set_time(deadline).await;
+    let params = env.queue_params(goal_address);
+    env.run_computation(
+        mxe::finalize_and_reveal_total(&params, &[owner, member.pubkey()]),
+        &[],
+    )
+    .await
+    .unwrap();
+    let goal: SavingsGoal = env.account(&goal_address).await.unwrap();
+    assert!(goal.status == GoalStatus::Finalized);
+    assert_eq!(goal.revealed_total, Some(1_100));
+
+    // Transfer
+    env.fund_vault(&goal_address, LAMPORTS_PER_SOL);
+    env.send(
+        &[
+            goal::request_transfer(&owner, &goal_address, recipient, 1_000),
+            goal::approve_transfer(&owner, &goal_address, &recipient),
+        ],
+        &[],
+    )
+    .await
+    .unwrap();
+    let paid = env.raw_account(&recipient).await.unwrap().lamports - LAMPORTS_PER_SOL;
+    assert_eq!(paid, 1_000);
+}
+
+#[tokio::test]
+async fn check_reports_unreached_goal() {
+    let mut env = TestEnv::start().await;
+    let owner = env.payer();
+    let goal_address = pda::goal(&owner, 1);
+    env.send(
+        &[
+            mxe::initialize_config(&owner, owner),
+            goal::create_goal(
+                &owner,
+                1,
+                "trip".into(),
+                1_000,
+                None,
+                None,
+                None,
+                vec![],
+                None,
+                vec![],
+            ),
+            mxe::pin_goal_circuits(&owner, &goal_address),
+        ],
+        &[],
+    )
+    .await
+    .unwrap();
+
+    let total = encrypt_u64(&ClientKeypair::generate(), &env.mxe.public_key(), 999);
+
+    // Only members can check against the goal
+    let outsider = env.funded_keypair(LAMPORTS_PER_SOL).await;
+    let mut params = env.queue_params(goal_address);
+    params.payer = outsider.pubkey();
+    let err = env
+        .send(
+            &[mxe::check_goal_reached(
+                &params,
+                total.ciphertext,
+                1_000,
+                total.pub_key,
+                total.nonce,
+            )],
+            &[&outsider],
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(custom_error(&err), Some(u32::from(ErrorCode::NotAMember)));
+
+    let params = env.queue_params(goal_address);
+    let events = env
+        .run_computation(
+            mxe::check_goal_reached(&params, total.ciphertext, 1_000, total.pub_key, total.nonce),
+            &[],
+        )
+        .await
+        .unwrap();
+    assert_eq!(goal_check(&events), Some(false));
+}
+
+#[tokio::test]
+async fn aggregation_marks_goal_reached() {
+    let mut env = TestEnv::start().await;
+    let owner = env.payer();
+    let member = env.funded_keypair(LAMPORTS_PER_SOL).await;
+    let late_member = env.funded_keypair(LAMPORTS_PER_SOL).await;
+    let goal_address = pda::goal(&owner, 1);
+    env.send(
+        &[
+            mxe::initialize_config(&owner, owner),
+            goal::create_goal(
+                &owner,
+                1,
+                "trip".into(),
+                1_000,
+                None,
+                None,
+                None,
+                vec![],
+                None,
+                vec![],
+            ),
+        ],
+        &[],
+    )
+    .await
+    .unwrap();
+    env.send(
+        &[
+            goal::invite_member(&owner, &goal_address, &member.pubkey()),
+            goal::invite_member(&owner, &goal_address, &late_member.pubkey()),
+            mxe::pin_goal_circuits(&owner, &goal_address),
+        ],
+        &[],
+    )
+    .await
+    .unwrap();
+
+    let mxe_key = env.mxe.public_key();
+    let owner_amount = encrypt_u64(&ClientKeypair::generate(), &mxe_key, 300);
+    let member_amount = encrypt_u64(&ClientKeypair::generate(), &mxe_key, 500);
+    env.send(
+        &[goal::add_contribution(&owner, &goal_address, &owner_amount)],
+        &[],
+    )
+    .await
+    .unwrap();
+    env.send(
+        &[goal::add_contribution(
+            &member.pubkey(),
+            &goal_address,
+            &member_amount,
+        )],
+        &[&member],
+    )
+    .await
+    .unwrap();
+
+    // 800 < 1_000: the goal stays active. The late member has no
+    // contribution yet and is skipped.
+    let members = [owner, member.pubkey(), late_member.pubkey()];
+    let params = env.queue_params(goal_address);
+    let events = env
+        .run_computation(mxe::add_and_check_goal(&params, &members), &[])
+        .await
+        .unwrap();
+    assert_eq!(goal_check(&events), Some(false));
+    assert!(!events
+        .iter()
+        .any(|event| matches!(event, SavingsEvent::GoalReached(_))));
+    let goal: SavingsGoal = env.account(&goal_address).await.unwrap();
+    assert!(goal.status == GoalStatus::Active);
+
+    // Every member has to be passed, contributor or not
+    let params = env.queue_params(goal_address);
+    let err = env
+        .send(&[mxe::add_and_check_goal(&params, &members[..2])], &[])
+        .await
+        .unwrap_err();
+    assert_eq!(
+        custom_error(&err),
+        Some(ErrorCode::MissingContributions.into())
+    );
+
+    // No two contributions reach the target, all three do: 300 + 500 + 300
+    // crosses it and the callback marks the goal reached
+    let late_amount = encrypt_u64(&ClientKeypair::generate(), &mxe_key, 300);
+    env.send(
+        &[goal::add_contribution(
+            &late_member.pubkey(),
+            &goal_address,
+            &late_amount,
+        )],
+        &[&late_member],
+    )
+    .await
+    .unwrap();
+    let params = env.queue_params(goal_address);
+    let events = env
+        .run_computation(mxe::add_and_check_goal(&params, &members), &[])
+        .await
+        .unwrap();
+    assert_eq!(goal_check(&events), Some(true));
+    assert!(events
+        .iter()
+        .any(|event| matches!(event, SavingsEvent::GoalReached(e) if e.goal_id == 1)));
+    let goal: SavingsGoal = env.account(&goal_address).await.unwrap();
+    assert!(goal.status == GoalStatus::Reached);
+
+    // A reached goal can be finalized without a deadline, by any member
+    env.send(
+        &[goal::finalize_and_reveal(&member.pubkey(), &goal_address)],
+        &[&member],
+    )
+    .await
+    .unwrap();
+    let goal: SavingsGoal = env.account(&goal_address).await.unwrap();
+    assert!(goal.status == GoalStatus::Finalized);
+}
+
+#[tokio::test]
+async fn period_checks_flag_short_contributions() {
+    let mut env = TestEnv::start().await;
+    let owner = env.payer();
+    let member = env.funded_keypair(LAMPORTS_PER_SOL).await;
+    let goal_address = pda::goal(&owner, 1);
+    let schedule = Schedule {
+        interval: 3_600,
+        expected_amount: 100,
+        start_time: env.now().await,
+    };
+    env.send(
+        &[
+            mxe::initialize_config(&owner, owner),
+            goal::create_goal(
+                &owner,
+                1,
+                "rent".into(),
+                1_000,
+                None,
+                Some(schedule),
+                None,
+                vec![],
+                None,
+                vec![],
+            ),
+        ],
+        &[],
+    )
+    .await
+    .unwrap();
+    env.send(
+        &[
+            goal::invite_member(&owner, &goal_address, &member.pubkey()),
+            mxe::pin_goal_circuits(&owner, &goal_address),
+        ],
+        &[],
+    )
+    .await
+    .unwrap();
+
+    let mxe_key = env.mxe.public_key();
+    let owner_amount = encrypt_u64(&ClientKeypair::generate(), &mxe_key, 120);
+    let member_amount = encrypt_u64(&ClientKeypair::generate(), &mxe_key, 60);
+    env.send(
+        &[goal::contribute_for_period(
+            &owner,
+            &goal_address,
+            0,
+            &owner_amount,
+        )],
+        &[],
+    )
+    .await
+    .unwrap();
+    env.send(
+        &[goal::contribute_for_period(
+            &member.pubkey(),
+            &goal_address,
+            0,
+            &member_amount,
+        )],
+        &[&member],
+    )
+    .await
+    .unwrap();
+
+    // 120 >= 100: met, and nothing is announced
+    let params = env.queue_params(goal_address);
+    let events = env
+        .run_computation(mxe::check_period_contribution(&params, &owner, 0), &[])
+        .await
+        .unwrap();
+    assert!(events.iter().any(|event| matches!(
+        event,
+        SavingsEvent::PeriodCheck(e) if e.met && e.member == owner && e.period == 0
+    )));
+    assert!(!events
+        .iter()
+        .any(|event| matches!(event, SavingsEvent::ContributionMissed(_))));
+    let record: PeriodContribution = env
+        .account(&pda::period_contribution(&goal_address, &owner, 0))
+        .await
+        .unwrap();
+    assert!(record.status == PeriodStatus::Met);
+
+    // 60 < 100: short, which the group hears about without learning the amount
+    let params = env.queue_params(goal_address);
+    let events = env
+        .run_computation(
+            mxe::check_period_contribution(&params, &member.pubkey(), 0),
+            &[],
+        )
+        .await
+        .unwrap();
+    assert!(events.iter().any(|event| matches!(
+        event,
+        SavingsEvent::ContributionMissed(e)
+            if e.member == member.pubkey() && e.period == 0 && e.contributed
+    )));
+    let record: PeriodContribution = env
+        .account(&pda::period_contribution(
+            &goal_address,
+            &member.pubkey(),
+            0,
+        ))
+        .await
+        .unwrap();
+    assert!(record.status == PeriodStatus::Short);
+
+    // A settled period is not checked again
+    let params = env.queue_params(goal_address);
+    let err = env
+        .send(&[mxe::check_period_contribution(&params, &owner, 0)], &[])
+        .await
+        .unwrap_err();
+    assert_eq!(
+        custom_error(&err),
+        Some(u32::from(ErrorCode::PeriodNotPending))
+    );
+}
+
+#[tokio::test]
+async fn member_progress_is_readable_only_by_the_member() {
+    let mut env = TestEnv::start().await;
+    let owner = env.payer();
+    let member = env.funded_keypair(LAMPORTS_PER_SOL).await;
+    let goal_address = pda::goal(&owner, 1);
+    env.send(
+        &[
+            mxe::initialize_config(&owner, owner),
+            goal::create_goal(
+                &owner,
+                1,
+                "trip".into(),
+                1_000,
+                None,
+                None,
+                None,
+                vec![],
+                None,
+                vec![],
+            ),
+        ],
+        &[],
+    )
+    .await
+    .unwrap();
+    env.send(
+        &[
+            goal::invite_member(&owner, &goal_address, &member.pubkey()),
+            mxe::pin_goal_circuits(&owner, &goal_address),
+        ],
+        &[],
+    )
+    .await
+    .unwrap();
+
+    let mxe_key = env.mxe.public_key();
+    let member_key = ClientKeypair::generate();
+    let amount = encrypt_u64(&member_key, &mxe_key, 400);
+    env.send(
+        &[goal::add_contribution(
+            &member.pubkey(),
+            &goal_address,
+            &amount,
+        )],
+        &[&member],
+    )
+    .await
+    .unwrap();
+
+    // 400 of a 500 commitment: 100 short
+    let mut params = env.queue_params(goal_address);
+    params.payer = member.pubkey();
+    let events = env
+        .run_computation(
+            mxe::check_member_progress(
+                &params,
+                &encrypt_u64(&member_key, &mxe_key, 500),
+                random_nonce(),
+            ),
+            &[&member],
+        )
+        .await
+        .unwrap();
+    assert!(events.iter().any(|event| matches!(
+        event,
+        SavingsEvent::MemberProgress(e) if e.member == member.pubkey()
+    )));
+    let progress: MemberProgress = env
+        .account(&pda::member_progress(&goal_address, &member.pubkey()))
+        .await
+        .unwrap();
+    assert_eq!(progress.computation_offset, params.computation_offset);
+    assert_eq!(progress.encryption_key, member_key.public_key());
+    let decrypt = |keypair: &ClientKeypair| {
+        keypair
+            .shared_cipher(&mxe_key)
+            .decrypt(&[progress.encrypted_shortfall], progress.nonce)
+    };
+    assert_eq!(decrypt(&member_key), Ok(vec![100]));
+    assert_ne!(decrypt(&ClientKeypair::generate()), Ok(vec![100]));
+
+    // A lower commitment is met; the account keeps only the latest result
+    let mut params = env.queue_params(goal_address);
+    params.payer = member.pubkey();
+    env.run_computation(
+        mxe::check_member_progress(
+            &params,
+            &encrypt_u64(&member_key, &mxe_key, 400),
+            random_nonce(),
+        ),
+        &[&member],
+    )
+    .await
+    .unwrap();
+    let progress: MemberProgress = env
+        .account(&pda::member_progress(&goal_address, &member.pubkey()))
+        .await
+        .unwrap();
+    let shortfall = member_key
+        .shared_cipher(&mxe_key)
+        .decrypt(&[progress.encrypted_shortfall], progress.nonce)
+        .unwrap();
+    assert_eq!(shortfall, vec![0]);
+
+    // The owner has no contribution of their own to check, and cannot queue
+    // a check of the member's
+    let params = env.queue_params(goal_address);
+    assert!(env
+        .send(
+            &[mxe::check_member_progress(
+                &params,
+                &encrypt_u64(&ClientKeypair::generate(), &mxe_key, 500),
+                random_nonce(),
+            )],
+            &[],
+        )
+        .await
+        .is_err());
+}
+
+#[tokio::test]
+async fn group_stats_reveal_only_aggregates() {
+    let mut env = TestEnv::start().await;
+    let owner = env.payer();
+    let alice = env.funded_keypair(LAMPORTS_PER_SOL).await;
+    let bob = env.funded_keypair(LAMPORTS_PER_SOL).await;
+    let goal_address = pda::goal(&owner, 1);
+    env.send(
+        &[
+            mxe::initialize_config(&owner, owner),
+            goal::create_goal(
+                &owner,
+                1,
+                "trip".into(),
+                1_000,
+                None,
+                None,
+                None,
+                vec![],
+                None,
+                vec![],
+            ),
+        ],
+        &[],
+    )
+    .await
+    .unwrap();
+    env.send(
+        &[
+            goal::invite_member(&owner, &goal_address, &alice.pubkey()),
+            goal::invite_member(&owner, &goal_address, &bob.pubkey()),
+            mxe::pin_goal_circuits(&owner, &goal_address),
+        ],
+        &[],
+    )
+    .await
+    .unwrap();
+
+    let mxe_key = env.mxe.public_key();
+    let owner_amount = encrypt_u64(&ClientKeypair::generate(), &mxe_key, 300);
+    env.send(
+        &[goal::add_contribution(&owner, &goal_address, &owner_amount)],
+        &[],
+    )
+    .await
+    .unwrap();
+    let members = [owner, alice.pubkey(), bob.pubkey()];
+    let contribute = |member: &Keypair, amount| {
+        goal::add_contribution(
+            &member.pubkey(),
+            &goal_address,
+            &encrypt_u64(&ClientKeypair::generate(), &mxe_key, amount),
+        )
+    };
+    env.send(&[contribute(&alice, 0)], &[&alice]).await.unwrap();
+
+    // Too few contributions would give individual amounts away; bob has not
+    // contributed yet
+    let params = env.queue_params(goal_address);
+    let err = env
+        .send(&[mxe::compute_group_stats(&params, &members)], &[])
+        .await
+        .unwrap_err();
+    assert_eq!(
+        custom_error(&err),
+        Some(u32::from(ErrorCode::InvalidContributionCount))
+    );
+    env.send(&[contribute(&bob, 900)], &[&bob]).await.unwrap();
+
+    // Every member is included, in order, so subsets cannot be differenced
+    let params = env.queue_params(goal_address);
+    let err = env
+        .send(&[mxe::compute_group_stats(&params, &members[..2])], &[])
+        .await
+        .unwrap_err();
+    assert_eq!(
+        custom_error(&err),
+        Some(u32::from(ErrorCode::MissingContributions))
+    );
+    let params = env.queue_params(goal_address);
+    let err = env
+        .send(
+            &[mxe::compute_group_stats(
+                &params,
+                &[alice.pubkey(), owner, bob.pubkey()],
+            )],
+            &[],
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(
+        custom_error(&err),
+        Some(u32::from(ErrorCode::WrongContribution))
+    );
+
+    // Only the goal owner asks for statistics
+    let mut params = env.queue_params(goal_address);
+    params.payer = alice.pubkey();
+    let err = env
+        .send(&[mxe::compute_group_stats(&params, &members)], &[&alice])
+        .await
+        .unwrap_err();
+    assert_eq!(custom_error(&err), Some(u32::from(ErrorCode::Unauthorized)));
+
+    let params = env.queue_params(goal_address);
+    let events = env
+        .run_computation(mxe::compute_group_stats(&params, &members), &[])
+        .await
+        .unwrap();
+    assert!(events.iter().any(|event| matches!(
+        event,
+        SavingsEvent::GroupStats(e)
+            if e.contributors == 3 && e.average == 400 && e.min == 0 && e.max == 900 && e.active == 2
+    )));
+    let stats: GroupStats = env.account(&pda::group_stats(&goal_address)).await.unwrap();
+    assert_eq!(stats.computation_offset, params.computation_offset);
+    assert_eq!(
+        (
+            stats.contributors,
+            stats.average,
+            stats.min,
+            stats.max,
+            stats.active
+        ),
+        (3, 400, 0, 900, 2)
+    );
+}
+
+#[tokio::test]
+async fn reveals_reach_only_their_recipients() {
+    let mut env = TestEnv::start().await;
+    let owner = env.payer();
+    let alice = env.funded_keypair(LAMPORTS_PER_SOL).await;
+    let bob = env.funded_keypair(LAMPORTS_PER_SOL).await;
+    let stranger = env.funded_keypair(LAMPORTS_PER_SOL).await;
+    let goal_address = pda::goal(&owner, 1);
+    env.send(
+        &[
+            mxe::initialize_config(&owner, owner),
+            goal::create_goal(
+                &owner,
+                1,
+                "trip".into(),
+                1_000,
+                None,
+                None,
+                None,
+                vec![],
+                None,
+                vec![],
+            ),
+        ],
+        &[],
+    )
+    .await
+    .unwrap();
+    env.send(
+        &[
+            goal::invite_member(&owner, &goal_address, &alice.pubkey()),
+            goal::invite_member(&owner, &goal_address, &bob.pubkey()),
+            mxe::pin_goal_circuits(&owner, &goal_address),
+        ],
+        &[],
+    )
+    .await
+    .unwrap();
+
+    let mxe_key = env.mxe.public_key();
+    let alice_key = ClientKeypair::generate();
+    let bob_key = ClientKeypair::generate();
+    for (member, key, amount) in [(&alice, &alice_key, 250), (&bob, &bob_key, 700)] {
+        let encrypted = encrypt_u64(key, &mxe_key, amount);
+        env.send(
+            &[goal::add_contribution(
+                &member.pubkey(),
+                &goal_address,
+                &encrypted,
+            )],
+            &[member],
+        )
+        .await
+        .unwrap();
+    }
+    let contributors = [alice.pubkey(), bob.pubkey()];
+
+    // Selective reveal: every contributor can read their own amount only
+    let mut params = env.queue_params(goal_address);
+    params.payer = alice.pubkey();
+    let events = env
+        .run_computation(
+            mxe::reveal_own_contributions(&params, &contributors),
+            &[&alice],
+        )
+        .await
+        .unwrap();
+    let revealed: Vec<_> = events
+        .iter()
+        .filter_map(|event| match event {
+            SavingsEvent::ContributionRevealed(e) => Some(e),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(revealed.len(), 2);
+    for (e, key, amount) in [(revealed[0], &alice_key, 250), (revealed[1], &bob_key, 700)] {
+        assert_eq!(e.encryption_key, key.public_key());
+        let decrypt = |keypair: &ClientKeypair| {
+            keypair
+                .shared_cipher(&mxe_key)
+                .decrypt(&[e.encrypted_amount], e.nonce)
+        };
+        assert_eq!(decrypt(key), Ok(vec![amount]));
+        assert_ne!(decrypt(&ClientKeypair::generate()), Ok(vec![amount]));
+    }
+
+    // Outsiders cannot queue reveals
+    let mut params = env.queue_params(goal_address);
+    params.payer = stranger.pubkey();
+    let err = env
+        .send(
+            &[mxe::reveal_own_contributions(&params, &contributors)],
+            &[&stranger],
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(custom_error(&err), Some(u32::from(ErrorCode::NotAMember)));
+
+    // Owner reveal needs every contributor's opt-in
+    let owner_key = ClientKeypair::generate();
+    let params = env.queue_params(goal_address);
+    let err = env
+        .send(
+            &[mxe::reveal_to_owner(
+                &params,
+                &contributors,
+                owner_key.public_key(),
+                random_nonce(),
+            )],
+            &[],
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(
+        custom_error(&err),
+        Some(u32::from(ErrorCode::OwnerRevealNotAllowed))
+    );
+    for member in [&alice, &bob] {
+        env.send(
+            &[goal::set_owner_reveal(
+                &member.pubkey(),
+                &goal_address,
+                true,
+            )],
+            &[member],
+        )
+        .await
+        .unwrap();
+    }
+    let contribution: Contribution = env
+        .account(&pda::contribution(&goal_address, &alice.pubkey()))
+        .await
+        .unwrap();
+    assert!(contribution.owner_reveal);
+
+    // ...and only the owner can ask for it
+    let mut params = env.queue_params(goal_address);
+    params.payer = alice.pubkey();
+    let err = env
+        .send(
+            &[mxe::reveal_to_owner(
+                &params,
+                &contributors,
+                alice_key.public_key(),
+                random_nonce(),
+            )],
+            &[&alice],
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(custom_error(&err), Some(u32::from(ErrorCode::Unauthorized)));
+
+    let params = env.queue_params(goal_address);
+    let events = env
+        .run_computation(
+            mxe::reveal_to_owner(
+                &params,
+                &contributors,
+                owner_key.public_key(),
+                random_nonce(),
+            ),
+            &[],
+        )
+        .await
+        .unwrap();
+    let reveal = events
+        .iter()
+        .find_map(|event| match event {
+            SavingsEvent::OwnerReveal(e) => Some(e),
+            _ => None,
+        })
+        .expect("owner reveal event");
+    assert_eq!(reveal.encryption_key, owner_key.public_key());
+    let amounts = owner_key
+        .shared_cipher(&mxe_key)
+        .decrypt(&reveal.encrypted_amounts, reveal.nonce)
+        .unwrap();
+    assert_eq!(amounts, vec![250, 700]);
+}
+
+#[tokio::test]
+async fn audit_is_readable_by_the_auditor_only() {
+    let mut env = TestEnv::start().await;
+    let owner = env.payer();
+    let alice = env.funded_keypair(LAMPORTS_PER_SOL).await;
+    let bob = env.funded_keypair(LAMPORTS_PER_SOL).await;
+    let auditor_key = ClientKeypair::generate();
+    let goal_address = pda::goal(&owner, 1);
+    let unaudited = pda::goal(&owner, 2);
+    env.send(
+        &[
+            mxe::initialize_config(&owner, owner),
+            goal::create_goal(
+                &owner,
+                1,
+                "coop".into(),
+                1_000,
+                None,
+                None,
+                Some(auditor_key.public_key()),
+                vec![],
+                None,
+                vec![],
+            ),
+            goal::create_goal(
+                &owner,
+                2,
+                "trip".into(),
+                1_000,
+                None,
+                None,
+                None,
+                vec![],
+                None,
+                vec![],
+            ),
+        ],
+        &[],
+    )
+    .await
+    .unwrap();
+    env.send(
+        &[
+            goal::invite_member(&owner, &goal_address, &alice.pubkey()),
+            goal::invite_member(&owner, &goal_address, &bob.pubkey()),
+            mxe::pin_goal_circuits(&owner, &goal_address),
+            mxe::pin_goal_circuits(&owner, &unaudited),
+        ],
+        &[],
+    )
+    .await
+    .unwrap();
+    let goal: SavingsGoal = env.account(&goal_address).await.unwrap();
+    assert_eq!(goal.auditor, Some(auditor_key.public_key()));
+
+    let mxe_key = env.mxe.public_key();
+    for (member, amount) in [(&alice, 250), (&bob, 700)] {
+        let encrypted = encrypt_u64(&ClientKeypair::generate(), &mxe_key, amount);
+        env.send(
+            &[goal::add_contribution(
+                &member.pubkey(),
+                &goal_address,
+                &encrypted,
+            )],
+            &[member],
+        )
+        .await
+        .unwrap();
+    }
+    let contributors = [alice.pubkey(), bob.pubkey()];
+
+    // Only the owner queues audits
+    let mut params = env.queue_params(goal_address);
+    params.payer = alice.pubkey();
+    let err = env
+        .send(
+            &[mxe::audit_contributions(
+                &params,
+                &contributors,
+                random_nonce(),
+            )],
+            &[&alice],
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(custom_error(&err), Some(u32::from(ErrorCode::Unauthorized)));
+
+    let params = env.queue_params(goal_address);
+    let events = env
+        .run_computation(
+            mxe::audit_contributions(&params, &contributors, random_nonce()),
+            &[],
+        )
+        .await
+        .unwrap();
+    assert!(events.iter().any(|event| matches!(
+        event,
+        SavingsEvent::Audit(e) if e.goal == goal_address && e.contributors == 2
+    )));
+
+    let audit: Audit = env.account(&pda::audit(&goal_address)).await.unwrap();
+    assert_eq!(audit.computation_offset, params.computation_offset);
+    assert_eq!(audit.contributors, contributors.to_vec());
+    assert_eq!(audit.encryption_key, auditor_key.public_key());
+    let mut ciphertexts = audit.encrypted_amounts.clone();
+    ciphertexts.push(audit.encrypted_total);
+    let decrypt = |keypair: &ClientKeypair| {
+        keypair
+            .shared_cipher(&mxe_key)
+            .decrypt(&ciphertexts, audit.nonce)
+    };
+    assert_eq!(decrypt(&auditor_key), Ok(vec![250, 700, 950]));
+    assert_ne!(decrypt(&ClientKeypair::generate()), Ok(vec![250, 700, 950]));
+
+    // Goals created without an auditor cannot be audited
+    let params = env.queue_params(unaudited);
+    let err = env
+        .send(
+            &[mxe::audit_contributions(&params, &[], random_nonce())],
+            &[],
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(custom_error(&err), Some(u32::from(ErrorCode::NoAuditor)));
+}
+
+#[tokio::test]
+async fn final_total_is_revealed_only_when_reached() {
+    let mut env = TestEnv::start().await;
+    let owner = env.payer();
+    let alice = env.funded_keypair(LAMPORTS_PER_SOL).await;
+    let bob = Keypair::new().pubkey();
+    let reached = pda::goal(&owner, 1);
+    let short = pda::goal(&owner, 2);
+    let deadline = env.now().await + 3_600;
+    env.send(
+        &[
+            mxe::initialize_config(&owner, owner),
+            goal::create_goal(
+                &owner,
+                1,
+                "trip".into(),
+                1_000_000,
+                Some(deadline),
+                None,
+                None,
+                vec![],
+                None,
+                vec![],
+            ),
+            goal::create_goal(
+                &owner,
+                2,
+                "bike".into(),
+                1_000_000,
+                Some(deadline),
+                None,
+                None,
+                vec![],
+                None,
+                vec![],
+            ),
+        ],
+        &[],
+    )
+    .await
+    .unwrap();
+    env.send(
+        &[
+            goal::invite_member(&owner, &reached, &alice.pubkey()),
+            goal::invite_member(&owner, &reached, &bob),
+            mxe::pin_goal_circuits(&owner, &reached),
+            mxe::pin_goal_circuits(&owner, &short),
+        ],
+        &[],
+    )
+    .await
+    .unwrap();
+
+    let mxe_key = env.mxe.public_key();
+    let encrypt = |amount| encrypt_u64(&ClientKeypair::generate(), &mxe_key, amount);
+    env.send(
+        &[
+            goal::add_contribution(&owner, &reached, &encrypt(400_000)),
+            goal::add_contribution(&owner, &short, &encrypt(300_000)),
+        ],
+        &[],
+    )
+    .await
+    .unwrap();
+    env.send(
+        &[goal::add_contribution(
+            &alice.pubkey(),
+            &reached,
+            &encrypt(700_000),
+        )],
+        &[&alice],
+    )
+    .await
+    .unwrap();
+    env.set_time(deadline).await;
+
+    // Every member's contribution slot is passed, even bob's empty one
+    let params = env.queue_params(reached);
+    let err = env
+        .send(
+            &[mxe::finalize_and_reveal_total(
+                &params,
+                &[owner, alice.pubkey()],
+            )],
+            &[],
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(
+        custom_error(&err),
+        Some(u32::from(ErrorCode::MissingContributions))
+    );
+
+    let params = env.queue_params(reached);
+    let events = env
+        .run_computation(
+            mxe::finalize_and_reveal_total(&params, &[owner, alice.pubkey(), bob]),
+            &[],
+        )
+        .await
+        .unwrap();
+    assert!(events.iter().any(|event| matches!(
+        event,
+        SavingsEvent::FinalTotal(e) if e.goal == reached && e.total == Some(1_100_000)
+    )));
+    let goal: SavingsGoal = env.account(&reached).await.unwrap();
+    assert!(goal.status == GoalStatus::Finalized);
+    assert_eq!(goal.revealed_total, Some(1_100_000));
+
+    // A goal that fell short is finalized without revealing anything
+    let params = env.queue_params(short);
+    let events = env
+        .run_computation(mxe::finalize_and_reveal_total(&params, &[owner]), &[])
+        .await
+        .unwrap();
+    assert!(events.iter().any(|event| matches!(
+        event,
+        SavingsEvent::FinalTotal(e) if e.goal == short && e.total.is_none()
+    )));
+    let goal: SavingsGoal = env.account(&short).await.unwrap();
+    assert!(goal.status == GoalStatus::Finalized);
+    assert_eq!(goal.revealed_total, None);
+
+    // Payouts are capped at the revealed total
+    env.fund_vault(&reached, LAMPORTS_PER_SOL);
+    let greedy = Keypair::new().pubkey();
+    let err = env
+        .send(
+            &[
+                goal::request_transfer(&owner, &reached, greedy, 1_100_001),
+                goal::approve_transfer(&owner, &reached, &greedy),
+            ],
+            &[],
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(
+        custom_error(&err),
+        Some(u32::from(GoalError::TransferExceedsRevealedTotal))
+    );
+    let recipient = Keypair::new().pubkey();
+    env.send(
+        &[
+            goal::request_transfer(&owner, &reached, recipient, 1_100_000),
+            goal::approve_transfer(&owner, &reached, &recipient),
+        ],
+        &[],
+    )
+    .await
+    .unwrap();
+}
+
+#[tokio::test]
+async fn vault_checks_flag_undeposited_contributions() {
+    let mut env = TestEnv::start().await;
+    let owner = env.payer();
+    let alice = env.funded_keypair(LAMPORTS_PER_SOL).await;
+    let outsider = env.funded_keypair(LAMPORTS_PER_SOL).await;
+    let goal_address = pda::goal(&owner, 1);
+    let deadline = env.now().await + 3_600;
+    env.send(
+        &[
+            mxe::initialize_config(&owner, owner),
+            goal::create_goal(
+                &owner,
+                1,
+                "trip".into(),
+                1_000_000,
+                Some(deadline),
+                None,
+                None,
+                vec![],
+                None,
+                vec![],
+            ),
+        ],
+        &[],
+    )
+    .await
+    .unwrap();
+    env.send(
+        &[
+            goal::invite_member(&owner, &goal_address, &alice.pubkey()),
+            mxe::pin_goal_circuits(&owner, &goal_address),
+        ],
+        &[],
+    )
+    .await
+    .unwrap();
+
+    let mxe_key = env.mxe.public_key();
+    let encrypted = encrypt_u64(&ClientKeypair::generate(), &mxe_key, 600_000);
+    env.send(
+        &[goal::add_contribution(&owner, &goal_address, &encrypted)],
+        &[],
+    )
+    .await
+    .unwrap();
+    let encrypted = encrypt_u64(&ClientKeypair::generate(), &mxe_key, 500_000);
+    env.send(
+        &[goal::add_contribution(
+            &alice.pubkey(),
+            &goal_address,
+            &encrypted,
+        )],
+        &[&alice],
+    )
+    .await
+    .unwrap();
+    let members = [owner, alice.pubkey()];
+
+    // Only members can check
+    let mut params = env.queue_params(goal_address);
+    params.payer = outsider.pubkey();
+    let err = env
+        .send(&[mxe::check_vault_balance(&params, &members)], &[&outsider])
+        .await
+        .unwrap_err();
+    assert_eq!(custom_error(&err), Some(u32::from(ErrorCode::NotAMember)));
+
+    // Lamports sent to the vault directly aren't deposits, so they can't be
+    // used to probe the encrypted total
+    env.fund_vault(&goal_address, 2 * LAMPORTS_PER_SOL);
+    env.send(&[goal::deposit(&owner, &goal_address, 600_000)], &[])
+        .await
+        .unwrap();
+    let params = env.queue_params(goal_address);
+    let events = env
+        .run_computation(mxe::check_vault_balance(&params, &members), &[])
+        .await
+        .unwrap();
+    assert!(events.iter().any(|event| matches!(
+        event,
+        SavingsEvent::VaultCheck(e)
+            if e.balance == VaultBalance::Shortfall && e.vault_balance == 600_000
+    )));
+    assert!(events.iter().any(|event| matches!(
+        event,
+        SavingsEvent::VaultChecked(e) if e.inconsistent
+    )));
+    let goal: SavingsGoal = env.account(&goal_address).await.unwrap();
+    assert!(goal.inconsistent);
+    assert_eq!(goal.deposited, 600_000);
+
+    // A later matching check clears the flag
+    for (amount, vault_balance, expected) in [
+        (500_000, 1_100_000, VaultBalance::Matches),
+        (900_000, 2_000_000, VaultBalance::Surplus),
+    ] {
+        env.send(
+            &[goal::deposit(&alice.pubkey(), &goal_address, amount)],
+            &[&alice],
+        )
+        .await
+        .unwrap();
+        let params = env.queue_params(goal_address);
+        let events = env
+            .run_computation(mxe::check_vault_balance(&params, &members), &[])
+            .await
+            .unwrap();
+        assert!(events.iter().any(|event| matches!(
+            event,
+            SavingsEvent::VaultCheck(e)
+                if e.balance == expected && e.vault_balance == vault_balance
+        )));
+        let goal: SavingsGoal = env.account(&goal_address).await.unwrap();
+        assert!(!goal.inconsistent);
+    }
+
+    // Checks go on after finalization, and payouts don't change what was
+    // deposited
+    env.set_time(deadline).await;
+    let recipient = Keypair::new().pubkey();
+    let params = env.queue_params(goal_address);
+    env.run_computation(mxe::finalize_and_reveal_total(&params, &members), &[])
+        .await
+        .unwrap();
+    env.send(
+        &[
+            goal::request_transfer(&owner, &goal_address, recipient, 1_000_000),
+            goal::approve_transfer(&owner, &goal_address, &recipient),
+        ],
+        &[],
+    )
+    .await
+    .unwrap();
+    let params = env.queue_params(goal_address);
+    let events = env
+        .run_computation(mxe::check_vault_balance(&params, &members), &[])
+        .await
+        .unwrap();
+    assert!(events.iter().any(|event| matches!(
+        event,
+        SavingsEvent::VaultCheck(e)
+            if e.balance == VaultBalance::Surplus && e.vault_balance == 2_000_000
+    )));
+    let goal: SavingsGoal = env.account(&goal_address).await.unwrap();
+    assert_eq!(goal.released, 1_000_000);
+    assert!(goal.vault_checked_at >= goal.finalized_at);
+}
+
+#[tokio::test]
+async fn milestones_unlock_partial_releases() {
+    let mut env = TestEnv::start().await;
+    let owner = env.payer();
+    let alice = env.funded_keypair(LAMPORTS_PER_SOL).await;
+    let builder = Keypair::new().pubkey();
+    let goal_address = pda::goal(&owner, 1);
+    let milestones = vec![
+        Milestone {
+            percent: 25,
+            recipient: Some(builder),
+        },
+        Milestone {
+            percent: 50,
+            recipient: None,
+        },
+    ];
+    env.send(
+        &[
+            mxe::initialize_config(&owner, owner),
+            goal::create_goal(
+                &owner,
+                1,
+                "roof".into(),
+                1_000_000,
+                None,
+                None,
+                None,
+                milestones,
+                None,
+                vec![],
+            ),
+        ],
+        &[],
+    )
+    .await
+    .unwrap();
+    env.send(
+        &[
+            goal::invite_member(&owner, &goal_address, &alice.pubkey()),
+            mxe::pin_goal_circuits(&owner, &goal_address),
+        ],
+        &[],
+    )
+    .await
+    .unwrap();
+
+    let mxe_key = env.mxe.public_key();
+    let encrypted = encrypt_u64(&ClientKeypair::generate(), &mxe_key, 300_000);
+    env.send(
+        &[goal::add_contribution(&owner, &goal_address, &encrypted)],
+        &[],
+    )
+    .await
+    .unwrap();
+    let encrypted = encrypt_u64(&ClientKeypair::generate(), &mxe_key, 300_000);
+    env.send(
+        &[goal::add_contribution(
+            &alice.pubkey(),
+            &goal_address,
+            &encrypted,
+        )],
+        &[&alice],
+    )
+    .await
+    .unwrap();
+    let vault_rent = env
+        .context
+        .banks_client
+        .get_rent()
+        .await
+        .unwrap()
+        .minimum_balance(0);
+    env.fund_vault(&goal_address, vault_rent);
+    env.send(
+        &[
+            goal::deposit(&owner, &goal_address, 300_000),
+            goal::deposit(&alice.pubkey(), &goal_address, 300_000),
+        ],
+        &[&alice],
+    )
+    .await
+    .unwrap();
+    let members = [owner, alice.pubkey()];
+
+    // Nothing is released before the MXE has checked the milestones
+    let err = env
+        .send(
+            &[goal::request_milestone_transfer(
+                &owner,
+                &goal_address,
+                0,
+                builder,
+                250_000,
+            )],
+            &[],
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(
+        custom_error(&err),
+        Some(u32::from(GoalError::MilestoneNotReached))
+    );
+
+    // 600_000 crosses both the 25% and the 50% thresholds
+    let params = env.queue_params(goal_address);
+    let events = env
+        .run_computation(mxe::check_milestones(&params, &members), &[])
+        .await
+        .unwrap();
+    assert!(events.iter().any(|event| matches!(
+        event,
+        SavingsEvent::MilestoneCheck(e) if e.reached == 2
+    )));
+    assert_eq!(
+        events
+            .iter()
+            .filter(|event| matches!(event, SavingsEvent::MilestoneReached(_)))
+            .count(),
+        2
+    );
+    let goal: SavingsGoal = env.account(&goal_address).await.unwrap();
+    assert_eq!(goal.milestones_reached, 2);
+    assert!(goal.status == GoalStatus::Active);
+
+    // A milestone with a fixed recipient pays only them, and each milestone
+    // unlocks only its own share of the target
+    let err = env
+        .send(
+            &[goal::request_milestone_transfer(
+                &owner,
+                &goal_address,
+                0,
+                Keypair::new().pubkey(),
+                250_000,
+            )],
+            &[],
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(
+        custom_error(&err),
+        Some(u32::from(GoalError::WrongMilestoneRecipient))
+    );
+    let err = env
+        .send(
+            &[goal::request_milestone_transfer(
+                &owner,
+                &goal_address,
+                1,
+                builder,
+                250_001,
+            )],
+            &[],
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(
+        custom_error(&err),
+        Some(u32::from(GoalError::MilestoneCeilingExceeded))
+    );
+
+    env.send(
+        &[
+            goal::request_milestone_transfer(&owner, &goal_address, 0, builder, 250_000),
+            goal::approve_milestone_transfer(&owner, &goal_address, 0, &builder),
+        ],
+        &[],
+    )
+    .await
+    .unwrap();
+    let goal: SavingsGoal = env.account(&goal_address).await.unwrap();
+    assert_eq!(goal.released, 250_000);
+    let vault = env.raw_account(&pda::vault(&goal_address)).await.unwrap();
+    assert_eq!(vault.lamports, vault_rent + 350_000);
+
+    // Payouts don't change what was deposited
+    let params = env.queue_params(goal_address);
+    let events = env
+        .run_computation(mxe::check_vault_balance(&params, &members), &[])
+        .await
+        .unwrap();
+    assert!(events.iter().any(|event| matches!(
+        event,
+        SavingsEvent::VaultCheck(e)
+            if e.balance == VaultBalance::Matches && e.vault_balance == 600_000
+    )));
+    let goal: SavingsGoal = env.account(&goal_address).await.unwrap();
+    assert!(!goal.inconsistent);
+}
+
+#[tokio::test]
+async fn milestone_payouts_count_against_the_period_limit() {
+    let mut env = TestEnv::start().await;
+    let owner = env.payer();
+    let recipient = Keypair::new().pubkey();
+    let goal_address = pda::goal(&owner, 1);
+    let milestones = [25, 50]
+        .map(|percent| Milestone {
+            percent,
+            recipient: None,
+        })
+        .to_vec();
+    let policy = TransferPolicy {
+        max_per_transfer: None,
+        max_per_period: Some(300_000),
+        period: 86_400,
+        timelock: 0,
+    };
+    env.send(
+        &[
+            mxe::initialize_config(&owner, owner),
+            goal::create_goal(
+                &owner,
+                1,
+                "roof".into(),
+                1_000_000,
+                None,
+                None,
+                None,
+                milestones,
+                Some(policy),
+                vec![],
+            ),
+            mxe::pin_goal_circuits(&owner, &goal_address),
+        ],
+        &[],
+    )
+    .await
+    .unwrap();
+    let encrypted = encrypt_u64(&ClientKeypair::generate(), &env.mxe.public_key(), 600_000);
+    env.send(
+        &[goal::add_contribution(&owner, &goal_address, &encrypted)],
+        &[],
+    )
+    .await
+    .unwrap();
+    env.fund_vault(&goal_address, 600_000);
+    let params = env.queue_params(goal_address);
+    env.run_computation(mxe::check_milestones(&params, &[owner]), &[])
+        .await
+        .unwrap();
+
+    env.send(
+        &[
+            goal::request_milestone_transfer(&owner, &goal_address, 0, recipient, 250_000),
+            goal::approve_milestone_transfer(&owner, &goal_address, 0, &recipient),
+            goal::request_milestone_transfer(&owner, &goal_address, 1, recipient, 250_000),
+        ],
+        &[],
+    )
+    .await
+    .unwrap();
+    let err = env
+        .send(
+            &[goal::approve_milestone_transfer(
+                &owner,
+                &goal_address,
+                1,
+                &recipient,
+            )],
+            &[],
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(
+        custom_error(&err),
+        Some(u32::from(GoalError::PeriodLimitExceeded))
+    );
+
+    // The next period starts from nothing
+    let goal: SavingsGoal = env.account(&goal_address).await.unwrap();
+    env.set_time(goal.created_at + 86_400).await;
+    env.send(
+        &[goal::approve_milestone_transfer(
+            &owner,
+            &goal_address,
+            1,
+            &recipient,
+        )],
+        &[],
+    )
+    .await
+    .unwrap();
+    let goal: SavingsGoal = env.account(&goal_address).await.unwrap();
+    assert_eq!(goal.period_spent, 250_000);
+    assert_eq!(goal.released, 500_000);
+}
+
+#[tokio::test]
+async fn aborted_check_is_recorded_and_retried() {
+    let mut env = TestEnv::start().await;
+    let owner = env.payer();
+    let goal_address = pda::goal(&owner, 1);
+    env.send(
+        &[
+            mxe::initialize_config(&owner, owner),
+            goal::create_goal(
+                &owner,
+                1,
+                "trip".into(),
+                1_000,
+                None,
+                None,
+                None,
+                vec![],
+                None,
+                vec![],
+            ),
+            mxe::pin_goal_circuits(&owner, &goal_address),
+        ],
+        &[],
+    )
+    .await
+    .unwrap();
+
+    let total = encrypt_u64(&ClientKeypair::generate(), &env.mxe.public_key(), 1_200);
+    let failed = env.queue_params(goal_address);
+    let events = env
+        .abort_computation(
+            mxe::check_goal_reached(&failed, total.ciphertext, 1_000, total.pub_key, total.nonce),
+            &[],
+        )
+        .await
+        .unwrap();
+    assert!(matches!(
+        &events[..],
+        [SavingsEvent::ComputationFailed(e)]
+            if e.computation_offset == failed.computation_offset && e.reason == FailureReason::Aborted
+    ));
+    let record: ComputationRecord = env
+        .account(&pda::computation_record(failed.computation_offset))
+        .await
+        .unwrap();
+    assert!(record.status == ComputationStatus::Failed);
+
+    // The retry reuses the stored ciphertext
+    let retry = env.queue_params(goal_address);
+    let events = env
+        .run_computation(
+            mxe::retry_computation(&retry, failed.computation_offset, Circuit::CheckGoalReached),
+            &[],
+        )
+        .await
+        .unwrap();
+    assert_eq!(goal_check(&events), Some(true));
+    let record: ComputationRecord = env
+        .account(&pda::computation_record(failed.computation_offset))
+        .await
+        .unwrap();
+    assert_eq!(record.retried_by, Some(retry.computation_offset));
+
+    // A failed computation is retried at most once
+    let again = env.queue_params(goal_address);
+    let err = env
+        .send(
+            &[mxe::retry_computation(
+                &again,
+                failed.computation_offset,
+                Circuit::CheckGoalReached,
+            )],
+            &[],
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(
+        custom_error(&err),
+        Some(u32::from(ErrorCode::ComputationNotRetryable))
+    );
+
+    // Any circuit can be retried, and its callback gets the accounts it was
+    // first queued with
+    let encrypted = encrypt_u64(&ClientKeypair::generate(), &env.mxe.public_key(), 1_200);
+    env.send(
+        &[goal::add_contribution(&owner, &goal_address, &encrypted)],
+        &[],
+    )
+    .await
+    .unwrap();
+    let failed = env.queue_params(goal_address);
+    env.abort_computation(mxe::add_and_check_goal(&failed, &[owner]), &[])
+        .await
+        .unwrap();
+    let retry = env.queue_params(goal_address);
+    let events = env
+        .run_computation(
+            mxe::retry_computation(&retry, failed.computation_offset, Circuit::AddAndCheckGoal),
+            &[],
+        )
+        .await
+        .unwrap();
+    assert_eq!(goal_check(&events), Some(true));
+    let goal: SavingsGoal = env.account(&goal_address).await.unwrap();
+    assert!(goal.status == GoalStatus::Reached);
+}
+
+#[tokio::test]
+async fn yield_is_shared_in_proportion_to_contributions() {
+    let mut env = TestEnv::start().await;
+    let owner = env.payer();
+    let bob = env.funded_keypair(LAMPORTS_PER_SOL).await;
+    let alice = env.funded_keypair(LAMPORTS_PER_SOL).await;
+    let goal_address = pda::goal(&owner, 1);
+    let deadline = env.now().await + 3_600;
+    let vote_account = env.create_vote_account().await;
+
+    env.send(
+        &[
+            mxe::initialize_config(&owner, owner),
+            goal::create_goal(
+                &owner,
+                1,
+                "trip".into(),
+                1_000_000,
+                Some(deadline),
+                None,
+                None,
+                vec![],
+                None,
+                vec![],
+            ),
+        ],
+        &[],
+    )
+    .await
+    .unwrap();
+    env.send(
+        &[
+            goal::invite_member(&owner, &goal_address, &bob.pubkey()),
+            goal::invite_member(&owner, &goal_address, &alice.pubkey()),
+            mxe::pin_goal_circuits(&owner, &goal_address),
+            goal::set_yield_adapter(
+                &owner,
+                &goal_address,
+                Some(YieldAdapter::NativeStake { vote_account }),
+            ),
+        ],
+        &[],
+    )
+    .await
+    .unwrap();
+
+    // Bob never contributes
+    let mxe_key = env.mxe.public_key();
+    let owner_amount = encrypt_u64(&ClientKeypair::generate(), &mxe_key, 600_000);
+    let alice_amount = encrypt_u64(&ClientKeypair::generate(), &mxe_key, 200_000);
+    env.send(
+        &[goal::add_contribution(&owner, &goal_address, &owner_amount)],
+        &[],
+    )
+    .await
+    .unwrap();
+    env.send(
+        &[goal::add_contribution(
+            &alice.pubkey(),
+            &goal_address,
+            &alice_amount,
+        )],
+        &[&alice],
+    )
+    .await
+    .unwrap();
+    let members = [owner, bob.pubkey(), alice.pubkey()];
+
+    // Stake the vault, earn 8_000 and take it all back. Alice hasn't
+    // deposited her contribution yet.
+    env.fund_vault(&goal_address, LAMPORTS_PER_SOL);
+    env.send(&[goal::deposit(&owner, &goal_address, 600_000)], &[])
+        .await
+        .unwrap();
+    env.send(
+        &[goal::deposit_to_yield(&owner, &goal_address, &vote_account)],
+        &[],
+    )
+    .await
+    .unwrap();
+    env.add_lamports(&pda::stake(&goal_address), 8_000).await;
+    env.set_time(deadline).await;
+    env.send(
+        &[
+            goal::finalize_and_reveal(&owner, &goal_address),
+            goal::begin_yield_withdrawal(&owner, &goal_address),
+        ],
+        &[],
+    )
+    .await
+    .unwrap();
+
+    // Nothing to share while the deposit is still staked
+    let params = env.queue_params(goal_address);
+    let err = env
+        .send(&[mxe::distribute_yield(&params, &members)], &[])
+        .await
+        .unwrap_err();
+    assert_eq!(
+        custom_error(&err),
+        Some(u32::from(ErrorCode::NoYieldToDistribute))
+    );
+
+    env.send(&[goal::withdraw_from_yield(&goal_address, &owner)], &[])
+        .await
+        .unwrap();
+
+    // Only once a vault check since finalization found no shortfall
+    let params = env.queue_params(goal_address);
+    let err = env
+        .send(&[mxe::distribute_yield(&params, &members)], &[])
+        .await
+        .unwrap_err();
+    assert_eq!(
+        custom_error(&err),
+        Some(u32::from(ErrorCode::VaultNotChecked))
+    );
+    let params = env.queue_params(goal_address);
+    env.run_computation(mxe::check_vault_balance(&params, &members), &[])
+        .await
+        .unwrap();
+    let params = env.queue_params(goal_address);
+    let err = env
+        .send(&[mxe::distribute_yield(&params, &members)], &[])
+        .await
+        .unwrap_err();
+    assert_eq!(
+        custom_error(&err),
+        Some(u32::from(ErrorCode::VaultInconsistent))
+    );
+    env.send(
+        &[goal::deposit(&alice.pubkey(), &goal_address, 200_000)],
+        &[&alice],
+    )
+    .await
+    .unwrap();
+    let params = env.queue_params(goal_address);
+    let events = env
+        .run_computation(mxe::check_vault_balance(&params, &members), &[])
+        .await
+        .unwrap();
+    assert!(events.iter().any(|event| matches!(
+        event,
+        SavingsEvent::VaultChecked(e) if !e.inconsistent
+    )));
+
+    let params = env.queue_params(goal_address);
+    let events = env
+        .run_computation(mxe::distribute_yield(&params, &members), &[])
+        .await
+        .unwrap();
+    assert!(events.iter().any(|event| matches!(
+        event,
+        SavingsEvent::YieldDistribution(e) if e.shares == [6_000, 0, 2_000]
+    )));
+    let goal: SavingsGoal = env.account(&goal_address).await.unwrap();
+    assert_eq!(goal.yield_shares, [6_000, 0, 2_000]);
+
+    let params = env.queue_params(goal_address);
+    let err = env
+        .send(&[mxe::distribute_yield(&params, &members)], &[])
+        .await
+        .unwrap_err();
+    assert_eq!(
+        custom_error(&err),
+        Some(u32::from(ErrorCode::YieldAlreadyDistributed))
+    );
+
+    // Each member claims their own share, once
+    let before = env.raw_account(&alice.pubkey()).await.unwrap().lamports;
+    env.send(
+        &[goal::claim_yield(&alice.pubkey(), &goal_address)],
+        &[&alice],
+    )
+    .await
+    .unwrap();
+    let after = env.raw_account(&alice.pubkey()).await.unwrap().lamports;
+    assert_eq!(after - before, 2_000);
+    for member in [&alice, &bob] {
+        let err = env
+            .send(
+                &[goal::claim_yield(&member.pubkey(), &goal_address)],
+                &[member],
+            )
+            .await
+            .unwrap_err();
+        assert_eq!(custom_error(&err), Some(u32::from(GoalError::NoYieldShare)));
+    }
+    let goal: SavingsGoal = env.account(&goal_address).await.unwrap();
+    assert_eq!(goal.yield_shares, [6_000, 0, 0]);
+    assert_eq!(goal.unclaimed_yield(), 6_000);
+}
@@ -0,0 +1,949 @@
+use anchor_lang::{InstructionData, ToAccountMetas};
+use savings_client::accounts::{
+    GoalStatus, PeriodContribution, PeriodStatus, SavingsGoal, Schedule, TransferPolicy,
+    TransferRequest, YieldAdapter,
+};
+use savings_client::cipher::{encrypt_u64, ClientKeypair};
+use savings_client::events::SavingsEvent;
+use savings_client::{goal, pda, Instruction, SAVINGS_GOAL_ID};
+use savings_goal::ErrorCode;
+use savings_harness::{custom_error, TestEnv};
+use solana_sdk::native_token::LAMPORTS_PER_SOL;
+use solana_sdk::signature::{Keypair, Signer};
+
+#[tokio::test]
+async fn create_goal_and_invite_members() {
+    let mut env = TestEnv::start().await;
+    let owner = env.payer();
+    let member = Keypair::new().pubkey();
+    let goal_address = pda::goal(&owner, 1);
+
+    env.send(
+        &[goal::create_goal(
+            &owner,
+            1,
+            "trip".into(),
+            1_000,
+            None,
+            None,
+            None,
+            vec![],
+            None,
+            vec![],
+        )],
+        &[],
+    )
+    .await
+    .unwrap();
+    env.send(&[goal::invite_member(&owner, &goal_address, &member)], &[])
+        .await
+        .unwrap();
+
+    let goal: SavingsGoal = env.account(&goal_address).await.unwrap();
+    assert_eq!(goal.owner, owner);
+    assert_eq!(goal.target_amount, 1_000);
+    assert_eq!(goal.members, vec![owner, member]);
+    assert!(goal.status == GoalStatus::Active);
+
+    let err = env
+        .send(&[goal::invite_member(&owner, &goal_address, &member)], &[])
+        .await
+        .unwrap_err();
+    assert_eq!(
+        custom_error(&err),
+        Some(ErrorCode::MemberAlreadyExists.into())
+    );
+}
+
+#[tokio::test]
+async fn only_the_owner_can_invite() {
+    let mut env = TestEnv::start().await;
+    let owner = env.payer();
+    let outsider = env.funded_keypair(LAMPORTS_PER_SOL).await;
+    let goal_address = pda::goal(&owner, 1);
+    env.send(
+        &[goal::create_goal(
+            &owner,
+            1,
+            "trip".into(),
+            1_000,
+            None,
+            None,
+            None,
+            vec![],
+            None,
+            vec![],
+        )],
+        &[],
+    )
+    .await
+    .unwrap();
+
+    let err = env
+        .send(
+            &[goal::invite_member(
+                &outsider.pubkey(),
+                &goal_address,
+                &outsider.pubkey(),
+            )],
+            &[&outsider],
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(custom_error(&err), Some(ErrorCode::Unauthorized.into()));
+}
+
+#[tokio::test]
+async fn members_deposits_are_recorded_on_the_goal() {
+    let mut env = TestEnv::start().await;
+    let owner = env.payer();
+    let outsider = env.funded_keypair(LAMPORTS_PER_SOL).await;
+    let goal_address = pda::goal(&owner, 1);
+    env.send(
+        &[goal::create_goal(
+            &owner,
+            1,
+            "trip".into(),
+            1_000,
+            None,
+            None,
+            None,
+            vec![],
+            None,
+            vec![],
+        )],
+        &[],
+    )
+    .await
+    .unwrap();
+    env.fund_vault(&goal_address, LAMPORTS_PER_SOL);
+
+    let events = env
+        .send(&[goal::deposit(&owner, &goal_address, 700)], &[])
+        .await
+        .unwrap();
+    assert!(events.iter().any(|event| matches!(
+        event,
+        SavingsEvent::Deposited(e) if e.member == owner && e.amount == 700
+    )));
+    let vault = env.raw_account(&pda::vault(&goal_address)).await.unwrap();
+    assert_eq!(vault.lamports, LAMPORTS_PER_SOL + 700);
+
+    // Only members deposit; lamports sent any other way are not recorded
+    let err = env
+        .send(
+            &[goal::deposit(&outsider.pubkey(), &goal_address, 300)],
+            &[&outsider],
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(custom_error(&err), Some(ErrorCode::NotAMember.into()));
+    let goal: SavingsGoal = env.account(&goal_address).await.unwrap();
+    assert_eq!(goal.deposited, 700);
+}
+
+#[tokio::test]
+async fn only_the_mxe_can_mark_a_goal_reached() {
+    let mut env = TestEnv::start().await;
+    let owner = env.payer();
+    let goal_address = pda::goal(&owner, 1);
+    env.send(
+        &[goal::create_goal(
+            &owner,
+            1,
+            "trip".into(),
+            1_000,
+            None,
+            None,
+            None,
+            vec![],
+            None,
+            vec![],
+        )],
+        &[],
+    )
+    .await
+    .unwrap();
+
+    // Any signer other than savings_mxe's goal authority PDA is rejected,
+    // the goal owner included
+    let ix = Instruction {
+        program_id: SAVINGS_GOAL_ID,
+        accounts: savings_goal::accounts::MarkGoalReached {
+            goal_authority: owner,
+            goal: goal_address,
+        }
+        .to_account_metas(None),
+        data: savings_goal::instruction::MarkGoalReached {}.data(),
+    };
+    let err = env.send(&[ix], &[]).await.unwrap_err();
+    assert_eq!(
+        custom_error(&err),
+        Some(anchor_lang::error::ErrorCode::ConstraintSeeds.into())
+    );
+    let goal: SavingsGoal = env.account(&goal_address).await.unwrap();
+    assert!(goal.status == GoalStatus::Active);
+}
+
+#[tokio::test]
+async fn anyone_can_finalize_after_deadline() {
+    let mut env = TestEnv::start().await;
+    let owner = env.payer();
+    let outsider = env.funded_keypair(LAMPORTS_PER_SOL).await;
+    let goal_address = pda::goal(&owner, 1);
+    let deadline = env.now().await + 3_600;
+    env.send(
+        &[goal::create_goal(
+            &owner,
+            1,
+            "trip".into(),
+            1_000,
+            Some(deadline),
+            None,
+            None,
+            vec![],
+            None,
+            vec![],
+        )],
+        &[],
+    )
+    .await
+    .unwrap();
+
+    let err = env
+        .send(
+            &[goal::finalize_and_reveal(&outsider.pubkey(), &goal_address)],
+            &[&outsider],
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(
+        custom_error(&err),
+        Some(ErrorCode::CannotFinalizeYet.into())
+    );
+
+    env.set_time(deadline).await;
+    env.send(
+        &[goal::finalize_and_reveal(&outsider.pubkey(), &goal_address)],
+        &[&outsider],
+    )
+    .await
+    .unwrap();
+    let goal: SavingsGoal = env.account(&goal_address).await.unwrap();
+    assert!(goal.status == GoalStatus::Finalized);
+}
+
+#[tokio::test]
+async fn owner_can_finalize_before_the_deadline() {
+    let mut env = TestEnv::start().await;
+    let owner = env.payer();
+    let member = env.funded_keypair(LAMPORTS_PER_SOL).await;
+    let goal_address = pda::goal(&owner, 1);
+    let deadline = env.now().await + 3_600;
+    env.send(
+        &[
+            goal::create_goal(
+                &owner,
+                1,
+                "trip".into(),
+                1_000,
+                Some(deadline),
+                None,
+                None,
+                vec![],
+                None,
+                vec![],
+            ),
+            goal::invite_member(&owner, &goal_address, &member.pubkey()),
+        ],
+        &[],
+    )
+    .await
+    .unwrap();
+
+    // Members have to wait for the deadline or the target
+    let err = env
+        .send(
+            &[goal::finalize_and_reveal(&member.pubkey(), &goal_address)],
+            &[&member],
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(
+        custom_error(&err),
+        Some(ErrorCode::CannotFinalizeYet.into())
+    );
+
+    env.send(&[goal::finalize_and_reveal(&owner, &goal_address)], &[])
+        .await
+        .unwrap();
+    let goal: SavingsGoal = env.account(&goal_address).await.unwrap();
+    assert!(goal.status == GoalStatus::Finalized);
+    assert!(goal.finalized_at.unwrap() < deadline);
+
+    let err = env
+        .send(&[goal::finalize_and_reveal(&owner, &goal_address)], &[])
+        .await
+        .unwrap_err();
+    assert_eq!(custom_error(&err), Some(ErrorCode::AlreadyFinalized.into()));
+}
+
+#[tokio::test]
+async fn scheduled_contributions_and_missed_periods() {
+    let mut env = TestEnv::start().await;
+    let owner = env.payer();
+    let member = env.funded_keypair(LAMPORTS_PER_SOL).await;
+    let goal_address = pda::goal(&owner, 1);
+    let start_time = env.now().await;
+    let schedule = Schedule {
+        interval: 3_600,
+        expected_amount: 100,
+        start_time,
+    };
+    env.send(
+        &[goal::create_goal(
+            &owner,
+            1,
+            "rent".into(),
+            1_000,
+            None,
+            Some(schedule),
+            None,
+            vec![],
+            None,
+            vec![],
+        )],
+        &[],
+    )
+    .await
+    .unwrap();
+    env.send(
+        &[goal::invite_member(&owner, &goal_address, &member.pubkey())],
+        &[],
+    )
+    .await
+    .unwrap();
+
+    let amount = encrypt_u64(&ClientKeypair::generate(), &env.mxe.public_key(), 100);
+    let err = env
+        .send(
+            &[goal::contribute_for_period(
+                &owner,
+                &goal_address,
+                1,
+                &amount,
+            )],
+            &[],
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(custom_error(&err), Some(ErrorCode::NotCurrentPeriod.into()));
+    env.send(
+        &[goal::contribute_for_period(
+            &owner,
+            &goal_address,
+            0,
+            &amount,
+        )],
+        &[],
+    )
+    .await
+    .unwrap();
+    let record: PeriodContribution = env
+        .account(&pda::period_contribution(&goal_address, &owner, 0))
+        .await
+        .unwrap();
+    assert!(record.status == PeriodStatus::Contributed);
+
+    // The member skipped period 0; anyone can close it once it is over
+    let err = env
+        .send(
+            &[goal::close_period(
+                &owner,
+                &goal_address,
+                &member.pubkey(),
+                0,
+            )],
+            &[],
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(custom_error(&err), Some(ErrorCode::PeriodNotOver.into()));
+    env.set_time(schedule.period_end(0)).await;
+    env.send(
+        &[goal::close_period(
+            &owner,
+            &goal_address,
+            &member.pubkey(),
+            0,
+        )],
+        &[],
+    )
+    .await
+    .unwrap();
+    let record: PeriodContribution = env
+        .account(&pda::period_contribution(
+            &goal_address,
+            &member.pubkey(),
+            0,
+        ))
+        .await
+        .unwrap();
+    assert!(record.status == PeriodStatus::Missed);
+
+    // Too late to contribute for period 0 now
+    let err = env
+        .send(
+            &[goal::contribute_for_period(
+                &member.pubkey(),
+                &goal_address,
+                0,
+                &amount,
+            )],
+            &[&member],
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(custom_error(&err), Some(ErrorCode::NotCurrentPeriod.into()));
+}
+
+#[tokio::test]
+async fn finalized_goal_pays_out_once_its_total_is_revealed() {
+    let mut env = TestEnv::start().await;
+    let owner = env.payer();
+    let recipient = Keypair::new().pubkey();
+    let goal_address = pda::goal(&owner, 1);
+    let deadline = env.now().await + 3_600;
+    env.send(
+        &[goal::create_goal(
+            &owner,
+            1,
+            "trip".into(),
+            1_000,
+            Some(deadline),
+            None,
+            None,
+            vec![],
+            None,
+            vec![],
+        )],
+        &[],
+    )
+    .await
+    .unwrap();
+
+    env.set_time(deadline).await;
+    env.send(&[goal::finalize_and_reveal(&owner, &goal_address)], &[])
+        .await
+        .unwrap();
+    let goal: SavingsGoal = env.account(&goal_address).await.unwrap();
+    assert!(goal.status == GoalStatus::Finalized);
+
+    env.fund_vault(&goal_address, LAMPORTS_PER_SOL);
+    env.send(
+        &[goal::request_transfer(
+            &owner,
+            &goal_address,
+            recipient,
+            LAMPORTS_PER_SOL / 2,
+        )],
+        &[],
+    )
+    .await
+    .unwrap();
+
+    // Finalizing directly reveals nothing, so there is no cap to pay out
+    // against yet
+    let err = env
+        .send(
+            &[goal::approve_transfer(&owner, &goal_address, &recipient)],
+            &[],
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(custom_error(&err), Some(ErrorCode::TotalNotRevealed.into()));
+
+    env.set_revealed_total(&goal_address, LAMPORTS_PER_SOL / 4)
+        .await;
+    let err = env
+        .send(
+            &[goal::approve_transfer(&owner, &goal_address, &recipient)],
+            &[],
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(
+        custom_error(&err),
+        Some(ErrorCode::TransferExceedsRevealedTotal.into())
+    );
+
+    env.set_revealed_total(&goal_address, LAMPORTS_PER_SOL)
+        .await;
+    env.send(
+        &[goal::approve_transfer(&owner, &goal_address, &recipient)],
+        &[],
+    )
+    .await
+    .unwrap();
+
+    let request: TransferRequest = env.account(&pda::transfer(&goal_address)).await.unwrap();
+    assert!(request.approved);
+    let paid = env.raw_account(&recipient).await.unwrap().lamports;
+    assert_eq!(paid, LAMPORTS_PER_SOL / 2);
+}
+
+#[tokio::test]
+async fn timelocked_transfers_can_be_vetoed_by_members() {
+    let mut env = TestEnv::start().await;
+    let owner = env.payer();
+    let member = env.funded_keypair(LAMPORTS_PER_SOL).await;
+    let outsider = env.funded_keypair(LAMPORTS_PER_SOL).await;
+    let recipient = Keypair::new().pubkey();
+    let goal_address = pda::goal(&owner, 1);
+    let deadline = env.now().await + 3_600;
+    let policy = TransferPolicy {
+        max_per_transfer: Some(LAMPORTS_PER_SOL / 2),
+        max_per_period: None,
+        period: 0,
+        timelock: 86_400,
+    };
+    env.send(
+        &[
+            goal::create_goal(
+                &owner,
+                1,
+                "trip".into(),
+                1_000,
+                Some(deadline),
+                None,
+                None,
+                vec![],
+                Some(policy),
+                vec![],
+            ),
+            goal::invite_member(&owner, &goal_address, &member.pubkey()),
+        ],
+        &[],
+    )
+    .await
+    .unwrap();
+    env.set_time(deadline).await;
+    env.send(&[goal::finalize_and_reveal(&owner, &goal_address)], &[])
+        .await
+        .unwrap();
+    env.set_revealed_total(&goal_address, LAMPORTS_PER_SOL)
+        .await;
+    env.fund_vault(&goal_address, LAMPORTS_PER_SOL);
+
+    let err = env
+        .send(
+            &[goal::request_transfer(
+                &owner,
+                &goal_address,
+                recipient,
+                LAMPORTS_PER_SOL * 3 / 4,
+            )],
+            &[],
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(
+        custom_error(&err),
+        Some(ErrorCode::TransferLimitExceeded.into())
+    );
+
+    env.send(
+        &[goal::request_transfer(
+            &owner,
+            &goal_address,
+            recipient,
+            LAMPORTS_PER_SOL / 2,
+        )],
+        &[],
+    )
+    .await
+    .unwrap();
+    let err = env
+        .send(
+            &[goal::approve_transfer(&owner, &goal_address, &recipient)],
+            &[],
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(
+        custom_error(&err),
+        Some(ErrorCode::TransferTimelocked.into())
+    );
+
+    // Only members can object, and doing so drops the request
+    let err = env
+        .send(
+            &[goal::veto_transfer(
+                &outsider.pubkey(),
+                &goal_address,
+                &owner,
+                None,
+            )],
+            &[&outsider],
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(custom_error(&err), Some(ErrorCode::NotAMember.into()));
+    env.send(
+        &[goal::veto_transfer(
+            &member.pubkey(),
+            &goal_address,
+            &owner,
+            None,
+        )],
+        &[&member],
+    )
+    .await
+    .unwrap();
+    assert!(env
+        .raw_account(&pda::transfer(&goal_address))
+        .await
+        .is_none());
+
+    // Asked again, nobody objects before the timelock runs out
+    env.send(
+        &[goal::request_transfer(
+            &owner,
+            &goal_address,
+            recipient,
+            LAMPORTS_PER_SOL / 4,
+        )],
+        &[],
+    )
+    .await
+    .unwrap();
+    env.set_time(deadline + 86_400).await;
+    let err = env
+        .send(
+            &[goal::veto_transfer(
+                &member.pubkey(),
+                &goal_address,
+                &owner,
+                None,
+            )],
+            &[&member],
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(custom_error(&err), Some(ErrorCode::VetoPeriodOver.into()));
+    env.send(
+        &[goal::approve_transfer(&owner, &goal_address, &recipient)],
+        &[],
+    )
+    .await
+    .unwrap();
+    let paid = env.raw_account(&recipient).await.unwrap().lamports;
+    assert_eq!(paid, LAMPORTS_PER_SOL / 4);
+}
+
+#[tokio::test]
+async fn transfers_only_go_to_allowed_recipients() {
+    let mut env = TestEnv::start().await;
+    let owner = env.payer();
+    let alice = env.funded_keypair(LAMPORTS_PER_SOL).await;
+    let bob = env.funded_keypair(LAMPORTS_PER_SOL).await;
+    let allowed = Keypair::new().pubkey();
+    let proposed = Keypair::new().pubkey();
+    let goal_address = pda::goal(&owner, 1);
+    let deadline = env.now().await + 3_600;
+    env.send(
+        &[
+            goal::create_goal(
+                &owner,
+                1,
+                "trip".into(),
+                1_000,
+                Some(deadline),
+                None,
+                None,
+                vec![],
+                None,
+                vec![allowed],
+            ),
+            goal::invite_member(&owner, &goal_address, &alice.pubkey()),
+            goal::invite_member(&owner, &goal_address, &bob.pubkey()),
+        ],
+        &[],
+    )
+    .await
+    .unwrap();
+    env.set_time(deadline).await;
+    env.send(&[goal::finalize_and_reveal(&owner, &goal_address)], &[])
+        .await
+        .unwrap();
+    env.set_revealed_total(&goal_address, LAMPORTS_PER_SOL)
+        .await;
+    env.fund_vault(&goal_address, LAMPORTS_PER_SOL);
+
+    let err = env
+        .send(
+            &[goal::request_transfer(
+                &owner,
+                &goal_address,
+                proposed,
+                LAMPORTS_PER_SOL / 2,
+            )],
+            &[],
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(
+        custom_error(&err),
+        Some(ErrorCode::RecipientNotAllowed.into())
+    );
+
+    // Approval pays the request's recipient and nobody else
+    env.send(
+        &[goal::request_transfer(
+            &owner,
+            &goal_address,
+            allowed,
+            LAMPORTS_PER_SOL / 2,
+        )],
+        &[],
+    )
+    .await
+    .unwrap();
+    let err = env
+        .send(
+            &[goal::approve_transfer(&owner, &goal_address, &proposed)],
+            &[],
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(
+        custom_error(&err),
+        Some(ErrorCode::RecipientMismatch.into())
+    );
+
+    // Two of the three members allow a new recipient
+    env.send(
+        &[goal::propose_recipient(
+            &alice.pubkey(),
+            &goal_address,
+            proposed,
+        )],
+        &[&alice],
+    )
+    .await
+    .unwrap();
+    let err = env
+        .send(
+            &[goal::approve_recipient(
+                &alice.pubkey(),
+                &goal_address,
+                proposed,
+            )],
+            &[&alice],
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(
+        custom_error(&err),
+        Some(ErrorCode::RecipientAlreadyApproved.into())
+    );
+    let goal: SavingsGoal = env.account(&goal_address).await.unwrap();
+    assert_eq!(goal.allowed_recipients, vec![allowed]);
+
+    env.send(
+        &[goal::approve_recipient(
+            &bob.pubkey(),
+            &goal_address,
+            proposed,
+        )],
+        &[&bob],
+    )
+    .await
+    .unwrap();
+    let goal: SavingsGoal = env.account(&goal_address).await.unwrap();
+    assert_eq!(goal.allowed_recipients, vec![allowed, proposed]);
+    let err = env
+        .send(
+            &[goal::approve_recipient(&owner, &goal_address, proposed)],
+            &[],
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(
+        custom_error(&err),
+        Some(ErrorCode::RecipientAlreadyAllowed.into())
+    );
+}
+
+#[tokio::test]
+async fn idle_funds_are_staked_until_withdrawn() {
+    let mut env = TestEnv::start().await;
+    let owner = env.payer();
+    let member = env.funded_keypair(LAMPORTS_PER_SOL).await;
+    let recipient = Keypair::new().pubkey();
+    let goal_address = pda::goal(&owner, 1);
+    let deadline = env.now().await + 3_600;
+    env.send(
+        &[
+            goal::create_goal(
+                &owner,
+                1,
+                "trip".into(),
+                1_000,
+                Some(deadline),
+                None,
+                None,
+                vec![],
+                None,
+                vec![],
+            ),
+            goal::invite_member(&owner, &goal_address, &member.pubkey()),
+        ],
+        &[],
+    )
+    .await
+    .unwrap();
+    let vote_account = env.create_vote_account().await;
+    let vault_rent = env
+        .context
+        .banks_client
+        .get_rent()
+        .await
+        .unwrap()
+        .minimum_balance(0);
+    let deposit = 2 * LAMPORTS_PER_SOL;
+    env.fund_vault(&goal_address, vault_rent + deposit);
+
+    let err = env
+        .send(
+            &[goal::deposit_to_yield(&owner, &goal_address, &vote_account)],
+            &[],
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(custom_error(&err), Some(ErrorCode::NoYieldAdapter.into()));
+
+    let adapter = YieldAdapter::NativeStake { vote_account };
+    env.send(
+        &[goal::set_yield_adapter(
+            &owner,
+            &goal_address,
+            Some(adapter),
+        )],
+        &[],
+    )
+    .await
+    .unwrap();
+    let err = env
+        .send(
+            &[goal::deposit_to_yield(
+                &owner,
+                &goal_address,
+                &Keypair::new().pubkey(),
+            )],
+            &[],
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(
+        custom_error(&err),
+        Some(ErrorCode::WrongYieldAccount.into())
+    );
+
+    // Everything above the vault's rent is staked
+    let events = env
+        .send(
+            &[goal::deposit_to_yield(&owner, &goal_address, &vote_account)],
+            &[],
+        )
+        .await
+        .unwrap();
+    assert!(events.iter().any(|event| matches!(
+        event,
+        SavingsEvent::YieldDeposited(e) if e.amount == deposit
+    )));
+    let vault = env.raw_account(&pda::vault(&goal_address)).await.unwrap();
+    assert_eq!(vault.lamports, vault_rent);
+    let goal: SavingsGoal = env.account(&goal_address).await.unwrap();
+    assert_eq!(goal.yield_deposited, deposit);
+
+    let err = env
+        .send(&[goal::set_yield_adapter(&owner, &goal_address, None)], &[])
+        .await
+        .unwrap_err();
+    assert_eq!(custom_error(&err), Some(ErrorCode::FundsDeposited.into()));
+
+    // Before finalization only the owner can take the deposit out
+    let err = env
+        .send(
+            &[goal::begin_yield_withdrawal(
+                &member.pubkey(),
+                &goal_address,
+            )],
+            &[&member],
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(custom_error(&err), Some(ErrorCode::Unauthorized.into()));
+
+    // Stands in for the staking rewards
+    env.add_lamports(&pda::stake(&goal_address), 5_000).await;
+
+    // Payouts wait until the deposit is back, and leave the yield alone
+    let payout = vault_rent + deposit + 1;
+    env.set_time(deadline).await;
+    env.send(
+        &[
+            goal::finalize_and_reveal(&owner, &goal_address),
+            goal::request_transfer(&owner, &goal_address, recipient, payout),
+        ],
+        &[],
+    )
+    .await
+    .unwrap();
+    env.set_revealed_total(&goal_address, payout).await;
+    let err = env
+        .send(
+            &[goal::approve_transfer(&owner, &goal_address, &recipient)],
+            &[],
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(custom_error(&err), Some(ErrorCode::FundsDeposited.into()));
+
+    env.send(
+        &[goal::begin_yield_withdrawal(
+            &member.pubkey(),
+            &goal_address,
+        )],
+        &[&member],
+    )
+    .await
+    .unwrap();
+    let events = env
+        .send(&[goal::withdraw_from_yield(&goal_address, &owner)], &[])
+        .await
+        .unwrap();
+    assert!(events.iter().any(|event| matches!(
+        event,
+        SavingsEvent::YieldWithdrawn(e) if e.deposited == deposit && e.earned == 5_000
+    )));
+    assert!(env.raw_account(&pda::stake(&goal_address)).await.is_none());
+    let vault = env.raw_account(&pda::vault(&goal_address)).await.unwrap();
+    assert_eq!(vault.lamports, vault_rent + deposit + 5_000);
+    let goal: SavingsGoal = env.account(&goal_address).await.unwrap();
+    assert_eq!(goal.yield_deposited, 0);
+    assert_eq!(goal.yield_earned, 5_000);
+
+    let err = env
+        .send(
+            &[goal::approve_transfer(&owner, &goal_address, &recipient)],
+            &[],
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(
+        custom_error(&err),
+        Some(ErrorCode::InsufficientVaultBalance.into())
+    );
+}
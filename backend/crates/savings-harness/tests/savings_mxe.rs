@@ -0,0 +1,204 @@
+use savings_client::accounts::{GoalCircuits, MxeConfig, RegisteredCircuitVersion};
+use savings_client::mxe::{Circuit, CircuitVersion};
+use savings_client::{goal, mxe, pda};
+use savings_harness::{custom_error, TestEnv};
+use savings_mxe::ErrorCode;
+use solana_sdk::native_token::LAMPORTS_PER_SOL;
+use solana_sdk::signature::Signer;
+
+#[tokio::test]
+async fn only_the_upgrade_authority_initializes_the_config() {
+    let mut env = TestEnv::start().await;
+    let admin = env.payer();
+    let outsider = env.funded_keypair(LAMPORTS_PER_SOL).await;
+
+    let err = env
+        .send(
+            &[mxe::initialize_config(
+                &outsider.pubkey(),
+                outsider.pubkey(),
+            )],
+            &[&outsider],
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(custom_error(&err), Some(ErrorCode::Unauthorized.into()));
+
+    // An immutable program has nobody to initialize it
+    env.set_mxe_upgrade_authority(None).await;
+    let err = env
+        .send(&[mxe::initialize_config(&admin, admin)], &[])
+        .await
+        .unwrap_err();
+    assert_eq!(custom_error(&err), Some(ErrorCode::Unauthorized.into()));
+
+    env.set_mxe_upgrade_authority(Some(admin)).await;
+    env.send(&[mxe::initialize_config(&admin, admin)], &[])
+        .await
+        .unwrap();
+    let config: MxeConfig = env.account(&pda::config()).await.unwrap();
+    assert_eq!(config.admin, admin);
+}
+
+#[tokio::test]
+async fn admin_rotation_takes_two_steps() {
+    let mut env = TestEnv::start().await;
+    let admin = env.payer();
+    let successor = env.funded_keypair(LAMPORTS_PER_SOL).await;
+    let outsider = env.funded_keypair(LAMPORTS_PER_SOL).await;
+    env.send(&[mxe::initialize_config(&admin, admin)], &[])
+        .await
+        .unwrap();
+
+    let err = env
+        .send(
+            &[mxe::propose_admin(&outsider.pubkey(), outsider.pubkey())],
+            &[&outsider],
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(custom_error(&err), Some(ErrorCode::Unauthorized.into()));
+
+    env.send(&[mxe::propose_admin(&admin, successor.pubkey())], &[])
+        .await
+        .unwrap();
+    let err = env
+        .send(&[mxe::accept_admin(&outsider.pubkey())], &[&outsider])
+        .await
+        .unwrap_err();
+    assert_eq!(custom_error(&err), Some(ErrorCode::NotPendingAdmin.into()));
+
+    env.send(&[mxe::accept_admin(&successor.pubkey())], &[&successor])
+        .await
+        .unwrap();
+    let config: MxeConfig = env.account(&pda::config()).await.unwrap();
+    assert_eq!(config.admin, successor.pubkey());
+    assert_eq!(config.pending_admin, None);
+}
+
+#[tokio::test]
+async fn goals_pin_the_active_circuit_versions() {
+    let mut env = TestEnv::start().await;
+    let owner = env.payer();
+    let goal_address = pda::goal(&owner, 1);
+    env.send(
+        &[
+            mxe::initialize_config(&owner, owner),
+            goal::create_goal(
+                &owner,
+                1,
+                "trip".into(),
+                1_000,
+                None,
+                None,
+                None,
+                vec![],
+                None,
+                vec![],
+            ),
+            mxe::pin_goal_circuits(&owner, &goal_address),
+        ],
+        &[],
+    )
+    .await
+    .unwrap();
+
+    let pinned: GoalCircuits = env
+        .account(&pda::goal_circuits(&goal_address))
+        .await
+        .unwrap();
+    assert_eq!(pinned.goal, goal_address);
+    assert_eq!(pinned.versions, Circuit::compiled_versions());
+}
+
+#[tokio::test]
+async fn earlier_circuit_versions_are_registered_next_to_the_active_one() {
+    let mut env = TestEnv::start().await;
+    let admin = env.payer();
+    let compiled = mxe::compiled_version(Circuit::AddTwoContributions);
+    env.send(&[mxe::initialize_config(&admin, admin)], &[])
+        .await
+        .unwrap();
+
+    let err = env
+        .send(
+            &[mxe::register_circuit_version(
+                &admin,
+                Circuit::AddTwoContributions,
+                compiled + 1,
+            )],
+            &[],
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(
+        custom_error(&err),
+        Some(ErrorCode::CircuitVersionNotCompiled.into())
+    );
+
+    env.send(
+        &[
+            mxe::register_circuit_version(&admin, Circuit::AddTwoContributions, compiled),
+            mxe::set_active_version(&admin, Circuit::AddTwoContributions, compiled),
+        ],
+        &[],
+    )
+    .await
+    .unwrap();
+    let registered: RegisteredCircuitVersion = env
+        .account(&pda::circuit_version(
+            Circuit::AddTwoContributions,
+            compiled,
+        ))
+        .await
+        .unwrap();
+    assert_eq!(
+        registered.comp_def_offset,
+        pda::comp_def_offset_of(Circuit::AddTwoContributions, compiled)
+    );
+
+    // The previous version is recorded alongside, but cannot be activated
+    let previous = compiled - 1;
+    env.send(
+        &[mxe::register_circuit_version(
+            &admin,
+            Circuit::AddTwoContributions,
+            previous,
+        )],
+        &[],
+    )
+    .await
+    .unwrap();
+    let registered: RegisteredCircuitVersion = env
+        .account(&pda::circuit_version(
+            Circuit::AddTwoContributions,
+            previous,
+        ))
+        .await
+        .unwrap();
+    assert_eq!(registered.version, previous);
+    assert_eq!(
+        registered.comp_def_offset,
+        pda::comp_def_offset_of(Circuit::AddTwoContributions, previous)
+    );
+    let err = env
+        .send(
+            &[mxe::set_active_version(
+                &admin,
+                Circuit::AddTwoContributions,
+                previous,
+            )],
+            &[],
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(
+        custom_error(&err),
+        Some(ErrorCode::CircuitVersionNotCompiled.into())
+    );
+    let config: MxeConfig = env.account(&pda::config()).await.unwrap();
+    assert!(config.active_versions.contains(&CircuitVersion {
+        circuit: Circuit::AddTwoContributions,
+        version: compiled,
+    }));
+}
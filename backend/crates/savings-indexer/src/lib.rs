@@ -0,0 +1,14 @@
+//! Materializes `savings_goal` / `savings_mxe` events into SQLite.
+//!
+//! Transactions come from a [`source`]: either followed live over RPC or
+//! replayed from a JSON-lines file. The [`store`] decodes the events in each
+//! transaction's logs and applies them to the `goals`, `members`,
+//! `contributions`, `transfers`, `computations`, `periods`, `group_stats`,
+//! `milestones`, `recipients` and `yield_shares` tables.
+//! Applying a transaction twice is a no-op, so sources may overlap.
+
+pub mod source;
+pub mod store;
+
+pub use source::IndexedTransaction;
+pub use store::Store;
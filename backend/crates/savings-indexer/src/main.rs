@@ -0,0 +1,139 @@
+//! `savings-indexer`: keep a SQLite database of savings goal history.
+
+use std::collections::HashSet;
+use std::path::PathBuf;
+use std::thread::sleep;
+use std::time::Duration;
+
+use anyhow::Result;
+use clap::{Parser, Subcommand};
+use savings_client::{Pubkey, SAVINGS_GOAL_ID, SAVINGS_MXE_ID};
+use savings_indexer::source::{self, RpcSource};
+use savings_indexer::Store;
+use serde_json::json;
+
+#[derive(Parser)]
+#[command(
+    name = "savings-indexer",
+    about = "Index savings goal events into SQLite"
+)]
+struct Cli {
+    /// SQLite database to maintain
+    #[arg(
+        long,
+        env = "SAVINGS_INDEXER_DB",
+        default_value = "savings.db",
+        global = true
+    )]
+    db: PathBuf,
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Follow both programs over RPC
+    Follow {
+        #[arg(long, env = "SAVINGS_RPC_URL", default_value = "http://127.0.0.1:8899")]
+        url: String,
+        #[arg(long, default_value_t = 5)]
+        poll_secs: u64,
+        /// Also append every fetched transaction to this replay file
+        #[arg(long)]
+        record: Option<PathBuf>,
+    },
+    /// Index the transactions in a JSON-lines replay file
+    Replay {
+        #[arg(long)]
+        file: PathBuf,
+    },
+    /// Print what is indexed for a goal as JSON
+    Show {
+        #[arg(long)]
+        goal: Pubkey,
+    },
+}
+
+fn main() -> Result<()> {
+    let cli = Cli::parse();
+    let mut store = Store::open(&cli.db)?;
+    match cli.command {
+        Command::Follow {
+            url,
+            poll_secs,
+            record,
+        } => follow(&mut store, &RpcSource::new(&url), poll_secs, record),
+        Command::Replay { file } => {
+            let transactions = source::read_file(&file)?;
+            let mut applied = 0;
+            for tx in &transactions {
+                applied += store.apply(tx)?;
+            }
+            println!(
+                "{}",
+                json!({ "transactions": transactions.len(), "events_applied": applied })
+            );
+            Ok(())
+        }
+        Command::Show { goal } => {
+            println!(
+                "{}",
+                json!({
+                    "goal": store.goal(&goal)?,
+                    "members": store.members(&goal)?,
+                    "contributions": store.contributions(&goal)?,
+                    "transfer": store.transfer(&goal)?,
+                    "computations": store.computations(&goal)?,
+                    "periods": store.periods(&goal)?,
+                    "group_stats": store.group_stats(&goal)?,
+                    "milestones": store.milestones(&goal)?,
+                    "recipients": store.recipients(&goal)?,
+                    "yield_shares": store.yield_shares(&goal)?,
+                })
+            );
+            Ok(())
+        }
+    }
+}
+
+fn follow(
+    store: &mut Store,
+    rpc: &RpcSource,
+    poll_secs: u64,
+    record: Option<PathBuf>,
+) -> Result<()> {
+    loop {
+        let mut batch = Vec::new();
+        let mut newest = Vec::new();
+        for program in [SAVINGS_GOAL_ID, SAVINGS_MXE_ID] {
+            let signatures = rpc.signatures_since(&program, store.cursor(&program)?.as_deref())?;
+            if let Some((signature, _)) = signatures.last() {
+                newest.push((program, signature.clone()));
+            }
+            batch.extend(signatures);
+        }
+        batch.sort_by_key(|(_, slot)| *slot);
+
+        // Transactions touching both programs are listed twice
+        let mut seen = HashSet::new();
+        for (signature, _) in batch {
+            if !seen.insert(signature.clone()) {
+                continue;
+            }
+            let Some(tx) = rpc.fetch(&signature)? else {
+                continue;
+            };
+            if let Some(path) = &record {
+                source::append_to_file(path, &tx)?;
+            }
+            let applied = store.apply(&tx)?;
+            if applied > 0 {
+                eprintln!("{} (slot {}): {applied} event(s)", tx.signature, tx.slot);
+            }
+        }
+        for (program, signature) in newest {
+            store.set_cursor(&program, &signature)?;
+        }
+        sleep(Duration::from_secs(poll_secs));
+    }
+}
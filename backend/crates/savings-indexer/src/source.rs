@@ -0,0 +1,152 @@
+//! Where indexed transactions come from.
+
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::Path;
+
+use anyhow::{Context as _, Result};
+use savings_client::Pubkey;
+use serde::{Deserialize, Serialize};
+use solana_client::rpc_client::{GetConfirmedSignaturesForAddress2Config, RpcClient};
+use solana_client::rpc_config::RpcTransactionConfig;
+use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::signature::Signature;
+use solana_transaction_status::UiTransactionEncoding;
+
+// Page size of getSignaturesForAddress
+const SIGNATURE_PAGE: usize = 1000;
+
+/// A successful transaction as the store consumes it. This is also the line
+/// format of replay files.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IndexedTransaction {
+    pub signature: String,
+    pub slot: u64,
+    #[serde(default)]
+    pub block_time: Option<i64>,
+    /// Account keys of the transaction, used to tell apart goals that share a
+    /// `goal_id` (ids are only unique per owner)
+    #[serde(default)]
+    pub accounts: Vec<String>,
+    pub logs: Vec<String>,
+}
+
+impl IndexedTransaction {
+    pub fn account_keys(&self) -> Vec<Pubkey> {
+        self.accounts
+            .iter()
+            .filter_map(|a| a.parse().ok())
+            .collect()
+    }
+}
+
+/// Read a replay file: one JSON-encoded [`IndexedTransaction`] per line
+pub fn read_file(path: &Path) -> Result<Vec<IndexedTransaction>> {
+    let file = File::open(path).with_context(|| format!("failed to open {}", path.display()))?;
+    let mut transactions = Vec::new();
+    for (number, line) in BufReader::new(file).lines().enumerate() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let tx = serde_json::from_str(&line)
+            .with_context(|| format!("{}:{}: invalid transaction", path.display(), number + 1))?;
+        transactions.push(tx);
+    }
+    Ok(transactions)
+}
+
+/// Append `tx` to a replay file
+pub fn append_to_file(path: &Path, tx: &IndexedTransaction) -> Result<()> {
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    writeln!(file, "{}", serde_json::to_string(tx)?)?;
+    Ok(())
+}
+
+pub struct RpcSource {
+    rpc: RpcClient,
+}
+
+impl RpcSource {
+    pub fn new(url: &str) -> Self {
+        Self {
+            rpc: RpcClient::new_with_commitment(url.to_string(), CommitmentConfig::confirmed()),
+        }
+    }
+
+    /// Signatures of successful transactions of `program` newer than `until`,
+    /// oldest first, with their slots
+    pub fn signatures_since(
+        &self,
+        program: &Pubkey,
+        until: Option<&str>,
+    ) -> Result<Vec<(String, u64)>> {
+        let until = until.map(str::parse::<Signature>).transpose()?;
+        let mut before = None;
+        let mut signatures = Vec::new();
+        loop {
+            let page = self.rpc.get_signatures_for_address_with_config(
+                program,
+                GetConfirmedSignaturesForAddress2Config {
+                    before,
+                    until,
+                    limit: Some(SIGNATURE_PAGE),
+                    commitment: Some(CommitmentConfig::confirmed()),
+                },
+            )?;
+            let full = page.len() == SIGNATURE_PAGE;
+            before = match page.last() {
+                Some(status) => Some(status.signature.parse()?),
+                None => None,
+            };
+            signatures.extend(
+                page.into_iter()
+                    .filter(|status| status.err.is_none())
+                    .map(|status| (status.signature, status.slot)),
+            );
+            if !full {
+                break;
+            }
+        }
+        signatures.reverse();
+        Ok(signatures)
+    }
+
+    /// Fetch a transaction with its logs and account keys; `None` if it failed
+    pub fn fetch(&self, signature: &str) -> Result<Option<IndexedTransaction>> {
+        let tx = self.rpc.get_transaction_with_config(
+            &signature.parse()?,
+            RpcTransactionConfig {
+                encoding: Some(UiTransactionEncoding::Base64),
+                commitment: Some(CommitmentConfig::confirmed()),
+                max_supported_transaction_version: Some(0),
+            },
+        )?;
+        let Some(meta) = tx.transaction.meta else {
+            return Ok(None);
+        };
+        if meta.err.is_some() {
+            return Ok(None);
+        }
+        let accounts = tx
+            .transaction
+            .transaction
+            .decode()
+            .map(|decoded| {
+                decoded
+                    .message
+                    .static_account_keys()
+                    .iter()
+                    .map(|key| key.to_string())
+                    .collect()
+            })
+            .unwrap_or_default();
+        Ok(Some(IndexedTransaction {
+            signature: signature.to_string(),
+            slot: tx.slot,
+            block_time: tx.block_time,
+            accounts,
+            logs: Option::<Vec<String>>::from(meta.log_messages).unwrap_or_default(),
+        }))
+    }
+}
@@ -0,0 +1,996 @@
+//! SQLite tables and how events update them.
+
+use std::path::Path;
+
+use anyhow::Result;
+use rusqlite::{params, Connection, OptionalExtension, Transaction};
+use savings_client::accounts::FailureReason;
+use savings_client::events::{events_from_logs, SavingsEvent};
+use savings_client::mxe::Circuit;
+use savings_client::{pda, Pubkey};
+use serde::Serialize;
+
+use crate::IndexedTransaction;
+
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS transactions (
+    signature TEXT PRIMARY KEY,
+    slot INTEGER NOT NULL,
+    block_time INTEGER
+);
+CREATE TABLE IF NOT EXISTS cursors (
+    program TEXT PRIMARY KEY,
+    signature TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS goals (
+    address TEXT PRIMARY KEY,
+    goal_id INTEGER NOT NULL,
+    owner TEXT NOT NULL,
+    name TEXT NOT NULL,
+    target_amount INTEGER NOT NULL,
+    deadline INTEGER,
+    status TEXT NOT NULL,
+    goal_reached INTEGER,
+    created_at INTEGER,
+    finalized_at INTEGER,
+    revealed_total INTEGER,
+    inconsistent INTEGER NOT NULL DEFAULT 0,
+    yield_deposited INTEGER NOT NULL DEFAULT 0,
+    yield_earned INTEGER
+);
+CREATE INDEX IF NOT EXISTS goals_by_id ON goals (goal_id);
+CREATE TABLE IF NOT EXISTS members (
+    goal TEXT NOT NULL REFERENCES goals (address),
+    member TEXT NOT NULL,
+    joined_at INTEGER,
+    PRIMARY KEY (goal, member)
+);
+CREATE TABLE IF NOT EXISTS contributions (
+    goal TEXT NOT NULL REFERENCES goals (address),
+    contributor TEXT NOT NULL,
+    timestamp INTEGER NOT NULL,
+    signature TEXT NOT NULL,
+    owner_reveal INTEGER NOT NULL DEFAULT 0,
+    PRIMARY KEY (goal, contributor)
+);
+CREATE TABLE IF NOT EXISTS transfers (
+    goal TEXT PRIMARY KEY REFERENCES goals (address),
+    recipient TEXT NOT NULL,
+    amount INTEGER NOT NULL,
+    status TEXT NOT NULL,
+    requested_signature TEXT NOT NULL,
+    completed_signature TEXT
+);
+CREATE TABLE IF NOT EXISTS computations (
+    computation_offset INTEGER PRIMARY KEY,
+    goal TEXT NOT NULL,
+    kind TEXT NOT NULL,
+    status TEXT NOT NULL,
+    requester TEXT,
+    queued_signature TEXT,
+    completed_signature TEXT,
+    total INTEGER,
+    reached INTEGER,
+    failure TEXT,
+    retried_by INTEGER
+);
+CREATE INDEX IF NOT EXISTS computations_by_goal ON computations (goal);
+CREATE TABLE IF NOT EXISTS periods (
+    goal TEXT NOT NULL REFERENCES goals (address),
+    member TEXT NOT NULL,
+    period INTEGER NOT NULL,
+    status TEXT NOT NULL,
+    signature TEXT NOT NULL,
+    PRIMARY KEY (goal, member, period)
+);
+CREATE TABLE IF NOT EXISTS group_stats (
+    goal TEXT PRIMARY KEY REFERENCES goals (address),
+    computation_offset INTEGER NOT NULL,
+    contributors INTEGER NOT NULL,
+    average INTEGER NOT NULL,
+    min INTEGER NOT NULL,
+    max INTEGER NOT NULL,
+    active INTEGER NOT NULL,
+    signature TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS milestones (
+    goal TEXT NOT NULL REFERENCES goals (address),
+    milestone INTEGER NOT NULL,
+    percent INTEGER NOT NULL,
+    status TEXT NOT NULL,
+    recipient TEXT,
+    amount INTEGER,
+    signature TEXT NOT NULL,
+    PRIMARY KEY (goal, milestone)
+);
+CREATE TABLE IF NOT EXISTS recipients (
+    goal TEXT NOT NULL REFERENCES goals (address),
+    recipient TEXT NOT NULL,
+    status TEXT NOT NULL,
+    approvals INTEGER NOT NULL DEFAULT 0,
+    signature TEXT NOT NULL,
+    PRIMARY KEY (goal, recipient)
+);
+CREATE TABLE IF NOT EXISTS yield_shares (
+    goal TEXT NOT NULL REFERENCES goals (address),
+    member TEXT NOT NULL,
+    amount INTEGER NOT NULL,
+    status TEXT NOT NULL,
+    signature TEXT NOT NULL,
+    PRIMARY KEY (goal, member)
+);
+";
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct GoalRow {
+    pub address: String,
+    pub goal_id: u64,
+    pub owner: String,
+    pub name: String,
+    pub target_amount: u64,
+    pub deadline: Option<i64>,
+    pub status: String,
+    pub goal_reached: Option<bool>,
+    pub created_at: Option<i64>,
+    pub finalized_at: Option<i64>,
+    /// Final total revealed by the MXE once the goal reached its target
+    pub revealed_total: Option<u64>,
+    /// Whether the last vault check found less than the members declared
+    pub inconsistent: bool,
+    /// Lamports currently staked through the goal's yield adapter
+    pub yield_deposited: u64,
+    /// What the last deposit earned, once withdrawn
+    pub yield_earned: Option<u64>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ContributionRow {
+    pub contributor: String,
+    pub timestamp: i64,
+    pub signature: String,
+    /// Whether the contributor lets the goal owner reveal their amount
+    pub owner_reveal: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct TransferRow {
+    pub recipient: String,
+    pub amount: u64,
+    /// `requested`, `completed` or `vetoed`
+    pub status: String,
+    pub requested_signature: String,
+    pub completed_signature: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ComputationRow {
+    pub computation_offset: u64,
+    pub kind: String,
+    pub status: String,
+    pub requester: Option<String>,
+    pub queued_signature: Option<String>,
+    pub completed_signature: Option<String>,
+    pub total: Option<u64>,
+    pub reached: Option<bool>,
+    pub failure: Option<String>,
+    pub retried_by: Option<u64>,
+}
+
+/// One member's standing for one period of a scheduled goal
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct PeriodRow {
+    pub member: String,
+    pub period: u32,
+    /// `contributed`, `met`, `short` or `missed`
+    pub status: String,
+    /// Transaction that last changed the status
+    pub signature: String,
+}
+
+/// Latest revealed statistics over a goal's contributions
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct GroupStatsRow {
+    pub computation_offset: u64,
+    pub contributors: u8,
+    pub average: u64,
+    pub min: u64,
+    pub max: u64,
+    pub active: u8,
+    pub signature: String,
+}
+
+/// A goal milestone the MXE found crossed, and its payout
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct MilestoneRow {
+    pub milestone: u8,
+    pub percent: u8,
+    /// `reached`, `requested`, `vetoed` or `released`
+    pub status: String,
+    pub recipient: Option<String>,
+    pub amount: Option<u64>,
+    /// Transaction that last changed the status
+    pub signature: String,
+}
+
+/// A recipient on a goal's allow-list, or proposed for it
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct RecipientRow {
+    pub recipient: String,
+    /// `proposed` or `allowed`
+    pub status: String,
+    /// Members who approved the proposal; 0 if allowed at creation
+    pub approvals: u8,
+    /// Transaction that last changed the row
+    pub signature: String,
+}
+
+/// A member's share of a goal's yield, as split by the MXE
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct YieldShareRow {
+    pub member: String,
+    pub amount: u64,
+    /// `distributed` or `claimed`
+    pub status: String,
+    /// Transaction that last changed the status
+    pub signature: String,
+}
+
+pub struct Store {
+    conn: Connection,
+}
+
+impl Store {
+    pub fn open(path: &Path) -> Result<Self> {
+        Self::init(Connection::open(path)?)
+    }
+
+    pub fn open_in_memory() -> Result<Self> {
+        Self::init(Connection::open_in_memory()?)
+    }
+
+    fn init(conn: Connection) -> Result<Self> {
+        conn.execute_batch(SCHEMA)?;
+        Ok(Self { conn })
+    }
+
+    /// Apply the events in `tx`. Returns how many were applied; a transaction
+    /// that was already indexed applies none.
+    pub fn apply(&mut self, tx: &IndexedTransaction) -> Result<usize> {
+        let db = self.conn.transaction()?;
+        let new = db.execute(
+            "INSERT OR IGNORE INTO transactions (signature, slot, block_time) VALUES (?1, ?2, ?3)",
+            params![tx.signature, tx.slot, tx.block_time],
+        )?;
+        if new == 0 {
+            return Ok(0);
+        }
+        let accounts = tx.account_keys();
+        let mut applied = 0;
+        for event in events_from_logs(&tx.logs) {
+            if apply_event(&db, tx, &accounts, &event)? {
+                applied += 1;
+            }
+        }
+        db.commit()?;
+        Ok(applied)
+    }
+
+    /// Newest signature of `program` already fetched by the live source
+    pub fn cursor(&self, program: &Pubkey) -> Result<Option<String>> {
+        Ok(self
+            .conn
+            .query_row(
+                "SELECT signature FROM cursors WHERE program = ?1",
+                [program.to_string()],
+                |row| row.get(0),
+            )
+            .optional()?)
+    }
+
+    pub fn set_cursor(&self, program: &Pubkey, signature: &str) -> Result<()> {
+        self.conn.execute(
+            "INSERT OR REPLACE INTO cursors (program, signature) VALUES (?1, ?2)",
+            params![program.to_string(), signature],
+        )?;
+        Ok(())
+    }
+
+    pub fn goals(&self) -> Result<Vec<GoalRow>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT address, goal_id, owner, name, target_amount, deadline, status, goal_reached,
+                    created_at, finalized_at, revealed_total, inconsistent, yield_deposited, yield_earned
+             FROM goals ORDER BY created_at, address",
+        )?;
+        let rows = stmt.query_map([], goal_row)?;
+        Ok(rows.collect::<rusqlite::Result<_>>()?)
+    }
+
+    pub fn goal(&self, address: &Pubkey) -> Result<Option<GoalRow>> {
+        Ok(self
+            .conn
+            .query_row(
+                "SELECT address, goal_id, owner, name, target_amount, deadline, status,
+                        goal_reached, created_at, finalized_at, revealed_total, inconsistent,
+                        yield_deposited, yield_earned
+                 FROM goals WHERE address = ?1",
+                [address.to_string()],
+                goal_row,
+            )
+            .optional()?)
+    }
+
+    pub fn members(&self, goal: &Pubkey) -> Result<Vec<String>> {
+        let mut stmt = self
+            .conn
+            .prepare("SELECT member FROM members WHERE goal = ?1 ORDER BY joined_at, rowid")?;
+        let rows = stmt.query_map([goal.to_string()], |row| row.get(0))?;
+        Ok(rows.collect::<rusqlite::Result<_>>()?)
+    }
+
+    pub fn contributions(&self, goal: &Pubkey) -> Result<Vec<ContributionRow>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT contributor, timestamp, signature, owner_reveal FROM contributions
+             WHERE goal = ?1 ORDER BY timestamp, contributor",
+        )?;
+        let rows = stmt.query_map([goal.to_string()], |row| {
+            Ok(ContributionRow {
+                contributor: row.get(0)?,
+                timestamp: row.get(1)?,
+                signature: row.get(2)?,
+                owner_reveal: row.get(3)?,
+            })
+        })?;
+        Ok(rows.collect::<rusqlite::Result<_>>()?)
+    }
+
+    pub fn transfer(&self, goal: &Pubkey) -> Result<Option<TransferRow>> {
+        Ok(self
+            .conn
+            .query_row(
+                "SELECT recipient, amount, status, requested_signature, completed_signature
+                 FROM transfers WHERE goal = ?1",
+                [goal.to_string()],
+                |row| {
+                    Ok(TransferRow {
+                        recipient: row.get(0)?,
+                        amount: row.get(1)?,
+                        status: row.get(2)?,
+                        requested_signature: row.get(3)?,
+                        completed_signature: row.get(4)?,
+                    })
+                },
+            )
+            .optional()?)
+    }
+
+    /// Computations queued for `goal`, oldest first
+    pub fn computations(&self, goal: &Pubkey) -> Result<Vec<ComputationRow>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT computation_offset, kind, status, requester, queued_signature,
+                    completed_signature, total, reached, failure, retried_by
+             FROM computations WHERE goal = ?1 ORDER BY rowid",
+        )?;
+        let rows = stmt.query_map([goal.to_string()], |row| {
+            Ok(ComputationRow {
+                computation_offset: row.get(0)?,
+                kind: row.get(1)?,
+                status: row.get(2)?,
+                requester: row.get(3)?,
+                queued_signature: row.get(4)?,
+                completed_signature: row.get(5)?,
+                total: row.get(6)?,
+                reached: row.get(7)?,
+                failure: row.get(8)?,
+                retried_by: row.get(9)?,
+            })
+        })?;
+        Ok(rows.collect::<rusqlite::Result<_>>()?)
+    }
+
+    /// Period records of `goal`, by period then member
+    pub fn periods(&self, goal: &Pubkey) -> Result<Vec<PeriodRow>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT member, period, status, signature FROM periods
+             WHERE goal = ?1 ORDER BY period, member",
+        )?;
+        let rows = stmt.query_map([goal.to_string()], |row| {
+            Ok(PeriodRow {
+                member: row.get(0)?,
+                period: row.get(1)?,
+                status: row.get(2)?,
+                signature: row.get(3)?,
+            })
+        })?;
+        Ok(rows.collect::<rusqlite::Result<_>>()?)
+    }
+
+    /// Crossed milestones of `goal`, in order
+    pub fn milestones(&self, goal: &Pubkey) -> Result<Vec<MilestoneRow>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT milestone, percent, status, recipient, amount, signature FROM milestones
+             WHERE goal = ?1 ORDER BY milestone",
+        )?;
+        let rows = stmt.query_map([goal.to_string()], |row| {
+            Ok(MilestoneRow {
+                milestone: row.get(0)?,
+                percent: row.get(1)?,
+                status: row.get(2)?,
+                recipient: row.get(3)?,
+                amount: row.get(4)?,
+                signature: row.get(5)?,
+            })
+        })?;
+        Ok(rows.collect::<rusqlite::Result<_>>()?)
+    }
+
+    /// Allowed and proposed recipients of `goal`
+    pub fn recipients(&self, goal: &Pubkey) -> Result<Vec<RecipientRow>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT recipient, status, approvals, signature FROM recipients
+             WHERE goal = ?1 ORDER BY recipient",
+        )?;
+        let rows = stmt.query_map([goal.to_string()], |row| {
+            Ok(RecipientRow {
+                recipient: row.get(0)?,
+                status: row.get(1)?,
+                approvals: row.get(2)?,
+                signature: row.get(3)?,
+            })
+        })?;
+        Ok(rows.collect::<rusqlite::Result<_>>()?)
+    }
+
+    /// Members' shares of `goal`'s yield, in member order
+    pub fn yield_shares(&self, goal: &Pubkey) -> Result<Vec<YieldShareRow>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT y.member, y.amount, y.status, y.signature FROM yield_shares y
+             JOIN members m ON m.goal = y.goal AND m.member = y.member
+             WHERE y.goal = ?1 ORDER BY m.joined_at, m.rowid",
+        )?;
+        let rows = stmt.query_map([goal.to_string()], |row| {
+            Ok(YieldShareRow {
+                member: row.get(0)?,
+                amount: row.get(1)?,
+                status: row.get(2)?,
+                signature: row.get(3)?,
+            })
+        })?;
+        Ok(rows.collect::<rusqlite::Result<_>>()?)
+    }
+
+    pub fn group_stats(&self, goal: &Pubkey) -> Result<Option<GroupStatsRow>> {
+        Ok(self
+            .conn
+            .query_row(
+                "SELECT computation_offset, contributors, average, min, max, active, signature
+                 FROM group_stats WHERE goal = ?1",
+                [goal.to_string()],
+                |row| {
+                    Ok(GroupStatsRow {
+                        computation_offset: row.get(0)?,
+                        contributors: row.get(1)?,
+                        average: row.get(2)?,
+                        min: row.get(3)?,
+                        max: row.get(4)?,
+                        active: row.get(5)?,
+                        signature: row.get(6)?,
+                    })
+                },
+            )
+            .optional()?)
+    }
+}
+
+fn goal_row(row: &rusqlite::Row) -> rusqlite::Result<GoalRow> {
+    Ok(GoalRow {
+        address: row.get(0)?,
+        goal_id: row.get(1)?,
+        owner: row.get(2)?,
+        name: row.get(3)?,
+        target_amount: row.get(4)?,
+        deadline: row.get(5)?,
+        status: row.get(6)?,
+        goal_reached: row.get(7)?,
+        created_at: row.get(8)?,
+        finalized_at: row.get(9)?,
+        revealed_total: row.get(10)?,
+        inconsistent: row.get(11)?,
+        yield_deposited: row.get(12)?,
+        yield_earned: row.get(13)?,
+    })
+}
+
+fn failure_reason(reason: &FailureReason) -> &'static str {
+    match reason {
+        FailureReason::Aborted => "aborted",
+    }
+}
+
+/// The indexed goal an event's `goal_id` refers to: the one whose address is
+/// among the transaction's accounts, else the only goal with that id
+fn resolve_goal(db: &Transaction, goal_id: u64, accounts: &[Pubkey]) -> Result<Option<String>> {
+    let mut stmt = db.prepare("SELECT address FROM goals WHERE goal_id = ?1")?;
+    let candidates: Vec<String> = stmt
+        .query_map([goal_id], |row| row.get(0))?
+        .collect::<rusqlite::Result<_>>()?;
+    let in_tx = candidates
+        .iter()
+        .find(|address| accounts.iter().any(|key| key.to_string() == **address));
+    Ok(match (in_tx, candidates.len()) {
+        (Some(address), _) => Some(address.clone()),
+        (None, 1) => candidates.into_iter().next(),
+        (None, _) => None,
+    })
+}
+
+/// Returns whether the event changed anything. Events for goals created
+/// before indexing started cannot be attributed and are skipped.
+fn apply_event(
+    db: &Transaction,
+    tx: &IndexedTransaction,
+    accounts: &[Pubkey],
+    event: &SavingsEvent,
+) -> Result<bool> {
+    let goal_of = |goal_id| resolve_goal(db, goal_id, accounts);
+    let changed = match event {
+        SavingsEvent::GoalCreated(e) => {
+            let address = pda::goal(&e.owner, e.goal_id).to_string();
+            db.execute(
+                "INSERT OR IGNORE INTO goals
+                 (address, goal_id, owner, name, target_amount, deadline, status, created_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, 'active', ?7)",
+                params![
+                    address,
+                    e.goal_id,
+                    e.owner.to_string(),
+                    e.name,
+                    e.target_amount,
+                    e.deadline,
+                    tx.block_time
+                ],
+            )?;
+            db.execute(
+                "INSERT OR IGNORE INTO members (goal, member, joined_at) VALUES (?1, ?2, ?3)",
+                params![address, e.owner.to_string(), tx.block_time],
+            )?
+        }
+        SavingsEvent::MemberInvited(e) => match goal_of(e.goal_id)? {
+            Some(goal) => db.execute(
+                "INSERT OR IGNORE INTO members (goal, member, joined_at) VALUES (?1, ?2, ?3)",
+                params![goal, e.member.to_string(), tx.block_time],
+            )?,
+            None => 0,
+        },
+        SavingsEvent::ContributionAdded(e) => match goal_of(e.goal_id)? {
+            Some(goal) => db.execute(
+                "INSERT OR IGNORE INTO contributions (goal, contributor, timestamp, signature)
+                 VALUES (?1, ?2, ?3, ?4)",
+                params![goal, e.contributor.to_string(), e.timestamp, tx.signature],
+            )?,
+            None => 0,
+        },
+        SavingsEvent::OwnerRevealChanged(e) => match goal_of(e.goal_id)? {
+            Some(goal) => db.execute(
+                "UPDATE contributions SET owner_reveal = ?3 WHERE goal = ?1 AND contributor = ?2",
+                params![goal, e.contributor.to_string(), e.allowed],
+            )?,
+            None => 0,
+        },
+        SavingsEvent::PeriodContributionAdded(e) => match goal_of(e.goal_id)? {
+            Some(goal) => db.execute(
+                "INSERT OR IGNORE INTO periods (goal, member, period, status, signature)
+                 VALUES (?1, ?2, ?3, 'contributed', ?4)",
+                params![goal, e.member.to_string(), e.period, tx.signature],
+            )?,
+            None => 0,
+        },
+        // Either closed with nothing paid in, or found short by the MXE
+        SavingsEvent::ContributionMissed(e) => match goal_of(e.goal_id)? {
+            Some(goal) => db.execute(
+                "INSERT INTO periods (goal, member, period, status, signature)
+                 VALUES (?1, ?2, ?3, ?4, ?5)
+                 ON CONFLICT (goal, member, period) DO UPDATE SET status = ?4, signature = ?5",
+                params![
+                    goal,
+                    e.member.to_string(),
+                    e.period,
+                    if e.contributed { "short" } else { "missed" },
+                    tx.signature
+                ],
+            )?,
+            None => 0,
+        },
+        SavingsEvent::GoalReached(e) => match goal_of(e.goal_id)? {
+            Some(goal) => db.execute(
+                "UPDATE goals SET status = 'reached' WHERE address = ?1 AND status = 'active'",
+                params![goal],
+            )?,
+            None => 0,
+        },
+        SavingsEvent::GoalFinalized(e) => match goal_of(e.goal_id)? {
+            Some(goal) => db.execute(
+                "UPDATE goals SET status = 'finalized', finalized_at = ?2, goal_reached = ?3
+                 WHERE address = ?1",
+                params![goal, e.finalized_at, e.goal_reached],
+            )?,
+            None => 0,
+        },
+        SavingsEvent::TotalRevealed(e) => match goal_of(e.goal_id)? {
+            Some(goal) => db.execute(
+                "UPDATE goals SET revealed_total = ?2 WHERE address = ?1",
+                params![goal, e.total],
+            )?,
+            None => 0,
+        },
+        SavingsEvent::VaultChecked(e) => match goal_of(e.goal_id)? {
+            Some(goal) => db.execute(
+                "UPDATE goals SET inconsistent = ?2 WHERE address = ?1",
+                params![goal, e.inconsistent],
+            )?,
+            None => 0,
+        },
+        SavingsEvent::MilestoneReached(e) => match goal_of(e.goal_id)? {
+            Some(goal) => db.execute(
+                "INSERT OR IGNORE INTO milestones (goal, milestone, percent, status, signature)
+                 VALUES (?1, ?2, ?3, 'reached', ?4)",
+                params![goal, e.milestone, e.percent, tx.signature],
+            )?,
+            None => 0,
+        },
+        SavingsEvent::MilestoneTransferRequested(e) => match goal_of(e.goal_id)? {
+            Some(goal) => db.execute(
+                "UPDATE milestones SET status = 'requested', recipient = ?3, amount = ?4,
+                 signature = ?5
+                 WHERE goal = ?1 AND milestone = ?2 AND status IN ('reached', 'vetoed')",
+                params![
+                    goal,
+                    e.milestone,
+                    e.recipient.to_string(),
+                    e.amount,
+                    tx.signature
+                ],
+            )?,
+            None => 0,
+        },
+        SavingsEvent::MilestoneReleased(e) => match goal_of(e.goal_id)? {
+            Some(goal) => db.execute(
+                "UPDATE milestones SET status = 'released', signature = ?3
+                 WHERE goal = ?1 AND milestone = ?2 AND status = 'requested'",
+                params![goal, e.milestone, tx.signature],
+            )?,
+            None => 0,
+        },
+        SavingsEvent::RecipientApproved(e) => match goal_of(e.goal_id)? {
+            Some(goal) => db.execute(
+                "INSERT INTO recipients (goal, recipient, status, approvals, signature)
+                 VALUES (?1, ?2, 'proposed', ?3, ?4)
+                 ON CONFLICT (goal, recipient) DO UPDATE SET approvals = ?3, signature = ?4",
+                params![goal, e.recipient.to_string(), e.approvals, tx.signature],
+            )?,
+            None => 0,
+        },
+        // Emitted after the approval that completes the majority, or at creation
+        SavingsEvent::RecipientAllowed(e) => match goal_of(e.goal_id)? {
+            Some(goal) => db.execute(
+                "INSERT INTO recipients (goal, recipient, status, signature)
+                 VALUES (?1, ?2, 'allowed', ?3)
+                 ON CONFLICT (goal, recipient) DO UPDATE SET status = 'allowed', signature = ?3",
+                params![goal, e.recipient.to_string(), tx.signature],
+            )?,
+            None => 0,
+        },
+        SavingsEvent::TransferRequested(e) => match goal_of(e.goal_id)? {
+            Some(goal) => db.execute(
+                "INSERT OR REPLACE INTO transfers
+                 (goal, recipient, amount, status, requested_signature)
+                 VALUES (?1, ?2, ?3, 'requested', ?4)",
+                params![goal, e.recipient.to_string(), e.amount, tx.signature],
+            )?,
+            None => 0,
+        },
+        SavingsEvent::TransferCompleted(e) => match goal_of(e.goal_id)? {
+            Some(goal) => db.execute(
+                "UPDATE transfers SET status = 'completed', completed_signature = ?2
+                 WHERE goal = ?1",
+                params![goal, tx.signature],
+            )?,
+            None => 0,
+        },
+        // The owner may request it again, which replaces the row
+        SavingsEvent::TransferVetoed(e) => match (goal_of(e.goal_id)?, e.milestone) {
+            (Some(goal), Some(milestone)) => db.execute(
+                "UPDATE milestones SET status = 'vetoed', signature = ?3
+                 WHERE goal = ?1 AND milestone = ?2 AND status = 'requested'",
+                params![goal, milestone, tx.signature],
+            )?,
+            (Some(goal), None) => db.execute(
+                "UPDATE transfers SET status = 'vetoed' WHERE goal = ?1 AND status = 'requested'",
+                params![goal],
+            )?,
+            (None, _) => 0,
+        },
+        SavingsEvent::YieldDeposited(e) => match goal_of(e.goal_id)? {
+            Some(goal) => db.execute(
+                "UPDATE goals SET yield_deposited = ?2 WHERE address = ?1",
+                params![goal, e.amount],
+            )?,
+            None => 0,
+        },
+        SavingsEvent::YieldWithdrawn(e) => match goal_of(e.goal_id)? {
+            Some(goal) => db.execute(
+                "UPDATE goals SET yield_deposited = 0, yield_earned = ?2 WHERE address = ?1",
+                params![goal, e.earned],
+            )?,
+            None => 0,
+        },
+        SavingsEvent::YieldClaimed(e) => match goal_of(e.goal_id)? {
+            Some(goal) => db.execute(
+                "UPDATE yield_shares SET status = 'claimed', signature = ?3
+                 WHERE goal = ?1 AND member = ?2",
+                params![goal, e.member.to_string(), tx.signature],
+            )?,
+            None => 0,
+        },
+        // The adapter, unstaking state and deposited total live on the goal
+        // account; the shares follow from the YieldDistributionEvent emitted
+        // alongside
+        SavingsEvent::Deposited(_)
+        | SavingsEvent::YieldAdapterChanged(_)
+        | SavingsEvent::YieldWithdrawalStarted(_)
+        | SavingsEvent::YieldDistributed(_) => 0,
+        SavingsEvent::ComputationQueued(e) => db.execute(
+            "INSERT INTO computations
+             (computation_offset, goal, kind, status, requester, queued_signature)
+             VALUES (?1, ?2, ?3, 'queued', ?4, ?5)
+             ON CONFLICT (computation_offset) DO UPDATE
+             SET requester = ?4, queued_signature = ?5",
+            params![
+                e.computation_offset,
+                e.goal.to_string(),
+                e.kind.name(),
+                e.requester.to_string(),
+                tx.signature
+            ],
+        )?,
+        SavingsEvent::ComputationRetried(e) => db.execute(
+            "UPDATE computations SET retried_by = ?2 WHERE computation_offset = ?1",
+            params![e.failed_offset, e.computation_offset],
+        )?,
+        // Results may be seen without their queue transaction when indexing
+        // started in between
+        SavingsEvent::ComputationFailed(e) => db.execute(
+            "INSERT INTO computations
+             (computation_offset, goal, kind, status, completed_signature, failure)
+             VALUES (?1, ?2, ?3, 'failed', ?4, ?5)
+             ON CONFLICT (computation_offset) DO UPDATE
+             SET status = 'failed', completed_signature = ?4, failure = ?5",
+            params![
+                e.computation_offset,
+                e.goal.to_string(),
+                e.kind.name(),
+                tx.signature,
+                failure_reason(&e.reason)
+            ],
+        )?,
+        SavingsEvent::Aggregation(e) => db.execute(
+            "INSERT INTO computations
+             (computation_offset, goal, kind, status, completed_signature, total)
+             VALUES (?1, ?2, ?3, 'completed', ?4, ?5)
+             ON CONFLICT (computation_offset) DO UPDATE
+             SET status = 'completed', completed_signature = ?4, total = ?5",
+            params![
+                e.computation_offset,
+                e.goal.to_string(),
+                Circuit::AddTwoContributions.name(),
+                tx.signature,
+                e.total
+            ],
+        )?,
+        // add_and_check_goal reports through the same event; a row seen at
+        // queue time keeps its kind
+        SavingsEvent::GoalCheck(e) => db.execute(
+            "INSERT INTO computations
+             (computation_offset, goal, kind, status, completed_signature, reached)
+             VALUES (?1, ?2, ?3, 'completed', ?4, ?5)
+             ON CONFLICT (computation_offset) DO UPDATE
+             SET status = 'completed', completed_signature = ?4, reached = ?5",
+            params![
+                e.computation_offset,
+                e.goal.to_string(),
+                Circuit::CheckGoalReached.name(),
+                tx.signature,
+                e.reached
+            ],
+        )?,
+        // The result itself is only readable by the member
+        SavingsEvent::MemberProgress(e) => db.execute(
+            "INSERT INTO computations
+             (computation_offset, goal, kind, status, requester, completed_signature)
+             VALUES (?1, ?2, ?3, 'completed', ?4, ?5)
+             ON CONFLICT (computation_offset) DO UPDATE
+             SET status = 'completed', completed_signature = ?5",
+            params![
+                e.computation_offset,
+                e.goal.to_string(),
+                Circuit::MemberProgress.name(),
+                e.member.to_string(),
+                tx.signature
+            ],
+        )?,
+        // Reveal results are only readable by their recipients; one
+        // ContributionRevealed is emitted per contribution
+        SavingsEvent::ContributionRevealed(e) => db.execute(
+            "INSERT INTO computations
+             (computation_offset, goal, kind, status, completed_signature)
+             VALUES (?1, ?2, ?3, 'completed', ?4)
+             ON CONFLICT (computation_offset) DO UPDATE
+             SET status = 'completed', completed_signature = ?4",
+            params![
+                e.computation_offset,
+                e.goal.to_string(),
+                Circuit::RevealOwnContributions.name(),
+                tx.signature
+            ],
+        )?,
+        SavingsEvent::OwnerReveal(e) => db.execute(
+            "INSERT INTO computations
+             (computation_offset, goal, kind, status, completed_signature)
+             VALUES (?1, ?2, ?3, 'completed', ?4)
+             ON CONFLICT (computation_offset) DO UPDATE
+             SET status = 'completed', completed_signature = ?4",
+            params![
+                e.computation_offset,
+                e.goal.to_string(),
+                Circuit::RevealContributions10.name(),
+                tx.signature
+            ],
+        )?,
+        // The ciphertexts themselves stay in the goal's audit account
+        SavingsEvent::Audit(e) => db.execute(
+            "INSERT INTO computations
+             (computation_offset, goal, kind, status, completed_signature)
+             VALUES (?1, ?2, ?3, 'completed', ?4)
+             ON CONFLICT (computation_offset) DO UPDATE
+             SET status = 'completed', completed_signature = ?4",
+            params![
+                e.computation_offset,
+                e.goal.to_string(),
+                Circuit::AuditContributions.name(),
+                tx.signature
+            ],
+        )?,
+        // No total means the goal fell short of its target
+        SavingsEvent::FinalTotal(e) => db.execute(
+            "INSERT INTO computations
+             (computation_offset, goal, kind, status, completed_signature, total)
+             VALUES (?1, ?2, ?3, 'completed', ?4, ?5)
+             ON CONFLICT (computation_offset) DO UPDATE
+             SET status = 'completed', completed_signature = ?4, total = ?5",
+            params![
+                e.computation_offset,
+                e.goal.to_string(),
+                Circuit::RevealFinalTotal.name(),
+                tx.signature,
+                e.total
+            ],
+        )?,
+        // The goal's flag follows from the VaultChecked emitted alongside
+        SavingsEvent::VaultCheck(e) => db.execute(
+            "INSERT INTO computations
+             (computation_offset, goal, kind, status, completed_signature)
+             VALUES (?1, ?2, ?3, 'completed', ?4)
+             ON CONFLICT (computation_offset) DO UPDATE
+             SET status = 'completed', completed_signature = ?4",
+            params![
+                e.computation_offset,
+                e.goal.to_string(),
+                Circuit::CheckVaultBalance.name(),
+                tx.signature
+            ],
+        )?,
+        // Crossed milestones follow from the MilestoneReached emitted alongside
+        SavingsEvent::MilestoneCheck(e) => db.execute(
+            "INSERT INTO computations
+             (computation_offset, goal, kind, status, completed_signature)
+             VALUES (?1, ?2, ?3, 'completed', ?4)
+             ON CONFLICT (computation_offset) DO UPDATE
+             SET status = 'completed', completed_signature = ?4",
+            params![
+                e.computation_offset,
+                e.goal.to_string(),
+                Circuit::CheckMilestones.name(),
+                tx.signature
+            ],
+        )?,
+        // Shares are in member order, which the members table keeps
+        SavingsEvent::YieldDistribution(e) => {
+            let members: Vec<String> = db
+                .prepare("SELECT member FROM members WHERE goal = ?1 ORDER BY joined_at, rowid")?
+                .query_map([e.goal.to_string()], |row| row.get(0))?
+                .collect::<rusqlite::Result<_>>()?;
+            let mut changed = 0;
+            for (member, amount) in members.iter().zip(&e.shares) {
+                changed += db.execute(
+                    "INSERT OR IGNORE INTO yield_shares (goal, member, amount, status, signature)
+                     VALUES (?1, ?2, ?3, 'distributed', ?4)",
+                    params![e.goal.to_string(), member, amount, tx.signature],
+                )?;
+            }
+            changed
+                + db.execute(
+                    "INSERT INTO computations
+                     (computation_offset, goal, kind, status, completed_signature)
+                     VALUES (?1, ?2, ?3, 'completed', ?4)
+                     ON CONFLICT (computation_offset) DO UPDATE
+                     SET status = 'completed', completed_signature = ?4",
+                    params![
+                        e.computation_offset,
+                        e.goal.to_string(),
+                        Circuit::DistributeYield.name(),
+                        tx.signature
+                    ],
+                )?
+        }
+        SavingsEvent::GroupStats(e) => {
+            db.execute(
+                "INSERT INTO group_stats
+                 (goal, computation_offset, contributors, average, min, max, active, signature)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)
+                 ON CONFLICT (goal) DO UPDATE
+                 SET computation_offset = ?2, contributors = ?3, average = ?4, min = ?5,
+                     max = ?6, active = ?7, signature = ?8",
+                params![
+                    e.goal.to_string(),
+                    e.computation_offset,
+                    e.contributors,
+                    e.average,
+                    e.min,
+                    e.max,
+                    e.active,
+                    tx.signature
+                ],
+            )? + db.execute(
+                "INSERT INTO computations
+                 (computation_offset, goal, kind, status, completed_signature)
+                 VALUES (?1, ?2, ?3, 'completed', ?4)
+                 ON CONFLICT (computation_offset) DO UPDATE
+                 SET status = 'completed', completed_signature = ?4",
+                params![
+                    e.computation_offset,
+                    e.goal.to_string(),
+                    Circuit::GroupStats.name(),
+                    tx.signature
+                ],
+            )?
+        }
+        // A short result is also reported by ContributionMissed from the
+        // CPI that settles the period
+        SavingsEvent::PeriodCheck(e) => {
+            let settled = if e.met {
+                db.execute(
+                    "INSERT INTO periods (goal, member, period, status, signature)
+                     VALUES (?1, ?2, ?3, 'met', ?4)
+                     ON CONFLICT (goal, member, period) DO UPDATE SET status = 'met', signature = ?4",
+                    params![e.goal.to_string(), e.member.to_string(), e.period, tx.signature],
+                )?
+            } else {
+                0
+            };
+            settled
+                + db.execute(
+                    "INSERT INTO computations
+                     (computation_offset, goal, kind, status, completed_signature)
+                     VALUES (?1, ?2, ?3, 'completed', ?4)
+                     ON CONFLICT (computation_offset) DO UPDATE
+                     SET status = 'completed', completed_signature = ?4",
+                    params![
+                        e.computation_offset,
+                        e.goal.to_string(),
+                        Circuit::CheckPeriodContribution.name(),
+                        tx.signature
+                    ],
+                )?
+        }
+    };
+    Ok(changed > 0)
+}
@@ -0,0 +1,1218 @@
+use anchor_lang::Event;
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use savings_client::{pda, Pubkey, SAVINGS_GOAL_ID, SAVINGS_MXE_ID};
+use savings_goal::{
+    ContributionAdded, ContributionMissed, GoalCreated, GoalFinalized, GoalReached, MemberInvited,
+    MilestoneReached, MilestoneReleased, MilestoneTransferRequested, OwnerRevealChanged,
+    PeriodContributionAdded, RecipientAllowed, RecipientApproved, TotalRevealed, TransferCompleted,
+    TransferRequested, TransferVetoed, VaultChecked, YieldClaimed, YieldDeposited, YieldWithdrawn,
+};
+use savings_indexer::source::{self, IndexedTransaction};
+use savings_indexer::Store;
+use savings_mxe::{
+    AggregationEvent, AuditEvent, Circuit, ComputationFailed, ComputationQueued,
+    ComputationRetried, ContributionRevealed, FailureReason, FinalTotalEvent, GoalCheckEvent,
+    GroupStatsEvent, MemberProgressEvent, MilestoneCheckEvent, OwnerRevealEvent, PeriodCheckEvent,
+    VaultBalance, VaultCheckEvent, YieldDistributionEvent,
+};
+
+/// Logs of a top-level invocation of `program` that emitted `events`
+fn invocation(program: Pubkey, events: &[Vec<u8>]) -> Vec<String> {
+    let mut logs = vec![format!("Program {program} invoke [1]")];
+    logs.extend(
+        events
+            .iter()
+            .map(|data| format!("Program data: {}", STANDARD.encode(data))),
+    );
+    logs.push(format!("Program {program} success"));
+    logs
+}
+
+fn tx(slot: u64, accounts: &[Pubkey], logs: Vec<String>) -> IndexedTransaction {
+    IndexedTransaction {
+        signature: format!("sig{slot}"),
+        slot,
+        block_time: Some(1_700_000_000 + slot as i64),
+        accounts: accounts.iter().map(|a| a.to_string()).collect(),
+        logs,
+    }
+}
+
+fn goal_created(owner: Pubkey, goal_id: u64) -> Vec<u8> {
+    GoalCreated {
+        goal_id,
+        owner,
+        name: "trip".into(),
+        target_amount: 1_000,
+        deadline: None,
+    }
+    .data()
+}
+
+#[test]
+fn replays_a_goal_lifecycle() {
+    let owner = Pubkey::new_unique();
+    let member = Pubkey::new_unique();
+    let recipient = Pubkey::new_unique();
+    let goal = pda::goal(&owner, 7);
+    let history = [
+        tx(
+            1,
+            &[owner, goal],
+            invocation(SAVINGS_GOAL_ID, &[goal_created(owner, 7)]),
+        ),
+        tx(
+            2,
+            &[owner, goal],
+            invocation(
+                SAVINGS_GOAL_ID,
+                &[MemberInvited { goal_id: 7, member }.data()],
+            ),
+        ),
+        tx(
+            3,
+            &[member, goal],
+            invocation(
+                SAVINGS_GOAL_ID,
+                &[ContributionAdded {
+                    goal_id: 7,
+                    contributor: member,
+                    timestamp: 3,
+                }
+                .data()],
+            ),
+        ),
+        tx(
+            4,
+            &[member],
+            invocation(
+                SAVINGS_MXE_ID,
+                &[ComputationQueued {
+                    goal,
+                    computation_offset: 40,
+                    kind: Circuit::AddTwoContributions,
+                    requester: member,
+                }
+                .data()],
+            ),
+        ),
+        // Callbacks arrive as a CPI from the Arcium program
+        tx(5, &[], {
+            let arcium = Pubkey::new_unique();
+            let mut logs = vec![format!("Program {arcium} invoke [1]")];
+            logs.extend(
+                invocation(
+                    SAVINGS_MXE_ID,
+                    &[AggregationEvent {
+                        goal,
+                        computation_offset: 40,
+                        total: 1_100,
+                    }
+                    .data()],
+                )
+                .into_iter()
+                .map(|line| line.replace("[1]", "[2]")),
+            );
+            logs.push(format!("Program {arcium} success"));
+            logs
+        }),
+        // A result whose queue transaction was never indexed
+        tx(
+            6,
+            &[],
+            invocation(
+                SAVINGS_MXE_ID,
+                &[GoalCheckEvent {
+                    goal,
+                    computation_offset: 41,
+                    reached: true,
+                }
+                .data()],
+            ),
+        ),
+        tx(
+            7,
+            &[owner, goal],
+            invocation(
+                SAVINGS_GOAL_ID,
+                &[GoalFinalized {
+                    goal_id: 7,
+                    finalized_at: 7,
+                    goal_reached: true,
+                }
+                .data()],
+            ),
+        ),
+        tx(
+            8,
+            &[owner, goal],
+            invocation(
+                SAVINGS_GOAL_ID,
+                &[TransferRequested {
+                    goal_id: 7,
+                    recipient,
+                    amount: 500,
+                }
+                .data()],
+            ),
+        ),
+        tx(
+            9,
+            &[owner, goal, recipient],
+            invocation(
+                SAVINGS_GOAL_ID,
+                &[TransferCompleted {
+                    goal_id: 7,
+                    recipient,
+                    amount: 500,
+                }
+                .data()],
+            ),
+        ),
+    ];
+
+    let dir = tempfile::tempdir().unwrap();
+    let file = dir.path().join("history.jsonl");
+    for tx in &history {
+        source::append_to_file(&file, tx).unwrap();
+    }
+    let replayed = source::read_file(&file).unwrap();
+    assert_eq!(replayed, history);
+
+    let mut store = Store::open_in_memory().unwrap();
+    let applied: usize = replayed.iter().map(|tx| store.apply(tx).unwrap()).sum();
+    assert_eq!(applied, 9);
+
+    let row = store.goal(&goal).unwrap().unwrap();
+    assert_eq!(row.owner, owner.to_string());
+    assert_eq!(row.status, "finalized");
+    assert_eq!(row.goal_reached, Some(true));
+    assert_eq!(
+        store.members(&goal).unwrap(),
+        vec![owner.to_string(), member.to_string()]
+    );
+    let contributions = store.contributions(&goal).unwrap();
+    assert_eq!(contributions.len(), 1);
+    assert_eq!(contributions[0].contributor, member.to_string());
+    let transfer = store.transfer(&goal).unwrap().unwrap();
+    assert_eq!(transfer.amount, 500);
+    assert_eq!(transfer.status, "completed");
+    let computations = store.computations(&goal).unwrap();
+    assert_eq!(computations.len(), 2);
+    assert_eq!(computations[0].computation_offset, 40);
+    assert_eq!(computations[0].kind, "add_two_contributions");
+    assert_eq!(computations[0].status, "completed");
+    assert_eq!(computations[0].requester, Some(member.to_string()));
+    assert_eq!(computations[0].queued_signature.as_deref(), Some("sig4"));
+    assert_eq!(computations[0].completed_signature.as_deref(), Some("sig5"));
+    assert_eq!(computations[0].total, Some(1_100));
+    assert_eq!(computations[1].computation_offset, 41);
+    assert_eq!(computations[1].reached, Some(true));
+    assert_eq!(computations[1].queued_signature, None);
+
+    // Replaying again changes nothing
+    let reapplied: usize = replayed.iter().map(|tx| store.apply(tx).unwrap()).sum();
+    assert_eq!(reapplied, 0);
+    assert_eq!(store.computations(&goal).unwrap().len(), 2);
+}
+
+#[test]
+fn goals_sharing_an_id_are_told_apart_by_accounts() {
+    let alice = Pubkey::new_unique();
+    let bob = Pubkey::new_unique();
+    let member = Pubkey::new_unique();
+    let alice_goal = pda::goal(&alice, 1);
+    let bob_goal = pda::goal(&bob, 1);
+
+    let mut store = Store::open_in_memory().unwrap();
+    for tx in [
+        tx(
+            1,
+            &[alice, alice_goal],
+            invocation(SAVINGS_GOAL_ID, &[goal_created(alice, 1)]),
+        ),
+        tx(
+            2,
+            &[bob, bob_goal],
+            invocation(SAVINGS_GOAL_ID, &[goal_created(bob, 1)]),
+        ),
+        tx(
+            3,
+            &[bob, bob_goal, member],
+            invocation(
+                SAVINGS_GOAL_ID,
+                &[MemberInvited { goal_id: 1, member }.data()],
+            ),
+        ),
+        // Without the goal among the accounts the invite is ambiguous
+        tx(
+            4,
+            &[],
+            invocation(
+                SAVINGS_GOAL_ID,
+                &[MemberInvited {
+                    goal_id: 1,
+                    member: alice,
+                }
+                .data()],
+            ),
+        ),
+    ] {
+        store.apply(&tx).unwrap();
+    }
+
+    assert_eq!(store.members(&alice_goal).unwrap(), vec![alice.to_string()]);
+    assert_eq!(
+        store.members(&bob_goal).unwrap(),
+        vec![bob.to_string(), member.to_string()]
+    );
+    assert_eq!(store.goals().unwrap().len(), 2);
+}
+
+#[test]
+fn failed_computations_link_to_their_retry() {
+    let goal = Pubkey::new_unique();
+    let requester = Pubkey::new_unique();
+    let queued = |offset| {
+        ComputationQueued {
+            goal,
+            computation_offset: offset,
+            kind: Circuit::CheckGoalReached,
+            requester,
+        }
+        .data()
+    };
+
+    let mut store = Store::open_in_memory().unwrap();
+    for tx in [
+        tx(1, &[requester], invocation(SAVINGS_MXE_ID, &[queued(10)])),
+        tx(
+            2,
+            &[],
+            invocation(
+                SAVINGS_MXE_ID,
+                &[ComputationFailed {
+                    goal,
+                    computation_offset: 10,
+                    kind: Circuit::CheckGoalReached,
+                    reason: FailureReason::Aborted,
+                }
+                .data()],
+            ),
+        ),
+        tx(
+            3,
+            &[requester],
+            invocation(
+                SAVINGS_MXE_ID,
+                &[
+                    queued(11),
+                    ComputationRetried {
+                        goal,
+                        failed_offset: 10,
+                        computation_offset: 11,
+                    }
+                    .data(),
+                ],
+            ),
+        ),
+    ] {
+        store.apply(&tx).unwrap();
+    }
+
+    let computations = store.computations(&goal).unwrap();
+    assert_eq!(computations.len(), 2);
+    assert_eq!(computations[0].status, "failed");
+    assert_eq!(computations[0].failure.as_deref(), Some("aborted"));
+    assert_eq!(computations[0].retried_by, Some(11));
+    assert_eq!(computations[1].status, "queued");
+    assert_eq!(computations[1].failure, None);
+}
+
+#[test]
+fn goal_reached_through_the_mxe_callback() {
+    let owner = Pubkey::new_unique();
+    let goal = pda::goal(&owner, 3);
+    let queued = ComputationQueued {
+        goal,
+        computation_offset: 60,
+        kind: Circuit::AddAndCheckGoal,
+        requester: owner,
+    };
+    // savings_goal emits GoalReached from inside the callback's CPI
+    let mut callback = vec![
+        format!("Program {SAVINGS_MXE_ID} invoke [1]"),
+        format!(
+            "Program data: {}",
+            STANDARD.encode(
+                GoalCheckEvent {
+                    goal,
+                    computation_offset: 60,
+                    reached: true,
+                }
+                .data()
+            )
+        ),
+    ];
+    callback.extend(invocation(
+        SAVINGS_GOAL_ID,
+        &[GoalReached {
+            goal_id: 3,
+            reached_at: 3,
+        }
+        .data()],
+    ));
+    callback.push(format!("Program {SAVINGS_MXE_ID} success"));
+    let history = [
+        tx(
+            1,
+            &[owner, goal],
+            invocation(SAVINGS_GOAL_ID, &[goal_created(owner, 3)]),
+        ),
+        tx(
+            2,
+            &[owner, goal],
+            invocation(SAVINGS_MXE_ID, &[queued.data()]),
+        ),
+        tx(3, &[goal], callback),
+    ];
+
+    let mut store = Store::open_in_memory().unwrap();
+    for tx in &history {
+        store.apply(tx).unwrap();
+    }
+    assert_eq!(store.goal(&goal).unwrap().unwrap().status, "reached");
+    let computations = store.computations(&goal).unwrap();
+    assert_eq!(computations.len(), 1);
+    assert_eq!(computations[0].kind, "add_and_check_goal_10");
+    assert_eq!(computations[0].reached, Some(true));
+}
+
+#[test]
+fn period_contributions_are_settled_by_checks_and_closes() {
+    let owner = Pubkey::new_unique();
+    let member = Pubkey::new_unique();
+    let goal = pda::goal(&owner, 4);
+    let contributed = |who, slot| {
+        tx(
+            slot,
+            &[who, goal],
+            invocation(
+                SAVINGS_GOAL_ID,
+                &[PeriodContributionAdded {
+                    goal_id: 4,
+                    member: who,
+                    period: 0,
+                    timestamp: slot as i64,
+                }
+                .data()],
+            ),
+        )
+    };
+    let check = |who, offset, met| PeriodCheckEvent {
+        goal,
+        computation_offset: offset,
+        member: who,
+        period: 0,
+        met,
+    };
+    // A short check settles the period through a CPI into savings_goal
+    let mut short_callback = vec![
+        format!("Program {SAVINGS_MXE_ID} invoke [1]"),
+        format!(
+            "Program data: {}",
+            STANDARD.encode(check(member, 71, false).data())
+        ),
+    ];
+    short_callback.extend(invocation(
+        SAVINGS_GOAL_ID,
+        &[ContributionMissed {
+            goal_id: 4,
+            member,
+            period: 0,
+            contributed: true,
+        }
+        .data()],
+    ));
+    short_callback.push(format!("Program {SAVINGS_MXE_ID} success"));
+    let history = [
+        tx(
+            1,
+            &[owner, goal],
+            invocation(SAVINGS_GOAL_ID, &[goal_created(owner, 4)]),
+        ),
+        contributed(owner, 2),
+        contributed(member, 3),
+        tx(
+            4,
+            &[goal],
+            invocation(SAVINGS_MXE_ID, &[check(owner, 70, true).data()]),
+        ),
+        tx(5, &[goal], short_callback),
+        // Nobody paid in for period 1 and a crank closed it
+        tx(
+            6,
+            &[owner, goal],
+            invocation(
+                SAVINGS_GOAL_ID,
+                &[ContributionMissed {
+                    goal_id: 4,
+                    member: owner,
+                    period: 1,
+                    contributed: false,
+                }
+                .data()],
+            ),
+        ),
+    ];
+
+    let mut store = Store::open_in_memory().unwrap();
+    for tx in &history {
+        store.apply(tx).unwrap();
+    }
+    let status = |who: Pubkey, period| {
+        store
+            .periods(&goal)
+            .unwrap()
+            .into_iter()
+            .find(|row| row.member == who.to_string() && row.period == period)
+            .map(|row| row.status)
+    };
+    assert_eq!(status(owner, 0).as_deref(), Some("met"));
+    assert_eq!(status(member, 0).as_deref(), Some("short"));
+    assert_eq!(status(owner, 1).as_deref(), Some("missed"));
+    assert_eq!(status(member, 1), None);
+    let computations = store.computations(&goal).unwrap();
+    assert_eq!(computations.len(), 2);
+    assert!(computations
+        .iter()
+        .all(|row| row.kind == "check_period_contribution" && row.status == "completed"));
+}
+
+#[test]
+fn member_progress_results_complete_their_computation() {
+    let owner = Pubkey::new_unique();
+    let member = Pubkey::new_unique();
+    let goal = pda::goal(&owner, 5);
+    let history = [
+        tx(
+            1,
+            &[owner, goal],
+            invocation(SAVINGS_GOAL_ID, &[goal_created(owner, 5)]),
+        ),
+        // Indexing started after the check was queued
+        tx(
+            2,
+            &[goal],
+            invocation(
+                SAVINGS_MXE_ID,
+                &[MemberProgressEvent {
+                    goal,
+                    computation_offset: 80,
+                    member,
+                }
+                .data()],
+            ),
+        ),
+    ];
+
+    let mut store = Store::open_in_memory().unwrap();
+    for tx in &history {
+        store.apply(tx).unwrap();
+    }
+    let computations = store.computations(&goal).unwrap();
+    assert_eq!(computations.len(), 1);
+    assert_eq!(computations[0].kind, "member_progress");
+    assert_eq!(computations[0].status, "completed");
+    assert_eq!(computations[0].requester, Some(member.to_string()));
+    assert_eq!(computations[0].total, None);
+}
+
+#[test]
+fn group_stats_keep_the_latest_result_per_goal() {
+    let owner = Pubkey::new_unique();
+    let goal = pda::goal(&owner, 6);
+    let stats = |computation_offset, average| GroupStatsEvent {
+        goal,
+        computation_offset,
+        contributors: 3,
+        average,
+        min: 0,
+        max: 900,
+        active: 2,
+    };
+    let history = [
+        tx(
+            1,
+            &[owner, goal],
+            invocation(SAVINGS_GOAL_ID, &[goal_created(owner, 6)]),
+        ),
+        tx(
+            2,
+            &[owner, goal],
+            invocation(
+                SAVINGS_MXE_ID,
+                &[ComputationQueued {
+                    goal,
+                    computation_offset: 90,
+                    kind: Circuit::GroupStats,
+                    requester: owner,
+                }
+                .data()],
+            ),
+        ),
+        tx(
+            3,
+            &[goal],
+            invocation(SAVINGS_MXE_ID, &[stats(90, 400).data()]),
+        ),
+        tx(
+            4,
+            &[goal],
+            invocation(SAVINGS_MXE_ID, &[stats(91, 450).data()]),
+        ),
+    ];
+
+    let mut store = Store::open_in_memory().unwrap();
+    for tx in &history {
+        store.apply(tx).unwrap();
+    }
+    let computations = store.computations(&goal).unwrap();
+    assert_eq!(computations.len(), 2);
+    assert!(computations
+        .iter()
+        .all(|c| c.kind == "group_stats_10" && c.status == "completed"));
+    let latest = store.group_stats(&goal).unwrap().unwrap();
+    assert_eq!(latest.computation_offset, 91);
+    assert_eq!(
+        (
+            latest.contributors,
+            latest.average,
+            latest.min,
+            latest.max,
+            latest.active
+        ),
+        (3, 450, 0, 900, 2)
+    );
+}
+
+#[test]
+fn reveals_complete_their_computations_and_track_opt_ins() {
+    let owner = Pubkey::new_unique();
+    let member = Pubkey::new_unique();
+    let goal = pda::goal(&owner, 8);
+    let revealed = |encryption_key| ContributionRevealed {
+        goal,
+        computation_offset: 100,
+        encryption_key,
+        nonce: 2,
+        encrypted_amount: [9; 32],
+    };
+    let history = [
+        tx(
+            1,
+            &[owner, goal],
+            invocation(SAVINGS_GOAL_ID, &[goal_created(owner, 8)]),
+        ),
+        tx(
+            2,
+            &[member, goal],
+            invocation(
+                SAVINGS_GOAL_ID,
+                &[ContributionAdded {
+                    goal_id: 8,
+                    contributor: member,
+                    timestamp: 2,
+                }
+                .data()],
+            ),
+        ),
+        tx(
+            3,
+            &[member, goal],
+            invocation(
+                SAVINGS_GOAL_ID,
+                &[OwnerRevealChanged {
+                    goal_id: 8,
+                    contributor: member,
+                    allowed: true,
+                }
+                .data()],
+            ),
+        ),
+        // One event per contribution, all for the same computation
+        tx(
+            4,
+            &[goal],
+            invocation(
+                SAVINGS_MXE_ID,
+                &[revealed([1; 32]).data(), revealed([2; 32]).data()],
+            ),
+        ),
+        tx(
+            5,
+            &[goal],
+            invocation(
+                SAVINGS_MXE_ID,
+                &[OwnerRevealEvent {
+                    goal,
+                    computation_offset: 101,
+                    encryption_key: [3; 32],
+                    nonce: 4,
+                    encrypted_amounts: vec![[9; 32]],
+                }
+                .data()],
+            ),
+        ),
+        tx(
+            6,
+            &[goal],
+            invocation(
+                SAVINGS_MXE_ID,
+                &[AuditEvent {
+                    goal,
+                    computation_offset: 102,
+                    contributors: 1,
+                }
+                .data()],
+            ),
+        ),
+    ];
+
+    let mut store = Store::open_in_memory().unwrap();
+    for tx in &history {
+        store.apply(tx).unwrap();
+    }
+    let contributions = store.contributions(&goal).unwrap();
+    assert_eq!(contributions.len(), 1);
+    assert!(contributions[0].owner_reveal);
+    let computations = store.computations(&goal).unwrap();
+    let kinds: Vec<_> = computations
+        .iter()
+        .map(|c| (c.computation_offset, c.kind.as_str(), c.status.as_str()))
+        .collect();
+    assert_eq!(
+        kinds,
+        [
+            (100, "reveal_own_contributions_5", "completed"),
+            (101, "reveal_contributions_10", "completed"),
+            (102, "audit_contributions_10", "completed"),
+        ]
+    );
+}
+
+#[test]
+fn final_total_reveals_are_recorded_on_their_goal() {
+    let owner = Pubkey::new_unique();
+    let reached = pda::goal(&owner, 8);
+    let short = pda::goal(&owner, 9);
+    // finalize_and_reveal_total finalizes through a CPI before queueing
+    let finalize = |goal_id, offset| {
+        let goal = pda::goal(&owner, goal_id);
+        let mut logs = vec![format!("Program {SAVINGS_MXE_ID} invoke [1]")];
+        logs.extend(invocation(
+            SAVINGS_GOAL_ID,
+            &[GoalFinalized {
+                goal_id,
+                finalized_at: 5,
+                goal_reached: false,
+            }
+            .data()],
+        ));
+        logs.push(format!(
+            "Program data: {}",
+            STANDARD.encode(
+                ComputationQueued {
+                    goal,
+                    computation_offset: offset,
+                    kind: Circuit::RevealFinalTotal,
+                    requester: owner,
+                }
+                .data()
+            )
+        ));
+        logs.push(format!("Program {SAVINGS_MXE_ID} success"));
+        logs
+    };
+    // A reached total is recorded on the goal through a CPI from the callback
+    let mut revealed = vec![
+        format!("Program {SAVINGS_MXE_ID} invoke [1]"),
+        format!(
+            "Program data: {}",
+            STANDARD.encode(
+                FinalTotalEvent {
+                    goal: reached,
+                    computation_offset: 80,
+                    total: Some(1_100),
+                }
+                .data()
+            )
+        ),
+    ];
+    revealed.extend(invocation(
+        SAVINGS_GOAL_ID,
+        &[TotalRevealed {
+            goal_id: 8,
+            total: 1_100,
+        }
+        .data()],
+    ));
+    revealed.push(format!("Program {SAVINGS_MXE_ID} success"));
+    let history = [
+        tx(
+            1,
+            &[owner, reached],
+            invocation(SAVINGS_GOAL_ID, &[goal_created(owner, 8)]),
+        ),
+        tx(
+            2,
+            &[owner, short],
+            invocation(SAVINGS_GOAL_ID, &[goal_created(owner, 9)]),
+        ),
+        tx(3, &[owner, reached], finalize(8, 80)),
+        tx(4, &[owner, short], finalize(9, 81)),
+        tx(5, &[reached], revealed),
+        tx(
+            6,
+            &[short],
+            invocation(
+                SAVINGS_MXE_ID,
+                &[FinalTotalEvent {
+                    goal: short,
+                    computation_offset: 81,
+                    total: None,
+                }
+                .data()],
+            ),
+        ),
+    ];
+
+    let mut store = Store::open_in_memory().unwrap();
+    for tx in &history {
+        store.apply(tx).unwrap();
+    }
+    let row = store.goal(&reached).unwrap().unwrap();
+    assert_eq!(row.status, "finalized");
+    assert_eq!(row.revealed_total, Some(1_100));
+    let computations = store.computations(&reached).unwrap();
+    assert_eq!(computations.len(), 1);
+    assert_eq!(computations[0].kind, "reveal_final_total_10");
+    assert_eq!(computations[0].status, "completed");
+    assert_eq!(computations[0].total, Some(1_100));
+
+    let row = store.goal(&short).unwrap().unwrap();
+    assert_eq!(row.status, "finalized");
+    assert_eq!(row.revealed_total, None);
+    let computations = store.computations(&short).unwrap();
+    assert_eq!(computations[0].status, "completed");
+    assert_eq!(computations[0].total, None);
+}
+
+#[test]
+fn vault_checks_flag_and_clear_inconsistent_goals() {
+    let owner = Pubkey::new_unique();
+    let goal = pda::goal(&owner, 10);
+    // savings_goal records the flag from inside the callback's CPI
+    let callback = |offset, vault_balance, balance, inconsistent| {
+        let mut logs = vec![
+            format!("Program {SAVINGS_MXE_ID} invoke [1]"),
+            format!(
+                "Program data: {}",
+                STANDARD.encode(
+                    VaultCheckEvent {
+                        goal,
+                        computation_offset: offset,
+                        vault_balance,
+                        balance,
+                    }
+                    .data()
+                )
+            ),
+        ];
+        logs.extend(invocation(
+            SAVINGS_GOAL_ID,
+            &[VaultChecked {
+                goal_id: 10,
+                inconsistent,
+            }
+            .data()],
+        ));
+        logs.push(format!("Program {SAVINGS_MXE_ID} success"));
+        logs
+    };
+    let mut store = Store::open_in_memory().unwrap();
+    store
+        .apply(&tx(
+            1,
+            &[owner, goal],
+            invocation(SAVINGS_GOAL_ID, &[goal_created(owner, 10)]),
+        ))
+        .unwrap();
+    assert!(!store.goal(&goal).unwrap().unwrap().inconsistent);
+
+    store
+        .apply(&tx(
+            2,
+            &[goal],
+            callback(90, 400, VaultBalance::Shortfall, true),
+        ))
+        .unwrap();
+    assert!(store.goal(&goal).unwrap().unwrap().inconsistent);
+
+    store
+        .apply(&tx(
+            3,
+            &[goal],
+            callback(91, 1_000, VaultBalance::Matches, false),
+        ))
+        .unwrap();
+    assert!(!store.goal(&goal).unwrap().unwrap().inconsistent);
+    let computations = store.computations(&goal).unwrap();
+    assert_eq!(computations.len(), 2);
+    assert!(computations
+        .iter()
+        .all(|c| c.kind == "check_vault_balance_10" && c.status == "completed"));
+}
+
+#[test]
+fn milestones_are_tracked_from_reached_to_released() {
+    let owner = Pubkey::new_unique();
+    let builder = Pubkey::new_unique();
+    let goal = pda::goal(&owner, 11);
+    let mut check = vec![
+        format!("Program {SAVINGS_MXE_ID} invoke [1]"),
+        format!(
+            "Program data: {}",
+            STANDARD.encode(
+                MilestoneCheckEvent {
+                    goal,
+                    computation_offset: 95,
+                    reached: 2,
+                }
+                .data()
+            )
+        ),
+    ];
+    check.extend(invocation(
+        SAVINGS_GOAL_ID,
+        &[
+            MilestoneReached {
+                goal_id: 11,
+                milestone: 0,
+                percent: 25,
+            }
+            .data(),
+            MilestoneReached {
+                goal_id: 11,
+                milestone: 1,
+                percent: 50,
+            }
+            .data(),
+        ],
+    ));
+    check.push(format!("Program {SAVINGS_MXE_ID} success"));
+    let history = [
+        tx(
+            1,
+            &[owner, goal],
+            invocation(SAVINGS_GOAL_ID, &[goal_created(owner, 11)]),
+        ),
+        tx(2, &[goal], check),
+        tx(
+            3,
+            &[owner, goal],
+            invocation(
+                SAVINGS_GOAL_ID,
+                &[MilestoneTransferRequested {
+                    goal_id: 11,
+                    milestone: 0,
+                    recipient: builder,
+                    amount: 250,
+                }
+                .data()],
+            ),
+        ),
+        tx(
+            4,
+            &[owner, goal, builder],
+            invocation(
+                SAVINGS_GOAL_ID,
+                &[MilestoneReleased {
+                    goal_id: 11,
+                    milestone: 0,
+                    recipient: builder,
+                    amount: 250,
+                }
+                .data()],
+            ),
+        ),
+    ];
+    let mut store = Store::open_in_memory().unwrap();
+    for tx in &history {
+        store.apply(tx).unwrap();
+    }
+
+    let milestones = store.milestones(&goal).unwrap();
+    assert_eq!(milestones.len(), 2);
+    assert_eq!(milestones[0].percent, 25);
+    assert_eq!(milestones[0].status, "released");
+    assert_eq!(milestones[0].recipient, Some(builder.to_string()));
+    assert_eq!(milestones[0].amount, Some(250));
+    assert_eq!(milestones[0].signature, "sig4");
+    assert_eq!(milestones[1].status, "reached");
+    assert_eq!(milestones[1].recipient, None);
+    let computations = store.computations(&goal).unwrap();
+    assert_eq!(computations.len(), 1);
+    assert_eq!(computations[0].kind, "check_milestones_10");
+    assert_eq!(computations[0].status, "completed");
+
+    // Replaying changes nothing
+    for tx in &history {
+        assert_eq!(store.apply(tx).unwrap(), 0);
+    }
+}
+
+#[test]
+fn vetoed_transfers_are_marked_until_requested_again() {
+    let owner = Pubkey::new_unique();
+    let member = Pubkey::new_unique();
+    let recipient = Pubkey::new_unique();
+    let goal = pda::goal(&owner, 12);
+    let requested = |slot, amount| {
+        tx(
+            slot,
+            &[owner, goal],
+            invocation(
+                SAVINGS_GOAL_ID,
+                &[TransferRequested {
+                    goal_id: 12,
+                    recipient,
+                    amount,
+                }
+                .data()],
+            ),
+        )
+    };
+    let mut store = Store::open_in_memory().unwrap();
+    store
+        .apply(&tx(
+            1,
+            &[owner, goal],
+            invocation(SAVINGS_GOAL_ID, &[goal_created(owner, 12)]),
+        ))
+        .unwrap();
+    store.apply(&requested(2, 500)).unwrap();
+    store
+        .apply(&tx(
+            3,
+            &[member, goal, owner],
+            invocation(
+                SAVINGS_GOAL_ID,
+                &[TransferVetoed {
+                    goal_id: 12,
+                    milestone: None,
+                    member,
+                    recipient,
+                    amount: 500,
+                }
+                .data()],
+            ),
+        ))
+        .unwrap();
+    let transfer = store.transfer(&goal).unwrap().unwrap();
+    assert_eq!(transfer.status, "vetoed");
+    assert_eq!(transfer.completed_signature, None);
+
+    store.apply(&requested(4, 300)).unwrap();
+    let transfer = store.transfer(&goal).unwrap().unwrap();
+    assert_eq!(transfer.status, "requested");
+    assert_eq!(transfer.amount, 300);
+    assert_eq!(transfer.requested_signature, "sig4");
+}
+
+#[test]
+fn recipients_are_allowed_at_creation_or_by_majority() {
+    let owner = Pubkey::new_unique();
+    let member = Pubkey::new_unique();
+    let builder = Pubkey::new_unique();
+    let supplier = Pubkey::new_unique();
+    let goal = pda::goal(&owner, 13);
+    let mut store = Store::open_in_memory().unwrap();
+    store
+        .apply(&tx(
+            1,
+            &[owner, goal],
+            invocation(
+                SAVINGS_GOAL_ID,
+                &[
+                    goal_created(owner, 13),
+                    RecipientAllowed {
+                        goal_id: 13,
+                        recipient: builder,
+                    }
+                    .data(),
+                ],
+            ),
+        ))
+        .unwrap();
+    store
+        .apply(&tx(
+            2,
+            &[owner, goal],
+            invocation(
+                SAVINGS_GOAL_ID,
+                &[RecipientApproved {
+                    goal_id: 13,
+                    recipient: supplier,
+                    member: owner,
+                    approvals: 1,
+                }
+                .data()],
+            ),
+        ))
+        .unwrap();
+
+    let status = |store: &Store, recipient: Pubkey| {
+        store
+            .recipients(&goal)
+            .unwrap()
+            .into_iter()
+            .find(|row| row.recipient == recipient.to_string())
+            .map(|row| (row.status, row.approvals))
+    };
+    assert_eq!(status(&store, builder), Some(("allowed".into(), 0)));
+    assert_eq!(status(&store, supplier), Some(("proposed".into(), 1)));
+
+    // The approval completing the majority allows the recipient in the same transaction
+    store
+        .apply(&tx(
+            3,
+            &[member, goal],
+            invocation(
+                SAVINGS_GOAL_ID,
+                &[
+                    RecipientApproved {
+                        goal_id: 13,
+                        recipient: supplier,
+                        member,
+                        approvals: 2,
+                    }
+                    .data(),
+                    RecipientAllowed {
+                        goal_id: 13,
+                        recipient: supplier,
+                    }
+                    .data(),
+                ],
+            ),
+        ))
+        .unwrap();
+    assert_eq!(status(&store, supplier), Some(("allowed".into(), 2)));
+}
+
+#[test]
+fn yield_is_tracked_from_deposit_to_claim() {
+    let owner = Pubkey::new_unique();
+    let bob = Pubkey::new_unique();
+    let alice = Pubkey::new_unique();
+    let goal = pda::goal(&owner, 14);
+    let mut store = Store::open_in_memory().unwrap();
+    store
+        .apply(&tx(
+            1,
+            &[owner, goal],
+            invocation(
+                SAVINGS_GOAL_ID,
+                &[
+                    goal_created(owner, 14),
+                    MemberInvited {
+                        goal_id: 14,
+                        member: bob,
+                    }
+                    .data(),
+                    MemberInvited {
+                        goal_id: 14,
+                        member: alice,
+                    }
+                    .data(),
+                    YieldDeposited {
+                        goal_id: 14,
+                        amount: 2_000_000,
+                    }
+                    .data(),
+                ],
+            ),
+        ))
+        .unwrap();
+    let row = store.goal(&goal).unwrap().unwrap();
+    assert_eq!((row.yield_deposited, row.yield_earned), (2_000_000, None));
+
+    store
+        .apply(&tx(
+            2,
+            &[goal],
+            invocation(
+                SAVINGS_GOAL_ID,
+                &[YieldWithdrawn {
+                    goal_id: 14,
+                    deposited: 2_000_000,
+                    earned: 8_000,
+                }
+                .data()],
+            ),
+        ))
+        .unwrap();
+    let row = store.goal(&goal).unwrap().unwrap();
+    assert_eq!((row.yield_deposited, row.yield_earned), (0, Some(8_000)));
+
+    // The shares come in member order
+    store
+        .apply(&tx(
+            3,
+            &[owner, goal],
+            invocation(
+                SAVINGS_MXE_ID,
+                &[YieldDistributionEvent {
+                    goal,
+                    computation_offset: 40,
+                    shares: vec![6_000, 0, 2_000],
+                }
+                .data()],
+            ),
+        ))
+        .unwrap();
+    store
+        .apply(&tx(
+            4,
+            &[alice, goal],
+            invocation(
+                SAVINGS_GOAL_ID,
+                &[YieldClaimed {
+                    goal_id: 14,
+                    member: alice,
+                    amount: 2_000,
+                }
+                .data()],
+            ),
+        ))
+        .unwrap();
+
+    let shares: Vec<_> = store
+        .yield_shares(&goal)
+        .unwrap()
+        .into_iter()
+        .map(|row| (row.member, row.amount, row.status))
+        .collect();
+    assert_eq!(
+        shares,
+        [
+            (owner.to_string(), 6_000, "distributed".to_string()),
+            (bob.to_string(), 0, "distributed".to_string()),
+            (alice.to_string(), 2_000, "claimed".to_string()),
+        ]
+    );
+    let computations = store.computations(&goal).unwrap();
+    assert_eq!(computations[0].kind, Circuit::DistributeYield.name());
+    assert_eq!(computations[0].status, "completed");
+}
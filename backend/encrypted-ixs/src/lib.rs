@@ -1,5 +1,9 @@
  use arcis_imports::*;
 
+pub mod reference;
+
+// Every #[instruction] below has a plaintext twin in `reference`, pinned to
+// its source in tests/reference.rs
 #[encrypted]
 mod circuits {
     use arcis_imports::*;
@@ -8,27 +12,35 @@ mod circuits {
     // Reveal Functions - Decrypt individual contributions after goal completion
     // ========================
 
-    /// Reveal 5 individual encrypted contributions
+    /// Re-encrypt each of 5 contributions to its own contributor, so every
+    /// member gets back their own amount and nobody else's
     #[instruction]
-    pub fn reveal_contributions_5_v4(
+    pub fn reveal_own_contributions_5_v1(
         c1: Enc<Shared, u64>,
         c2: Enc<Shared, u64>,
         c3: Enc<Shared, u64>,
         c4: Enc<Shared, u64>,
         c5: Enc<Shared, u64>,
-    ) -> Enc<Shared, [u64; 5]> {
-        let a1 = c1.to_arcis();
-        let a2 = c2.to_arcis();
-        let a3 = c3.to_arcis();
-        let a4 = c4.to_arcis();
-        let a5 = c5.to_arcis();
-        let results = [a1, a2, a3, a4, a5];
-        c1.owner.from_arcis(results)
+    ) -> (
+        Enc<Shared, u64>,
+        Enc<Shared, u64>,
+        Enc<Shared, u64>,
+        Enc<Shared, u64>,
+        Enc<Shared, u64>,
+    ) {
+        (
+            c1.owner.from_arcis(c1.to_arcis()),
+            c2.owner.from_arcis(c2.to_arcis()),
+            c3.owner.from_arcis(c3.to_arcis()),
+            c4.owner.from_arcis(c4.to_arcis()),
+            c5.owner.from_arcis(c5.to_arcis()),
+        )
     }
 
-    /// Reveal 10 individual encrypted contributions
+    /// Reveal 10 individual encrypted contributions to `owner`. Only queued
+    /// for contributions whose contributors opted in to owner reveals.
     #[instruction]
-    pub fn reveal_contributions_10_v4(
+    pub fn reveal_contributions_10_v5(
         c1: Enc<Shared, u64>,
         c2: Enc<Shared, u64>,
         c3: Enc<Shared, u64>,
@@ -39,6 +51,7 @@ mod circuits {
         c8: Enc<Shared, u64>,
         c9: Enc<Shared, u64>,
         c10: Enc<Shared, u64>,
+        owner: Shared,
     ) -> Enc<Shared, [u64; 10]> {
         let a1 = c1.to_arcis();
         let a2 = c2.to_arcis();
@@ -51,7 +64,322 @@ mod circuits {
         let a9 = c9.to_arcis();
         let a10 = c10.to_arcis();
         let results = [a1, a2, a3, a4, a5, a6, a7, a8, a9, a10];
-        c1.owner.from_arcis(results)
+        owner.from_arcis(results)
+    }
+
+    // ========================
+    // Group Statistics - Aggregates without individual disclosure
+    // ========================
+
+    /// Statistics over the first `count` of 10 contributions, revealing only
+    /// `[average, min, max, active]` where `active` is the number of non-zero
+    /// contributions. Slots from `count` on are padding and ignored; `count`
+    /// is at least 1.
+    #[instruction]
+    pub fn group_stats_10_v1(
+        c1: Enc<Shared, u64>,
+        c2: Enc<Shared, u64>,
+        c3: Enc<Shared, u64>,
+        c4: Enc<Shared, u64>,
+        c5: Enc<Shared, u64>,
+        c6: Enc<Shared, u64>,
+        c7: Enc<Shared, u64>,
+        c8: Enc<Shared, u64>,
+        c9: Enc<Shared, u64>,
+        c10: Enc<Shared, u64>,
+        count: u64,
+    ) -> [u64; 4] {
+        let amounts = [
+            c1.to_arcis(),
+            c2.to_arcis(),
+            c3.to_arcis(),
+            c4.to_arcis(),
+            c5.to_arcis(),
+            c6.to_arcis(),
+            c7.to_arcis(),
+            c8.to_arcis(),
+            c9.to_arcis(),
+            c10.to_arcis(),
+        ];
+        let mut total: u64 = 0;
+        let mut min: u64 = u64::MAX;
+        let mut max: u64 = 0;
+        let mut active: u64 = 0;
+        for i in 0..10 {
+            if (i as u64) < count {
+                let amount = amounts[i];
+                total = total + amount;
+                if amount < min {
+                    min = amount;
+                }
+                if amount > max {
+                    max = amount;
+                }
+                if amount > 0 {
+                    active = active + 1;
+                }
+            }
+        }
+        let average = total / count;
+        [average.reveal(), min.reveal(), max.reveal(), active.reveal()]
+    }
+
+    // ========================
+    // Final Total - Public only for goals that succeeded
+    // ========================
+
+    /// Total of the first `count` of 10 contributions, revealed only if it
+    /// reaches `target`; otherwise the revealed value is zero. Slots from
+    /// `count` on are padding and ignored.
+    #[instruction]
+    pub fn reveal_final_total_10_v1(
+        c1: Enc<Shared, u64>,
+        c2: Enc<Shared, u64>,
+        c3: Enc<Shared, u64>,
+        c4: Enc<Shared, u64>,
+        c5: Enc<Shared, u64>,
+        c6: Enc<Shared, u64>,
+        c7: Enc<Shared, u64>,
+        c8: Enc<Shared, u64>,
+        c9: Enc<Shared, u64>,
+        c10: Enc<Shared, u64>,
+        count: u64,
+        target: u64,
+    ) -> u64 {
+        let amounts = [
+            c1.to_arcis(),
+            c2.to_arcis(),
+            c3.to_arcis(),
+            c4.to_arcis(),
+            c5.to_arcis(),
+            c6.to_arcis(),
+            c7.to_arcis(),
+            c8.to_arcis(),
+            c9.to_arcis(),
+            c10.to_arcis(),
+        ];
+        let mut total: u64 = 0;
+        for i in 0..10 {
+            if (i as u64) < count {
+                total = total + amounts[i];
+            }
+        }
+        let revealed = if total >= target { total } else { 0 };
+        revealed.reveal()
+    }
+
+    // ========================
+    // Vault Balance - Declared contributions against deposits
+    // ========================
+
+    /// Compare the total of the first `count` of 10 contributions with the
+    /// plaintext `vault_balance`. Only the sign of the difference is
+    /// revealed: 0 if they match, 1 if the vault holds more, 2 if it holds
+    /// less than was declared. Slots from `count` on are padding.
+    #[instruction]
+    pub fn check_vault_balance_10_v1(
+        c1: Enc<Shared, u64>,
+        c2: Enc<Shared, u64>,
+        c3: Enc<Shared, u64>,
+        c4: Enc<Shared, u64>,
+        c5: Enc<Shared, u64>,
+        c6: Enc<Shared, u64>,
+        c7: Enc<Shared, u64>,
+        c8: Enc<Shared, u64>,
+        c9: Enc<Shared, u64>,
+        c10: Enc<Shared, u64>,
+        count: u64,
+        vault_balance: u64,
+    ) -> u8 {
+        let amounts = [
+            c1.to_arcis(),
+            c2.to_arcis(),
+            c3.to_arcis(),
+            c4.to_arcis(),
+            c5.to_arcis(),
+            c6.to_arcis(),
+            c7.to_arcis(),
+            c8.to_arcis(),
+            c9.to_arcis(),
+            c10.to_arcis(),
+        ];
+        let mut total: u64 = 0;
+        for i in 0..10 {
+            if (i as u64) < count {
+                total = total + amounts[i];
+            }
+        }
+        let sign: u8 = if vault_balance == total {
+            0
+        } else if vault_balance > total {
+            1
+        } else {
+            2
+        };
+        sign.reveal()
+    }
+
+    // ========================
+    // Milestones - Staged payouts
+    // ========================
+
+    /// How many of the first `milestones` of 4 increasing thresholds the
+    /// total of the first `count` of 10 contributions has reached. Only
+    /// that count is revealed; padding slots and thresholds are ignored.
+    #[instruction]
+    pub fn check_milestones_10_v1(
+        c1: Enc<Shared, u64>,
+        c2: Enc<Shared, u64>,
+        c3: Enc<Shared, u64>,
+        c4: Enc<Shared, u64>,
+        c5: Enc<Shared, u64>,
+        c6: Enc<Shared, u64>,
+        c7: Enc<Shared, u64>,
+        c8: Enc<Shared, u64>,
+        c9: Enc<Shared, u64>,
+        c10: Enc<Shared, u64>,
+        count: u64,
+        milestones: u64,
+        t1: u64,
+        t2: u64,
+        t3: u64,
+        t4: u64,
+    ) -> u8 {
+        let amounts = [
+            c1.to_arcis(),
+            c2.to_arcis(),
+            c3.to_arcis(),
+            c4.to_arcis(),
+            c5.to_arcis(),
+            c6.to_arcis(),
+            c7.to_arcis(),
+            c8.to_arcis(),
+            c9.to_arcis(),
+            c10.to_arcis(),
+        ];
+        let mut total: u64 = 0;
+        for i in 0..10 {
+            if (i as u64) < count {
+                total = total + amounts[i];
+            }
+        }
+        let thresholds = [t1, t2, t3, t4];
+        let mut reached: u8 = 0;
+        for i in 0..4 {
+            if (i as u64) < milestones && total >= thresholds[i] {
+                reached = reached + 1;
+            }
+        }
+        reached.reveal()
+    }
+
+    // ========================
+    // Yield - Proportional shares of what idle funds earned
+    // ========================
+
+    /// Split `yield_amount` over the first `count` of 10 contributions in
+    /// proportion to their amounts, rounding each share down. The shares are
+    /// revealed so they can be paid out; the contributions and their total
+    /// are not. Padding slots get a zero share.
+    #[instruction]
+    pub fn distribute_yield_10_v1(
+        c1: Enc<Shared, u64>,
+        c2: Enc<Shared, u64>,
+        c3: Enc<Shared, u64>,
+        c4: Enc<Shared, u64>,
+        c5: Enc<Shared, u64>,
+        c6: Enc<Shared, u64>,
+        c7: Enc<Shared, u64>,
+        c8: Enc<Shared, u64>,
+        c9: Enc<Shared, u64>,
+        c10: Enc<Shared, u64>,
+        count: u64,
+        yield_amount: u64,
+    ) -> [u64; 10] {
+        let amounts = [
+            c1.to_arcis(),
+            c2.to_arcis(),
+            c3.to_arcis(),
+            c4.to_arcis(),
+            c5.to_arcis(),
+            c6.to_arcis(),
+            c7.to_arcis(),
+            c8.to_arcis(),
+            c9.to_arcis(),
+            c10.to_arcis(),
+        ];
+        // u128 so neither the total nor yield * amount can wrap
+        let mut total: u128 = 0;
+        for i in 0..10 {
+            if (i as u64) < count {
+                total = total + amounts[i] as u128;
+            }
+        }
+        let divisor = if total > 0 { total } else { 1 };
+        let mut shares = [0u64; 10];
+        for i in 0..10 {
+            if (i as u64) < count {
+                shares[i] = ((yield_amount as u128) * (amounts[i] as u128) / divisor) as u64;
+            }
+        }
+        [
+            shares[0].reveal(),
+            shares[1].reveal(),
+            shares[2].reveal(),
+            shares[3].reveal(),
+            shares[4].reveal(),
+            shares[5].reveal(),
+            shares[6].reveal(),
+            shares[7].reveal(),
+            shares[8].reveal(),
+            shares[9].reveal(),
+        ]
+    }
+
+    // ========================
+    // Audit - Everything, to the goal's auditor only
+    // ========================
+
+    /// Re-encrypt the first `count` of 10 contributions and their total to
+    /// `auditor`, as `[amount_1, .., amount_10, total]`. Slots from `count`
+    /// on are padding and come out as zero.
+    #[instruction]
+    pub fn audit_contributions_10_v1(
+        c1: Enc<Shared, u64>,
+        c2: Enc<Shared, u64>,
+        c3: Enc<Shared, u64>,
+        c4: Enc<Shared, u64>,
+        c5: Enc<Shared, u64>,
+        c6: Enc<Shared, u64>,
+        c7: Enc<Shared, u64>,
+        c8: Enc<Shared, u64>,
+        c9: Enc<Shared, u64>,
+        c10: Enc<Shared, u64>,
+        count: u64,
+        auditor: Shared,
+    ) -> Enc<Shared, [u64; 11]> {
+        let amounts = [
+            c1.to_arcis(),
+            c2.to_arcis(),
+            c3.to_arcis(),
+            c4.to_arcis(),
+            c5.to_arcis(),
+            c6.to_arcis(),
+            c7.to_arcis(),
+            c8.to_arcis(),
+            c9.to_arcis(),
+            c10.to_arcis(),
+        ];
+        let mut results = [0u64; 11];
+        let mut total: u64 = 0;
+        for i in 0..10 {
+            if (i as u64) < count {
+                results[i] = amounts[i];
+                total = total + amounts[i];
+            }
+        }
+        results[10] = total;
+        auditor.from_arcis(results)
     }
 
     // ========================
@@ -70,6 +398,72 @@ mod circuits {
         total.reveal()
     }
 
+    /// Add up the first `count` of 10 contributions and compare the total
+    /// against the target, revealing only whether it was reached. Slots from
+    /// `count` on are padding and ignored.
+    #[instruction]
+    pub fn add_and_check_goal_10_v1(
+        c1: Enc<Shared, u64>,
+        c2: Enc<Shared, u64>,
+        c3: Enc<Shared, u64>,
+        c4: Enc<Shared, u64>,
+        c5: Enc<Shared, u64>,
+        c6: Enc<Shared, u64>,
+        c7: Enc<Shared, u64>,
+        c8: Enc<Shared, u64>,
+        c9: Enc<Shared, u64>,
+        c10: Enc<Shared, u64>,
+        count: u64,
+        target: u64,
+    ) -> bool {
+        let amounts = [
+            c1.to_arcis(),
+            c2.to_arcis(),
+            c3.to_arcis(),
+            c4.to_arcis(),
+            c5.to_arcis(),
+            c6.to_arcis(),
+            c7.to_arcis(),
+            c8.to_arcis(),
+            c9.to_arcis(),
+            c10.to_arcis(),
+        ];
+        let mut total: u64 = 0;
+        for i in 0..10 {
+            if (i as u64) < count {
+                total = total + amounts[i];
+            }
+        }
+        let reached = total >= target;
+        reached.reveal()
+    }
+
+    /// Check whether a member's contribution for one period of a schedule
+    /// meets the expected amount, revealing only the outcome
+    #[instruction]
+    pub fn check_period_contribution_v1(
+        amount: Enc<Shared, u64>,
+        expected: u64,
+    ) -> bool {
+        let met = amount.to_arcis() >= expected;
+        met.reveal()
+    }
+
+    /// How far a member's contribution falls short of their personal
+    /// commitment (zero when on track), re-encrypted to `member` alone. The
+    /// commitment is encrypted too, so neither amount is ever in the clear.
+    #[instruction]
+    pub fn member_progress_v2(
+        total: Enc<Shared, u64>,
+        commitment: Enc<Shared, u64>,
+        member: Shared,
+    ) -> Enc<Shared, u64> {
+        let total = total.to_arcis();
+        let commitment = commitment.to_arcis();
+        let shortfall = if total >= commitment { 0 } else { commitment - total };
+        member.from_arcis(shortfall)
+    }
+
     /// Check if aggregate exceeds target (for goal completion check)
     #[instruction]
     pub fn check_goal_reached_v4(
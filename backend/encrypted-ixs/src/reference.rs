@@ -0,0 +1,131 @@
+//! Plaintext twins of the circuits in `circuits`.
+//!
+//! Each function computes what the `#[instruction]` of the same name (minus
+//! its `_vN` suffix) computes, on decrypted values. They are what the mock MXE
+//! in `savings-harness` runs, and `tests/reference.rs` checks that they agree
+//! with each other, e.g. that aggregating then checking gives the same answer
+//! as checking the sum. That test also pins a hash of every circuit's source,
+//! so a circuit cannot change without its twin being revisited.
+
+/// `add_two_contributions`: the revealed sum. Arcis `u64` addition wraps.
+pub fn add_two_contributions(amount1: u64, amount2: u64) -> u64 {
+    amount1.wrapping_add(amount2)
+}
+
+/// `check_goal_reached`: the revealed `current_total >= target`
+pub fn check_goal_reached(current_total: u64, target: u64) -> bool {
+    current_total >= target
+}
+
+/// `add_and_check_goal_10`: whether the wrapping total of `contributions`
+/// reaches `target`; the total itself stays secret
+pub fn add_and_check_goal(contributions: &[u64], target: u64) -> bool {
+    let total = contributions
+        .iter()
+        .fold(0, |total, &amount| add_two_contributions(total, amount));
+    check_goal_reached(total, target)
+}
+
+/// `check_period_contribution`: whether one period's contribution meets the
+/// schedule's expected amount
+pub fn check_period_contribution(amount: u64, expected: u64) -> bool {
+    amount >= expected
+}
+
+/// `member_progress`: what is still missing to reach `commitment`, which
+/// only the member can decrypt
+pub fn member_progress(total: u64, commitment: u64) -> u64 {
+    commitment.saturating_sub(total)
+}
+
+/// `group_stats_10`: `[average, min, max, active]` over `contributions`,
+/// `active` counting the non-zero ones. The average divides the wrapping
+/// sum. `contributions` holds the `count` used slots and is not empty.
+pub fn group_stats(contributions: &[u64]) -> [u64; 4] {
+    let total = contributions
+        .iter()
+        .fold(0, |total, &amount| add_two_contributions(total, amount));
+    let min = contributions.iter().copied().min().unwrap_or(u64::MAX);
+    let max = contributions.iter().copied().max().unwrap_or(0);
+    let active = contributions.iter().filter(|&&amount| amount > 0).count() as u64;
+    [total / contributions.len() as u64, min, max, active]
+}
+
+/// `reveal_contributions_10`: the inputs in
+/// argument order, re-encrypted together to the `owner` key passed in
+pub fn reveal_contributions<const N: usize>(contributions: [u64; N]) -> [u64; N] {
+    contributions
+}
+
+/// `reveal_own_contributions_5`: each input unchanged, but re-encrypted
+/// separately to the key it was encrypted with
+pub fn reveal_own_contributions(contributions: [u64; 5]) -> [u64; 5] {
+    contributions
+}
+
+/// `audit_contributions_10`: the used `contributions` followed by zeroes for
+/// the padding slots, and their wrapping total last, all re-encrypted to the
+/// goal's auditor. `contributions` holds at most 10 amounts.
+pub fn audit_contributions(contributions: &[u64]) -> [u64; 11] {
+    let mut results = [0; 11];
+    results[..contributions.len()].copy_from_slice(contributions);
+    results[10] = contributions
+        .iter()
+        .fold(0, |total, &amount| add_two_contributions(total, amount));
+    results
+}
+
+/// `reveal_final_total_10`: the wrapping total of `contributions` if it
+/// reaches `target`, zero otherwise. With a non-zero target, zero therefore
+/// means the goal fell short and nothing else is learned.
+pub fn reveal_final_total(contributions: &[u64], target: u64) -> u64 {
+    let total = contributions
+        .iter()
+        .fold(0, |total, &amount| add_two_contributions(total, amount));
+    if check_goal_reached(total, target) {
+        total
+    } else {
+        0
+    }
+}
+
+/// `check_vault_balance_10`: how `vault_balance` compares with the wrapping
+/// total of `contributions`; 0 if equal, 1 if the vault holds more, 2 if it
+/// holds less
+pub fn check_vault_balance(contributions: &[u64], vault_balance: u64) -> u8 {
+    let total = contributions
+        .iter()
+        .fold(0, |total, &amount| add_two_contributions(total, amount));
+    match vault_balance.cmp(&total) {
+        std::cmp::Ordering::Equal => 0,
+        std::cmp::Ordering::Greater => 1,
+        std::cmp::Ordering::Less => 2,
+    }
+}
+
+/// `check_milestones_10`: how many of the increasing `thresholds` (at most
+/// 4) the wrapping total of `contributions` reaches
+pub fn check_milestones(contributions: &[u64], thresholds: &[u64]) -> u8 {
+    let total = contributions
+        .iter()
+        .fold(0, |total, &amount| add_two_contributions(total, amount));
+    thresholds
+        .iter()
+        .filter(|&&threshold| check_goal_reached(total, threshold))
+        .count() as u8
+}
+
+/// `distribute_yield_10`: `yield_amount` split in proportion to
+/// `contributions`, each share rounded down. The total does not wrap here;
+/// the circuit sums in `u128`. All shares are zero if nothing was contributed.
+pub fn distribute_yield(contributions: &[u64], yield_amount: u64) -> Vec<u64> {
+    let total: u128 = contributions.iter().map(|&amount| u128::from(amount)).sum();
+    contributions
+        .iter()
+        .map(|&amount| {
+            (u128::from(yield_amount) * u128::from(amount))
+                .checked_div(total)
+                .unwrap_or(0) as u64
+        })
+        .collect()
+}
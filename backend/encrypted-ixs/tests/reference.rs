@@ -0,0 +1,338 @@
+//! Invariants that tie the plaintext circuit twins to each other: every
+//! circuit that aggregates contributions has to agree with the others on the
+//! same wrapping total, including when it overflows at `u64::MAX`.
+
+use encrypted_ixs::reference::{
+    add_and_check_goal, add_two_contributions, audit_contributions, check_goal_reached,
+    check_milestones, check_period_contribution, check_vault_balance, distribute_yield,
+    group_stats, member_progress, reveal_contributions, reveal_final_total,
+    reveal_own_contributions,
+};
+use proptest::prelude::*;
+
+/// The total every aggregating circuit works on, built only from
+/// `add_two_contributions`
+fn pairwise_total(contributions: &[u64]) -> u64 {
+    contributions
+        .iter()
+        .fold(0, |total, &amount| add_two_contributions(total, amount))
+}
+
+/// Up to 10 contributions, from anywhere in the `u64` range or close enough
+/// to `u64::MAX` that their total overflows
+fn contributions() -> impl Strategy<Value = Vec<u64>> {
+    prop_oneof![
+        prop::collection::vec(any::<u64>(), 1..=10),
+        prop::collection::vec(u64::MAX - 1_000..=u64::MAX, 1..=10),
+    ]
+}
+
+/// Every aggregating circuit's view of whether `contributions` reach
+/// `target`. They must all agree.
+fn reached_by_each_circuit(contributions: &[u64], target: u64) -> [bool; 5] {
+    let total = audit_contributions(contributions)[10];
+    [
+        add_and_check_goal(contributions, target),
+        check_goal_reached(total, target),
+        check_milestones(contributions, &[target]) == 1,
+        check_vault_balance(contributions, target) != 1,
+        member_progress(total, target) == 0,
+    ]
+}
+
+proptest! {
+    #[test]
+    fn aggregate_then_check_agrees_with_a_check_on_the_sum(
+        contributions in contributions(),
+        target in any::<u64>(),
+    ) {
+        let reached = check_goal_reached(pairwise_total(&contributions), target);
+        prop_assert_eq!(reached_by_each_circuit(&contributions, target), [reached; 5]);
+        prop_assert_eq!(
+            check_period_contribution(pairwise_total(&contributions), target),
+            reached
+        );
+    }
+
+    #[test]
+    fn aggregating_is_independent_of_grouping_and_order(
+        contributions in contributions(),
+        split in 0usize..=10,
+        target in any::<u64>(),
+    ) {
+        // Aggregating two partial totals is the same as aggregating all of
+        // the contributions at once
+        let (left, right) = contributions.split_at(split.min(contributions.len()));
+        let partials = [pairwise_total(left), pairwise_total(right)];
+        prop_assert_eq!(
+            add_and_check_goal(&partials, target),
+            add_and_check_goal(&contributions, target)
+        );
+        prop_assert_eq!(audit_contributions(&partials)[10], audit_contributions(&contributions)[10]);
+
+        let mut reversed = contributions.clone();
+        reversed.reverse();
+        prop_assert_eq!(group_stats(&reversed), group_stats(&contributions));
+        prop_assert_eq!(
+            reveal_final_total(&reversed, target),
+            reveal_final_total(&contributions, target)
+        );
+    }
+
+    #[test]
+    fn final_total_is_the_audited_total_once_reached(
+        contributions in contributions(),
+        target in 1u64..,
+    ) {
+        let audited = audit_contributions(&contributions);
+        let revealed = reveal_final_total(&contributions, target);
+        if add_and_check_goal(&contributions, target) {
+            prop_assert_eq!(revealed, audited[10]);
+            prop_assert_eq!(check_vault_balance(&contributions, revealed), 0);
+        } else {
+            prop_assert_eq!(revealed, 0);
+        }
+    }
+
+    #[test]
+    fn vault_check_agrees_with_the_audited_total(
+        contributions in contributions(),
+        vault_balance in any::<u64>(),
+    ) {
+        let total = audit_contributions(&contributions)[10];
+        let expected = match vault_balance.cmp(&total) {
+            std::cmp::Ordering::Equal => 0,
+            std::cmp::Ordering::Greater => 1,
+            std::cmp::Ordering::Less => 2,
+        };
+        prop_assert_eq!(check_vault_balance(&contributions, vault_balance), expected);
+        prop_assert_eq!(check_vault_balance(&contributions, total), 0);
+    }
+
+    #[test]
+    fn milestones_count_what_the_goal_check_reaches(
+        contributions in contributions(),
+        thresholds in prop::collection::vec(any::<u64>(), 0..=4),
+    ) {
+        let reached = thresholds
+            .iter()
+            .filter(|&&threshold| add_and_check_goal(&contributions, threshold))
+            .count();
+        prop_assert_eq!(usize::from(check_milestones(&contributions, &thresholds)), reached);
+    }
+
+    #[test]
+    fn group_stats_agree_with_the_audit(contributions in contributions()) {
+        let audited = audit_contributions(&contributions);
+        let used = contributions.len();
+        let [average, min, max, active] = group_stats(&contributions);
+        prop_assert_eq!(average, audited[10] / used as u64);
+        prop_assert!(audited[..used].iter().all(|&amount| min <= amount && amount <= max));
+        prop_assert!(audited[..used].contains(&min) && audited[..used].contains(&max));
+        prop_assert_eq!(
+            active as usize,
+            audited[..used].iter().filter(|&&amount| amount > 0).count()
+        );
+        // Nothing is shared out if no one is active
+        let shares = distribute_yield(&contributions, 1_000);
+        prop_assert_eq!(active == 0, shares.iter().all(|&share| share == 0));
+    }
+
+    #[test]
+    fn reveals_return_what_the_audit_sees(amounts in any::<[u64; 10]>()) {
+        let audited = audit_contributions(&amounts);
+        prop_assert_eq!(&reveal_contributions(amounts)[..], &audited[..10]);
+        let first_five: [u64; 5] = amounts[..5].try_into().unwrap();
+        prop_assert_eq!(&reveal_own_contributions(first_five)[..], &audited[..5]);
+    }
+
+    #[test]
+    fn yield_shares_never_exceed_the_yield(
+        contributions in contributions(),
+        yield_amount in any::<u64>(),
+    ) {
+        let shares = distribute_yield(&contributions, yield_amount);
+        let distributed: u128 = shares.iter().map(|&share| u128::from(share)).sum();
+        prop_assert_eq!(shares.len(), contributions.len());
+        prop_assert!(distributed <= u128::from(yield_amount));
+        if group_stats(&contributions)[3] > 0 {
+            // Each share rounds down by less than one lamport
+            prop_assert!(u128::from(yield_amount) - distributed < contributions.len() as u128);
+            for (i, j) in (0..shares.len()).flat_map(|i| (0..shares.len()).map(move |j| (i, j))) {
+                if contributions[i] >= contributions[j] {
+                    prop_assert!(shares[i] >= shares[j]);
+                }
+            }
+        }
+    }
+}
+
+/// Every `#[instruction]` in `src/lib.rs`, its twin in `reference`, and an
+/// FNV-1a hash of its signature and body with whitespace removed. A circuit
+/// edit fails [`circuits_match_their_pinned_twins`] until the twin has been
+/// brought in line and the hash updated.
+const TWINS: [(&str, &str, u64); 13] = [
+    (
+        "reveal_own_contributions_5_v1",
+        "reveal_own_contributions",
+        0x3509051db518ff07,
+    ),
+    (
+        "reveal_contributions_10_v5",
+        "reveal_contributions",
+        0x1e3cde7ca0c3ca10,
+    ),
+    ("group_stats_10_v1", "group_stats", 0xaa970e682af8bc7a),
+    (
+        "reveal_final_total_10_v1",
+        "reveal_final_total",
+        0xce7abe04795db700,
+    ),
+    (
+        "check_vault_balance_10_v1",
+        "check_vault_balance",
+        0x4d8b9e8bebc66acd,
+    ),
+    (
+        "check_milestones_10_v1",
+        "check_milestones",
+        0x4143be00fea3e2e1,
+    ),
+    (
+        "distribute_yield_10_v1",
+        "distribute_yield",
+        0x5dbfecbd8f24ea3f,
+    ),
+    (
+        "audit_contributions_10_v1",
+        "audit_contributions",
+        0x122d5160fb6989ed,
+    ),
+    (
+        "add_two_contributions_v4",
+        "add_two_contributions",
+        0x677cc332553f78ab,
+    ),
+    (
+        "add_and_check_goal_10_v1",
+        "add_and_check_goal",
+        0x1cf6b9402b2d9af8,
+    ),
+    (
+        "check_period_contribution_v1",
+        "check_period_contribution",
+        0x46e6a1a424b59608,
+    ),
+    ("member_progress_v2", "member_progress", 0xf1b62605fb6bb9a9),
+    (
+        "check_goal_reached_v4",
+        "check_goal_reached",
+        0x6dacf779e552b3be,
+    ),
+];
+
+fn fnv1a(bytes: impl IntoIterator<Item = u8>) -> u64 {
+    bytes.into_iter().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+/// Each `#[instruction]` fn in `source` by name, hashed from `pub fn` to its
+/// closing brace
+fn circuit_hashes(source: &str) -> Vec<(&str, u64)> {
+    source
+        .split("    #[instruction]\n")
+        .skip(1)
+        .map(|circuit| {
+            let circuit = circuit.trim_start();
+            let end = circuit
+                .find("\n    }\n")
+                .expect("circuit has no closing brace");
+            let circuit = &circuit[..end];
+            let name = circuit
+                .strip_prefix("pub fn ")
+                .and_then(|rest| rest.split('(').next())
+                .expect("circuit is not a pub fn");
+            let hash = fnv1a(circuit.bytes().filter(|byte| !byte.is_ascii_whitespace()));
+            (name, hash)
+        })
+        .collect()
+}
+
+#[test]
+fn circuits_match_their_pinned_twins() {
+    let circuits = circuit_hashes(include_str!("../src/lib.rs"));
+    for (name, hash) in &circuits {
+        let (_, twin, pinned) = TWINS
+            .iter()
+            .find(|(circuit, _, _)| circuit == name)
+            .unwrap_or_else(|| panic!("{name} has no twin in TWINS"));
+        assert_eq!(
+            hash, pinned,
+            "{name} changed: update reference::{twin} to match, then its hash in TWINS"
+        );
+    }
+    assert_eq!(
+        circuits.len(),
+        TWINS.len(),
+        "TWINS lists a circuit that no longer exists"
+    );
+}
+
+#[test]
+fn composed_circuits_wrap_together_at_u64_max() {
+    // The total wraps to 0, so the goal is missed everywhere at once
+    let wrapped = [u64::MAX, 1];
+    assert_eq!(reached_by_each_circuit(&wrapped, 1), [false; 5]);
+    assert_eq!(reveal_final_total(&wrapped, 1), 0);
+    assert_eq!(check_vault_balance(&wrapped, 0), 0);
+    assert_eq!(group_stats(&wrapped), [0, 1, u64::MAX, 2]);
+
+    // Exactly u64::MAX still reaches u64::MAX
+    let full = [u64::MAX - 1, 1];
+    assert_eq!(reached_by_each_circuit(&full, u64::MAX), [true; 5]);
+    assert_eq!(reveal_final_total(&full, u64::MAX), u64::MAX);
+    assert_eq!(check_milestones(&full, &[1, u64::MAX]), 2);
+
+    // Ten maximal amounts wrap to u64::MAX - 9
+    let ten = [u64::MAX; 10];
+    assert_eq!(audit_contributions(&ten)[10], u64::MAX - 9);
+    assert_eq!(reached_by_each_circuit(&ten, u64::MAX - 9), [true; 5]);
+    assert_eq!(reached_by_each_circuit(&ten, u64::MAX - 8), [false; 5]);
+    assert_eq!(reveal_final_total(&ten, u64::MAX), 0);
+    assert_eq!(check_vault_balance(&ten, u64::MAX), 1);
+    // The wrapped total drags the average below the minimum
+    assert_eq!(
+        group_stats(&ten),
+        [(u64::MAX - 9) / 10, u64::MAX, u64::MAX, 10]
+    );
+}
+
+#[test]
+fn yield_is_split_without_wrapping_at_u64_max() {
+    // Unlike the other circuits the yield split sums in u128
+    assert_eq!(distribute_yield(&[u64::MAX, 1], 0), [0, 0]);
+    assert_eq!(
+        distribute_yield(&[u64::MAX, u64::MAX], u64::MAX),
+        [u64::MAX / 2, u64::MAX / 2]
+    );
+    assert_eq!(distribute_yield(&[u64::MAX; 10], 10), [1; 10]);
+    assert_eq!(
+        distribute_yield(&[1, u64::MAX], u64::MAX),
+        [0, u64::MAX - 1]
+    );
+    // Even where the wrapping total of the others would be 0
+    assert_eq!(audit_contributions(&[u64::MAX, 1])[10], 0);
+    assert_eq!(distribute_yield(&[u64::MAX, 1], 2), [1, 0]);
+}
+
+#[test]
+fn progress_saturates_at_the_ends_of_the_range() {
+    assert_eq!(member_progress(0, u64::MAX), u64::MAX);
+    assert_eq!(member_progress(u64::MAX, u64::MAX), 0);
+    assert_eq!(member_progress(u64::MAX, 0), 0);
+    // A member whose amounts wrap is behind again
+    let total = pairwise_total(&[u64::MAX, 2]);
+    assert_eq!(member_progress(total, 2), 1);
+    assert!(!check_period_contribution(total, 2));
+}
@@ -2,26 +2,79 @@ use anchor_lang::prelude::*;
 
 declare_id!("7yqGsfLu8hYo4ugmTC43KR8iTY7JxtLFeCoSDoRkMY47");
 
+/// The `savings_mxe` program, whose `goal_authority` PDA may mark goals reached
+pub const SAVINGS_MXE_PROGRAM_ID: Pubkey =
+    anchor_lang::solana_program::pubkey!("4rWRT9mgwWdz9GDpsYeZPZ6arBPCsjG2rquAbLpxGa4i");
+
+/// Seed of the `savings_mxe` PDA that signs `mark_goal_reached`
+pub const GOAL_AUTHORITY_SEED: &[u8] = b"goal_authority";
+
+/// Most milestones a goal can be split into
+pub const MAX_MILESTONES: usize = 4;
+
+/// Most recipients a goal's allow-list can hold
+pub const MAX_ALLOWED_RECIPIENTS: usize = 8;
+
+/// Most members a goal can have
+pub const MAX_MEMBERS: usize = 10;
+
 #[program]
 pub mod savings_goal {
     use super::*;
 
     /// Create a new savings goal
+    #[allow(clippy::too_many_arguments)]
     pub fn create_goal(
         ctx: Context<CreateGoal>,
         goal_id: u64,
         name: String,
         target_amount: u64,
         deadline: Option<i64>,
+        schedule: Option<Schedule>,
+        auditor: Option<[u8; 32]>,
+        milestones: Vec<Milestone>,
+        transfer_policy: Option<TransferPolicy>,
+        allowed_recipients: Vec<Pubkey>,
     ) -> Result<()> {
         require!(name.len() <= 50, ErrorCode::NameTooLong);
         require!(target_amount > 0, ErrorCode::InvalidTargetAmount);
+
+        // Percentages must climb towards the full target
+        require!(
+            milestones.len() <= MAX_MILESTONES
+                && milestones.iter().all(|m| (1..=100).contains(&m.percent))
+                && milestones.windows(2).all(|w| w[0].percent < w[1].percent),
+            ErrorCode::InvalidMilestones
+        );
+
+        if let Some(policy) = &transfer_policy {
+            let period_limit_valid = match policy.max_per_period {
+                Some(limit) => limit > 0 && policy.period > 0,
+                None => true,
+            };
+            require!(
+                policy.max_per_transfer != Some(0) && period_limit_valid && policy.timelock >= 0,
+                ErrorCode::InvalidTransferPolicy
+            );
+        }
+
+        require!(
+            allowed_recipients.len() <= MAX_ALLOWED_RECIPIENTS,
+            ErrorCode::AllowListFull
+        );
         
         if let Some(deadline_ts) = deadline {
             let current_time = Clock::get()?.unix_timestamp;
             require!(deadline_ts > current_time, ErrorCode::InvalidDeadline);
         }
 
+        if let Some(schedule) = &schedule {
+            require!(
+                schedule.interval > 0 && schedule.expected_amount > 0,
+                ErrorCode::InvalidSchedule
+            );
+        }
+
         let goal = &mut ctx.accounts.goal;
         let clock = Clock::get()?;
 
@@ -35,6 +88,24 @@ pub mod savings_goal {
         goal.status = GoalStatus::Active;
         goal.created_at = clock.unix_timestamp;
         goal.finalized_at = None;
+        goal.schedule = schedule;
+        goal.auditor = auditor;
+        goal.revealed_total = None;
+        goal.inconsistent = false;
+        goal.milestones = milestones;
+        goal.milestones_reached = 0;
+        goal.released = 0;
+        goal.transfer_policy = transfer_policy;
+        goal.period_started_at = clock.unix_timestamp;
+        goal.period_spent = 0;
+        goal.allowed_recipients = allowed_recipients;
+        goal.yield_adapter = None;
+        goal.yield_deposited = 0;
+        goal.yield_withdrawing = false;
+        goal.yield_earned = 0;
+        goal.yield_shares = vec![];
+        goal.vault_checked_at = None;
+        goal.deposited = 0;
 
         emit!(GoalCreated {
             goal_id,
@@ -43,6 +114,12 @@ pub mod savings_goal {
             target_amount,
             deadline,
         });
+        for recipient in &goal.allowed_recipients {
+            emit!(RecipientAllowed {
+                goal_id,
+                recipient: *recipient,
+            });
+        }
 
         Ok(())
     }
@@ -59,7 +136,7 @@ pub mod savings_goal {
             ErrorCode::MemberAlreadyExists
         );
 
-        require!(goal.members.len() < 10, ErrorCode::MaxMembersReached);
+        require!(goal.members.len() < MAX_MEMBERS, ErrorCode::MaxMembersReached);
 
         goal.members.push(member_wallet);
 
@@ -71,23 +148,58 @@ pub mod savings_goal {
         Ok(())
     }
 
+    /// Propose adding `recipient` to the goal's allow-list (any member). The
+    /// proposer's approval is counted; the recipient is allowed once a
+    /// majority of the members approved.
+    pub fn propose_recipient(
+        ctx: Context<ProposeRecipient>,
+        recipient: Pubkey,
+    ) -> Result<()> {
+        let proposal = &mut ctx.accounts.proposal;
+        proposal.goal = ctx.accounts.goal.key();
+        proposal.recipient = recipient;
+        proposal.approvals = vec![];
+        proposal.bump = ctx.bumps.proposal;
+
+        approve_recipient_proposal(&mut ctx.accounts.goal, proposal, ctx.accounts.member.key())
+    }
+
+    /// Approve a proposed recipient (any member)
+    pub fn approve_recipient(
+        ctx: Context<ApproveRecipient>,
+        _recipient: Pubkey,
+    ) -> Result<()> {
+        approve_recipient_proposal(
+            &mut ctx.accounts.goal,
+            &mut ctx.accounts.proposal,
+            ctx.accounts.member.key(),
+        )
+    }
+
     /// Add an encrypted contribution
+    ///
+    /// The amount is an `Enc<Shared, u64>` ciphertext; `encryption_pubkey` and
+    /// `nonce` are the x25519 key and nonce it was encrypted under, stored in the
+    /// form the MXE takes them so the contribution can be queued directly.
     pub fn add_contribution(
         ctx: Context<AddContribution>,
         encrypted_amount: [u8; 32],
-        nonce: [u8; 16],
+        encryption_pubkey: [u8; 32],
+        nonce: u128,
     ) -> Result<()> {
         let goal = &mut ctx.accounts.goal;
         
-        require!(goal.status == GoalStatus::Active, ErrorCode::GoalNotActive);
+        require!(goal.is_open(), ErrorCode::GoalNotActive);
         
         // Store encrypted contribution
         let contribution = &mut ctx.accounts.contribution;
         contribution.goal_id = goal.goal_id;
         contribution.contributor = ctx.accounts.contributor.key();
         contribution.encrypted_amount = encrypted_amount;
+        contribution.encryption_pubkey = encryption_pubkey;
         contribution.nonce = nonce;
         contribution.timestamp = Clock::get()?.unix_timestamp;
+        contribution.owner_reveal = false;
 
         emit!(ContributionAdded {
             goal_id: goal.goal_id,
@@ -98,30 +210,203 @@ pub mod savings_goal {
         Ok(())
     }
 
-    /// Finalize goal and reveal contributions (when goal reached OR deadline passed)
+    /// Opt in to (or back out of) revealing this contribution to the goal
+    /// owner through `savings_mxe::reveal_to_owner`. Off by default.
+    pub fn set_owner_reveal(ctx: Context<SetOwnerReveal>, allowed: bool) -> Result<()> {
+        let contribution = &mut ctx.accounts.contribution;
+        contribution.owner_reveal = allowed;
+
+        emit!(OwnerRevealChanged {
+            goal_id: ctx.accounts.goal.goal_id,
+            contributor: contribution.contributor,
+            allowed,
+        });
+        Ok(())
+    }
+
+    /// Pay `amount` into the goal's vault (members only), also after
+    /// finalization to make up a shortfall. Deposits are recorded on the
+    /// goal, and vault checks compare the members' encrypted contributions
+    /// with them rather than with the vault's balance, which anyone can move
+    /// by sending it lamports.
+    pub fn deposit(ctx: Context<Deposit>, amount: u64) -> Result<()> {
+        let goal = &mut ctx.accounts.goal;
+        anchor_lang::system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                anchor_lang::system_program::Transfer {
+                    from: ctx.accounts.member.to_account_info(),
+                    to: ctx.accounts.vault.to_account_info(),
+                },
+            ),
+            amount,
+        )?;
+        goal.deposited = goal.deposited.saturating_add(amount);
+
+        emit!(Deposited {
+            goal_id: goal.goal_id,
+            member: ctx.accounts.member.key(),
+            amount,
+        });
+        Ok(())
+    }
+
+    /// Contribute a member's encrypted amount for the current period of the
+    /// goal's schedule. The amount is checked against the expected amount by
+    /// the MXE (`savings_mxe::check_period_contribution`).
+    pub fn contribute_for_period(
+        ctx: Context<ContributeForPeriod>,
+        period: u32,
+        encrypted_amount: [u8; 32],
+        encryption_pubkey: [u8; 32],
+        nonce: u128,
+    ) -> Result<()> {
+        let goal = &ctx.accounts.goal;
+        let member = ctx.accounts.member.key();
+
+        require!(goal.is_open(), ErrorCode::GoalNotActive);
+        require!(goal.members.contains(&member), ErrorCode::NotAMember);
+        let schedule = goal.schedule.ok_or(ErrorCode::NoSchedule)?;
+        let now = Clock::get()?.unix_timestamp;
+        require!(
+            schedule.period_at(now) == Some(period),
+            ErrorCode::NotCurrentPeriod
+        );
+
+        let record = &mut ctx.accounts.period_contribution;
+        record.goal = goal.key();
+        record.member = member;
+        record.period = period;
+        record.status = PeriodStatus::Contributed;
+        record.encrypted_amount = encrypted_amount;
+        record.encryption_pubkey = encryption_pubkey;
+        record.nonce = nonce;
+        record.bump = ctx.bumps.period_contribution;
+
+        emit!(PeriodContributionAdded {
+            goal_id: goal.goal_id,
+            member,
+            period,
+            timestamp: now,
+        });
+
+        Ok(())
+    }
+
+    /// Crank: close out `period` for a member who did not contribute in it.
+    /// Anyone can call it once the period is over; members who did contribute
+    /// already have a record and are settled by the MXE check instead.
+    pub fn close_period(
+        ctx: Context<ClosePeriod>,
+        period: u32,
+    ) -> Result<()> {
+        let goal = &ctx.accounts.goal;
+        let member = ctx.accounts.member.key();
+
+        require!(goal.members.contains(&member), ErrorCode::NotAMember);
+        let schedule = goal.schedule.ok_or(ErrorCode::NoSchedule)?;
+        require!(
+            Clock::get()?.unix_timestamp >= schedule.period_end(period),
+            ErrorCode::PeriodNotOver
+        );
+
+        let record = &mut ctx.accounts.period_contribution;
+        record.goal = goal.key();
+        record.member = member;
+        record.period = period;
+        record.status = PeriodStatus::Missed;
+        record.bump = ctx.bumps.period_contribution;
+
+        emit!(ContributionMissed {
+            goal_id: goal.goal_id,
+            member,
+            period,
+            contributed: false,
+        });
+
+        Ok(())
+    }
+
+    /// Record the MXE's verdict on a period contribution. Only callable by
+    /// `savings_mxe` through its `goal_authority` PDA.
+    pub fn record_period_check(
+        ctx: Context<RecordPeriodCheck>,
+        met: bool,
+    ) -> Result<()> {
+        let record = &mut ctx.accounts.period_contribution;
+
+        require!(
+            record.status == PeriodStatus::Contributed,
+            ErrorCode::PeriodAlreadySettled
+        );
+
+        if met {
+            record.status = PeriodStatus::Met;
+        } else {
+            record.status = PeriodStatus::Short;
+            emit!(ContributionMissed {
+                goal_id: ctx.accounts.goal.goal_id,
+                member: record.member,
+                period: record.period,
+                contributed: true,
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Mark a goal as reached once the MXE has confirmed its contributions
+    /// meet the target. Only callable by `savings_mxe` through its
+    /// `goal_authority` PDA.
+    pub fn mark_goal_reached(
+        ctx: Context<MarkGoalReached>,
+    ) -> Result<()> {
+        let goal = &mut ctx.accounts.goal;
+
+        require!(goal.status == GoalStatus::Active, ErrorCode::GoalNotActive);
+
+        goal.status = GoalStatus::Reached;
+
+        emit!(GoalReached {
+            goal_id: goal.goal_id,
+            reached_at: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Finalize the goal.
+    /// Anyone may finalize once the deadline has passed or the MXE has
+    /// confirmed the target; finalizing earlier is up to the owner.
+    /// `savings_mxe::finalize_and_reveal_total` calls this and queues the
+    /// reveal of the group total.
     pub fn finalize_and_reveal(
         ctx: Context<FinalizeAndReveal>,
     ) -> Result<()> {
         let goal = &mut ctx.accounts.goal;
         let clock = Clock::get()?;
 
-        let goal_reached = goal.current_total >= goal.target_amount;
+        let goal_reached =
+            goal.status == GoalStatus::Reached || goal.current_total >= goal.target_amount;
         let deadline_passed = if let Some(deadline) = goal.deadline {
             clock.unix_timestamp >= deadline
         } else {
             false
         };
 
-        require!(
-            goal_reached || deadline_passed,
-            ErrorCode::CannotFinalizeYet
-        );
-
         require!(
             goal.status != GoalStatus::Finalized,
             ErrorCode::AlreadyFinalized
         );
 
+        if !(goal_reached || deadline_passed) {
+            require_keys_eq!(
+                ctx.accounts.caller.key(),
+                goal.owner,
+                ErrorCode::CannotFinalizeYet
+            );
+        }
+
         goal.status = GoalStatus::Finalized;
         goal.finalized_at = Some(clock.unix_timestamp);
 
@@ -134,6 +419,177 @@ pub mod savings_goal {
         Ok(())
     }
 
+    /// Record the group total revealed by the MXE for a finalized goal that
+    /// met its target. Only callable by `savings_mxe` through its
+    /// `goal_authority` PDA.
+    pub fn record_revealed_total(
+        ctx: Context<RecordRevealedTotal>,
+        total: u64,
+    ) -> Result<()> {
+        let goal = &mut ctx.accounts.goal;
+
+        require!(
+            goal.status == GoalStatus::Finalized,
+            ErrorCode::GoalNotFinalized
+        );
+        require!(
+            goal.revealed_total.is_none(),
+            ErrorCode::TotalAlreadyRevealed
+        );
+
+        goal.revealed_total = Some(total);
+
+        emit!(TotalRevealed {
+            goal_id: goal.goal_id,
+            total,
+        });
+
+        Ok(())
+    }
+
+    /// Record the outcome of a vault balance check: the goal is flagged
+    /// inconsistent while less was deposited than the members' encrypted
+    /// contributions add up to. `checked_at` is when the deposits were
+    /// read. Only callable by `savings_mxe` through its `goal_authority` PDA.
+    pub fn record_vault_check(
+        ctx: Context<RecordVaultCheck>,
+        inconsistent: bool,
+        checked_at: i64,
+    ) -> Result<()> {
+        let goal = &mut ctx.accounts.goal;
+
+        goal.inconsistent = inconsistent;
+        goal.vault_checked_at = Some(checked_at);
+
+        emit!(VaultChecked {
+            goal_id: goal.goal_id,
+            inconsistent,
+        });
+
+        Ok(())
+    }
+
+    /// Record how many milestones the encrypted total has crossed. Only
+    /// ever moves forward. Only callable by `savings_mxe` through its
+    /// `goal_authority` PDA.
+    pub fn record_milestones(
+        ctx: Context<RecordMilestones>,
+        reached: u8,
+    ) -> Result<()> {
+        let goal = &mut ctx.accounts.goal;
+
+        require!(
+            usize::from(reached) <= goal.milestones.len(),
+            ErrorCode::InvalidMilestones
+        );
+
+        for milestone in goal.milestones_reached..reached {
+            emit!(MilestoneReached {
+                goal_id: goal.goal_id,
+                milestone,
+                percent: goal.milestones[usize::from(milestone)].percent,
+            });
+        }
+        goal.milestones_reached = goal.milestones_reached.max(reached);
+
+        Ok(())
+    }
+
+    /// Request the payout unlocked by a reached milestone, before the goal
+    /// is finalized (owner only). At most the milestone's share of the
+    /// target, to its recipient if it has one; one request per milestone.
+    pub fn request_milestone_transfer(
+        ctx: Context<RequestMilestoneTransfer>,
+        milestone: u8,
+        recipient: Pubkey,
+        amount: u64,
+    ) -> Result<()> {
+        let goal = &ctx.accounts.goal;
+
+        require!(
+            goal.status != GoalStatus::Finalized,
+            ErrorCode::AlreadyFinalized
+        );
+        require!(
+            milestone < goal.milestones_reached,
+            ErrorCode::MilestoneNotReached
+        );
+        if let Some(expected) = goal.milestones[usize::from(milestone)].recipient {
+            require_keys_eq!(recipient, expected, ErrorCode::WrongMilestoneRecipient);
+        }
+        require!(
+            goal.allows_recipient(&recipient),
+            ErrorCode::RecipientNotAllowed
+        );
+        require!(
+            amount <= goal.milestone_ceiling(milestone),
+            ErrorCode::MilestoneCeilingExceeded
+        );
+        require!(
+            goal.within_transfer_limit(amount),
+            ErrorCode::TransferLimitExceeded
+        );
+
+        let transfer_request = &mut ctx.accounts.transfer_request;
+        transfer_request.goal_id = goal.goal_id;
+        transfer_request.recipient = recipient;
+        transfer_request.amount = amount;
+        transfer_request.requested_at = Clock::get()?.unix_timestamp;
+        transfer_request.approved = false;
+
+        emit!(MilestoneTransferRequested {
+            goal_id: goal.goal_id,
+            milestone,
+            recipient,
+            amount,
+        });
+
+        Ok(())
+    }
+
+    /// Approve and execute a milestone payout (owner only)
+    pub fn approve_milestone_transfer(
+        ctx: Context<ApproveMilestoneTransfer>,
+        milestone: u8,
+    ) -> Result<()> {
+        let goal = &mut ctx.accounts.goal;
+        let transfer_request = &mut ctx.accounts.transfer_request;
+        let now = Clock::get()?.unix_timestamp;
+
+        require!(
+            !transfer_request.approved,
+            ErrorCode::TransferAlreadyApproved
+        );
+        require!(
+            now >= goal.unlocks_at(transfer_request.requested_at),
+            ErrorCode::TransferTimelocked
+        );
+
+        // Yield that members have yet to claim stays in the vault
+        require!(goal.yield_deposited == 0, ErrorCode::FundsDeposited);
+        let vault_balance = ctx.accounts.vault.lamports();
+        require!(
+            vault_balance.saturating_sub(goal.unclaimed_yield()) >= transfer_request.amount,
+            ErrorCode::InsufficientVaultBalance
+        );
+        goal.record_spending(transfer_request.amount, now)?;
+
+        **ctx.accounts.vault.try_borrow_mut_lamports()? -= transfer_request.amount;
+        **ctx.accounts.recipient.try_borrow_mut_lamports()? += transfer_request.amount;
+
+        transfer_request.approved = true;
+        goal.released = goal.released.saturating_add(transfer_request.amount);
+
+        emit!(MilestoneReleased {
+            goal_id: goal.goal_id,
+            milestone,
+            recipient: transfer_request.recipient,
+            amount: transfer_request.amount,
+        });
+
+        Ok(())
+    }
+
     /// Request a transfer from the vault (owner only)
     pub fn request_transfer(
         ctx: Context<RequestTransfer>,
@@ -146,6 +602,14 @@ pub mod savings_goal {
             goal.status == GoalStatus::Finalized,
             ErrorCode::GoalNotFinalized
         );
+        require!(
+            goal.within_transfer_limit(amount),
+            ErrorCode::TransferLimitExceeded
+        );
+        require!(
+            goal.allows_recipient(&recipient),
+            ErrorCode::RecipientNotAllowed
+        );
 
         let transfer_request = &mut ctx.accounts.transfer_request;
         transfer_request.goal_id = goal.goal_id;
@@ -163,12 +627,15 @@ pub mod savings_goal {
         Ok(())
     }
 
-    /// Approve and execute transfer (owner only)
+    /// Approve and execute transfer (owner only), once its timelock has
+    /// passed and the goal's total was revealed, within the goal's spending
+    /// limits
     pub fn approve_transfer(
         ctx: Context<ApproveTransfer>,
     ) -> Result<()> {
-        let goal = &ctx.accounts.goal;
+        let goal = &mut ctx.accounts.goal;
         let transfer_request = &mut ctx.accounts.transfer_request;
+        let now = Clock::get()?.unix_timestamp;
 
         require!(
             goal.status == GoalStatus::Finalized,
@@ -179,17 +646,34 @@ pub mod savings_goal {
             !transfer_request.approved,
             ErrorCode::TransferAlreadyApproved
         );
+        require!(
+            now >= goal.unlocks_at(transfer_request.requested_at),
+            ErrorCode::TransferTimelocked
+        );
 
+        // Payouts wait for the MXE to reveal the total and cannot exceed
+        // it, milestone payouts included. A goal finalized short of its
+        // target never gets one revealed.
+        let total = goal.revealed_total.ok_or(ErrorCode::TotalNotRevealed)?;
+        require!(
+            transfer_request.amount <= total.saturating_sub(goal.released),
+            ErrorCode::TransferExceedsRevealedTotal
+        );
+
+        // Yield that members have yet to claim stays in the vault
+        require!(goal.yield_deposited == 0, ErrorCode::FundsDeposited);
         let vault_balance = ctx.accounts.vault.lamports();
         require!(
-            vault_balance >= transfer_request.amount,
+            vault_balance.saturating_sub(goal.unclaimed_yield()) >= transfer_request.amount,
             ErrorCode::InsufficientVaultBalance
         );
+        goal.record_spending(transfer_request.amount, now)?;
 
         **ctx.accounts.vault.try_borrow_mut_lamports()? -= transfer_request.amount;
         **ctx.accounts.recipient.try_borrow_mut_lamports()? += transfer_request.amount;
 
         transfer_request.approved = true;
+        goal.released = goal.released.saturating_add(transfer_request.amount);
 
         emit!(TransferCompleted {
             goal_id: goal.goal_id,
@@ -199,61 +683,601 @@ pub mod savings_goal {
 
         Ok(())
     }
-}
 
-// ============================================================================
-// Account Structs
-// ============================================================================
+    /// Object to a pending transfer while its timelock runs (any member).
+    /// The request is closed, so the owner has to request it again. Pass
+    /// the milestone for a milestone payout, `None` for the goal's final one.
+    /// Goals without a `TransferPolicy::timelock` unlock transfers as soon as
+    /// they are requested, so their transfers can't be vetoed.
+    pub fn veto_transfer(
+        ctx: Context<VetoTransfer>,
+        milestone: Option<u8>,
+    ) -> Result<()> {
+        let goal = &ctx.accounts.goal;
+        let transfer_request = &ctx.accounts.transfer_request;
 
-#[derive(Accounts)]
-#[instruction(goal_id: u64)]
-pub struct CreateGoal<'info> {
-    #[account(mut)]
-    pub owner: Signer<'info>,
-    #[account(
-        init,
-        payer = owner,
-        space = 8 + 8 + 32 + 50 + 8 + 8 + 9 + 320 + 1 + 8 + 9,
-        seeds = [b"goal", owner.key().as_ref(), &goal_id.to_le_bytes()],
-        bump
-    )]
-    pub goal: Account<'info, SavingsGoal>,
-    pub system_program: Program<'info, System>,
-}
+        require!(
+            !transfer_request.approved,
+            ErrorCode::TransferAlreadyApproved
+        );
+        require!(
+            Clock::get()?.unix_timestamp < goal.unlocks_at(transfer_request.requested_at),
+            ErrorCode::VetoPeriodOver
+        );
 
-#[derive(Accounts)]
-pub struct InviteMember<'info> {
-    #[account(mut)]
-    pub owner: Signer<'info>,
-    #[account(
-        mut,
-        constraint = goal.owner == owner.key() @ ErrorCode::Unauthorized
-    )]
-    pub goal: Account<'info, SavingsGoal>,
-    /// CHECK: member wallet
-    pub member_wallet: AccountInfo<'info>,
-}
+        emit!(TransferVetoed {
+            goal_id: goal.goal_id,
+            milestone,
+            member: ctx.accounts.member.key(),
+            recipient: transfer_request.recipient,
+            amount: transfer_request.amount,
+        });
 
-#[derive(Accounts)]
-pub struct AddContribution<'info> {
-    #[account(mut)]
-    pub contributor: Signer<'info>,
-    #[account(mut)]
-    pub goal: Account<'info, SavingsGoal>,
-    #[account(
+        Ok(())
+    }
+
+    /// Choose where the vault's idle funds earn yield, or `None` to stop
+    /// using an adapter (owner only, while nothing is deposited)
+    pub fn set_yield_adapter(
+        ctx: Context<SetYieldAdapter>,
+        adapter: Option<YieldAdapter>,
+    ) -> Result<()> {
+        let goal = &mut ctx.accounts.goal;
+
+        require!(goal.is_open(), ErrorCode::GoalNotActive);
+        require!(goal.yield_deposited == 0, ErrorCode::FundsDeposited);
+
+        goal.yield_adapter = adapter;
+
+        emit!(YieldAdapterChanged {
+            goal_id: goal.goal_id,
+            adapter,
+        });
+
+        Ok(())
+    }
+
+    /// Deposit the vault's balance with the goal's yield adapter
+    /// (owner only, before the goal is finalized). Payouts wait until the
+    /// funds are withdrawn again. For native staking the owner pays the
+    /// stake account's rent, which `withdraw_from_yield` returns.
+    pub fn deposit_to_yield(ctx: Context<DepositToYield>) -> Result<()> {
+        let goal = &mut ctx.accounts.goal;
+
+        require!(goal.is_open(), ErrorCode::GoalNotActive);
+        require!(goal.yield_deposited == 0, ErrorCode::FundsDeposited);
+        let Some(YieldAdapter::NativeStake { vote_account }) = goal.yield_adapter else {
+            return err!(ErrorCode::NoYieldAdapter);
+        };
+        require_keys_eq!(
+            ctx.accounts.vote_account.key(),
+            vote_account,
+            ErrorCode::WrongYieldAccount
+        );
+        // The vault keeps its rent so it stays a program account
+        let amount = ctx
+            .accounts
+            .vault
+            .lamports()
+            .saturating_sub(Rent::get()?.minimum_balance(0));
+        require!(amount > 0, ErrorCode::InsufficientVaultBalance);
+
+        let goal_key = goal.key();
+        native_stake::deposit(
+            &ctx.accounts.owner,
+            &ctx.accounts.vault,
+            &ctx.accounts.stake_account,
+            &ctx.accounts.vote_account,
+            &ctx.accounts.clock.to_account_info(),
+            &ctx.accounts.rent.to_account_info(),
+            &ctx.accounts.stake_history,
+            &ctx.accounts.stake_config,
+            &ctx.accounts.system_program,
+            &[b"vault", goal_key.as_ref(), &[ctx.bumps.vault]],
+            &[b"stake", goal_key.as_ref(), &[ctx.bumps.stake_account]],
+            amount,
+        )?;
+
+        goal.yield_deposited = amount;
+
+        emit!(YieldDeposited {
+            goal_id: goal.goal_id,
+            amount,
+        });
+
+        Ok(())
+    }
+
+    /// Start taking the deposit back out of the yield adapter: the owner at
+    /// any time, anyone once the goal is finalized. Native stake has to cool
+    /// down for an epoch before `withdraw_from_yield` succeeds.
+    pub fn begin_yield_withdrawal(ctx: Context<BeginYieldWithdrawal>) -> Result<()> {
+        let goal = &mut ctx.accounts.goal;
+
+        require!(goal.yield_deposited > 0, ErrorCode::NothingDeposited);
+        require!(!goal.yield_withdrawing, ErrorCode::YieldWithdrawalStarted);
+        if goal.status != GoalStatus::Finalized {
+            require_keys_eq!(
+                ctx.accounts.caller.key(),
+                goal.owner,
+                ErrorCode::Unauthorized
+            );
+        }
+
+        let goal_key = goal.key();
+        native_stake::deactivate(
+            &ctx.accounts.vault,
+            &ctx.accounts.stake_account,
+            &ctx.accounts.clock.to_account_info(),
+            &[b"vault", goal_key.as_ref(), &[ctx.bumps.vault]],
+        )?;
+
+        goal.yield_withdrawing = true;
+
+        emit!(YieldWithdrawalStarted {
+            goal_id: goal.goal_id,
+        });
+
+        Ok(())
+    }
+
+    /// Crank: move the deposit and what it earned back into the vault once
+    /// the adapter releases it. The yield is kept apart for the members
+    /// until `savings_mxe::distribute_yield` splits it.
+    pub fn withdraw_from_yield(ctx: Context<WithdrawFromYield>) -> Result<()> {
+        let goal = &mut ctx.accounts.goal;
+
+        require!(goal.yield_withdrawing, ErrorCode::YieldWithdrawalNotStarted);
+
+        let goal_key = goal.key();
+        let withdrawn = ctx.accounts.stake_account.lamports();
+        native_stake::withdraw(
+            &ctx.accounts.vault,
+            &ctx.accounts.stake_account,
+            &ctx.accounts.clock.to_account_info(),
+            &ctx.accounts.stake_history,
+            &[b"vault", goal_key.as_ref(), &[ctx.bumps.vault]],
+            withdrawn,
+        )?;
+
+        // The stake account's rent goes back to the owner who paid it
+        let reserve = Rent::get()?.minimum_balance(native_stake::SPACE);
+        **ctx.accounts.vault.try_borrow_mut_lamports()? -= reserve;
+        **ctx.accounts.owner.try_borrow_mut_lamports()? += reserve;
+
+        let deposited = goal.yield_deposited;
+        let earned = withdrawn.saturating_sub(reserve).saturating_sub(deposited);
+        goal.yield_earned = goal.yield_earned.saturating_add(earned);
+        goal.yield_deposited = 0;
+        goal.yield_withdrawing = false;
+
+        emit!(YieldWithdrawn {
+            goal_id: goal.goal_id,
+            deposited,
+            earned,
+        });
+
+        Ok(())
+    }
+
+    /// Record each member's share of the goal's yield, in member order, as
+    /// computed by the MXE from their encrypted contributions. Only callable
+    /// by `savings_mxe` through its `goal_authority` PDA.
+    pub fn record_yield_shares(
+        ctx: Context<RecordYieldShares>,
+        shares: Vec<u64>,
+    ) -> Result<()> {
+        let goal = &mut ctx.accounts.goal;
+
+        require!(
+            goal.status == GoalStatus::Finalized,
+            ErrorCode::GoalNotFinalized
+        );
+        require!(goal.yield_shares.is_empty(), ErrorCode::YieldAlreadyDistributed);
+        let total = shares.iter().try_fold(0u64, |total, &share| total.checked_add(share));
+        require!(
+            shares.len() <= goal.members.len()
+                && total.is_some_and(|total| total <= goal.yield_earned),
+            ErrorCode::InvalidYieldShares
+        );
+
+        goal.yield_shares = shares;
+
+        emit!(YieldDistributed {
+            goal_id: goal.goal_id,
+            earned: goal.yield_earned,
+            distributed: total.unwrap_or_default(),
+        });
+
+        Ok(())
+    }
+
+    /// Pay out the signing member's share of the goal's yield
+    pub fn claim_yield(ctx: Context<ClaimYield>) -> Result<()> {
+        let goal = &mut ctx.accounts.goal;
+        let member = ctx.accounts.member.key();
+
+        require!(goal.yield_deposited == 0, ErrorCode::FundsDeposited);
+        let share = goal
+            .members
+            .iter()
+            .position(|m| *m == member)
+            .and_then(|index| goal.yield_shares.get_mut(index))
+            .filter(|share| **share > 0)
+            .ok_or(ErrorCode::NoYieldShare)?;
+        let amount = std::mem::take(share);
+
+        **ctx.accounts.vault.try_borrow_mut_lamports()? -= amount;
+        **ctx.accounts.member.try_borrow_mut_lamports()? += amount;
+
+        emit!(YieldClaimed {
+            goal_id: goal.goal_id,
+            member,
+            amount,
+        });
+
+        Ok(())
+    }
+}
+
+// ============================================================================
+// Account Structs
+// ============================================================================
+
+#[derive(Accounts)]
+#[instruction(goal_id: u64)]
+pub struct CreateGoal<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + 8 + 32 + 50 + 8 + 8 + 9 + 320 + 1 + 8 + 9 + 25 + 33 + 9 + 1
+            + 4 + MAX_MILESTONES * 34 + 1 + 8 + 35 + 8 + 8
+            + 4 + MAX_ALLOWED_RECIPIENTS * 32
+            + 34 + 8 + 1 + 8 + 4 + MAX_MEMBERS * 8 + 9 + 8,
+        seeds = [b"goal", owner.key().as_ref(), &goal_id.to_le_bytes()],
+        bump
+    )]
+    pub goal: Account<'info, SavingsGoal>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct InviteMember<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    #[account(
+        mut,
+        constraint = goal.owner == owner.key() @ ErrorCode::Unauthorized
+    )]
+    pub goal: Account<'info, SavingsGoal>,
+    /// CHECK: member wallet
+    pub member_wallet: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+pub struct AddContribution<'info> {
+    #[account(mut)]
+    pub contributor: Signer<'info>,
+    #[account(mut)]
+    pub goal: Account<'info, SavingsGoal>,
+    #[account(
         init,
         payer = contributor,
-        space = 8 + 8 + 32 + 32 + 16 + 8,
+        space = 8 + 8 + 32 + 32 + 32 + 16 + 8 + 1,
+        seeds = [b"contribution", goal.key().as_ref(), contributor.key().as_ref()],
+        bump
+    )]
+    pub contribution: Account<'info, Contribution>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetOwnerReveal<'info> {
+    pub contributor: Signer<'info>,
+    pub goal: Account<'info, SavingsGoal>,
+    #[account(
+        mut,
         seeds = [b"contribution", goal.key().as_ref(), contributor.key().as_ref()],
         bump
     )]
-    pub contribution: Account<'info, Contribution>,
+    pub contribution: Account<'info, Contribution>,
+}
+
+#[derive(Accounts)]
+pub struct Deposit<'info> {
+    #[account(mut)]
+    pub member: Signer<'info>,
+    #[account(
+        mut,
+        constraint = goal.members.contains(&member.key()) @ ErrorCode::NotAMember
+    )]
+    pub goal: Account<'info, SavingsGoal>,
+    #[account(
+        mut,
+        seeds = [b"vault", goal.key().as_ref()],
+        bump
+    )]
+    /// CHECK: vault PDA
+    pub vault: AccountInfo<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(period: u32)]
+pub struct ContributeForPeriod<'info> {
+    #[account(mut)]
+    pub member: Signer<'info>,
+    pub goal: Account<'info, SavingsGoal>,
+    #[account(
+        init,
+        payer = member,
+        space = 8 + 32 + 32 + 4 + 1 + 32 + 32 + 16 + 1,
+        seeds = [b"period", goal.key().as_ref(), member.key().as_ref(), &period.to_le_bytes()],
+        bump
+    )]
+    pub period_contribution: Account<'info, PeriodContribution>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(period: u32)]
+pub struct ClosePeriod<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub goal: Account<'info, SavingsGoal>,
+    /// CHECK: member wallet, checked against `goal.members`
+    pub member: AccountInfo<'info>,
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + 32 + 32 + 4 + 1 + 32 + 32 + 16 + 1,
+        seeds = [b"period", goal.key().as_ref(), member.key().as_ref(), &period.to_le_bytes()],
+        bump
+    )]
+    pub period_contribution: Account<'info, PeriodContribution>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RecordPeriodCheck<'info> {
+    #[account(
+        seeds = [GOAL_AUTHORITY_SEED],
+        bump,
+        seeds::program = SAVINGS_MXE_PROGRAM_ID
+    )]
+    pub goal_authority: Signer<'info>,
+    pub goal: Account<'info, SavingsGoal>,
+    #[account(
+        mut,
+        seeds = [
+            b"period",
+            goal.key().as_ref(),
+            period_contribution.member.as_ref(),
+            &period_contribution.period.to_le_bytes()
+        ],
+        bump = period_contribution.bump
+    )]
+    pub period_contribution: Account<'info, PeriodContribution>,
+}
+
+#[derive(Accounts)]
+pub struct MarkGoalReached<'info> {
+    #[account(
+        seeds = [GOAL_AUTHORITY_SEED],
+        bump,
+        seeds::program = SAVINGS_MXE_PROGRAM_ID
+    )]
+    pub goal_authority: Signer<'info>,
+    #[account(mut)]
+    pub goal: Account<'info, SavingsGoal>,
+}
+
+#[derive(Accounts)]
+pub struct RecordRevealedTotal<'info> {
+    #[account(
+        seeds = [GOAL_AUTHORITY_SEED],
+        bump,
+        seeds::program = SAVINGS_MXE_PROGRAM_ID
+    )]
+    pub goal_authority: Signer<'info>,
+    #[account(mut)]
+    pub goal: Account<'info, SavingsGoal>,
+}
+
+#[derive(Accounts)]
+pub struct RecordVaultCheck<'info> {
+    #[account(
+        seeds = [GOAL_AUTHORITY_SEED],
+        bump,
+        seeds::program = SAVINGS_MXE_PROGRAM_ID
+    )]
+    pub goal_authority: Signer<'info>,
+    #[account(mut)]
+    pub goal: Account<'info, SavingsGoal>,
+}
+
+#[derive(Accounts)]
+pub struct RecordMilestones<'info> {
+    #[account(
+        seeds = [GOAL_AUTHORITY_SEED],
+        bump,
+        seeds::program = SAVINGS_MXE_PROGRAM_ID
+    )]
+    pub goal_authority: Signer<'info>,
+    #[account(mut)]
+    pub goal: Account<'info, SavingsGoal>,
+}
+
+#[derive(Accounts)]
+pub struct FinalizeAndReveal<'info> {
+    /// The owner, or anyone once the deadline has passed or the goal is reached
+    pub caller: Signer<'info>,
+    #[account(mut)]
+    pub goal: Account<'info, SavingsGoal>,
+}
+
+#[derive(Accounts)]
+pub struct RequestTransfer<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    #[account(
+        constraint = goal.owner == owner.key() @ ErrorCode::Unauthorized
+    )]
+    pub goal: Account<'info, SavingsGoal>,
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + 8 + 32 + 8 + 8 + 1,
+        seeds = [b"transfer", goal.key().as_ref()],
+        bump
+    )]
+    pub transfer_request: Account<'info, TransferRequest>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ApproveTransfer<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    #[account(
+        mut,
+        constraint = goal.owner == owner.key() @ ErrorCode::Unauthorized
+    )]
+    pub goal: Account<'info, SavingsGoal>,
+    #[account(
+        mut,
+        seeds = [b"transfer", goal.key().as_ref()],
+        bump
+    )]
+    pub transfer_request: Account<'info, TransferRequest>,
+    #[account(
+        mut,
+        seeds = [b"vault", goal.key().as_ref()],
+        bump
+    )]
+    /// CHECK: vault PDA
+    pub vault: AccountInfo<'info>,
+    #[account(mut, address = transfer_request.recipient @ ErrorCode::RecipientMismatch)]
+    /// CHECK: recipient
+    pub recipient: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(milestone: u8)]
+pub struct RequestMilestoneTransfer<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    #[account(
+        constraint = goal.owner == owner.key() @ ErrorCode::Unauthorized
+    )]
+    pub goal: Account<'info, SavingsGoal>,
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + 8 + 32 + 8 + 8 + 1,
+        seeds = [b"transfer", goal.key().as_ref(), &[milestone]],
+        bump
+    )]
+    pub transfer_request: Account<'info, TransferRequest>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(milestone: u8)]
+pub struct ApproveMilestoneTransfer<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    #[account(
+        mut,
+        constraint = goal.owner == owner.key() @ ErrorCode::Unauthorized
+    )]
+    pub goal: Account<'info, SavingsGoal>,
+    #[account(
+        mut,
+        seeds = [b"transfer", goal.key().as_ref(), &[milestone]],
+        bump
+    )]
+    pub transfer_request: Account<'info, TransferRequest>,
+    #[account(
+        mut,
+        seeds = [b"vault", goal.key().as_ref()],
+        bump
+    )]
+    /// CHECK: vault PDA
+    pub vault: AccountInfo<'info>,
+    #[account(mut, address = transfer_request.recipient @ ErrorCode::RecipientMismatch)]
+    /// CHECK: recipient
+    pub recipient: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(recipient: Pubkey)]
+pub struct ProposeRecipient<'info> {
+    /// Any goal member
+    #[account(mut)]
+    pub member: Signer<'info>,
+    #[account(
+        mut,
+        constraint = goal.members.contains(&member.key()) @ ErrorCode::NotAMember
+    )]
+    pub goal: Account<'info, SavingsGoal>,
+    #[account(
+        init,
+        payer = member,
+        space = 8 + 32 + 32 + 4 + MAX_MEMBERS * 32 + 1,
+        seeds = [b"recipient", goal.key().as_ref(), recipient.as_ref()],
+        bump
+    )]
+    pub proposal: Account<'info, RecipientProposal>,
     pub system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
-pub struct FinalizeAndReveal<'info> {
-    #[account(mut)]
+#[instruction(recipient: Pubkey)]
+pub struct ApproveRecipient<'info> {
+    /// Any goal member
+    pub member: Signer<'info>,
+    #[account(
+        mut,
+        constraint = goal.members.contains(&member.key()) @ ErrorCode::NotAMember
+    )]
+    pub goal: Account<'info, SavingsGoal>,
+    #[account(
+        mut,
+        seeds = [b"recipient", goal.key().as_ref(), recipient.as_ref()],
+        bump = proposal.bump
+    )]
+    pub proposal: Account<'info, RecipientProposal>,
+}
+
+#[derive(Accounts)]
+#[instruction(milestone: Option<u8>)]
+pub struct VetoTransfer<'info> {
+    /// Any goal member
+    pub member: Signer<'info>,
+    #[account(
+        constraint = goal.members.contains(&member.key()) @ ErrorCode::NotAMember
+    )]
+    pub goal: Account<'info, SavingsGoal>,
+    // The final transfer's seeds have no milestone, which an empty seed
+    // reproduces
+    #[account(
+        mut,
+        close = owner,
+        seeds = [b"transfer", goal.key().as_ref(), milestone.as_slice()],
+        bump
+    )]
+    pub transfer_request: Account<'info, TransferRequest>,
+    /// Gets the request's rent back
+    #[account(mut, address = goal.owner)]
+    /// CHECK: the goal owner
+    pub owner: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetYieldAdapter<'info> {
     pub owner: Signer<'info>,
     #[account(
         mut,
@@ -263,38 +1287,74 @@ pub struct FinalizeAndReveal<'info> {
 }
 
 #[derive(Accounts)]
-pub struct RequestTransfer<'info> {
+pub struct DepositToYield<'info> {
+    /// Pays the stake account's rent
     #[account(mut)]
     pub owner: Signer<'info>,
     #[account(
+        mut,
         constraint = goal.owner == owner.key() @ ErrorCode::Unauthorized
     )]
     pub goal: Account<'info, SavingsGoal>,
     #[account(
-        init,
-        payer = owner,
-        space = 8 + 8 + 32 + 8 + 8 + 1,
-        seeds = [b"transfer", goal.key().as_ref()],
+        mut,
+        seeds = [b"vault", goal.key().as_ref()],
         bump
     )]
-    pub transfer_request: Account<'info, TransferRequest>,
+    /// CHECK: vault PDA, staker and withdrawer of the stake account
+    pub vault: AccountInfo<'info>,
+    #[account(
+        mut,
+        seeds = [b"stake", goal.key().as_ref()],
+        bump
+    )]
+    /// CHECK: stake account PDA, created here
+    pub stake_account: AccountInfo<'info>,
+    /// CHECK: checked against the goal's yield adapter
+    pub vote_account: AccountInfo<'info>,
+    pub clock: Sysvar<'info, Clock>,
+    pub rent: Sysvar<'info, Rent>,
+    #[account(address = anchor_lang::solana_program::sysvar::stake_history::ID)]
+    /// CHECK: stake history sysvar
+    pub stake_history: AccountInfo<'info>,
+    #[account(address = native_stake::CONFIG_ID)]
+    /// CHECK: stake config, still passed to delegations
+    pub stake_config: AccountInfo<'info>,
+    #[account(address = native_stake::PROGRAM_ID)]
+    /// CHECK: native stake program
+    pub stake_program: AccountInfo<'info>,
     pub system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
-pub struct ApproveTransfer<'info> {
+pub struct BeginYieldWithdrawal<'info> {
+    /// The owner, or anyone once the goal is finalized
+    pub caller: Signer<'info>,
     #[account(mut)]
-    pub owner: Signer<'info>,
+    pub goal: Account<'info, SavingsGoal>,
     #[account(
-        constraint = goal.owner == owner.key() @ ErrorCode::Unauthorized
+        seeds = [b"vault", goal.key().as_ref()],
+        bump
     )]
-    pub goal: Account<'info, SavingsGoal>,
+    /// CHECK: vault PDA
+    pub vault: AccountInfo<'info>,
     #[account(
         mut,
-        seeds = [b"transfer", goal.key().as_ref()],
+        seeds = [b"stake", goal.key().as_ref()],
         bump
     )]
-    pub transfer_request: Account<'info, TransferRequest>,
+    /// CHECK: stake account PDA
+    pub stake_account: AccountInfo<'info>,
+    pub clock: Sysvar<'info, Clock>,
+    #[account(address = native_stake::PROGRAM_ID)]
+    /// CHECK: native stake program
+    pub stake_program: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawFromYield<'info> {
+    #[account(mut)]
+    pub goal: Account<'info, SavingsGoal>,
     #[account(
         mut,
         seeds = [b"vault", goal.key().as_ref()],
@@ -302,9 +1362,51 @@ pub struct ApproveTransfer<'info> {
     )]
     /// CHECK: vault PDA
     pub vault: AccountInfo<'info>,
+    #[account(
+        mut,
+        seeds = [b"stake", goal.key().as_ref()],
+        bump
+    )]
+    /// CHECK: stake account PDA
+    pub stake_account: AccountInfo<'info>,
+    /// Gets the stake account's rent back
+    #[account(mut, address = goal.owner)]
+    /// CHECK: the goal owner
+    pub owner: AccountInfo<'info>,
+    pub clock: Sysvar<'info, Clock>,
+    #[account(address = anchor_lang::solana_program::sysvar::stake_history::ID)]
+    /// CHECK: stake history sysvar
+    pub stake_history: AccountInfo<'info>,
+    #[account(address = native_stake::PROGRAM_ID)]
+    /// CHECK: native stake program
+    pub stake_program: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+pub struct RecordYieldShares<'info> {
+    #[account(
+        seeds = [GOAL_AUTHORITY_SEED],
+        bump,
+        seeds::program = SAVINGS_MXE_PROGRAM_ID
+    )]
+    pub goal_authority: Signer<'info>,
     #[account(mut)]
-    /// CHECK: recipient
-    pub recipient: AccountInfo<'info>,
+    pub goal: Account<'info, SavingsGoal>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimYield<'info> {
+    #[account(mut)]
+    pub member: Signer<'info>,
+    #[account(mut)]
+    pub goal: Account<'info, SavingsGoal>,
+    #[account(
+        mut,
+        seeds = [b"vault", goal.key().as_ref()],
+        bump
+    )]
+    /// CHECK: vault PDA
+    pub vault: AccountInfo<'info>,
 }
 
 // ============================================================================
@@ -323,6 +1425,301 @@ pub struct SavingsGoal {
     pub status: GoalStatus,
     pub created_at: i64,
     pub finalized_at: Option<i64>,
+    pub schedule: Option<Schedule>,
+    /// x25519 key of an auditor that `savings_mxe::audit_contributions`
+    /// re-encrypts every contribution to; fixed when the goal is created
+    pub auditor: Option<[u8; 32]>,
+    /// Group total revealed by the MXE after finalization; only set if it
+    /// met the target. Payouts cannot exceed it.
+    pub revealed_total: Option<u64>,
+    /// Whether the last vault balance check found less deposited than the
+    /// members declared in their contributions
+    pub inconsistent: bool,
+    /// Staged payouts, in increasing percent of the target
+    pub milestones: Vec<Milestone>,
+    /// How many milestones the MXE has seen the total cross
+    pub milestones_reached: u8,
+    /// Paid out of the vault so far, milestone transfers included
+    pub released: u64,
+    /// Limits on payouts from the vault; fixed when the goal is created
+    pub transfer_policy: Option<TransferPolicy>,
+    /// Start of the spending period `period_spent` counts
+    pub period_started_at: i64,
+    /// Paid out during the current spending period
+    pub period_spent: u64,
+    /// Recipients transfers may go to; any recipient while empty
+    pub allowed_recipients: Vec<Pubkey>,
+    /// Where idle vault funds earn yield, if anywhere
+    pub yield_adapter: Option<YieldAdapter>,
+    /// Deposited with the yield adapter and not yet withdrawn
+    pub yield_deposited: u64,
+    /// Whether `begin_yield_withdrawal` was called for the deposit
+    pub yield_withdrawing: bool,
+    /// Earned by deposits that were withdrawn back into the vault
+    pub yield_earned: u64,
+    /// Unclaimed yield per member, in member order; empty until the MXE
+    /// has split `yield_earned`
+    pub yield_shares: Vec<u64>,
+    /// When the deposits behind the last vault check were read
+    pub vault_checked_at: Option<i64>,
+    /// Paid into the vault with `deposit` so far
+    pub deposited: u64,
+}
+
+impl SavingsGoal {
+    /// Whether the goal still accepts contributions
+    pub fn is_open(&self) -> bool {
+        matches!(self.status, GoalStatus::Active | GoalStatus::Reached)
+    }
+
+    /// Amount the total has to reach to cross `milestone`
+    pub fn milestone_threshold(&self, milestone: u8) -> u64 {
+        let percent = self.milestones[usize::from(milestone)].percent;
+        (u128::from(self.target_amount) * u128::from(percent) / 100) as u64
+    }
+
+    /// Most that can be paid out for `milestone`: its share of the target
+    /// above the previous milestone
+    pub fn milestone_ceiling(&self, milestone: u8) -> u64 {
+        let previous = match milestone {
+            0 => 0,
+            _ => self.milestone_threshold(milestone - 1),
+        };
+        self.milestone_threshold(milestone) - previous
+    }
+
+    /// Whether transfers may go to `recipient`
+    pub fn allows_recipient(&self, recipient: &Pubkey) -> bool {
+        self.allowed_recipients.is_empty() || self.allowed_recipients.contains(recipient)
+    }
+
+    /// Earliest time a transfer requested at `requested_at` can be approved
+    pub fn unlocks_at(&self, requested_at: i64) -> i64 {
+        match &self.transfer_policy {
+            Some(policy) => requested_at.saturating_add(policy.timelock),
+            None => requested_at,
+        }
+    }
+
+    /// Whether a single transfer of `amount` is allowed
+    pub fn within_transfer_limit(&self, amount: u64) -> bool {
+        match self.transfer_policy.and_then(|policy| policy.max_per_transfer) {
+            Some(max) => amount <= max,
+            None => true,
+        }
+    }
+
+    /// Yield in the vault that belongs to the members rather than the goal
+    pub fn unclaimed_yield(&self) -> u64 {
+        if self.yield_shares.is_empty() {
+            self.yield_earned
+        } else {
+            self.yield_shares.iter().sum()
+        }
+    }
+
+    /// Count a payout of `amount` at `now` against the per-period limit.
+    /// Periods run back to back from the goal's creation.
+    pub fn record_spending(&mut self, amount: u64, now: i64) -> Result<()> {
+        let Some(TransferPolicy {
+            max_per_period: Some(limit),
+            period,
+            ..
+        }) = self.transfer_policy
+        else {
+            return Ok(());
+        };
+
+        let period_start = now - (now - self.created_at).rem_euclid(period);
+        if period_start != self.period_started_at {
+            self.period_started_at = period_start;
+            self.period_spent = 0;
+        }
+        let spent = self.period_spent.saturating_add(amount);
+        require!(spent <= limit, ErrorCode::PeriodLimitExceeded);
+        self.period_spent = spent;
+        Ok(())
+    }
+}
+
+/// Count `member`'s approval of `proposal` and allow its recipient once a
+/// majority of the goal's members approved
+fn approve_recipient_proposal(
+    goal: &mut SavingsGoal,
+    proposal: &mut RecipientProposal,
+    member: Pubkey,
+) -> Result<()> {
+    require!(
+        !goal.allowed_recipients.contains(&proposal.recipient),
+        ErrorCode::RecipientAlreadyAllowed
+    );
+    require!(
+        !proposal.approvals.contains(&member),
+        ErrorCode::RecipientAlreadyApproved
+    );
+
+    proposal.approvals.push(member);
+
+    emit!(RecipientApproved {
+        goal_id: goal.goal_id,
+        recipient: proposal.recipient,
+        member,
+        approvals: proposal.approvals.len() as u8,
+    });
+
+    if proposal.approvals.len() * 2 > goal.members.len() {
+        require!(
+            goal.allowed_recipients.len() < MAX_ALLOWED_RECIPIENTS,
+            ErrorCode::AllowListFull
+        );
+        goal.allowed_recipients.push(proposal.recipient);
+
+        emit!(RecipientAllowed {
+            goal_id: goal.goal_id,
+            recipient: proposal.recipient,
+        });
+    }
+
+    Ok(())
+}
+
+/// Native stake CPIs behind `YieldAdapter::NativeStake`. The stake account
+/// is the goal's `stake` PDA; the vault PDA is its staker and withdrawer.
+// solana-program 2.3 deprecates its stake re-exports in favour of
+// solana-stake-interface, which it re-exports unchanged
+#[allow(deprecated)]
+pub mod native_stake {
+    use anchor_lang::prelude::*;
+    use anchor_lang::solana_program::program::{invoke, invoke_signed};
+    use anchor_lang::solana_program::stake::{self, instruction, state};
+    use anchor_lang::system_program::{create_account, CreateAccount};
+
+    pub const PROGRAM_ID: Pubkey = stake::program::ID;
+    pub const CONFIG_ID: Pubkey = stake::config::ID;
+    pub const SPACE: usize = state::StakeStateV2::size_of();
+
+    /// Create the stake account, move `amount` from the vault into it and
+    /// delegate it to `vote_account`
+    #[allow(clippy::too_many_arguments)]
+    pub fn deposit<'info>(
+        payer: &AccountInfo<'info>,
+        vault: &AccountInfo<'info>,
+        stake_account: &AccountInfo<'info>,
+        vote_account: &AccountInfo<'info>,
+        clock: &AccountInfo<'info>,
+        rent: &AccountInfo<'info>,
+        stake_history: &AccountInfo<'info>,
+        stake_config: &AccountInfo<'info>,
+        system_program: &AccountInfo<'info>,
+        vault_seeds: &[&[u8]],
+        stake_seeds: &[&[u8]],
+        amount: u64,
+    ) -> Result<()> {
+        create_account(
+            CpiContext::new_with_signer(
+                system_program.clone(),
+                CreateAccount {
+                    from: payer.clone(),
+                    to: stake_account.clone(),
+                },
+                &[stake_seeds],
+            ),
+            Rent::get()?.minimum_balance(SPACE),
+            SPACE as u64,
+            &PROGRAM_ID,
+        )?;
+
+        **vault.try_borrow_mut_lamports()? -= amount;
+        **stake_account.try_borrow_mut_lamports()? += amount;
+
+        let authorized = state::Authorized {
+            staker: vault.key(),
+            withdrawer: vault.key(),
+        };
+        invoke(
+            &instruction::initialize(&stake_account.key(), &authorized, &state::Lockup::default()),
+            &[stake_account.clone(), rent.clone()],
+        )?;
+        invoke_signed(
+            &instruction::delegate_stake(&stake_account.key(), &vault.key(), &vote_account.key()),
+            &[
+                stake_account.clone(),
+                vote_account.clone(),
+                clock.clone(),
+                stake_history.clone(),
+                stake_config.clone(),
+                vault.clone(),
+            ],
+            &[vault_seeds],
+        )?;
+        Ok(())
+    }
+
+    pub fn deactivate<'info>(
+        vault: &AccountInfo<'info>,
+        stake_account: &AccountInfo<'info>,
+        clock: &AccountInfo<'info>,
+        vault_seeds: &[&[u8]],
+    ) -> Result<()> {
+        invoke_signed(
+            &instruction::deactivate_stake(&stake_account.key(), &vault.key()),
+            &[stake_account.clone(), clock.clone(), vault.clone()],
+            &[vault_seeds],
+        )?;
+        Ok(())
+    }
+
+    /// Withdraw `lamports` from the deactivated stake account into the vault
+    pub fn withdraw<'info>(
+        vault: &AccountInfo<'info>,
+        stake_account: &AccountInfo<'info>,
+        clock: &AccountInfo<'info>,
+        stake_history: &AccountInfo<'info>,
+        vault_seeds: &[&[u8]],
+        lamports: u64,
+    ) -> Result<()> {
+        invoke_signed(
+            &instruction::withdraw(&stake_account.key(), &vault.key(), &vault.key(), lamports, None),
+            &[
+                stake_account.clone(),
+                vault.clone(),
+                clock.clone(),
+                stake_history.clone(),
+            ],
+            &[vault_seeds],
+        )?;
+        Ok(())
+    }
+}
+
+/// Where a goal's idle vault funds earn yield. New sources are added as
+/// variants.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum YieldAdapter {
+    /// A native stake account delegated to `vote_account`
+    NativeStake { vote_account: Pubkey },
+}
+
+/// Limits on what leaves a goal's vault
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct TransferPolicy {
+    /// Most a single transfer may move
+    pub max_per_transfer: Option<u64>,
+    /// Most that may be paid out within one period
+    pub max_per_period: Option<u64>,
+    /// Period length in seconds; only used with `max_per_period`
+    pub period: i64,
+    /// Seconds between a transfer request and its approval, during which
+    /// members can veto it. With no timelock there is nothing to veto.
+    pub timelock: i64,
+}
+
+/// A staged payout, unlocked once the total crosses `percent` of the target
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Milestone {
+    pub percent: u8,
+    /// If set, the milestone's payout can only go here
+    pub recipient: Option<Pubkey>,
 }
 
 #[account]
@@ -330,8 +1727,65 @@ pub struct Contribution {
     pub goal_id: u64,
     pub contributor: Pubkey,
     pub encrypted_amount: [u8; 32],
-    pub nonce: [u8; 16],
+    pub encryption_pubkey: [u8; 32],
+    pub nonce: u128,
     pub timestamp: i64,
+    /// Whether the contributor agreed to have the amount revealed to the
+    /// goal owner
+    pub owner_reveal: bool,
+}
+
+/// Recurring contribution cadence: period `n` runs from
+/// `start_time + n * interval` until the next one starts
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Schedule {
+    /// Period length in seconds
+    pub interval: i64,
+    /// Amount each member is expected to contribute per period
+    pub expected_amount: u64,
+    pub start_time: i64,
+}
+
+impl Schedule {
+    /// The period `timestamp` falls in, if the schedule has started
+    pub fn period_at(&self, timestamp: i64) -> Option<u32> {
+        if timestamp < self.start_time {
+            return None;
+        }
+        u32::try_from((timestamp - self.start_time) / self.interval).ok()
+    }
+
+    /// When `period` is over and can be closed
+    pub fn period_end(&self, period: u32) -> i64 {
+        self.start_time
+            .saturating_add(self.interval.saturating_mul(i64::from(period) + 1))
+    }
+}
+
+/// A member's contribution status for one period of a goal's schedule
+#[account]
+pub struct PeriodContribution {
+    pub goal: Pubkey,
+    pub member: Pubkey,
+    pub period: u32,
+    pub status: PeriodStatus,
+    /// Zero for periods closed as missed
+    pub encrypted_amount: [u8; 32],
+    pub encryption_pubkey: [u8; 32],
+    pub nonce: u128,
+    pub bump: u8,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum PeriodStatus {
+    /// Contributed, waiting for the MXE to compare it with the expected amount
+    Contributed,
+    /// The MXE confirmed the expected amount was met
+    Met,
+    /// The MXE found the contribution below the expected amount
+    Short,
+    /// Closed by the crank without a contribution
+    Missed,
 }
 
 #[account]
@@ -343,10 +1797,23 @@ pub struct TransferRequest {
     pub approved: bool,
 }
 
+/// A recipient waiting for a majority of the members to allow it
+#[account]
+pub struct RecipientProposal {
+    pub goal: Pubkey,
+    pub recipient: Pubkey,
+    /// Members who approved, the proposer first
+    pub approvals: Vec<Pubkey>,
+    pub bump: u8,
+}
+
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
 pub enum GoalStatus {
     Active,
     Finalized,
+    /// The MXE confirmed the target was met; the goal can be finalized
+    /// before its deadline and still accepts contributions
+    Reached,
 }
 
 // ============================================================================
@@ -375,6 +1842,44 @@ pub struct ContributionAdded {
     pub timestamp: i64,
 }
 
+#[event]
+pub struct Deposited {
+    pub goal_id: u64,
+    pub member: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct OwnerRevealChanged {
+    pub goal_id: u64,
+    pub contributor: Pubkey,
+    pub allowed: bool,
+}
+
+#[event]
+pub struct PeriodContributionAdded {
+    pub goal_id: u64,
+    pub member: Pubkey,
+    pub period: u32,
+    pub timestamp: i64,
+}
+
+/// A member did not meet a period's expected amount: either nothing was
+/// contributed (`contributed == false`) or the MXE found it short
+#[event]
+pub struct ContributionMissed {
+    pub goal_id: u64,
+    pub member: Pubkey,
+    pub period: u32,
+    pub contributed: bool,
+}
+
+#[event]
+pub struct GoalReached {
+    pub goal_id: u64,
+    pub reached_at: i64,
+}
+
 #[event]
 pub struct GoalFinalized {
     pub goal_id: u64,
@@ -382,6 +1887,41 @@ pub struct GoalFinalized {
     pub goal_reached: bool,
 }
 
+#[event]
+pub struct TotalRevealed {
+    pub goal_id: u64,
+    pub total: u64,
+}
+
+#[event]
+pub struct VaultChecked {
+    pub goal_id: u64,
+    pub inconsistent: bool,
+}
+
+#[event]
+pub struct MilestoneReached {
+    pub goal_id: u64,
+    pub milestone: u8,
+    pub percent: u8,
+}
+
+#[event]
+pub struct MilestoneTransferRequested {
+    pub goal_id: u64,
+    pub milestone: u8,
+    pub recipient: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct MilestoneReleased {
+    pub goal_id: u64,
+    pub milestone: u8,
+    pub recipient: Pubkey,
+    pub amount: u64,
+}
+
 #[event]
 pub struct TransferRequested {
     pub goal_id: u64,
@@ -396,6 +1936,71 @@ pub struct TransferCompleted {
     pub amount: u64,
 }
 
+#[event]
+pub struct RecipientApproved {
+    pub goal_id: u64,
+    pub recipient: Pubkey,
+    pub member: Pubkey,
+    /// Approvals so far, this one included
+    pub approvals: u8,
+}
+
+#[event]
+pub struct RecipientAllowed {
+    pub goal_id: u64,
+    pub recipient: Pubkey,
+}
+
+#[event]
+pub struct YieldAdapterChanged {
+    pub goal_id: u64,
+    pub adapter: Option<YieldAdapter>,
+}
+
+#[event]
+pub struct YieldDeposited {
+    pub goal_id: u64,
+    pub amount: u64,
+}
+
+#[event]
+pub struct YieldWithdrawalStarted {
+    pub goal_id: u64,
+}
+
+#[event]
+pub struct YieldWithdrawn {
+    pub goal_id: u64,
+    pub deposited: u64,
+    pub earned: u64,
+}
+
+/// The MXE split the goal's yield; `distributed` is what the members can
+/// claim, short of `earned` by what the shares rounded down
+#[event]
+pub struct YieldDistributed {
+    pub goal_id: u64,
+    pub earned: u64,
+    pub distributed: u64,
+}
+
+#[event]
+pub struct YieldClaimed {
+    pub goal_id: u64,
+    pub member: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct TransferVetoed {
+    pub goal_id: u64,
+    /// Set for a milestone payout
+    pub milestone: Option<u8>,
+    pub member: Pubkey,
+    pub recipient: Pubkey,
+    pub amount: u64,
+}
+
 // ============================================================================
 // Error Codes
 // ============================================================================
@@ -426,4 +2031,68 @@ pub enum ErrorCode {
     TransferAlreadyApproved,
     #[msg("Insufficient vault balance")]
     InsufficientVaultBalance,
+    #[msg("Schedule interval and expected amount must be greater than zero")]
+    InvalidSchedule,
+    #[msg("Goal has no contribution schedule")]
+    NoSchedule,
+    #[msg("Not a member of this goal")]
+    NotAMember,
+    #[msg("Contributions are only accepted for the current period")]
+    NotCurrentPeriod,
+    #[msg("Period is not over yet")]
+    PeriodNotOver,
+    #[msg("Period contribution was already checked")]
+    PeriodAlreadySettled,
+    #[msg("The goal's total was already revealed")]
+    TotalAlreadyRevealed,
+    #[msg("Transfer amount exceeds the revealed goal total")]
+    TransferExceedsRevealedTotal,
+    #[msg("At most 4 milestones, with increasing percentages between 1 and 100")]
+    InvalidMilestones,
+    #[msg("Milestone has not been reached")]
+    MilestoneNotReached,
+    #[msg("Milestone payouts go to the milestone's recipient")]
+    WrongMilestoneRecipient,
+    #[msg("Transfer amount exceeds the milestone's share of the target")]
+    MilestoneCeilingExceeded,
+    #[msg("Transfer limits and the spending period must be positive, the timelock not negative")]
+    InvalidTransferPolicy,
+    #[msg("Transfer amount exceeds the goal's per-transfer limit")]
+    TransferLimitExceeded,
+    #[msg("Transfer would exceed the goal's spending limit for this period")]
+    PeriodLimitExceeded,
+    #[msg("Transfer is still timelocked")]
+    TransferTimelocked,
+    #[msg("Transfer can no longer be vetoed")]
+    VetoPeriodOver,
+    #[msg("Recipient is not on the goal's allow-list")]
+    RecipientNotAllowed,
+    #[msg("Recipient account does not match the transfer request")]
+    RecipientMismatch,
+    #[msg("At most 8 allowed recipients")]
+    AllowListFull,
+    #[msg("Recipient is already allowed")]
+    RecipientAlreadyAllowed,
+    #[msg("Member already approved this recipient")]
+    RecipientAlreadyApproved,
+    #[msg("Vault funds are deposited with the yield adapter")]
+    FundsDeposited,
+    #[msg("Goal has no yield adapter")]
+    NoYieldAdapter,
+    #[msg("Account does not match the goal's yield adapter")]
+    WrongYieldAccount,
+    #[msg("Nothing is deposited with the yield adapter")]
+    NothingDeposited,
+    #[msg("Withdrawal from the yield adapter already started")]
+    YieldWithdrawalStarted,
+    #[msg("Withdrawal from the yield adapter has not started")]
+    YieldWithdrawalNotStarted,
+    #[msg("The goal's yield was already distributed")]
+    YieldAlreadyDistributed,
+    #[msg("Yield shares exceed the members or the yield earned")]
+    InvalidYieldShares,
+    #[msg("No yield to claim for this member")]
+    NoYieldShare,
+    #[msg("The MXE has not revealed the goal's total")]
+    TotalNotRevealed,
 }
@@ -1,16 +1,112 @@
 use anchor_lang::prelude::*;
 use arcium_anchor::prelude::*;
-use anchor_lang::solana_program::pubkey;
+use arcium_client::idl::arcium::cpi::accounts::QueueComputation;
+use arcium_client::idl::arcium::types::{CircuitSource, OffChainCircuitSource};
+use savings_goal::program::SavingsGoal as SavingsGoalProgram;
+use savings_goal::{
+    Contribution, GoalStatus, PeriodContribution, PeriodStatus, SavingsGoal, GOAL_AUTHORITY_SEED,
+    MAX_MILESTONES,
+};
+
+// Circuit versions compiled into this program. The Arcis instruction for a
+// version is named `<Circuit::name()>_v<version>`, see `Circuit::ix_name`.
+// Queue instructions and callbacks are bound to these versions at compile
+// time, so only they can be activated; earlier versions can still be
+// registered, since goals may be pinned to them. Moving a circuit to a new
+// version means bumping its constant and upgrading the program.
+const ADD_TWO_VERSION: u16 = 4;
+const CHECK_GOAL_VERSION: u16 = 4;
+const REVEAL_10_VERSION: u16 = 5;
+const ADD_AND_CHECK_VERSION: u16 = 1;
+const CHECK_PERIOD_VERSION: u16 = 1;
+const MEMBER_PROGRESS_VERSION: u16 = 2;
+const GROUP_STATS_VERSION: u16 = 1;
+const REVEAL_OWN_VERSION: u16 = 1;
+const AUDIT_VERSION: u16 = 1;
+const FINAL_TOTAL_VERSION: u16 = 1;
+const VAULT_CHECK_VERSION: u16 = 1;
+const MILESTONES_VERSION: u16 = 1;
+const YIELD_VERSION: u16 = 1;
 
 // Computation definition offsets for each circuit
 // CRITICAL: Must match EXACT #[instruction] function names in encrypted-ixs/src/lib.rs
 const COMP_DEF_OFFSET_ADD_TWO: u32 = comp_def_offset("add_two_contributions_v4");
 const COMP_DEF_OFFSET_CHECK_GOAL: u32 = comp_def_offset("check_goal_reached_v4");
-const COMP_DEF_OFFSET_REVEAL_5: u32 = comp_def_offset("reveal_contributions_5_v4");
-const COMP_DEF_OFFSET_REVEAL_10: u32 = comp_def_offset("reveal_contributions_10_v4");
+const COMP_DEF_OFFSET_REVEAL_10: u32 = comp_def_offset("reveal_contributions_10_v5");
+const COMP_DEF_OFFSET_ADD_AND_CHECK: u32 = comp_def_offset("add_and_check_goal_10_v1");
+const COMP_DEF_OFFSET_CHECK_PERIOD: u32 = comp_def_offset("check_period_contribution_v1");
+const COMP_DEF_OFFSET_MEMBER_PROGRESS: u32 = comp_def_offset("member_progress_v2");
+const COMP_DEF_OFFSET_GROUP_STATS: u32 = comp_def_offset("group_stats_10_v1");
+const COMP_DEF_OFFSET_REVEAL_OWN: u32 = comp_def_offset("reveal_own_contributions_5_v1");
+const COMP_DEF_OFFSET_AUDIT: u32 = comp_def_offset("audit_contributions_10_v1");
+const COMP_DEF_OFFSET_FINAL_TOTAL: u32 = comp_def_offset("reveal_final_total_10_v1");
+const COMP_DEF_OFFSET_VAULT_CHECK: u32 = comp_def_offset("check_vault_balance_10_v1");
+const COMP_DEF_OFFSET_MILESTONES: u32 = comp_def_offset("check_milestones_10_v1");
+const COMP_DEF_OFFSET_YIELD: u32 = comp_def_offset("distribute_yield_10_v1");
+
+// Maximum number of logical circuits tracked in the config / per-goal pins
+const MAX_CIRCUITS: usize = 32;
+
+// Maximum number of per-comp-def settings entries stored in the config account
+const MAX_COMP_DEF_SETTINGS: usize = 16;
+
+// Maximum length of an off-chain circuit URL
+const MAX_CIRCUIT_URL_LEN: usize = 200;
+
+// Largest of the `ComputationInputs` variants without a sized constant of
+// their own: tag + stored contribution + encrypted commitment and its nonce
+// + member + recipient key and nonce
+const COMPUTATION_INPUTS_SPACE: usize = 1 + (32 + 16 + 32) + (32 + 16) + 32 + (32 + 16);
+
+// Slots of the group_stats circuit, and the fewest contributions it is run
+// over so the aggregates do not give away a single member's amount
+const MAX_STATS_CONTRIBUTIONS: usize = 10;
+const MIN_STATS_CONTRIBUTIONS: usize = 3;
+
+// Group stats records carry all ten slots and get an account sized for them
+const GROUP_STATS_INPUTS_SPACE: usize = 1 + MAX_STATS_CONTRIBUTIONS * (32 + 16 + 32) + 1;
+
+// Slots of the reveal circuits: each contribution back to its contributor,
+// or all of them to the goal owner
+const MAX_OWN_REVEAL_CONTRIBUTIONS: usize = 5;
+const MAX_OWNER_REVEAL_CONTRIBUTIONS: usize = 10;
 
-// MXE authority (wallet that initialized the MXE)
-const MXE_AUTHORITY: Pubkey = pubkey!("HmxiRU21VKdhgmjSWkujqreCaSayCVW1p9EmtHrvfzoT");
+// Reveal records carry every slot, plus the owner's key and nonce
+const REVEAL_OWN_INPUTS_SPACE: usize = 1 + MAX_OWN_REVEAL_CONTRIBUTIONS * (32 + 16 + 32) + 1;
+const REVEAL_TO_OWNER_INPUTS_SPACE: usize =
+    1 + MAX_OWNER_REVEAL_CONTRIBUTIONS * (32 + 16 + 32) + 1 + 32 + 16;
+
+// Slots of the audit circuit. Audit records also keep each slot's
+// contributor, so the callback can label the ciphertexts it stores.
+const MAX_AUDIT_CONTRIBUTIONS: usize = 10;
+const AUDIT_INPUTS_SPACE: usize =
+    1 + MAX_AUDIT_CONTRIBUTIONS * (32 + 16 + 32 + 32) + 1 + 32 + 16;
+
+// Goal checks cover every member's contribution; goals have at most 10
+// members
+const MAX_GOAL_CHECK_CONTRIBUTIONS: usize = 10;
+const GOAL_CHECK_INPUTS_SPACE: usize =
+    1 + MAX_GOAL_CHECK_CONTRIBUTIONS * (32 + 16 + 32) + 1 + 8;
+
+// The final total covers every member's contribution too
+const MAX_FINAL_TOTAL_CONTRIBUTIONS: usize = 10;
+const FINAL_TOTAL_INPUTS_SPACE: usize =
+    1 + MAX_FINAL_TOTAL_CONTRIBUTIONS * (32 + 16 + 32) + 1 + 8;
+
+// Vault checks cover every member's contribution too
+const MAX_VAULT_CHECK_CONTRIBUTIONS: usize = 10;
+const VAULT_CHECK_INPUTS_SPACE: usize =
+    1 + MAX_VAULT_CHECK_CONTRIBUTIONS * (32 + 16 + 32) + 1 + 8 + 8;
+
+// Milestone checks too, against one threshold per milestone
+const MAX_MILESTONE_CHECK_CONTRIBUTIONS: usize = 10;
+const MILESTONES_INPUTS_SPACE: usize =
+    1 + MAX_MILESTONE_CHECK_CONTRIBUTIONS * (32 + 16 + 32) + 1 + 1 + MAX_MILESTONES * 8;
+
+// Yield is split over every member's contribution; the record also keeps
+// which members contributed, to map the shares back onto `goal.members`
+const MAX_YIELD_CONTRIBUTIONS: usize = 10;
+const YIELD_INPUTS_SPACE: usize = 1 + MAX_YIELD_CONTRIBUTIONS * (32 + 16 + 32) + 1 + 2 + 8;
 
 declare_id!("4rWRT9mgwWdz9GDpsYeZPZ6arBPCsjG2rquAbLpxGa4i");
 
@@ -18,28 +114,363 @@ declare_id!("4rWRT9mgwWdz9GDpsYeZPZ6arBPCsjG2rquAbLpxGa4i");
 pub mod savings_mxe {
     use super::*;
 
+    // Program configuration
+
+    /// Create the config, making the program's upgrade authority its admin
+    pub fn initialize_config(ctx: Context<InitializeConfig>, mxe_authority: Pubkey) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+        config.admin = ctx.accounts.admin.key();
+        config.pending_admin = None;
+        config.mxe_authority = mxe_authority;
+        config.comp_defs = Vec::new();
+        config.active_versions = Circuit::compiled_versions();
+        config.bump = ctx.bumps.config;
+
+        emit!(ConfigInitialized {
+            admin: config.admin,
+            mxe_authority,
+        });
+        Ok(())
+    }
+
+    /// First step of admin rotation: the current admin nominates a successor
+    pub fn propose_admin(ctx: Context<UpdateConfig>, new_admin: Pubkey) -> Result<()> {
+        ctx.accounts.config.pending_admin = Some(new_admin);
+        Ok(())
+    }
+
+    /// Second step of admin rotation: the nominated admin accepts
+    pub fn accept_admin(ctx: Context<AcceptAdmin>) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+        let previous_admin = config.admin;
+        config.admin = ctx.accounts.new_admin.key();
+        config.pending_admin = None;
+
+        emit!(AdminRotated {
+            previous_admin,
+            new_admin: config.admin,
+        });
+        Ok(())
+    }
+
+    pub fn set_mxe_authority(ctx: Context<UpdateConfig>, mxe_authority: Pubkey) -> Result<()> {
+        ctx.accounts.config.mxe_authority = mxe_authority;
+        Ok(())
+    }
+
+    /// Insert or replace the settings used when initializing the comp def at `offset`
+    pub fn set_comp_def_settings(
+        ctx: Context<UpdateConfig>,
+        offset: u32,
+        finalize_during_callback: bool,
+        cu_amount: u64,
+    ) -> Result<()> {
+        let settings = CompDefSettings {
+            offset,
+            finalize_during_callback,
+            cu_amount,
+        };
+        let config = &mut ctx.accounts.config;
+        match config.comp_defs.iter_mut().find(|s| s.offset == offset) {
+            Some(existing) => *existing = settings,
+            None => {
+                require!(
+                    config.comp_defs.len() < MAX_COMP_DEF_SETTINGS,
+                    ErrorCode::TooManyCompDefSettings
+                );
+                config.comp_defs.push(settings);
+            }
+        }
+        Ok(())
+    }
+
+    // Circuit version registry
+
+    /// Record that `version` of `circuit` has a comp def. Earlier versions
+    /// than the compiled one can be registered next to it, but only the
+    /// compiled one can run and be activated, see `Circuit::compiled_versions`.
+    pub fn register_circuit_version(
+        ctx: Context<RegisterCircuitVersion>,
+        circuit: Circuit,
+        version: u16,
+    ) -> Result<()> {
+        require!(
+            circuit.is_known(version),
+            ErrorCode::CircuitVersionNotCompiled
+        );
+
+        let registered = &mut ctx.accounts.circuit_version;
+        registered.circuit = circuit;
+        registered.version = version;
+        registered.comp_def_offset = comp_def_offset(&circuit.ix_name(version));
+        registered.registered_at = Clock::get()?.unix_timestamp;
+        registered.bump = ctx.bumps.circuit_version;
+
+        emit!(CircuitVersionRegistered {
+            circuit,
+            version,
+            comp_def_offset: registered.comp_def_offset,
+        });
+        Ok(())
+    }
+
+    /// Select which registered version newly pinned goals will use
+    pub fn set_active_version(
+        ctx: Context<SetActiveVersion>,
+        circuit: Circuit,
+        version: u16,
+    ) -> Result<()> {
+        // A version registered by an earlier build may no longer be compiled in
+        require!(
+            circuit.is_compiled(version),
+            ErrorCode::CircuitVersionNotCompiled
+        );
+        ctx.accounts.config.set_version(circuit, version)
+    }
+
+    /// Pin the currently active circuit versions for a goal. Calling it again
+    /// moves the goal onto whatever versions are active now.
+    pub fn pin_goal_circuits(ctx: Context<PinGoalCircuits>) -> Result<()> {
+        let goal = ctx.accounts.goal.key();
+        let goal_circuits = &mut ctx.accounts.goal_circuits;
+        goal_circuits.goal = goal;
+        goal_circuits.versions = ctx.accounts.config.active_versions.clone();
+        goal_circuits.pinned_at = Clock::get()?.unix_timestamp;
+        goal_circuits.bump = ctx.bumps.goal_circuits;
+
+        emit!(GoalCircuitsPinned {
+            goal,
+            versions: goal_circuits.versions.clone(),
+        });
+        Ok(())
+    }
+
+    /// Return (via return data) the version of `circuit` a goal is pinned to
+    pub fn get_goal_circuit_version(
+        ctx: Context<GetGoalCircuitVersion>,
+        circuit: Circuit,
+    ) -> Result<u16> {
+        ctx.accounts
+            .goal_circuits
+            .version_of(circuit)
+            .ok_or_else(|| ErrorCode::CircuitVersionNotSet.into())
+    }
+
     // Initialize computation definitions
-    pub fn init_add_two_contributions_comp_def(ctx: Context<InitAddTwoCompDef>) -> Result<()> {
-        init_comp_def(ctx.accounts, true, 0, None, Some(MXE_AUTHORITY))?;
+    // Passing `circuit_source` stores only a URL + hash on-chain instead of
+    // uploading the compiled circuit into the comp def account.
+    pub fn init_add_two_contributions_comp_def(
+        ctx: Context<InitAddTwoCompDef>,
+        circuit_source: Option<OffChainSource>,
+    ) -> Result<()> {
+        let settings = ctx.accounts.config.comp_def_settings(COMP_DEF_OFFSET_ADD_TWO);
+        let authority = ctx.accounts.config.mxe_authority;
+        init_comp_def(
+            ctx.accounts,
+            settings.finalize_during_callback,
+            settings.cu_amount,
+            circuit_source_override(circuit_source)?,
+            Some(authority),
+        )?;
+        Ok(())
+    }
+
+    pub fn init_check_goal_reached_comp_def(
+        ctx: Context<InitCheckGoalCompDef>,
+        circuit_source: Option<OffChainSource>,
+    ) -> Result<()> {
+        let settings = ctx.accounts.config.comp_def_settings(COMP_DEF_OFFSET_CHECK_GOAL);
+        let authority = ctx.accounts.config.mxe_authority;
+        init_comp_def(
+            ctx.accounts,
+            settings.finalize_during_callback,
+            settings.cu_amount,
+            circuit_source_override(circuit_source)?,
+            Some(authority),
+        )?;
+        Ok(())
+    }
+
+    pub fn init_reveal_contributions_10_comp_def(
+        ctx: Context<InitReveal10CompDef>,
+        circuit_source: Option<OffChainSource>,
+    ) -> Result<()> {
+        let settings = ctx.accounts.config.comp_def_settings(COMP_DEF_OFFSET_REVEAL_10);
+        let authority = ctx.accounts.config.mxe_authority;
+        init_comp_def(
+            ctx.accounts,
+            settings.finalize_during_callback,
+            settings.cu_amount,
+            circuit_source_override(circuit_source)?,
+            Some(authority),
+        )?;
+        Ok(())
+    }
+
+    pub fn init_add_and_check_goal_comp_def(
+        ctx: Context<InitAddAndCheckGoalCompDef>,
+        circuit_source: Option<OffChainSource>,
+    ) -> Result<()> {
+        let settings = ctx.accounts.config.comp_def_settings(COMP_DEF_OFFSET_ADD_AND_CHECK);
+        let authority = ctx.accounts.config.mxe_authority;
+        init_comp_def(
+            ctx.accounts,
+            settings.finalize_during_callback,
+            settings.cu_amount,
+            circuit_source_override(circuit_source)?,
+            Some(authority),
+        )?;
+        Ok(())
+    }
+
+    pub fn init_check_period_contribution_comp_def(
+        ctx: Context<InitCheckPeriodCompDef>,
+        circuit_source: Option<OffChainSource>,
+    ) -> Result<()> {
+        let settings = ctx.accounts.config.comp_def_settings(COMP_DEF_OFFSET_CHECK_PERIOD);
+        let authority = ctx.accounts.config.mxe_authority;
+        init_comp_def(
+            ctx.accounts,
+            settings.finalize_during_callback,
+            settings.cu_amount,
+            circuit_source_override(circuit_source)?,
+            Some(authority),
+        )?;
+        Ok(())
+    }
+
+    pub fn init_group_stats_comp_def(
+        ctx: Context<InitGroupStatsCompDef>,
+        circuit_source: Option<OffChainSource>,
+    ) -> Result<()> {
+        let settings = ctx.accounts.config.comp_def_settings(COMP_DEF_OFFSET_GROUP_STATS);
+        let authority = ctx.accounts.config.mxe_authority;
+        init_comp_def(
+            ctx.accounts,
+            settings.finalize_during_callback,
+            settings.cu_amount,
+            circuit_source_override(circuit_source)?,
+            Some(authority),
+        )?;
+        Ok(())
+    }
+
+    pub fn init_reveal_own_contributions_comp_def(
+        ctx: Context<InitRevealOwnCompDef>,
+        circuit_source: Option<OffChainSource>,
+    ) -> Result<()> {
+        let settings = ctx.accounts.config.comp_def_settings(COMP_DEF_OFFSET_REVEAL_OWN);
+        let authority = ctx.accounts.config.mxe_authority;
+        init_comp_def(
+            ctx.accounts,
+            settings.finalize_during_callback,
+            settings.cu_amount,
+            circuit_source_override(circuit_source)?,
+            Some(authority),
+        )?;
+        Ok(())
+    }
+
+    pub fn init_audit_contributions_comp_def(
+        ctx: Context<InitAuditCompDef>,
+        circuit_source: Option<OffChainSource>,
+    ) -> Result<()> {
+        let settings = ctx.accounts.config.comp_def_settings(COMP_DEF_OFFSET_AUDIT);
+        let authority = ctx.accounts.config.mxe_authority;
+        init_comp_def(
+            ctx.accounts,
+            settings.finalize_during_callback,
+            settings.cu_amount,
+            circuit_source_override(circuit_source)?,
+            Some(authority),
+        )?;
+        Ok(())
+    }
+
+    pub fn init_reveal_final_total_comp_def(
+        ctx: Context<InitRevealFinalTotalCompDef>,
+        circuit_source: Option<OffChainSource>,
+    ) -> Result<()> {
+        let settings = ctx.accounts.config.comp_def_settings(COMP_DEF_OFFSET_FINAL_TOTAL);
+        let authority = ctx.accounts.config.mxe_authority;
+        init_comp_def(
+            ctx.accounts,
+            settings.finalize_during_callback,
+            settings.cu_amount,
+            circuit_source_override(circuit_source)?,
+            Some(authority),
+        )?;
+        Ok(())
+    }
+
+    pub fn init_check_vault_balance_comp_def(
+        ctx: Context<InitCheckVaultBalanceCompDef>,
+        circuit_source: Option<OffChainSource>,
+    ) -> Result<()> {
+        let settings = ctx.accounts.config.comp_def_settings(COMP_DEF_OFFSET_VAULT_CHECK);
+        let authority = ctx.accounts.config.mxe_authority;
+        init_comp_def(
+            ctx.accounts,
+            settings.finalize_during_callback,
+            settings.cu_amount,
+            circuit_source_override(circuit_source)?,
+            Some(authority),
+        )?;
         Ok(())
     }
 
-    pub fn init_check_goal_reached_comp_def(ctx: Context<InitCheckGoalCompDef>) -> Result<()> {
-        init_comp_def(ctx.accounts, true, 0, None, Some(MXE_AUTHORITY))?;
+    pub fn init_check_milestones_comp_def(
+        ctx: Context<InitCheckMilestonesCompDef>,
+        circuit_source: Option<OffChainSource>,
+    ) -> Result<()> {
+        let settings = ctx.accounts.config.comp_def_settings(COMP_DEF_OFFSET_MILESTONES);
+        let authority = ctx.accounts.config.mxe_authority;
+        init_comp_def(
+            ctx.accounts,
+            settings.finalize_during_callback,
+            settings.cu_amount,
+            circuit_source_override(circuit_source)?,
+            Some(authority),
+        )?;
         Ok(())
     }
 
-    pub fn init_reveal_contributions_5_comp_def(ctx: Context<InitReveal5CompDef>) -> Result<()> {
-        init_comp_def(ctx.accounts, true, 0, None, Some(MXE_AUTHORITY))?;
+    pub fn init_distribute_yield_comp_def(
+        ctx: Context<InitDistributeYieldCompDef>,
+        circuit_source: Option<OffChainSource>,
+    ) -> Result<()> {
+        let settings = ctx.accounts.config.comp_def_settings(COMP_DEF_OFFSET_YIELD);
+        let authority = ctx.accounts.config.mxe_authority;
+        init_comp_def(
+            ctx.accounts,
+            settings.finalize_during_callback,
+            settings.cu_amount,
+            circuit_source_override(circuit_source)?,
+            Some(authority),
+        )?;
         Ok(())
     }
 
-    pub fn init_reveal_contributions_10_comp_def(ctx: Context<InitReveal10CompDef>) -> Result<()> {
-        init_comp_def(ctx.accounts, true, 0, None, Some(MXE_AUTHORITY))?;
+    pub fn init_member_progress_comp_def(
+        ctx: Context<InitMemberProgressCompDef>,
+        circuit_source: Option<OffChainSource>,
+    ) -> Result<()> {
+        let settings = ctx.accounts.config.comp_def_settings(COMP_DEF_OFFSET_MEMBER_PROGRESS);
+        let authority = ctx.accounts.config.mxe_authority;
+        init_comp_def(
+            ctx.accounts,
+            settings.finalize_during_callback,
+            settings.cu_amount,
+            circuit_source_override(circuit_source)?,
+            Some(authority),
+        )?;
         Ok(())
     }
 
     // add_two_contributions - for iterative aggregation
+
+    /// Add two values encrypted under `pub_key` for a goal the payer is a
+    /// member of
     pub fn add_two_contributions(
         ctx: Context<AddTwoContributions>,
         computation_offset: u64,
@@ -48,20 +479,33 @@ pub mod savings_mxe {
         pub_key: [u8; 32],
         nonce: u128,
     ) -> Result<()> {
+        require!(
+            ctx.accounts.goal_circuits.uses(Circuit::AddTwoContributions, ADD_TWO_VERSION),
+            ErrorCode::CircuitVersionMismatch
+        );
         ctx.accounts.sign_pda_account.bump = ctx.bumps.sign_pda_account;
-        let args = vec![
-            Argument::ArcisPubkey(pub_key),
-            Argument::PlaintextU128(nonce),
-            Argument::EncryptedU8(ciphertext_0),
-            Argument::EncryptedU8(ciphertext_1),
-        ];
+        let goal = ctx.accounts.goal.key();
+        let inputs = ComputationInputs::AddTwoContributions {
+            ciphertext_0,
+            ciphertext_1,
+            pub_key,
+            nonce,
+        };
+        let record = record_queued(
+            &mut ctx.accounts.computation_record,
+            ctx.bumps.computation_record,
+            computation_offset,
+            goal,
+            inputs,
+            ctx.accounts.payer.key(),
+        )?;
 
         queue_computation(
             ctx.accounts,
             computation_offset,
-            args,
+            inputs.args(),
             None,
-            vec![AddTwoContributionsV4Callback::callback_ix(&[])],
+            vec![AddTwoContributionsV4Callback::callback_ix(&[record])],
         )?;
         Ok(())
     }
@@ -71,66 +515,2582 @@ pub mod savings_mxe {
         ctx: Context<AddTwoContributionsV4Callback>,
         output: ComputationOutputs<AddTwoContributionsV4Output>,
     ) -> Result<()> {
+        let record = &mut ctx.accounts.computation_record;
         let result = match output {
             ComputationOutputs::Success(AddTwoContributionsV4Output { field_0 }) => field_0,
-            _ => return Err(ErrorCode::AbortedComputation.into()),
+            _ => return record_failed(record, FailureReason::Aborted),
         };
+        record.complete()?;
 
         emit!(AggregationEvent {
+            goal: record.goal,
+            computation_offset: record.computation_offset,
             total: result,
         });
         Ok(())
     }
 
-    // check_goal_reached - for progress checking
-    pub fn check_goal_reached(
-        ctx: Context<CheckGoalReached>,
+    // add_and_check_goal - aggregation that completes the goal when the target is met
+
+    /// Add up every member's stored contribution and compare the total
+    /// against the goal's target in one computation. The `Contribution`
+    /// address of each member is passed as a remaining account, in member
+    /// order like for `finalize_and_reveal_total`. If the target is reached
+    /// the callback marks the goal reached, so it can be finalized right away.
+    pub fn add_and_check_goal<'info>(
+        ctx: Context<'_, '_, 'info, 'info, AddAndCheckGoal<'info>>,
         computation_offset: u64,
-        ciphertext_0: [u8; 32],
-        target: u64,
-        pub_key: [u8; 32],
-        nonce: u128,
     ) -> Result<()> {
+        require!(
+            ctx.accounts.goal_circuits.uses(Circuit::AddAndCheckGoal, ADD_AND_CHECK_VERSION),
+            ErrorCode::CircuitVersionMismatch
+        );
+        require!(
+            ctx.accounts.goal.status == GoalStatus::Active,
+            ErrorCode::GoalNotActive
+        );
         ctx.accounts.sign_pda_account.bump = ctx.bumps.sign_pda_account;
-        let args = vec![
-            Argument::ArcisPubkey(pub_key),
-            Argument::PlaintextU128(nonce),
-            Argument::EncryptedU8(ciphertext_0),
-            Argument::PlaintextU64(target),
-        ];
+        let goal = ctx.accounts.goal.key();
+
+        let contributions: Vec<StoredContributionInput> =
+            member_contributions(&ctx.accounts.goal, ctx.remaining_accounts)?
+                .iter()
+                .map(|contribution| StoredContributionInput::from(&**contribution))
+                .collect();
+        require!(!contributions.is_empty(), ErrorCode::NoContributions);
+
+        let inputs = ComputationInputs::AddAndCheckGoal {
+            contributions: contribution_slots(&contributions),
+            count: contributions.len() as u8,
+            target: ctx.accounts.goal.target_amount,
+        };
+        let record = record_queued(
+            &mut ctx.accounts.computation_record,
+            ctx.bumps.computation_record,
+            computation_offset,
+            goal,
+            inputs,
+            ctx.accounts.payer.key(),
+        )?;
 
         queue_computation(
             ctx.accounts,
             computation_offset,
-            args,
+            inputs.args(),
             None,
-            vec![CheckGoalReachedV4Callback::callback_ix(&[])],
+            vec![AddAndCheckGoal10V1Callback::callback_ix(
+                &goal_callback_accounts(record, goal),
+            )],
         )?;
         Ok(())
     }
 
-    #[arcium_callback(encrypted_ix = "check_goal_reached_v4")]
-    pub fn check_goal_reached_v4_callback(
-        ctx: Context<CheckGoalReachedV4Callback>,
-        output: ComputationOutputs<CheckGoalReachedV4Output>,
+    #[arcium_callback(encrypted_ix = "add_and_check_goal_10_v1")]
+    pub fn add_and_check_goal_10_v1_callback(
+        ctx: Context<AddAndCheckGoal10V1Callback>,
+        output: ComputationOutputs<AddAndCheckGoal10V1Output>,
     ) -> Result<()> {
+        let record = &mut ctx.accounts.computation_record;
         let reached = match output {
-            ComputationOutputs::Success(CheckGoalReachedV4Output { field_0 }) => field_0,
-            _ => return Err(ErrorCode::AbortedComputation.into()),
+            ComputationOutputs::Success(AddAndCheckGoal10V1Output { field_0 }) => field_0,
+            _ => return record_failed(record, FailureReason::Aborted),
         };
+        record.complete()?;
 
         emit!(GoalCheckEvent {
-            reached: reached,
+            goal: record.goal,
+            computation_offset: record.computation_offset,
+            reached,
+        });
+
+        // The goal may have been finalized while the computation ran
+        if reached && ctx.accounts.goal.status == GoalStatus::Active {
+            savings_goal::cpi::mark_goal_reached(CpiContext::new_with_signer(
+                ctx.accounts.savings_goal_program.to_account_info(),
+                savings_goal::cpi::accounts::MarkGoalReached {
+                    goal_authority: ctx.accounts.goal_authority.to_account_info(),
+                    goal: ctx.accounts.goal.to_account_info(),
+                },
+                &[&[GOAL_AUTHORITY_SEED, &[ctx.bumps.goal_authority]]],
+            ))?;
+        }
+        Ok(())
+    }
+
+    // check_period_contribution - recurring schedules
+
+    /// Compare a member's contribution for one period against the schedule's
+    /// expected amount. The callback records the outcome on the
+    /// `PeriodContribution` in `savings_goal`.
+    pub fn check_period_contribution(
+        ctx: Context<CheckPeriodContribution>,
+        computation_offset: u64,
+    ) -> Result<()> {
+        require!(
+            ctx.accounts.goal_circuits.uses(Circuit::CheckPeriodContribution, CHECK_PERIOD_VERSION),
+            ErrorCode::CircuitVersionMismatch
+        );
+        let schedule = ctx.accounts.goal.schedule.ok_or(ErrorCode::NoSchedule)?;
+        let period_contribution = &ctx.accounts.period_contribution;
+        require!(
+            period_contribution.status == PeriodStatus::Contributed,
+            ErrorCode::PeriodNotPending
+        );
+        ctx.accounts.sign_pda_account.bump = ctx.bumps.sign_pda_account;
+        let goal = ctx.accounts.goal.key();
+        let inputs = ComputationInputs::CheckPeriodContribution {
+            contribution: StoredContributionInput::from(&***period_contribution),
+            expected: schedule.expected_amount,
+            member: period_contribution.member,
+            period: period_contribution.period,
+        };
+        let period_contribution = period_contribution.key();
+        let record = record_queued(
+            &mut ctx.accounts.computation_record,
+            ctx.bumps.computation_record,
+            computation_offset,
+            goal,
+            inputs,
+            ctx.accounts.payer.key(),
+        )?;
+
+        queue_computation(
+            ctx.accounts,
+            computation_offset,
+            inputs.args(),
+            None,
+            vec![CheckPeriodContributionV1Callback::callback_ix(
+                &check_period_callback_accounts(record, goal, period_contribution),
+            )],
+        )?;
+        Ok(())
+    }
+
+    #[arcium_callback(encrypted_ix = "check_period_contribution_v1")]
+    pub fn check_period_contribution_v1_callback(
+        ctx: Context<CheckPeriodContributionV1Callback>,
+        output: ComputationOutputs<CheckPeriodContributionV1Output>,
+    ) -> Result<()> {
+        let record = &mut ctx.accounts.computation_record;
+        let met = match output {
+            ComputationOutputs::Success(CheckPeriodContributionV1Output { field_0 }) => field_0,
+            _ => return record_failed(record, FailureReason::Aborted),
+        };
+        record.complete()?;
+
+        let period_contribution = &ctx.accounts.period_contribution;
+        emit!(PeriodCheckEvent {
+            goal: record.goal,
+            computation_offset: record.computation_offset,
+            member: period_contribution.member,
+            period: period_contribution.period,
+            met,
+        });
+
+        // Another check of the same contribution may have settled it first
+        if period_contribution.status == PeriodStatus::Contributed {
+            savings_goal::cpi::record_period_check(
+                CpiContext::new_with_signer(
+                    ctx.accounts.savings_goal_program.to_account_info(),
+                    savings_goal::cpi::accounts::RecordPeriodCheck {
+                        goal_authority: ctx.accounts.goal_authority.to_account_info(),
+                        goal: ctx.accounts.goal.to_account_info(),
+                        period_contribution: ctx.accounts.period_contribution.to_account_info(),
+                    },
+                    &[&[GOAL_AUTHORITY_SEED, &[ctx.bumps.goal_authority]]],
+                ),
+                met,
+            )?;
+        }
+        Ok(())
+    }
+
+    // member_progress - private per-member "am I on track?"
+
+    /// Compare the signer's stored contribution against their personal
+    /// commitment, encrypted like a contribution with `recipient_pubkey` and
+    /// `commitment_nonce`. The shortfall comes back encrypted to
+    /// `recipient_pubkey`, the member's own x25519 key, and is kept on their
+    /// `MemberProgress`; the commitment itself is never stored. Only the
+    /// member can queue this, since the caller picks the key.
+    pub fn check_member_progress(
+        ctx: Context<CheckMemberProgress>,
+        computation_offset: u64,
+        commitment: [u8; 32],
+        commitment_nonce: u128,
+        recipient_pubkey: [u8; 32],
+        recipient_nonce: u128,
+    ) -> Result<()> {
+        require!(
+            ctx.accounts.goal_circuits.uses(Circuit::MemberProgress, MEMBER_PROGRESS_VERSION),
+            ErrorCode::CircuitVersionMismatch
+        );
+        ctx.accounts.sign_pda_account.bump = ctx.bumps.sign_pda_account;
+        let goal = ctx.accounts.goal.key();
+        let member = ctx.accounts.payer.key();
+
+        let progress = &mut ctx.accounts.member_progress;
+        progress.goal = goal;
+        progress.member = member;
+        progress.bump = ctx.bumps.member_progress;
+        let progress = progress.key();
+
+        let inputs = ComputationInputs::MemberProgress {
+            contribution: StoredContributionInput::from(&*ctx.accounts.contribution),
+            commitment,
+            commitment_nonce,
+            member,
+            recipient_pubkey,
+            recipient_nonce,
+        };
+        let record = record_queued(
+            &mut ctx.accounts.computation_record,
+            ctx.bumps.computation_record,
+            computation_offset,
+            goal,
+            inputs,
+            member,
+        )?;
+
+        queue_computation(
+            ctx.accounts,
+            computation_offset,
+            inputs.args(),
+            None,
+            vec![MemberProgressV2Callback::callback_ix(&[
+                record,
+                CallbackAccount {
+                    pubkey: progress,
+                    is_writable: true,
+                },
+            ])],
+        )?;
+        Ok(())
+    }
+
+    #[arcium_callback(encrypted_ix = "member_progress_v2")]
+    pub fn member_progress_v2_callback(
+        ctx: Context<MemberProgressV2Callback>,
+        output: ComputationOutputs<MemberProgressV2Output>,
+    ) -> Result<()> {
+        let record = &mut ctx.accounts.computation_record;
+        let shortfall = match output {
+            ComputationOutputs::Success(MemberProgressV2Output { field_0 }) => field_0,
+            _ => return record_failed(record, FailureReason::Aborted),
+        };
+        record.complete()?;
+
+        let progress = &mut ctx.accounts.member_progress;
+        progress.computation_offset = record.computation_offset;
+        progress.encryption_key = shortfall.encryption_key;
+        progress.nonce = shortfall.nonce;
+        progress.encrypted_shortfall = shortfall.ciphertexts[0];
+        progress.updated_at = record.completed_at;
+
+        // Nothing about the result itself: only the member can read it
+        emit!(MemberProgressEvent {
+            goal: record.goal,
+            computation_offset: record.computation_offset,
+            member: progress.member,
+        });
+        Ok(())
+    }
+
+    // group_stats - aggregate insights for the goal owner
+
+    /// Compute the average, min, max and number of non-zero contributions
+    /// over every member's stored contribution. The `Contribution` address of
+    /// each member is passed as a remaining account, in member order, so the
+    /// owner cannot pick overlapping subsets and difference their results.
+    /// Only the aggregates are revealed, into the goal's `GroupStats` and a
+    /// `GroupStatsEvent`.
+    pub fn compute_group_stats<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ComputeGroupStats<'info>>,
+        computation_offset: u64,
+    ) -> Result<()> {
+        require!(
+            ctx.accounts.goal_circuits.uses(Circuit::GroupStats, GROUP_STATS_VERSION),
+            ErrorCode::CircuitVersionMismatch
+        );
+        ctx.accounts.sign_pda_account.bump = ctx.bumps.sign_pda_account;
+        let goal = ctx.accounts.goal.key();
+
+        let contributions: Vec<StoredContributionInput> =
+            member_contributions(&ctx.accounts.goal, ctx.remaining_accounts)?
+                .iter()
+                .map(|contribution| StoredContributionInput::from(&**contribution))
+                .collect();
+        // Members who never contributed do not count towards the minimum
+        let count = contributions.len();
+        require!(
+            (MIN_STATS_CONTRIBUTIONS..=MAX_STATS_CONTRIBUTIONS).contains(&count),
+            ErrorCode::InvalidContributionCount
+        );
+        // The circuit skips the unused slots
+        let slots = contribution_slots(&contributions);
+
+        let stats = &mut ctx.accounts.group_stats;
+        stats.goal = goal;
+        stats.bump = ctx.bumps.group_stats;
+        let stats = stats.key();
+
+        let inputs = ComputationInputs::GroupStats {
+            contributions: slots,
+            count: count as u8,
+        };
+        let record = record_queued(
+            &mut ctx.accounts.computation_record,
+            ctx.bumps.computation_record,
+            computation_offset,
+            goal,
+            inputs,
+            ctx.accounts.payer.key(),
+        )?;
+
+        queue_computation(
+            ctx.accounts,
+            computation_offset,
+            inputs.args(),
+            None,
+            vec![GroupStats10V1Callback::callback_ix(&[
+                record,
+                CallbackAccount {
+                    pubkey: stats,
+                    is_writable: true,
+                },
+            ])],
+        )?;
+        Ok(())
+    }
+
+    #[arcium_callback(encrypted_ix = "group_stats_10_v1")]
+    pub fn group_stats_10_v1_callback(
+        ctx: Context<GroupStats10V1Callback>,
+        output: ComputationOutputs<GroupStats10V1Output>,
+    ) -> Result<()> {
+        let record = &mut ctx.accounts.computation_record;
+        let [average, min, max, active] = match output {
+            ComputationOutputs::Success(GroupStats10V1Output { field_0 }) => field_0,
+            _ => return record_failed(record, FailureReason::Aborted),
+        };
+        record.complete()?;
+        let ComputationInputs::GroupStats { count, .. } = record.inputs else {
+            return err!(ErrorCode::WrongComputationKind);
+        };
+
+        let stats = &mut ctx.accounts.group_stats;
+        stats.computation_offset = record.computation_offset;
+        stats.contributors = count;
+        stats.average = average;
+        stats.min = min;
+        stats.max = max;
+        stats.active = active as u8;
+        stats.updated_at = record.completed_at;
+
+        emit!(GroupStatsEvent {
+            goal: record.goal,
+            computation_offset: record.computation_offset,
+            contributors: count,
+            average,
+            min,
+            max,
+            active: active as u8,
         });
         Ok(())
     }
+
+    // Reveals - individual amounts, only to whoever may read them
+
+    /// Re-encrypt up to 5 stored contributions, passed as remaining accounts,
+    /// each to the key it was encrypted with. Every contributor gets back
+    /// their own amount in a `ContributionRevealed` event and learns nothing
+    /// about the others. Any goal member can queue it.
+    pub fn reveal_own_contributions<'info>(
+        ctx: Context<'_, '_, 'info, 'info, RevealOwnContributions<'info>>,
+        computation_offset: u64,
+    ) -> Result<()> {
+        require!(
+            ctx.accounts.goal_circuits.uses(Circuit::RevealOwnContributions, REVEAL_OWN_VERSION),
+            ErrorCode::CircuitVersionMismatch
+        );
+        let count = ctx.remaining_accounts.len();
+        require!(
+            (1..=MAX_OWN_REVEAL_CONTRIBUTIONS).contains(&count),
+            ErrorCode::InvalidRevealCount
+        );
+        ctx.accounts.sign_pda_account.bump = ctx.bumps.sign_pda_account;
+        let goal = ctx.accounts.goal.key();
+
+        let contributions: Vec<StoredContributionInput> =
+            goal_contributions(&goal, ctx.remaining_accounts)?
+                .iter()
+                .map(|contribution| StoredContributionInput::from(&**contribution))
+                .collect();
+        let inputs = ComputationInputs::RevealOwnContributions {
+            contributions: contribution_slots(&contributions),
+            count: count as u8,
+        };
+        let record = record_queued(
+            &mut ctx.accounts.computation_record,
+            ctx.bumps.computation_record,
+            computation_offset,
+            goal,
+            inputs,
+            ctx.accounts.payer.key(),
+        )?;
+
+        queue_computation(
+            ctx.accounts,
+            computation_offset,
+            inputs.args(),
+            None,
+            vec![RevealOwnContributions5V1Callback::callback_ix(&[record])],
+        )?;
+        Ok(())
+    }
+
+    #[arcium_callback(encrypted_ix = "reveal_own_contributions_5_v1")]
+    pub fn reveal_own_contributions_5_v1_callback(
+        ctx: Context<RevealOwnContributions5V1Callback>,
+        output: ComputationOutputs<RevealOwnContributions5V1Output>,
+    ) -> Result<()> {
+        let record = &mut ctx.accounts.computation_record;
+        let revealed = match output {
+            ComputationOutputs::Success(RevealOwnContributions5V1Output {
+                field_0:
+                    RevealOwnContributions5V1OutputStruct0 {
+                        field_0,
+                        field_1,
+                        field_2,
+                        field_3,
+                        field_4,
+                    },
+            }) => [field_0, field_1, field_2, field_3, field_4],
+            _ => return record_failed(record, FailureReason::Aborted),
+        };
+        record.complete()?;
+        let ComputationInputs::RevealOwnContributions { count, .. } = record.inputs else {
+            return err!(ErrorCode::WrongComputationKind);
+        };
+
+        // Padding slots repeat the first contribution and are left out
+        for amount in revealed.iter().take(usize::from(count)) {
+            emit!(ContributionRevealed {
+                goal: record.goal,
+                computation_offset: record.computation_offset,
+                encryption_key: amount.encryption_key,
+                nonce: amount.nonce,
+                encrypted_amount: amount.ciphertexts[0],
+            });
+        }
+        Ok(())
+    }
+
+    /// Reveal up to 10 stored contributions, passed as remaining accounts,
+    /// to the goal owner: re-encrypted together to `recipient_pubkey` and
+    /// emitted in an `OwnerRevealEvent`, in the order they were passed. Every
+    /// contributor must have opted in with `savings_goal::set_owner_reveal`.
+    pub fn reveal_to_owner<'info>(
+        ctx: Context<'_, '_, 'info, 'info, RevealToOwner<'info>>,
+        computation_offset: u64,
+        recipient_pubkey: [u8; 32],
+        recipient_nonce: u128,
+    ) -> Result<()> {
+        require!(
+            ctx.accounts.goal_circuits.uses(Circuit::RevealContributions10, REVEAL_10_VERSION),
+            ErrorCode::CircuitVersionMismatch
+        );
+        let count = ctx.remaining_accounts.len();
+        require!(
+            (1..=MAX_OWNER_REVEAL_CONTRIBUTIONS).contains(&count),
+            ErrorCode::InvalidRevealCount
+        );
+        ctx.accounts.sign_pda_account.bump = ctx.bumps.sign_pda_account;
+        let goal = ctx.accounts.goal.key();
+
+        let mut contributions = Vec::with_capacity(count);
+        for contribution in goal_contributions(&goal, ctx.remaining_accounts)? {
+            require!(contribution.owner_reveal, ErrorCode::OwnerRevealNotAllowed);
+            contributions.push(StoredContributionInput::from(&*contribution));
+        }
+        let inputs = ComputationInputs::RevealToOwner {
+            contributions: contribution_slots(&contributions),
+            count: count as u8,
+            recipient_pubkey,
+            recipient_nonce,
+        };
+        let record = record_queued(
+            &mut ctx.accounts.computation_record,
+            ctx.bumps.computation_record,
+            computation_offset,
+            goal,
+            inputs,
+            ctx.accounts.payer.key(),
+        )?;
+
+        queue_computation(
+            ctx.accounts,
+            computation_offset,
+            inputs.args(),
+            None,
+            vec![RevealContributions10V5Callback::callback_ix(&[record])],
+        )?;
+        Ok(())
+    }
+
+    #[arcium_callback(encrypted_ix = "reveal_contributions_10_v5")]
+    pub fn reveal_contributions_10_v5_callback(
+        ctx: Context<RevealContributions10V5Callback>,
+        output: ComputationOutputs<RevealContributions10V5Output>,
+    ) -> Result<()> {
+        let record = &mut ctx.accounts.computation_record;
+        let revealed = match output {
+            ComputationOutputs::Success(RevealContributions10V5Output { field_0 }) => field_0,
+            _ => return record_failed(record, FailureReason::Aborted),
+        };
+        record.complete()?;
+        let ComputationInputs::RevealToOwner { count, .. } = record.inputs else {
+            return err!(ErrorCode::WrongComputationKind);
+        };
+
+        emit!(OwnerRevealEvent {
+            goal: record.goal,
+            computation_offset: record.computation_offset,
+            encryption_key: revealed.encryption_key,
+            nonce: revealed.nonce,
+            encrypted_amounts: revealed.ciphertexts[..usize::from(count)].to_vec(),
+        });
+        Ok(())
+    }
+
+    // reveal_final_total - the group total, public only if the goal succeeded
+
+    /// Finalize the goal in `savings_goal` and queue the reveal of its group
+    /// total. Every member's `Contribution` address is passed as a remaining
+    /// account, in member order, so none can be left out; members who never
+    /// contributed are skipped. The callback records the total on the goal
+    /// only if it reached the target; otherwise nothing is revealed. Goals
+    /// that were already finalized directly only get the reveal queued.
+    pub fn finalize_and_reveal_total<'info>(
+        ctx: Context<'_, '_, 'info, 'info, FinalizeAndRevealTotal<'info>>,
+        computation_offset: u64,
+    ) -> Result<()> {
+        require!(
+            ctx.accounts.goal_circuits.uses(Circuit::RevealFinalTotal, FINAL_TOTAL_VERSION),
+            ErrorCode::CircuitVersionMismatch
+        );
+        require!(
+            ctx.accounts.goal.revealed_total.is_none(),
+            ErrorCode::TotalAlreadyRevealed
+        );
+        ctx.accounts.sign_pda_account.bump = ctx.bumps.sign_pda_account;
+        let goal = ctx.accounts.goal.key();
+
+        let contributions: Vec<StoredContributionInput> =
+            member_contributions(&ctx.accounts.goal, ctx.remaining_accounts)?
+                .iter()
+                .map(|contribution| StoredContributionInput::from(&**contribution))
+                .collect();
+        require!(!contributions.is_empty(), ErrorCode::NoContributions);
+
+        if ctx.accounts.goal.status != GoalStatus::Finalized {
+            savings_goal::cpi::finalize_and_reveal(CpiContext::new(
+                ctx.accounts.savings_goal_program.to_account_info(),
+                savings_goal::cpi::accounts::FinalizeAndReveal {
+                    caller: ctx.accounts.payer.to_account_info(),
+                    goal: ctx.accounts.goal.to_account_info(),
+                },
+            ))?;
+        }
+
+        let inputs = ComputationInputs::RevealFinalTotal {
+            contributions: contribution_slots(&contributions),
+            count: contributions.len() as u8,
+            target: ctx.accounts.goal.target_amount,
+        };
+        let record = record_queued(
+            &mut ctx.accounts.computation_record,
+            ctx.bumps.computation_record,
+            computation_offset,
+            goal,
+            inputs,
+            ctx.accounts.payer.key(),
+        )?;
+
+        queue_computation(
+            ctx.accounts,
+            computation_offset,
+            inputs.args(),
+            None,
+            vec![RevealFinalTotal10V1Callback::callback_ix(
+                &goal_callback_accounts(record, goal),
+            )],
+        )?;
+        Ok(())
+    }
+
+    #[arcium_callback(encrypted_ix = "reveal_final_total_10_v1")]
+    pub fn reveal_final_total_10_v1_callback(
+        ctx: Context<RevealFinalTotal10V1Callback>,
+        output: ComputationOutputs<RevealFinalTotal10V1Output>,
+    ) -> Result<()> {
+        let record = &mut ctx.accounts.computation_record;
+        let revealed = match output {
+            ComputationOutputs::Success(RevealFinalTotal10V1Output { field_0 }) => field_0,
+            _ => return record_failed(record, FailureReason::Aborted),
+        };
+        record.complete()?;
+
+        // Zero means the total fell short of the (non-zero) target
+        let total = (revealed > 0).then_some(revealed);
+        emit!(FinalTotalEvent {
+            goal: record.goal,
+            computation_offset: record.computation_offset,
+            total,
+        });
+
+        // Another reveal of the same goal may have landed first
+        if let (Some(total), None) = (total, ctx.accounts.goal.revealed_total) {
+            savings_goal::cpi::record_revealed_total(
+                CpiContext::new_with_signer(
+                    ctx.accounts.savings_goal_program.to_account_info(),
+                    savings_goal::cpi::accounts::RecordRevealedTotal {
+                        goal_authority: ctx.accounts.goal_authority.to_account_info(),
+                        goal: ctx.accounts.goal.to_account_info(),
+                    },
+                    &[&[GOAL_AUTHORITY_SEED, &[ctx.bumps.goal_authority]]],
+                ),
+                total,
+            )?;
+        }
+        Ok(())
+    }
+
+    // check_vault_balance - deposits against declared contributions
+
+    /// Compare what members deposited into the vault with the total of
+    /// every member's encrypted contribution, passed as remaining accounts
+    /// in member order like for `finalize_and_reveal_total`. Only whether
+    /// the deposits are the same, more or less is revealed; the callback
+    /// flags the goal inconsistent in `savings_goal` when they are less. The
+    /// deposits recorded on the goal are used rather than the vault's
+    /// balance: anyone can send the vault lamports, and re-checking after
+    /// each transfer would narrow down the encrypted total. Any member can
+    /// queue it, and one has to pass after finalization before the goal's
+    /// yield is distributed.
+    pub fn check_vault_balance<'info>(
+        ctx: Context<'_, '_, 'info, 'info, CheckVaultBalance<'info>>,
+        computation_offset: u64,
+    ) -> Result<()> {
+        require!(
+            ctx.accounts.goal_circuits.uses(Circuit::CheckVaultBalance, VAULT_CHECK_VERSION),
+            ErrorCode::CircuitVersionMismatch
+        );
+        ctx.accounts.sign_pda_account.bump = ctx.bumps.sign_pda_account;
+        let goal = ctx.accounts.goal.key();
+
+        let contributions: Vec<StoredContributionInput> =
+            member_contributions(&ctx.accounts.goal, ctx.remaining_accounts)?
+                .iter()
+                .map(|contribution| StoredContributionInput::from(&**contribution))
+                .collect();
+        require!(!contributions.is_empty(), ErrorCode::NoContributions);
+
+        let inputs = ComputationInputs::CheckVaultBalance {
+            contributions: contribution_slots(&contributions),
+            count: contributions.len() as u8,
+            vault_balance: ctx.accounts.goal.deposited,
+            read_at: Clock::get()?.unix_timestamp,
+        };
+        let record = record_queued(
+            &mut ctx.accounts.computation_record,
+            ctx.bumps.computation_record,
+            computation_offset,
+            goal,
+            inputs,
+            ctx.accounts.payer.key(),
+        )?;
+
+        queue_computation(
+            ctx.accounts,
+            computation_offset,
+            inputs.args(),
+            None,
+            vec![CheckVaultBalance10V1Callback::callback_ix(
+                &goal_callback_accounts(record, goal),
+            )],
+        )?;
+        Ok(())
+    }
+
+    #[arcium_callback(encrypted_ix = "check_vault_balance_10_v1")]
+    pub fn check_vault_balance_10_v1_callback(
+        ctx: Context<CheckVaultBalance10V1Callback>,
+        output: ComputationOutputs<CheckVaultBalance10V1Output>,
+    ) -> Result<()> {
+        let record = &mut ctx.accounts.computation_record;
+        let sign = match output {
+            ComputationOutputs::Success(CheckVaultBalance10V1Output { field_0 }) => field_0,
+            _ => return record_failed(record, FailureReason::Aborted),
+        };
+        record.complete()?;
+
+        let ComputationInputs::CheckVaultBalance {
+            vault_balance,
+            read_at,
+            ..
+        } = record.inputs
+        else {
+            return err!(ErrorCode::WrongComputationKind);
+        };
+        let balance = VaultBalance::from_sign(sign);
+        emit!(VaultCheckEvent {
+            goal: record.goal,
+            computation_offset: record.computation_offset,
+            vault_balance,
+            balance,
+        });
+
+        savings_goal::cpi::record_vault_check(
+            CpiContext::new_with_signer(
+                ctx.accounts.savings_goal_program.to_account_info(),
+                savings_goal::cpi::accounts::RecordVaultCheck {
+                    goal_authority: ctx.accounts.goal_authority.to_account_info(),
+                    goal: ctx.accounts.goal.to_account_info(),
+                },
+                &[&[GOAL_AUTHORITY_SEED, &[ctx.bumps.goal_authority]]],
+            ),
+            balance == VaultBalance::Shortfall,
+            read_at,
+        )?;
+        Ok(())
+    }
+
+    // check_milestones - staged payouts before the goal completes
+
+    /// Count how many of the goal's milestones the total of every member's
+    /// contribution has crossed, with the contributions passed as remaining
+    /// accounts in member order. Only that count is revealed; the callback
+    /// records newly crossed milestones in `savings_goal`, which unlocks
+    /// their payouts. Any member can queue it until the goal is finalized.
+    pub fn check_milestones<'info>(
+        ctx: Context<'_, '_, 'info, 'info, CheckMilestones<'info>>,
+        computation_offset: u64,
+    ) -> Result<()> {
+        require!(
+            ctx.accounts.goal_circuits.uses(Circuit::CheckMilestones, MILESTONES_VERSION),
+            ErrorCode::CircuitVersionMismatch
+        );
+        let milestones = ctx.accounts.goal.milestones.len();
+        require!(milestones > 0, ErrorCode::NoMilestones);
+        ctx.accounts.sign_pda_account.bump = ctx.bumps.sign_pda_account;
+        let goal = ctx.accounts.goal.key();
+
+        let contributions: Vec<StoredContributionInput> =
+            member_contributions(&ctx.accounts.goal, ctx.remaining_accounts)?
+                .iter()
+                .map(|contribution| StoredContributionInput::from(&**contribution))
+                .collect();
+        require!(!contributions.is_empty(), ErrorCode::NoContributions);
+
+        // Unused slots are never counted by the circuit
+        let mut thresholds = [u64::MAX; MAX_MILESTONES];
+        for (milestone, threshold) in thresholds.iter_mut().enumerate().take(milestones) {
+            *threshold = ctx.accounts.goal.milestone_threshold(milestone as u8);
+        }
+        let inputs = ComputationInputs::CheckMilestones {
+            contributions: contribution_slots(&contributions),
+            count: contributions.len() as u8,
+            milestones: milestones as u8,
+            thresholds,
+        };
+        let record = record_queued(
+            &mut ctx.accounts.computation_record,
+            ctx.bumps.computation_record,
+            computation_offset,
+            goal,
+            inputs,
+            ctx.accounts.payer.key(),
+        )?;
+
+        queue_computation(
+            ctx.accounts,
+            computation_offset,
+            inputs.args(),
+            None,
+            vec![CheckMilestones10V1Callback::callback_ix(
+                &goal_callback_accounts(record, goal),
+            )],
+        )?;
+        Ok(())
+    }
+
+    #[arcium_callback(encrypted_ix = "check_milestones_10_v1")]
+    pub fn check_milestones_10_v1_callback(
+        ctx: Context<CheckMilestones10V1Callback>,
+        output: ComputationOutputs<CheckMilestones10V1Output>,
+    ) -> Result<()> {
+        let record = &mut ctx.accounts.computation_record;
+        let reached = match output {
+            ComputationOutputs::Success(CheckMilestones10V1Output { field_0 }) => field_0,
+            _ => return record_failed(record, FailureReason::Aborted),
+        };
+        record.complete()?;
+
+        emit!(MilestoneCheckEvent {
+            goal: record.goal,
+            computation_offset: record.computation_offset,
+            reached,
+        });
+
+        // An earlier check may already have recorded these milestones
+        if reached > ctx.accounts.goal.milestones_reached {
+            savings_goal::cpi::record_milestones(
+                CpiContext::new_with_signer(
+                    ctx.accounts.savings_goal_program.to_account_info(),
+                    savings_goal::cpi::accounts::RecordMilestones {
+                        goal_authority: ctx.accounts.goal_authority.to_account_info(),
+                        goal: ctx.accounts.goal.to_account_info(),
+                    },
+                    &[&[GOAL_AUTHORITY_SEED, &[ctx.bumps.goal_authority]]],
+                ),
+                reached,
+            )?;
+        }
+        Ok(())
+    }
+
+    // distribute_yield - what idle vault funds earned, back to the members
+
+    /// Split the yield a finalized goal's vault earned over its members in
+    /// proportion to their contributions, passed as remaining accounts in
+    /// member order. Only the shares are revealed; the callback records them
+    /// in `savings_goal`, where each member claims theirs. Any member can
+    /// queue it once the deposit is back in the vault and a vault check
+    /// queued since finalization found no shortfall.
+    pub fn distribute_yield<'info>(
+        ctx: Context<'_, '_, 'info, 'info, DistributeYield<'info>>,
+        computation_offset: u64,
+    ) -> Result<()> {
+        require!(
+            ctx.accounts.goal_circuits.uses(Circuit::DistributeYield, YIELD_VERSION),
+            ErrorCode::CircuitVersionMismatch
+        );
+        ctx.accounts.sign_pda_account.bump = ctx.bumps.sign_pda_account;
+        let goal = ctx.accounts.goal.key();
+
+        let contributions: Vec<StoredContributionInput> =
+            member_contributions(&ctx.accounts.goal, ctx.remaining_accounts)?
+                .iter()
+                .map(|contribution| StoredContributionInput::from(&**contribution))
+                .collect();
+        require!(!contributions.is_empty(), ErrorCode::NoContributions);
+        // Members without a contribution get no share
+        let contributors = ctx
+            .remaining_accounts
+            .iter()
+            .enumerate()
+            .filter(|(_, contribution)| !contribution.data_is_empty())
+            .fold(0u16, |mask, (index, _)| mask | 1 << index);
+
+        let inputs = ComputationInputs::DistributeYield {
+            contributions: contribution_slots(&contributions),
+            count: contributions.len() as u8,
+            contributors,
+            yield_amount: ctx.accounts.goal.yield_earned,
+        };
+        let record = record_queued(
+            &mut ctx.accounts.computation_record,
+            ctx.bumps.computation_record,
+            computation_offset,
+            goal,
+            inputs,
+            ctx.accounts.payer.key(),
+        )?;
+
+        queue_computation(
+            ctx.accounts,
+            computation_offset,
+            inputs.args(),
+            None,
+            vec![DistributeYield10V1Callback::callback_ix(
+                &goal_callback_accounts(record, goal),
+            )],
+        )?;
+        Ok(())
+    }
+
+    #[arcium_callback(encrypted_ix = "distribute_yield_10_v1")]
+    pub fn distribute_yield_10_v1_callback(
+        ctx: Context<DistributeYield10V1Callback>,
+        output: ComputationOutputs<DistributeYield10V1Output>,
+    ) -> Result<()> {
+        let record = &mut ctx.accounts.computation_record;
+        let slots = match output {
+            ComputationOutputs::Success(DistributeYield10V1Output { field_0 }) => field_0,
+            _ => return record_failed(record, FailureReason::Aborted),
+        };
+        record.complete()?;
+        let ComputationInputs::DistributeYield { contributors, .. } = record.inputs else {
+            return err!(ErrorCode::WrongComputationKind);
+        };
+
+        // Slots are in contributor order; spread them back over the members
+        let members = ctx.accounts.goal.members.len();
+        let mut slots = slots.into_iter();
+        let shares: Vec<u64> = (0..members)
+            .map(|member| match contributors & (1 << member) {
+                0 => 0,
+                _ => slots.next().unwrap_or_default(),
+            })
+            .collect();
+
+        emit!(YieldDistributionEvent {
+            goal: record.goal,
+            computation_offset: record.computation_offset,
+            shares: shares.clone(),
+        });
+
+        // Another distribution of the same yield may have landed first
+        if ctx.accounts.goal.yield_shares.is_empty() {
+            savings_goal::cpi::record_yield_shares(
+                CpiContext::new_with_signer(
+                    ctx.accounts.savings_goal_program.to_account_info(),
+                    savings_goal::cpi::accounts::RecordYieldShares {
+                        goal_authority: ctx.accounts.goal_authority.to_account_info(),
+                        goal: ctx.accounts.goal.to_account_info(),
+                    },
+                    &[&[GOAL_AUTHORITY_SEED, &[ctx.bumps.goal_authority]]],
+                ),
+                shares,
+            )?;
+        }
+        Ok(())
+    }
+
+    // audit_contributions - compliance access for a goal's auditor
+
+    /// Re-encrypt up to 10 stored contributions, passed as remaining
+    /// accounts, and their total to the auditor key set on the goal at
+    /// creation. The callback stores the ciphertexts in the goal's `Audit`,
+    /// labelled with their contributors; only the auditor can decrypt them.
+    pub fn audit_contributions<'info>(
+        ctx: Context<'_, '_, 'info, 'info, AuditContributions<'info>>,
+        computation_offset: u64,
+        auditor_nonce: u128,
+    ) -> Result<()> {
+        require!(
+            ctx.accounts.goal_circuits.uses(Circuit::AuditContributions, AUDIT_VERSION),
+            ErrorCode::CircuitVersionMismatch
+        );
+        let auditor_pubkey = ctx.accounts.goal.auditor.ok_or(ErrorCode::NoAuditor)?;
+        let count = ctx.remaining_accounts.len();
+        require!(
+            (1..=MAX_AUDIT_CONTRIBUTIONS).contains(&count),
+            ErrorCode::InvalidAuditCount
+        );
+        ctx.accounts.sign_pda_account.bump = ctx.bumps.sign_pda_account;
+        let goal = ctx.accounts.goal.key();
+
+        let stored = goal_contributions(&goal, ctx.remaining_accounts)?;
+        let contributions: Vec<StoredContributionInput> = stored
+            .iter()
+            .map(|contribution| StoredContributionInput::from(&**contribution))
+            .collect();
+        let mut contributors = [stored[0].contributor; MAX_AUDIT_CONTRIBUTIONS];
+        for (slot, contribution) in contributors.iter_mut().zip(&stored) {
+            *slot = contribution.contributor;
+        }
+
+        let audit = &mut ctx.accounts.audit;
+        audit.goal = goal;
+        audit.bump = ctx.bumps.audit;
+        let audit = audit.key();
+
+        let inputs = ComputationInputs::Audit {
+            contributions: contribution_slots(&contributions),
+            contributors,
+            count: count as u8,
+            auditor_pubkey,
+            auditor_nonce,
+        };
+        let record = record_queued(
+            &mut ctx.accounts.computation_record,
+            ctx.bumps.computation_record,
+            computation_offset,
+            goal,
+            inputs,
+            ctx.accounts.payer.key(),
+        )?;
+
+        queue_computation(
+            ctx.accounts,
+            computation_offset,
+            inputs.args(),
+            None,
+            vec![AuditContributions10V1Callback::callback_ix(&[
+                record,
+                CallbackAccount {
+                    pubkey: audit,
+                    is_writable: true,
+                },
+            ])],
+        )?;
+        Ok(())
+    }
+
+    #[arcium_callback(encrypted_ix = "audit_contributions_10_v1")]
+    pub fn audit_contributions_10_v1_callback(
+        ctx: Context<AuditContributions10V1Callback>,
+        output: ComputationOutputs<AuditContributions10V1Output>,
+    ) -> Result<()> {
+        let record = &mut ctx.accounts.computation_record;
+        let audited = match output {
+            ComputationOutputs::Success(AuditContributions10V1Output { field_0 }) => field_0,
+            _ => return record_failed(record, FailureReason::Aborted),
+        };
+        record.complete()?;
+        let ComputationInputs::Audit {
+            contributors,
+            count,
+            ..
+        } = record.inputs
+        else {
+            return err!(ErrorCode::WrongComputationKind);
+        };
+        let used = usize::from(count);
+
+        let audit = &mut ctx.accounts.audit;
+        audit.computation_offset = record.computation_offset;
+        audit.contributors = contributors[..used].to_vec();
+        audit.encryption_key = audited.encryption_key;
+        audit.nonce = audited.nonce;
+        audit.encrypted_amounts = audited.ciphertexts[..used].to_vec();
+        audit.encrypted_total = audited.ciphertexts[MAX_AUDIT_CONTRIBUTIONS];
+        audit.updated_at = record.completed_at;
+
+        emit!(AuditEvent {
+            goal: record.goal,
+            computation_offset: record.computation_offset,
+            contributors: count,
+        });
+        Ok(())
+    }
+
+    // check_goal_reached - for progress checking
+
+    /// Compare an encrypted total with `target` for a goal the payer is a
+    /// member of
+    pub fn check_goal_reached(
+        ctx: Context<CheckGoalReached>,
+        computation_offset: u64,
+        ciphertext_0: [u8; 32],
+        target: u64,
+        pub_key: [u8; 32],
+        nonce: u128,
+    ) -> Result<()> {
+        require!(
+            ctx.accounts.goal_circuits.uses(Circuit::CheckGoalReached, CHECK_GOAL_VERSION),
+            ErrorCode::CircuitVersionMismatch
+        );
+        ctx.accounts.sign_pda_account.bump = ctx.bumps.sign_pda_account;
+        let goal = ctx.accounts.goal.key();
+        let inputs = ComputationInputs::CheckGoalReached {
+            ciphertext_0,
+            target,
+            pub_key,
+            nonce,
+        };
+        let record = record_queued(
+            &mut ctx.accounts.computation_record,
+            ctx.bumps.computation_record,
+            computation_offset,
+            goal,
+            inputs,
+            ctx.accounts.payer.key(),
+        )?;
+
+        queue_computation(
+            ctx.accounts,
+            computation_offset,
+            inputs.args(),
+            None,
+            vec![CheckGoalReachedV4Callback::callback_ix(&[record])],
+        )?;
+        Ok(())
+    }
+
+    #[arcium_callback(encrypted_ix = "check_goal_reached_v4")]
+    pub fn check_goal_reached_v4_callback(
+        ctx: Context<CheckGoalReachedV4Callback>,
+        output: ComputationOutputs<CheckGoalReachedV4Output>,
+    ) -> Result<()> {
+        let record = &mut ctx.accounts.computation_record;
+        let reached = match output {
+            ComputationOutputs::Success(CheckGoalReachedV4Output { field_0 }) => field_0,
+            _ => return record_failed(record, FailureReason::Aborted),
+        };
+        record.complete()?;
+
+        emit!(GoalCheckEvent {
+            goal: record.goal,
+            computation_offset: record.computation_offset,
+            reached,
+        });
+        Ok(())
+    }
+
+    // retry_computation - re-queue a computation the cluster aborted
+
+    /// Re-queue a failed computation of any circuit under
+    /// `computation_offset`, with the inputs it was first queued with. Only
+    /// its requester can retry it, and only once. The callback gets the same
+    /// accounts as the first time.
+    pub fn retry_computation(
+        ctx: Context<RetryComputation>,
+        computation_offset: u64,
+    ) -> Result<()> {
+        let goal = ctx.accounts.failed_record.goal;
+        let inputs = ctx.accounts.failed_record.inputs;
+        let circuit = inputs.circuit();
+        require!(
+            ctx.accounts.goal_circuits.uses(circuit, circuit.compiled_version()),
+            ErrorCode::CircuitVersionMismatch
+        );
+        ctx.accounts.sign_pda_account.bump = ctx.bumps.sign_pda_account;
+        let record = record_retry(
+            &mut ctx.accounts.failed_record,
+            &mut ctx.accounts.computation_record,
+            ctx.bumps.computation_record,
+            computation_offset,
+            ctx.accounts.payer.key(),
+        )?;
+
+        let writable = |pubkey| CallbackAccount {
+            pubkey,
+            is_writable: true,
+        };
+        let callback = match circuit {
+            Circuit::AddTwoContributions => AddTwoContributionsV4Callback::callback_ix(&[record]),
+            Circuit::CheckGoalReached => CheckGoalReachedV4Callback::callback_ix(&[record]),
+            Circuit::RevealContributions10 => {
+                RevealContributions10V5Callback::callback_ix(&[record])
+            }
+            Circuit::RevealOwnContributions => {
+                RevealOwnContributions5V1Callback::callback_ix(&[record])
+            }
+            Circuit::AddAndCheckGoal => {
+                AddAndCheckGoal10V1Callback::callback_ix(&goal_callback_accounts(record, goal))
+            }
+            Circuit::RevealFinalTotal => {
+                RevealFinalTotal10V1Callback::callback_ix(&goal_callback_accounts(record, goal))
+            }
+            Circuit::CheckVaultBalance => {
+                CheckVaultBalance10V1Callback::callback_ix(&goal_callback_accounts(record, goal))
+            }
+            Circuit::CheckMilestones => {
+                CheckMilestones10V1Callback::callback_ix(&goal_callback_accounts(record, goal))
+            }
+            Circuit::DistributeYield => {
+                DistributeYield10V1Callback::callback_ix(&goal_callback_accounts(record, goal))
+            }
+            Circuit::CheckPeriodContribution => {
+                let (member, period) = inputs
+                    .period_contribution()
+                    .ok_or(ErrorCode::WrongComputationKind)?;
+                let (period_contribution, _) = Pubkey::find_program_address(
+                    &[b"period", goal.as_ref(), member.as_ref(), &period.to_le_bytes()],
+                    &savings_goal::ID,
+                );
+                CheckPeriodContributionV1Callback::callback_ix(&check_period_callback_accounts(
+                    record,
+                    goal,
+                    period_contribution,
+                ))
+            }
+            Circuit::MemberProgress => {
+                let member = inputs.member_progress().ok_or(ErrorCode::WrongComputationKind)?;
+                let (progress, _) = Pubkey::find_program_address(
+                    &[b"member_progress", goal.as_ref(), member.as_ref()],
+                    &ID,
+                );
+                MemberProgressV2Callback::callback_ix(&[record, writable(progress)])
+            }
+            Circuit::GroupStats => {
+                let (stats, _) =
+                    Pubkey::find_program_address(&[b"group_stats", goal.as_ref()], &ID);
+                GroupStats10V1Callback::callback_ix(&[record, writable(stats)])
+            }
+            Circuit::AuditContributions => {
+                let (audit, _) = Pubkey::find_program_address(&[b"audit", goal.as_ref()], &ID);
+                AuditContributions10V1Callback::callback_ix(&[record, writable(audit)])
+            }
+        };
+
+        queue_computation(
+            ctx.accounts,
+            computation_offset,
+            inputs.args(),
+            None,
+            vec![callback],
+        )?;
+        Ok(())
+    }
+}
+
+/// Fill in a freshly created computation record and return it as the extra
+/// account the callback needs to mark it completed
+fn record_queued(
+    record: &mut Account<ComputationRecord>,
+    bump: u8,
+    computation_offset: u64,
+    goal: Pubkey,
+    inputs: ComputationInputs,
+    requester: Pubkey,
+) -> Result<CallbackAccount> {
+    let kind = inputs.circuit();
+    record.computation_offset = computation_offset;
+    record.goal = goal;
+    record.kind = kind;
+    record.status = ComputationStatus::Queued;
+    record.requester = requester;
+    record.queued_at = Clock::get()?.unix_timestamp;
+    record.completed_at = None;
+    record.failure = None;
+    record.retried_by = None;
+    record.inputs = inputs;
+    record.bump = bump;
+
+    emit!(ComputationQueued {
+        goal,
+        computation_offset,
+        kind,
+        requester,
+    });
+    Ok(CallbackAccount {
+        pubkey: record.key(),
+        is_writable: true,
+    })
+}
+
+/// Extra callback accounts of add_and_check_goal and reveal_final_total: the
+/// record, plus what the callback needs to update `goal` in `savings_goal`
+fn goal_callback_accounts(record: CallbackAccount, goal: Pubkey) -> Vec<CallbackAccount> {
+    let (goal_authority, _) = Pubkey::find_program_address(&[GOAL_AUTHORITY_SEED], &ID);
+    vec![
+        record,
+        CallbackAccount {
+            pubkey: goal,
+            is_writable: true,
+        },
+        CallbackAccount {
+            pubkey: goal_authority,
+            is_writable: false,
+        },
+        CallbackAccount {
+            pubkey: savings_goal::ID,
+            is_writable: false,
+        },
+    ]
+}
+
+/// Extra callback accounts of check_period_contribution: the record, plus what
+/// the callback needs to settle `period_contribution` in `savings_goal`
+fn check_period_callback_accounts(
+    record: CallbackAccount,
+    goal: Pubkey,
+    period_contribution: Pubkey,
+) -> Vec<CallbackAccount> {
+    let (goal_authority, _) = Pubkey::find_program_address(&[GOAL_AUTHORITY_SEED], &ID);
+    vec![
+        record,
+        CallbackAccount {
+            pubkey: goal,
+            is_writable: false,
+        },
+        CallbackAccount {
+            pubkey: period_contribution,
+            is_writable: true,
+        },
+        CallbackAccount {
+            pubkey: goal_authority,
+            is_writable: false,
+        },
+        CallbackAccount {
+            pubkey: savings_goal::ID,
+            is_writable: false,
+        },
+    ]
+}
+
+/// Queue a fresh record for `failed`'s inputs and link the two
+fn record_retry(
+    failed: &mut Account<ComputationRecord>,
+    record: &mut Account<ComputationRecord>,
+    bump: u8,
+    computation_offset: u64,
+    requester: Pubkey,
+) -> Result<CallbackAccount> {
+    failed.retried_by = Some(computation_offset);
+    let callback_account = record_queued(
+        record,
+        bump,
+        computation_offset,
+        failed.goal,
+        failed.inputs,
+        requester,
+    )?;
+
+    emit!(ComputationRetried {
+        goal: failed.goal,
+        failed_offset: failed.computation_offset,
+        computation_offset,
+    });
+    Ok(callback_account)
+}
+
+/// The `Contribution` accounts passed as remaining accounts, each checked to
+/// be a contribution to `goal` and passed only once
+fn goal_contributions<'info>(
+    goal: &Pubkey,
+    accounts: &'info [AccountInfo<'info>],
+) -> Result<Vec<Account<'info, Contribution>>> {
+    let mut contributions: Vec<Account<Contribution>> = Vec::with_capacity(accounts.len());
+    for info in accounts {
+        let contribution = Account::<Contribution>::try_from(info)?;
+        let (expected, _) = Pubkey::find_program_address(
+            &[b"contribution", goal.as_ref(), contribution.contributor.as_ref()],
+            &savings_goal::ID,
+        );
+        require_keys_eq!(info.key(), expected, ErrorCode::WrongContribution);
+        require!(
+            contributions.iter().all(|seen| seen.key() != expected),
+            ErrorCode::DuplicateContribution
+        );
+        contributions.push(contribution);
+    }
+    Ok(contributions)
+}
+
+/// The stored contributions of every member of `goal`. `accounts` holds the
+/// `Contribution` address of each member, in member order; addresses with
+/// no account yet belong to members who never contributed and are skipped.
+fn member_contributions<'info>(
+    goal: &Account<'info, SavingsGoal>,
+    accounts: &'info [AccountInfo<'info>],
+) -> Result<Vec<Account<'info, Contribution>>> {
+    require!(
+        accounts.len() == goal.members.len(),
+        ErrorCode::MissingContributions
+    );
+    let mut contributions = Vec::with_capacity(accounts.len());
+    for (member, info) in goal.members.iter().zip(accounts) {
+        let (expected, _) = Pubkey::find_program_address(
+            &[b"contribution", goal.key().as_ref(), member.as_ref()],
+            &savings_goal::ID,
+        );
+        require_keys_eq!(info.key(), expected, ErrorCode::WrongContribution);
+        if info.data_is_empty() {
+            continue;
+        }
+        contributions.push(Account::<Contribution>::try_from(info)?);
+    }
+    Ok(contributions)
+}
+
+/// Circuit slots for `contributions`; unused slots repeat the first one
+fn contribution_slots<const N: usize>(
+    contributions: &[StoredContributionInput],
+) -> [StoredContributionInput; N] {
+    let mut slots = [contributions[0]; N];
+    slots[..contributions.len()].copy_from_slice(contributions);
+    slots
+}
+
+/// Record a computation that produced no output. The callback still succeeds
+/// so the failure stays on-chain and the computation can be retried.
+fn record_failed(record: &mut ComputationRecord, reason: FailureReason) -> Result<()> {
+    record.fail(reason)?;
+
+    emit!(ComputationFailed {
+        goal: record.goal,
+        computation_offset: record.computation_offset,
+        kind: record.kind,
+        reason,
+    });
+    Ok(())
+}
+
+/// Convert the instruction-level off-chain source into Arcium's circuit source override
+fn circuit_source_override(source: Option<OffChainSource>) -> Result<Option<CircuitSource>> {
+    let Some(source) = source else {
+        return Ok(None);
+    };
+    require!(
+        !source.url.is_empty() && source.url.len() <= MAX_CIRCUIT_URL_LEN,
+        ErrorCode::InvalidCircuitSource
+    );
+    Ok(Some(CircuitSource::OffChain(OffChainCircuitSource {
+        source: source.url,
+        hash: source.hash,
+    })))
+}
+
+// Account Structs
+#[derive(Accounts)]
+pub struct InitializeConfig<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    #[account(
+        constraint = program.programdata_address()? == Some(program_data.key()) @ ErrorCode::Unauthorized
+    )]
+    pub program: Program<'info, crate::program::SavingsMxe>,
+    #[account(
+        constraint = program_data.upgrade_authority_address == Some(admin.key()) @ ErrorCode::Unauthorized
+    )]
+    pub program_data: Account<'info, ProgramData>,
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + 32 + 33 + 32 + 4 + MAX_COMP_DEF_SETTINGS * (4 + 1 + 8) + 4 + MAX_CIRCUITS * (1 + 2) + 1,
+        seeds = [b"config"],
+        bump
+    )]
+    pub config: Account<'info, MxeConfig>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateConfig<'info> {
+    pub admin: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = config.admin == admin.key() @ ErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, MxeConfig>,
+}
+
+#[derive(Accounts)]
+pub struct AcceptAdmin<'info> {
+    pub new_admin: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = config.pending_admin == Some(new_admin.key()) @ ErrorCode::NotPendingAdmin
+    )]
+    pub config: Account<'info, MxeConfig>,
+}
+
+#[derive(Accounts)]
+#[instruction(circuit: Circuit, version: u16)]
+pub struct RegisterCircuitVersion<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = config.admin == admin.key() @ ErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, MxeConfig>,
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + 1 + 2 + 4 + 8 + 1,
+        seeds = [b"circuit", &[circuit as u8], &version.to_le_bytes()],
+        bump
+    )]
+    pub circuit_version: Account<'info, RegisteredCircuitVersion>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(circuit: Circuit, version: u16)]
+pub struct SetActiveVersion<'info> {
+    pub admin: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = config.admin == admin.key() @ ErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, MxeConfig>,
+    #[account(
+        seeds = [b"circuit", &[circuit as u8], &version.to_le_bytes()],
+        bump = circuit_version.bump
+    )]
+    pub circuit_version: Account<'info, RegisteredCircuitVersion>,
+}
+
+#[derive(Accounts)]
+pub struct PinGoalCircuits<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    #[account(constraint = goal.owner == owner.key() @ ErrorCode::Unauthorized)]
+    pub goal: Account<'info, SavingsGoal>,
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, MxeConfig>,
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = 8 + 32 + 4 + MAX_CIRCUITS * (1 + 2) + 8 + 1,
+        seeds = [b"goal_circuits", goal.key().as_ref()],
+        bump
+    )]
+    pub goal_circuits: Account<'info, GoalCircuits>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct GetGoalCircuitVersion<'info> {
+    #[account(seeds = [b"goal_circuits", goal_circuits.goal.as_ref()], bump = goal_circuits.bump)]
+    pub goal_circuits: Account<'info, GoalCircuits>,
+}
+
+#[queue_computation_accounts("add_two_contributions_v4", payer)]
+#[derive(Accounts)]
+#[instruction(computation_offset: u64)]
+pub struct AddTwoContributions<'info> {
+    /// A goal member
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        init_if_needed,
+        space = 9,
+        payer = payer,
+        seeds = [&SIGN_PDA_SEED],
+        bump,
+        address = derive_sign_pda!(),
+    )]
+    pub sign_pda_account: Account<'info, SignerAccount>,
+    #[account(address = derive_mxe_pda!())]
+    pub mxe_account: Account<'info, MXEAccount>,
+    #[account(mut, address = derive_mempool_pda!())]
+    /// CHECK: mempool_account
+    pub mempool_account: UncheckedAccount<'info>,
+    #[account(mut, address = derive_execpool_pda!())]
+    /// CHECK: executing_pool
+    pub executing_pool: UncheckedAccount<'info>,
+    #[account(mut, address = derive_comp_pda!(computation_offset))]
+    /// CHECK: computation_account
+    pub computation_account: UncheckedAccount<'info>,
+    #[account(address = derive_comp_def_pda!(COMP_DEF_OFFSET_ADD_TWO))]
+    pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+    #[account(constraint = goal.members.contains(&payer.key()) @ ErrorCode::NotAMember)]
+    pub goal: Box<Account<'info, SavingsGoal>>,
+    #[account(seeds = [b"goal_circuits", goal.key().as_ref()], bump = goal_circuits.bump)]
+    pub goal_circuits: Account<'info, GoalCircuits>,
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + 8 + 32 + 1 + 1 + 32 + 8 + 9 + 2 + 9 + COMPUTATION_INPUTS_SPACE + 1,
+        seeds = [b"computation", &computation_offset.to_le_bytes()],
+        bump
+    )]
+    pub computation_record: Box<Account<'info, ComputationRecord>>,
+    #[account(mut, address = derive_cluster_pda!(mxe_account))]
+    pub cluster_account: Account<'info, Cluster>,
+    #[account(mut, address = ARCIUM_FEE_POOL_ACCOUNT_ADDRESS)]
+    pub pool_account: Account<'info, FeePool>,
+    #[account(address = ARCIUM_CLOCK_ACCOUNT_ADDRESS)]
+    pub clock_account: Account<'info, ClockAccount>,
+    pub system_program: Program<'info, System>,
+    pub arcium_program: Program<'info, Arcium>,
+}
+
+#[callback_accounts("add_two_contributions_v4")]
+#[derive(Accounts)]
+pub struct AddTwoContributionsV4Callback<'info> {
+    pub arcium_program: Program<'info, Arcium>,
+    #[account(address = derive_comp_def_pda!(COMP_DEF_OFFSET_ADD_TWO))]
+    pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+    #[account(address = ::anchor_lang::solana_program::sysvar::instructions::ID)]
+    /// CHECK: instructions_sysvar
+    pub instructions_sysvar: AccountInfo<'info>,
+    #[account(
+        mut,
+        seeds = [b"computation", &computation_record.computation_offset.to_le_bytes()],
+        bump = computation_record.bump
+    )]
+    pub computation_record: Box<Account<'info, ComputationRecord>>,
+}
+
+#[init_computation_definition_accounts("add_two_contributions_v4", payer)]
+#[derive(Accounts)]
+pub struct InitAddTwoCompDef<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(mut, address = derive_mxe_pda!())]
+    pub mxe_account: Box<Account<'info, MXEAccount>>,
+    #[account(mut)]
+    /// CHECK: Will be initialized
+    pub comp_def_account: UncheckedAccount<'info>,
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = config.admin == payer.key() @ ErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, MxeConfig>,
+    pub arcium_program: Program<'info, Arcium>,
+    pub system_program: Program<'info, System>,
+}
+
+#[queue_computation_accounts("check_goal_reached_v4", payer)]
+#[derive(Accounts)]
+#[instruction(computation_offset: u64)]
+pub struct CheckGoalReached<'info> {
+    /// A goal member
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        init_if_needed,
+        space = 9,
+        payer = payer,
+        seeds = [&SIGN_PDA_SEED],
+        bump,
+        address = derive_sign_pda!(),
+    )]
+    pub sign_pda_account: Account<'info, SignerAccount>,
+    #[account(address = derive_mxe_pda!())]
+    pub mxe_account: Account<'info, MXEAccount>,
+    #[account(mut, address = derive_mempool_pda!())]
+    /// CHECK: mempool_account
+    pub mempool_account: UncheckedAccount<'info>,
+    #[account(mut, address = derive_execpool_pda!())]
+    /// CHECK: executing_pool
+    pub executing_pool: UncheckedAccount<'info>,
+    #[account(mut, address = derive_comp_pda!(computation_offset))]
+    /// CHECK: computation_account
+    pub computation_account: UncheckedAccount<'info>,
+    #[account(address = derive_comp_def_pda!(COMP_DEF_OFFSET_CHECK_GOAL))]
+    pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+    #[account(constraint = goal.members.contains(&payer.key()) @ ErrorCode::NotAMember)]
+    pub goal: Box<Account<'info, SavingsGoal>>,
+    #[account(seeds = [b"goal_circuits", goal.key().as_ref()], bump = goal_circuits.bump)]
+    pub goal_circuits: Account<'info, GoalCircuits>,
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + 8 + 32 + 1 + 1 + 32 + 8 + 9 + 2 + 9 + COMPUTATION_INPUTS_SPACE + 1,
+        seeds = [b"computation", &computation_offset.to_le_bytes()],
+        bump
+    )]
+    pub computation_record: Box<Account<'info, ComputationRecord>>,
+    #[account(mut, address = derive_cluster_pda!(mxe_account))]
+    pub cluster_account: Account<'info, Cluster>,
+    #[account(mut, address = ARCIUM_FEE_POOL_ACCOUNT_ADDRESS)]
+    pub pool_account: Account<'info, FeePool>,
+    #[account(address = ARCIUM_CLOCK_ACCOUNT_ADDRESS)]
+    pub clock_account: Account<'info, ClockAccount>,
+    pub system_program: Program<'info, System>,
+    pub arcium_program: Program<'info, Arcium>,
+}
+
+#[callback_accounts("check_goal_reached_v4")]
+#[derive(Accounts)]
+pub struct CheckGoalReachedV4Callback<'info> {
+    pub arcium_program: Program<'info, Arcium>,
+    #[account(address = derive_comp_def_pda!(COMP_DEF_OFFSET_CHECK_GOAL))]
+    pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+    #[account(address = ::anchor_lang::solana_program::sysvar::instructions::ID)]
+    /// CHECK: instructions_sysvar
+    pub instructions_sysvar: AccountInfo<'info>,
+    #[account(
+        mut,
+        seeds = [b"computation", &computation_record.computation_offset.to_le_bytes()],
+        bump = computation_record.bump
+    )]
+    pub computation_record: Box<Account<'info, ComputationRecord>>,
+}
+
+#[init_computation_definition_accounts("check_goal_reached_v4", payer)]
+#[derive(Accounts)]
+pub struct InitCheckGoalCompDef<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(mut, address = derive_mxe_pda!())]
+    pub mxe_account: Box<Account<'info, MXEAccount>>,
+    #[account(mut)]
+    /// CHECK: Will be initialized
+    pub comp_def_account: UncheckedAccount<'info>,
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = config.admin == payer.key() @ ErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, MxeConfig>,
+    pub arcium_program: Program<'info, Arcium>,
+    pub system_program: Program<'info, System>,
+}
+
+#[init_computation_definition_accounts("reveal_contributions_10_v5", payer)]
+#[derive(Accounts)]
+pub struct InitReveal10CompDef<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(mut, address = derive_mxe_pda!())]
+    pub mxe_account: Box<Account<'info, MXEAccount>>,
+    #[account(mut)]
+    /// CHECK: Will be initialized
+    pub comp_def_account: UncheckedAccount<'info>,
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = config.admin == payer.key() @ ErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, MxeConfig>,
+    pub arcium_program: Program<'info, Arcium>,
+    pub system_program: Program<'info, System>,
+}
+
+#[queue_computation_accounts("add_and_check_goal_10_v1", payer)]
+#[derive(Accounts)]
+#[instruction(computation_offset: u64)]
+pub struct AddAndCheckGoal<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        init_if_needed,
+        space = 9,
+        payer = payer,
+        seeds = [&SIGN_PDA_SEED],
+        bump,
+        address = derive_sign_pda!(),
+    )]
+    pub sign_pda_account: Account<'info, SignerAccount>,
+    #[account(address = derive_mxe_pda!())]
+    pub mxe_account: Account<'info, MXEAccount>,
+    #[account(mut, address = derive_mempool_pda!())]
+    /// CHECK: mempool_account
+    pub mempool_account: UncheckedAccount<'info>,
+    #[account(mut, address = derive_execpool_pda!())]
+    /// CHECK: executing_pool
+    pub executing_pool: UncheckedAccount<'info>,
+    #[account(mut, address = derive_comp_pda!(computation_offset))]
+    /// CHECK: computation_account
+    pub computation_account: UncheckedAccount<'info>,
+    #[account(address = derive_comp_def_pda!(COMP_DEF_OFFSET_ADD_AND_CHECK))]
+    pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+    pub goal: Box<Account<'info, SavingsGoal>>,
+    #[account(seeds = [b"goal_circuits", goal.key().as_ref()], bump = goal_circuits.bump)]
+    pub goal_circuits: Account<'info, GoalCircuits>,
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + 8 + 32 + 1 + 1 + 32 + 8 + 9 + 2 + 9 + GOAL_CHECK_INPUTS_SPACE + 1,
+        seeds = [b"computation", &computation_offset.to_le_bytes()],
+        bump
+    )]
+    pub computation_record: Box<Account<'info, ComputationRecord>>,
+    #[account(mut, address = derive_cluster_pda!(mxe_account))]
+    pub cluster_account: Account<'info, Cluster>,
+    #[account(mut, address = ARCIUM_FEE_POOL_ACCOUNT_ADDRESS)]
+    pub pool_account: Account<'info, FeePool>,
+    #[account(address = ARCIUM_CLOCK_ACCOUNT_ADDRESS)]
+    pub clock_account: Account<'info, ClockAccount>,
+    pub system_program: Program<'info, System>,
+    pub arcium_program: Program<'info, Arcium>,
+}
+
+/// Accounts of `retry_computation`. `#[queue_computation_accounts]` binds an
+/// accounts struct to a single comp def, while a retry re-queues whichever
+/// circuit the failed computation ran, so `QueueCompAccs` is implemented by
+/// hand below with the comp def of `failed_record.kind`.
+#[derive(Accounts)]
+#[instruction(computation_offset: u64)]
+pub struct RetryComputation<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        init_if_needed,
+        space = 9,
+        payer = payer,
+        seeds = [&SIGN_PDA_SEED],
+        bump,
+        address = derive_sign_pda!(),
+    )]
+    pub sign_pda_account: Account<'info, SignerAccount>,
+    #[account(address = derive_mxe_pda!())]
+    pub mxe_account: Account<'info, MXEAccount>,
+    #[account(mut, address = derive_mempool_pda!())]
+    /// CHECK: mempool_account
+    pub mempool_account: UncheckedAccount<'info>,
+    #[account(mut, address = derive_execpool_pda!())]
+    /// CHECK: executing_pool
+    pub executing_pool: UncheckedAccount<'info>,
+    #[account(mut, address = derive_comp_pda!(computation_offset))]
+    /// CHECK: computation_account
+    pub computation_account: UncheckedAccount<'info>,
+    #[account(address = derive_comp_def_pda!(failed_record.kind.comp_def_offset()))]
+    pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+    #[account(seeds = [b"goal_circuits", failed_record.goal.as_ref()], bump = goal_circuits.bump)]
+    pub goal_circuits: Account<'info, GoalCircuits>,
+    #[account(
+        mut,
+        seeds = [b"computation", &failed_record.computation_offset.to_le_bytes()],
+        bump = failed_record.bump,
+        constraint = failed_record.can_retry() @ ErrorCode::ComputationNotRetryable,
+        constraint = failed_record.requester == payer.key() @ ErrorCode::NotComputationRequester
+    )]
+    pub failed_record: Box<Account<'info, ComputationRecord>>,
+    /// Sized like the failed record, since it takes the same inputs
+    #[account(
+        init,
+        payer = payer,
+        space = failed_record.to_account_info().data_len(),
+        seeds = [b"computation", &computation_offset.to_le_bytes()],
+        bump
+    )]
+    pub computation_record: Box<Account<'info, ComputationRecord>>,
+    #[account(mut, address = derive_cluster_pda!(mxe_account))]
+    pub cluster_account: Account<'info, Cluster>,
+    #[account(mut, address = ARCIUM_FEE_POOL_ACCOUNT_ADDRESS)]
+    pub pool_account: Account<'info, FeePool>,
+    #[account(address = ARCIUM_CLOCK_ACCOUNT_ADDRESS)]
+    pub clock_account: Account<'info, ClockAccount>,
+    pub system_program: Program<'info, System>,
+    pub arcium_program: Program<'info, Arcium>,
+}
+
+impl<'info> QueueCompAccs<'info> for RetryComputation<'info> {
+    fn comp_def_offset(&self) -> u32 {
+        self.failed_record.kind.comp_def_offset()
+    }
+
+    fn queue_comp_accs(&self) -> QueueComputation<'info> {
+        QueueComputation {
+            signer: self.payer.to_account_info(),
+            sign_seed: self.sign_pda_account.to_account_info(),
+            comp: self.computation_account.to_account_info(),
+            mxe: self.mxe_account.to_account_info(),
+            executing_pool: self.executing_pool.to_account_info(),
+            mempool: self.mempool_account.to_account_info(),
+            comp_def_acc: self.comp_def_account.to_account_info(),
+            cluster: self.cluster_account.to_account_info(),
+            pool_account: self.pool_account.to_account_info(),
+            system_program: self.system_program.to_account_info(),
+            clock: self.clock_account.to_account_info(),
+        }
+    }
+
+    fn arcium_program(&self) -> AccountInfo<'info> {
+        self.arcium_program.to_account_info()
+    }
+
+    fn mxe_program(&self) -> Pubkey {
+        ID
+    }
+
+    fn signer_pda_bump(&self) -> u8 {
+        self.sign_pda_account.bump
+    }
+}
+
+#[callback_accounts("add_and_check_goal_10_v1")]
+#[derive(Accounts)]
+pub struct AddAndCheckGoal10V1Callback<'info> {
+    pub arcium_program: Program<'info, Arcium>,
+    #[account(address = derive_comp_def_pda!(COMP_DEF_OFFSET_ADD_AND_CHECK))]
+    pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+    #[account(address = ::anchor_lang::solana_program::sysvar::instructions::ID)]
+    /// CHECK: instructions_sysvar
+    pub instructions_sysvar: AccountInfo<'info>,
+    #[account(
+        mut,
+        seeds = [b"computation", &computation_record.computation_offset.to_le_bytes()],
+        bump = computation_record.bump
+    )]
+    pub computation_record: Box<Account<'info, ComputationRecord>>,
+    #[account(mut, address = computation_record.goal)]
+    pub goal: Account<'info, SavingsGoal>,
+    #[account(seeds = [GOAL_AUTHORITY_SEED], bump)]
+    /// CHECK: PDA that signs `savings_goal::mark_goal_reached`
+    pub goal_authority: UncheckedAccount<'info>,
+    pub savings_goal_program: Program<'info, SavingsGoalProgram>,
+}
+
+#[init_computation_definition_accounts("add_and_check_goal_10_v1", payer)]
+#[derive(Accounts)]
+pub struct InitAddAndCheckGoalCompDef<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(mut, address = derive_mxe_pda!())]
+    pub mxe_account: Box<Account<'info, MXEAccount>>,
+    #[account(mut)]
+    /// CHECK: Will be initialized
+    pub comp_def_account: UncheckedAccount<'info>,
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = config.admin == payer.key() @ ErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, MxeConfig>,
+    pub arcium_program: Program<'info, Arcium>,
+    pub system_program: Program<'info, System>,
+}
+
+#[queue_computation_accounts("check_period_contribution_v1", payer)]
+#[derive(Accounts)]
+#[instruction(computation_offset: u64)]
+pub struct CheckPeriodContribution<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        init_if_needed,
+        space = 9,
+        payer = payer,
+        seeds = [&SIGN_PDA_SEED],
+        bump,
+        address = derive_sign_pda!(),
+    )]
+    pub sign_pda_account: Account<'info, SignerAccount>,
+    #[account(address = derive_mxe_pda!())]
+    pub mxe_account: Account<'info, MXEAccount>,
+    #[account(mut, address = derive_mempool_pda!())]
+    /// CHECK: mempool_account
+    pub mempool_account: UncheckedAccount<'info>,
+    #[account(mut, address = derive_execpool_pda!())]
+    /// CHECK: executing_pool
+    pub executing_pool: UncheckedAccount<'info>,
+    #[account(mut, address = derive_comp_pda!(computation_offset))]
+    /// CHECK: computation_account
+    pub computation_account: UncheckedAccount<'info>,
+    #[account(address = derive_comp_def_pda!(COMP_DEF_OFFSET_CHECK_PERIOD))]
+    pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+    pub goal: Account<'info, SavingsGoal>,
+    #[account(seeds = [b"goal_circuits", goal.key().as_ref()], bump = goal_circuits.bump)]
+    pub goal_circuits: Account<'info, GoalCircuits>,
+    #[account(
+        seeds = [
+            b"period",
+            goal.key().as_ref(),
+            period_contribution.member.as_ref(),
+            &period_contribution.period.to_le_bytes()
+        ],
+        seeds::program = savings_goal::ID,
+        bump = period_contribution.bump
+    )]
+    pub period_contribution: Box<Account<'info, PeriodContribution>>,
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + 8 + 32 + 1 + 1 + 32 + 8 + 9 + 2 + 9 + COMPUTATION_INPUTS_SPACE + 1,
+        seeds = [b"computation", &computation_offset.to_le_bytes()],
+        bump
+    )]
+    pub computation_record: Box<Account<'info, ComputationRecord>>,
+    #[account(mut, address = derive_cluster_pda!(mxe_account))]
+    pub cluster_account: Account<'info, Cluster>,
+    #[account(mut, address = ARCIUM_FEE_POOL_ACCOUNT_ADDRESS)]
+    pub pool_account: Account<'info, FeePool>,
+    #[account(address = ARCIUM_CLOCK_ACCOUNT_ADDRESS)]
+    pub clock_account: Account<'info, ClockAccount>,
+    pub system_program: Program<'info, System>,
+    pub arcium_program: Program<'info, Arcium>,
+}
+
+#[callback_accounts("check_period_contribution_v1")]
+#[derive(Accounts)]
+pub struct CheckPeriodContributionV1Callback<'info> {
+    pub arcium_program: Program<'info, Arcium>,
+    #[account(address = derive_comp_def_pda!(COMP_DEF_OFFSET_CHECK_PERIOD))]
+    pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+    #[account(address = ::anchor_lang::solana_program::sysvar::instructions::ID)]
+    /// CHECK: instructions_sysvar
+    pub instructions_sysvar: AccountInfo<'info>,
+    #[account(
+        mut,
+        seeds = [b"computation", &computation_record.computation_offset.to_le_bytes()],
+        bump = computation_record.bump
+    )]
+    pub computation_record: Box<Account<'info, ComputationRecord>>,
+    #[account(address = computation_record.goal)]
+    pub goal: Account<'info, SavingsGoal>,
+    #[account(
+        mut,
+        constraint = computation_record.inputs.period_contribution()
+            == Some((period_contribution.member, period_contribution.period))
+            @ ErrorCode::WrongPeriodContribution,
+        constraint = period_contribution.goal == goal.key() @ ErrorCode::WrongPeriodContribution
+    )]
+    pub period_contribution: Account<'info, PeriodContribution>,
+    #[account(seeds = [GOAL_AUTHORITY_SEED], bump)]
+    /// CHECK: PDA that signs `savings_goal::record_period_check`
+    pub goal_authority: UncheckedAccount<'info>,
+    pub savings_goal_program: Program<'info, SavingsGoalProgram>,
+}
+
+#[init_computation_definition_accounts("check_period_contribution_v1", payer)]
+#[derive(Accounts)]
+pub struct InitCheckPeriodCompDef<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(mut, address = derive_mxe_pda!())]
+    pub mxe_account: Box<Account<'info, MXEAccount>>,
+    #[account(mut)]
+    /// CHECK: Will be initialized
+    pub comp_def_account: UncheckedAccount<'info>,
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = config.admin == payer.key() @ ErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, MxeConfig>,
+    pub arcium_program: Program<'info, Arcium>,
+    pub system_program: Program<'info, System>,
+}
+
+#[queue_computation_accounts("group_stats_10_v1", payer)]
+#[derive(Accounts)]
+#[instruction(computation_offset: u64)]
+pub struct ComputeGroupStats<'info> {
+    /// The goal owner
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        init_if_needed,
+        space = 9,
+        payer = payer,
+        seeds = [&SIGN_PDA_SEED],
+        bump,
+        address = derive_sign_pda!(),
+    )]
+    pub sign_pda_account: Account<'info, SignerAccount>,
+    #[account(address = derive_mxe_pda!())]
+    pub mxe_account: Account<'info, MXEAccount>,
+    #[account(mut, address = derive_mempool_pda!())]
+    /// CHECK: mempool_account
+    pub mempool_account: UncheckedAccount<'info>,
+    #[account(mut, address = derive_execpool_pda!())]
+    /// CHECK: executing_pool
+    pub executing_pool: UncheckedAccount<'info>,
+    #[account(mut, address = derive_comp_pda!(computation_offset))]
+    /// CHECK: computation_account
+    pub computation_account: UncheckedAccount<'info>,
+    #[account(address = derive_comp_def_pda!(COMP_DEF_OFFSET_GROUP_STATS))]
+    pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+    #[account(constraint = goal.owner == payer.key() @ ErrorCode::Unauthorized)]
+    pub goal: Account<'info, SavingsGoal>,
+    #[account(seeds = [b"goal_circuits", goal.key().as_ref()], bump = goal_circuits.bump)]
+    pub goal_circuits: Account<'info, GoalCircuits>,
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + 32 + 8 + 1 + 8 + 8 + 8 + 1 + 9 + 1,
+        seeds = [b"group_stats", goal.key().as_ref()],
+        bump
+    )]
+    pub group_stats: Box<Account<'info, GroupStats>>,
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + 8 + 32 + 1 + 1 + 32 + 8 + 9 + 2 + 9 + GROUP_STATS_INPUTS_SPACE + 1,
+        seeds = [b"computation", &computation_offset.to_le_bytes()],
+        bump
+    )]
+    pub computation_record: Box<Account<'info, ComputationRecord>>,
+    #[account(mut, address = derive_cluster_pda!(mxe_account))]
+    pub cluster_account: Account<'info, Cluster>,
+    #[account(mut, address = ARCIUM_FEE_POOL_ACCOUNT_ADDRESS)]
+    pub pool_account: Account<'info, FeePool>,
+    #[account(address = ARCIUM_CLOCK_ACCOUNT_ADDRESS)]
+    pub clock_account: Account<'info, ClockAccount>,
+    pub system_program: Program<'info, System>,
+    pub arcium_program: Program<'info, Arcium>,
+}
+
+#[callback_accounts("group_stats_10_v1")]
+#[derive(Accounts)]
+pub struct GroupStats10V1Callback<'info> {
+    pub arcium_program: Program<'info, Arcium>,
+    #[account(address = derive_comp_def_pda!(COMP_DEF_OFFSET_GROUP_STATS))]
+    pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+    #[account(address = ::anchor_lang::solana_program::sysvar::instructions::ID)]
+    /// CHECK: instructions_sysvar
+    pub instructions_sysvar: AccountInfo<'info>,
+    #[account(
+        mut,
+        seeds = [b"computation", &computation_record.computation_offset.to_le_bytes()],
+        bump = computation_record.bump
+    )]
+    pub computation_record: Box<Account<'info, ComputationRecord>>,
+    #[account(
+        mut,
+        seeds = [b"group_stats", computation_record.goal.as_ref()],
+        bump = group_stats.bump
+    )]
+    pub group_stats: Account<'info, GroupStats>,
+}
+
+#[init_computation_definition_accounts("group_stats_10_v1", payer)]
+#[derive(Accounts)]
+pub struct InitGroupStatsCompDef<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(mut, address = derive_mxe_pda!())]
+    pub mxe_account: Box<Account<'info, MXEAccount>>,
+    #[account(mut)]
+    /// CHECK: Will be initialized
+    pub comp_def_account: UncheckedAccount<'info>,
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = config.admin == payer.key() @ ErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, MxeConfig>,
+    pub arcium_program: Program<'info, Arcium>,
+    pub system_program: Program<'info, System>,
+}
+
+#[queue_computation_accounts("reveal_own_contributions_5_v1", payer)]
+#[derive(Accounts)]
+#[instruction(computation_offset: u64)]
+pub struct RevealOwnContributions<'info> {
+    /// Any goal member
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        init_if_needed,
+        space = 9,
+        payer = payer,
+        seeds = [&SIGN_PDA_SEED],
+        bump,
+        address = derive_sign_pda!(),
+    )]
+    pub sign_pda_account: Account<'info, SignerAccount>,
+    #[account(address = derive_mxe_pda!())]
+    pub mxe_account: Account<'info, MXEAccount>,
+    #[account(mut, address = derive_mempool_pda!())]
+    /// CHECK: mempool_account
+    pub mempool_account: UncheckedAccount<'info>,
+    #[account(mut, address = derive_execpool_pda!())]
+    /// CHECK: executing_pool
+    pub executing_pool: UncheckedAccount<'info>,
+    #[account(mut, address = derive_comp_pda!(computation_offset))]
+    /// CHECK: computation_account
+    pub computation_account: UncheckedAccount<'info>,
+    #[account(address = derive_comp_def_pda!(COMP_DEF_OFFSET_REVEAL_OWN))]
+    pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+    #[account(constraint = goal.members.contains(&payer.key()) @ ErrorCode::NotAMember)]
+    pub goal: Account<'info, SavingsGoal>,
+    #[account(seeds = [b"goal_circuits", goal.key().as_ref()], bump = goal_circuits.bump)]
+    pub goal_circuits: Account<'info, GoalCircuits>,
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + 8 + 32 + 1 + 1 + 32 + 8 + 9 + 2 + 9 + REVEAL_OWN_INPUTS_SPACE + 1,
+        seeds = [b"computation", &computation_offset.to_le_bytes()],
+        bump
+    )]
+    pub computation_record: Box<Account<'info, ComputationRecord>>,
+    #[account(mut, address = derive_cluster_pda!(mxe_account))]
+    pub cluster_account: Account<'info, Cluster>,
+    #[account(mut, address = ARCIUM_FEE_POOL_ACCOUNT_ADDRESS)]
+    pub pool_account: Account<'info, FeePool>,
+    #[account(address = ARCIUM_CLOCK_ACCOUNT_ADDRESS)]
+    pub clock_account: Account<'info, ClockAccount>,
+    pub system_program: Program<'info, System>,
+    pub arcium_program: Program<'info, Arcium>,
+}
+
+#[callback_accounts("reveal_own_contributions_5_v1")]
+#[derive(Accounts)]
+pub struct RevealOwnContributions5V1Callback<'info> {
+    pub arcium_program: Program<'info, Arcium>,
+    #[account(address = derive_comp_def_pda!(COMP_DEF_OFFSET_REVEAL_OWN))]
+    pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+    #[account(address = ::anchor_lang::solana_program::sysvar::instructions::ID)]
+    /// CHECK: instructions_sysvar
+    pub instructions_sysvar: AccountInfo<'info>,
+    #[account(
+        mut,
+        seeds = [b"computation", &computation_record.computation_offset.to_le_bytes()],
+        bump = computation_record.bump
+    )]
+    pub computation_record: Box<Account<'info, ComputationRecord>>,
+}
+
+#[queue_computation_accounts("reveal_contributions_10_v5", payer)]
+#[derive(Accounts)]
+#[instruction(computation_offset: u64)]
+pub struct RevealToOwner<'info> {
+    /// The goal owner
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        init_if_needed,
+        space = 9,
+        payer = payer,
+        seeds = [&SIGN_PDA_SEED],
+        bump,
+        address = derive_sign_pda!(),
+    )]
+    pub sign_pda_account: Account<'info, SignerAccount>,
+    #[account(address = derive_mxe_pda!())]
+    pub mxe_account: Account<'info, MXEAccount>,
+    #[account(mut, address = derive_mempool_pda!())]
+    /// CHECK: mempool_account
+    pub mempool_account: UncheckedAccount<'info>,
+    #[account(mut, address = derive_execpool_pda!())]
+    /// CHECK: executing_pool
+    pub executing_pool: UncheckedAccount<'info>,
+    #[account(mut, address = derive_comp_pda!(computation_offset))]
+    /// CHECK: computation_account
+    pub computation_account: UncheckedAccount<'info>,
+    #[account(address = derive_comp_def_pda!(COMP_DEF_OFFSET_REVEAL_10))]
+    pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+    #[account(constraint = goal.owner == payer.key() @ ErrorCode::Unauthorized)]
+    pub goal: Account<'info, SavingsGoal>,
+    #[account(seeds = [b"goal_circuits", goal.key().as_ref()], bump = goal_circuits.bump)]
+    pub goal_circuits: Account<'info, GoalCircuits>,
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + 8 + 32 + 1 + 1 + 32 + 8 + 9 + 2 + 9 + REVEAL_TO_OWNER_INPUTS_SPACE + 1,
+        seeds = [b"computation", &computation_offset.to_le_bytes()],
+        bump
+    )]
+    pub computation_record: Box<Account<'info, ComputationRecord>>,
+    #[account(mut, address = derive_cluster_pda!(mxe_account))]
+    pub cluster_account: Account<'info, Cluster>,
+    #[account(mut, address = ARCIUM_FEE_POOL_ACCOUNT_ADDRESS)]
+    pub pool_account: Account<'info, FeePool>,
+    #[account(address = ARCIUM_CLOCK_ACCOUNT_ADDRESS)]
+    pub clock_account: Account<'info, ClockAccount>,
+    pub system_program: Program<'info, System>,
+    pub arcium_program: Program<'info, Arcium>,
+}
+
+#[callback_accounts("reveal_contributions_10_v5")]
+#[derive(Accounts)]
+pub struct RevealContributions10V5Callback<'info> {
+    pub arcium_program: Program<'info, Arcium>,
+    #[account(address = derive_comp_def_pda!(COMP_DEF_OFFSET_REVEAL_10))]
+    pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+    #[account(address = ::anchor_lang::solana_program::sysvar::instructions::ID)]
+    /// CHECK: instructions_sysvar
+    pub instructions_sysvar: AccountInfo<'info>,
+    #[account(
+        mut,
+        seeds = [b"computation", &computation_record.computation_offset.to_le_bytes()],
+        bump = computation_record.bump
+    )]
+    pub computation_record: Box<Account<'info, ComputationRecord>>,
+}
+
+#[init_computation_definition_accounts("reveal_own_contributions_5_v1", payer)]
+#[derive(Accounts)]
+pub struct InitRevealOwnCompDef<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(mut, address = derive_mxe_pda!())]
+    pub mxe_account: Box<Account<'info, MXEAccount>>,
+    #[account(mut)]
+    /// CHECK: Will be initialized
+    pub comp_def_account: UncheckedAccount<'info>,
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = config.admin == payer.key() @ ErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, MxeConfig>,
+    pub arcium_program: Program<'info, Arcium>,
+    pub system_program: Program<'info, System>,
+}
+
+#[queue_computation_accounts("reveal_final_total_10_v1", payer)]
+#[derive(Accounts)]
+#[instruction(computation_offset: u64)]
+pub struct FinalizeAndRevealTotal<'info> {
+    /// Whoever may finalize the goal in `savings_goal`
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        init_if_needed,
+        space = 9,
+        payer = payer,
+        seeds = [&SIGN_PDA_SEED],
+        bump,
+        address = derive_sign_pda!(),
+    )]
+    pub sign_pda_account: Account<'info, SignerAccount>,
+    #[account(address = derive_mxe_pda!())]
+    pub mxe_account: Account<'info, MXEAccount>,
+    #[account(mut, address = derive_mempool_pda!())]
+    /// CHECK: mempool_account
+    pub mempool_account: UncheckedAccount<'info>,
+    #[account(mut, address = derive_execpool_pda!())]
+    /// CHECK: executing_pool
+    pub executing_pool: UncheckedAccount<'info>,
+    #[account(mut, address = derive_comp_pda!(computation_offset))]
+    /// CHECK: computation_account
+    pub computation_account: UncheckedAccount<'info>,
+    #[account(address = derive_comp_def_pda!(COMP_DEF_OFFSET_FINAL_TOTAL))]
+    pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+    #[account(mut)]
+    pub goal: Box<Account<'info, SavingsGoal>>,
+    #[account(seeds = [b"goal_circuits", goal.key().as_ref()], bump = goal_circuits.bump)]
+    pub goal_circuits: Account<'info, GoalCircuits>,
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + 8 + 32 + 1 + 1 + 32 + 8 + 9 + 2 + 9 + FINAL_TOTAL_INPUTS_SPACE + 1,
+        seeds = [b"computation", &computation_offset.to_le_bytes()],
+        bump
+    )]
+    pub computation_record: Box<Account<'info, ComputationRecord>>,
+    #[account(mut, address = derive_cluster_pda!(mxe_account))]
+    pub cluster_account: Account<'info, Cluster>,
+    #[account(mut, address = ARCIUM_FEE_POOL_ACCOUNT_ADDRESS)]
+    pub pool_account: Account<'info, FeePool>,
+    #[account(address = ARCIUM_CLOCK_ACCOUNT_ADDRESS)]
+    pub clock_account: Account<'info, ClockAccount>,
+    pub savings_goal_program: Program<'info, SavingsGoalProgram>,
+    pub system_program: Program<'info, System>,
+    pub arcium_program: Program<'info, Arcium>,
+}
+
+#[callback_accounts("reveal_final_total_10_v1")]
+#[derive(Accounts)]
+pub struct RevealFinalTotal10V1Callback<'info> {
+    pub arcium_program: Program<'info, Arcium>,
+    #[account(address = derive_comp_def_pda!(COMP_DEF_OFFSET_FINAL_TOTAL))]
+    pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+    #[account(address = ::anchor_lang::solana_program::sysvar::instructions::ID)]
+    /// CHECK: instructions_sysvar
+    pub instructions_sysvar: AccountInfo<'info>,
+    #[account(
+        mut,
+        seeds = [b"computation", &computation_record.computation_offset.to_le_bytes()],
+        bump = computation_record.bump
+    )]
+    pub computation_record: Box<Account<'info, ComputationRecord>>,
+    #[account(mut, address = computation_record.goal)]
+    pub goal: Account<'info, SavingsGoal>,
+    #[account(seeds = [GOAL_AUTHORITY_SEED], bump)]
+    /// CHECK: PDA that signs `savings_goal::record_revealed_total`
+    pub goal_authority: UncheckedAccount<'info>,
+    pub savings_goal_program: Program<'info, SavingsGoalProgram>,
+}
+
+#[init_computation_definition_accounts("reveal_final_total_10_v1", payer)]
+#[derive(Accounts)]
+pub struct InitRevealFinalTotalCompDef<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(mut, address = derive_mxe_pda!())]
+    pub mxe_account: Box<Account<'info, MXEAccount>>,
+    #[account(mut)]
+    /// CHECK: Will be initialized
+    pub comp_def_account: UncheckedAccount<'info>,
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = config.admin == payer.key() @ ErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, MxeConfig>,
+    pub arcium_program: Program<'info, Arcium>,
+    pub system_program: Program<'info, System>,
+}
+
+#[queue_computation_accounts("check_vault_balance_10_v1", payer)]
+#[derive(Accounts)]
+#[instruction(computation_offset: u64)]
+pub struct CheckVaultBalance<'info> {
+    /// A goal member
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        init_if_needed,
+        space = 9,
+        payer = payer,
+        seeds = [&SIGN_PDA_SEED],
+        bump,
+        address = derive_sign_pda!(),
+    )]
+    pub sign_pda_account: Account<'info, SignerAccount>,
+    #[account(address = derive_mxe_pda!())]
+    pub mxe_account: Account<'info, MXEAccount>,
+    #[account(mut, address = derive_mempool_pda!())]
+    /// CHECK: mempool_account
+    pub mempool_account: UncheckedAccount<'info>,
+    #[account(mut, address = derive_execpool_pda!())]
+    /// CHECK: executing_pool
+    pub executing_pool: UncheckedAccount<'info>,
+    #[account(mut, address = derive_comp_pda!(computation_offset))]
+    /// CHECK: computation_account
+    pub computation_account: UncheckedAccount<'info>,
+    #[account(address = derive_comp_def_pda!(COMP_DEF_OFFSET_VAULT_CHECK))]
+    pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+    #[account(constraint = goal.members.contains(&payer.key()) @ ErrorCode::NotAMember)]
+    pub goal: Box<Account<'info, SavingsGoal>>,
+    #[account(seeds = [b"goal_circuits", goal.key().as_ref()], bump = goal_circuits.bump)]
+    pub goal_circuits: Account<'info, GoalCircuits>,
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + 8 + 32 + 1 + 1 + 32 + 8 + 9 + 2 + 9 + VAULT_CHECK_INPUTS_SPACE + 1,
+        seeds = [b"computation", &computation_offset.to_le_bytes()],
+        bump
+    )]
+    pub computation_record: Box<Account<'info, ComputationRecord>>,
+    #[account(mut, address = derive_cluster_pda!(mxe_account))]
+    pub cluster_account: Account<'info, Cluster>,
+    #[account(mut, address = ARCIUM_FEE_POOL_ACCOUNT_ADDRESS)]
+    pub pool_account: Account<'info, FeePool>,
+    #[account(address = ARCIUM_CLOCK_ACCOUNT_ADDRESS)]
+    pub clock_account: Account<'info, ClockAccount>,
+    pub system_program: Program<'info, System>,
+    pub arcium_program: Program<'info, Arcium>,
+}
+
+#[callback_accounts("check_vault_balance_10_v1")]
+#[derive(Accounts)]
+pub struct CheckVaultBalance10V1Callback<'info> {
+    pub arcium_program: Program<'info, Arcium>,
+    #[account(address = derive_comp_def_pda!(COMP_DEF_OFFSET_VAULT_CHECK))]
+    pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+    #[account(address = ::anchor_lang::solana_program::sysvar::instructions::ID)]
+    /// CHECK: instructions_sysvar
+    pub instructions_sysvar: AccountInfo<'info>,
+    #[account(
+        mut,
+        seeds = [b"computation", &computation_record.computation_offset.to_le_bytes()],
+        bump = computation_record.bump
+    )]
+    pub computation_record: Box<Account<'info, ComputationRecord>>,
+    #[account(mut, address = computation_record.goal)]
+    pub goal: Account<'info, SavingsGoal>,
+    #[account(seeds = [GOAL_AUTHORITY_SEED], bump)]
+    /// CHECK: PDA that signs `savings_goal::record_vault_check`
+    pub goal_authority: UncheckedAccount<'info>,
+    pub savings_goal_program: Program<'info, SavingsGoalProgram>,
+}
+
+#[init_computation_definition_accounts("check_vault_balance_10_v1", payer)]
+#[derive(Accounts)]
+pub struct InitCheckVaultBalanceCompDef<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(mut, address = derive_mxe_pda!())]
+    pub mxe_account: Box<Account<'info, MXEAccount>>,
+    #[account(mut)]
+    /// CHECK: Will be initialized
+    pub comp_def_account: UncheckedAccount<'info>,
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = config.admin == payer.key() @ ErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, MxeConfig>,
+    pub arcium_program: Program<'info, Arcium>,
+    pub system_program: Program<'info, System>,
+}
+
+#[queue_computation_accounts("check_milestones_10_v1", payer)]
+#[derive(Accounts)]
+#[instruction(computation_offset: u64)]
+pub struct CheckMilestones<'info> {
+    /// A goal member
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        init_if_needed,
+        space = 9,
+        payer = payer,
+        seeds = [&SIGN_PDA_SEED],
+        bump,
+        address = derive_sign_pda!(),
+    )]
+    pub sign_pda_account: Account<'info, SignerAccount>,
+    #[account(address = derive_mxe_pda!())]
+    pub mxe_account: Account<'info, MXEAccount>,
+    #[account(mut, address = derive_mempool_pda!())]
+    /// CHECK: mempool_account
+    pub mempool_account: UncheckedAccount<'info>,
+    #[account(mut, address = derive_execpool_pda!())]
+    /// CHECK: executing_pool
+    pub executing_pool: UncheckedAccount<'info>,
+    #[account(mut, address = derive_comp_pda!(computation_offset))]
+    /// CHECK: computation_account
+    pub computation_account: UncheckedAccount<'info>,
+    #[account(address = derive_comp_def_pda!(COMP_DEF_OFFSET_MILESTONES))]
+    pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+    #[account(
+        constraint = goal.members.contains(&payer.key()) @ ErrorCode::NotAMember,
+        constraint = goal.status != GoalStatus::Finalized @ ErrorCode::GoalFinalized
+    )]
+    pub goal: Box<Account<'info, SavingsGoal>>,
+    #[account(seeds = [b"goal_circuits", goal.key().as_ref()], bump = goal_circuits.bump)]
+    pub goal_circuits: Account<'info, GoalCircuits>,
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + 8 + 32 + 1 + 1 + 32 + 8 + 9 + 2 + 9 + MILESTONES_INPUTS_SPACE + 1,
+        seeds = [b"computation", &computation_offset.to_le_bytes()],
+        bump
+    )]
+    pub computation_record: Box<Account<'info, ComputationRecord>>,
+    #[account(mut, address = derive_cluster_pda!(mxe_account))]
+    pub cluster_account: Account<'info, Cluster>,
+    #[account(mut, address = ARCIUM_FEE_POOL_ACCOUNT_ADDRESS)]
+    pub pool_account: Account<'info, FeePool>,
+    #[account(address = ARCIUM_CLOCK_ACCOUNT_ADDRESS)]
+    pub clock_account: Account<'info, ClockAccount>,
+    pub system_program: Program<'info, System>,
+    pub arcium_program: Program<'info, Arcium>,
+}
+
+#[callback_accounts("check_milestones_10_v1")]
+#[derive(Accounts)]
+pub struct CheckMilestones10V1Callback<'info> {
+    pub arcium_program: Program<'info, Arcium>,
+    #[account(address = derive_comp_def_pda!(COMP_DEF_OFFSET_MILESTONES))]
+    pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+    #[account(address = ::anchor_lang::solana_program::sysvar::instructions::ID)]
+    /// CHECK: instructions_sysvar
+    pub instructions_sysvar: AccountInfo<'info>,
+    #[account(
+        mut,
+        seeds = [b"computation", &computation_record.computation_offset.to_le_bytes()],
+        bump = computation_record.bump
+    )]
+    pub computation_record: Box<Account<'info, ComputationRecord>>,
+    #[account(mut, address = computation_record.goal)]
+    pub goal: Account<'info, SavingsGoal>,
+    #[account(seeds = [GOAL_AUTHORITY_SEED], bump)]
+    /// CHECK: PDA that signs `savings_goal::record_milestones`
+    pub goal_authority: UncheckedAccount<'info>,
+    pub savings_goal_program: Program<'info, SavingsGoalProgram>,
+}
+
+#[init_computation_definition_accounts("check_milestones_10_v1", payer)]
+#[derive(Accounts)]
+pub struct InitCheckMilestonesCompDef<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(mut, address = derive_mxe_pda!())]
+    pub mxe_account: Box<Account<'info, MXEAccount>>,
+    #[account(mut)]
+    /// CHECK: Will be initialized
+    pub comp_def_account: UncheckedAccount<'info>,
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = config.admin == payer.key() @ ErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, MxeConfig>,
+    pub arcium_program: Program<'info, Arcium>,
+    pub system_program: Program<'info, System>,
 }
 
-// Account Structs
-#[queue_computation_accounts("add_two_contributions_v4", payer)]
+#[queue_computation_accounts("distribute_yield_10_v1", payer)]
 #[derive(Accounts)]
 #[instruction(computation_offset: u64)]
-pub struct AddTwoContributions<'info> {
+pub struct DistributeYield<'info> {
+    /// A goal member
     #[account(mut)]
     pub payer: Signer<'info>,
     #[account(
@@ -153,8 +3113,32 @@ pub struct AddTwoContributions<'info> {
     #[account(mut, address = derive_comp_pda!(computation_offset))]
     /// CHECK: computation_account
     pub computation_account: UncheckedAccount<'info>,
-    #[account(address = derive_comp_def_pda!(COMP_DEF_OFFSET_ADD_TWO))]
+    #[account(address = derive_comp_def_pda!(COMP_DEF_OFFSET_YIELD))]
     pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+    #[account(
+        constraint = goal.members.contains(&payer.key()) @ ErrorCode::NotAMember,
+        constraint = goal.status == GoalStatus::Finalized @ ErrorCode::GoalNotFinalized,
+        constraint = goal.yield_deposited == 0 && goal.yield_earned > 0
+            @ ErrorCode::NoYieldToDistribute,
+        constraint = goal.yield_shares.is_empty() @ ErrorCode::YieldAlreadyDistributed,
+        constraint = goal
+            .vault_checked_at
+            .zip(goal.finalized_at)
+            .is_some_and(|(checked_at, finalized_at)| checked_at >= finalized_at)
+            @ ErrorCode::VaultNotChecked,
+        constraint = !goal.inconsistent @ ErrorCode::VaultInconsistent
+    )]
+    pub goal: Box<Account<'info, SavingsGoal>>,
+    #[account(seeds = [b"goal_circuits", goal.key().as_ref()], bump = goal_circuits.bump)]
+    pub goal_circuits: Account<'info, GoalCircuits>,
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + 8 + 32 + 1 + 1 + 32 + 8 + 9 + 2 + 9 + YIELD_INPUTS_SPACE + 1,
+        seeds = [b"computation", &computation_offset.to_le_bytes()],
+        bump
+    )]
+    pub computation_record: Box<Account<'info, ComputationRecord>>,
     #[account(mut, address = derive_cluster_pda!(mxe_account))]
     pub cluster_account: Account<'info, Cluster>,
     #[account(mut, address = ARCIUM_FEE_POOL_ACCOUNT_ADDRESS)]
@@ -165,20 +3149,32 @@ pub struct AddTwoContributions<'info> {
     pub arcium_program: Program<'info, Arcium>,
 }
 
-#[callback_accounts("add_two_contributions_v4")]
+#[callback_accounts("distribute_yield_10_v1")]
 #[derive(Accounts)]
-pub struct AddTwoContributionsV4Callback<'info> {
+pub struct DistributeYield10V1Callback<'info> {
     pub arcium_program: Program<'info, Arcium>,
-    #[account(address = derive_comp_def_pda!(COMP_DEF_OFFSET_ADD_TWO))]
+    #[account(address = derive_comp_def_pda!(COMP_DEF_OFFSET_YIELD))]
     pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
     #[account(address = ::anchor_lang::solana_program::sysvar::instructions::ID)]
     /// CHECK: instructions_sysvar
     pub instructions_sysvar: AccountInfo<'info>,
+    #[account(
+        mut,
+        seeds = [b"computation", &computation_record.computation_offset.to_le_bytes()],
+        bump = computation_record.bump
+    )]
+    pub computation_record: Box<Account<'info, ComputationRecord>>,
+    #[account(mut, address = computation_record.goal)]
+    pub goal: Account<'info, SavingsGoal>,
+    #[account(seeds = [GOAL_AUTHORITY_SEED], bump)]
+    /// CHECK: PDA that signs `savings_goal::record_yield_shares`
+    pub goal_authority: UncheckedAccount<'info>,
+    pub savings_goal_program: Program<'info, SavingsGoalProgram>,
 }
 
-#[init_computation_definition_accounts("add_two_contributions_v4", payer)]
+#[init_computation_definition_accounts("distribute_yield_10_v1", payer)]
 #[derive(Accounts)]
-pub struct InitAddTwoCompDef<'info> {
+pub struct InitDistributeYieldCompDef<'info> {
     #[account(mut)]
     pub payer: Signer<'info>,
     #[account(mut, address = derive_mxe_pda!())]
@@ -186,14 +3182,21 @@ pub struct InitAddTwoCompDef<'info> {
     #[account(mut)]
     /// CHECK: Will be initialized
     pub comp_def_account: UncheckedAccount<'info>,
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = config.admin == payer.key() @ ErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, MxeConfig>,
     pub arcium_program: Program<'info, Arcium>,
     pub system_program: Program<'info, System>,
 }
 
-#[queue_computation_accounts("check_goal_reached_v4", payer)]
+#[queue_computation_accounts("audit_contributions_10_v1", payer)]
 #[derive(Accounts)]
 #[instruction(computation_offset: u64)]
-pub struct CheckGoalReached<'info> {
+pub struct AuditContributions<'info> {
+    /// The goal owner
     #[account(mut)]
     pub payer: Signer<'info>,
     #[account(
@@ -216,8 +3219,29 @@ pub struct CheckGoalReached<'info> {
     #[account(mut, address = derive_comp_pda!(computation_offset))]
     /// CHECK: computation_account
     pub computation_account: UncheckedAccount<'info>,
-    #[account(address = derive_comp_def_pda!(COMP_DEF_OFFSET_CHECK_GOAL))]
+    #[account(address = derive_comp_def_pda!(COMP_DEF_OFFSET_AUDIT))]
     pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+    #[account(constraint = goal.owner == payer.key() @ ErrorCode::Unauthorized)]
+    pub goal: Account<'info, SavingsGoal>,
+    #[account(seeds = [b"goal_circuits", goal.key().as_ref()], bump = goal_circuits.bump)]
+    pub goal_circuits: Account<'info, GoalCircuits>,
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + 32 + 8 + (4 + 32 * MAX_AUDIT_CONTRIBUTIONS) + 32 + 16
+            + (4 + 32 * MAX_AUDIT_CONTRIBUTIONS) + 32 + 9 + 1,
+        seeds = [b"audit", goal.key().as_ref()],
+        bump
+    )]
+    pub audit: Box<Account<'info, Audit>>,
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + 8 + 32 + 1 + 1 + 32 + 8 + 9 + 2 + 9 + AUDIT_INPUTS_SPACE + 1,
+        seeds = [b"computation", &computation_offset.to_le_bytes()],
+        bump
+    )]
+    pub computation_record: Box<Account<'info, ComputationRecord>>,
     #[account(mut, address = derive_cluster_pda!(mxe_account))]
     pub cluster_account: Account<'info, Cluster>,
     #[account(mut, address = ARCIUM_FEE_POOL_ACCOUNT_ADDRESS)]
@@ -228,20 +3252,32 @@ pub struct CheckGoalReached<'info> {
     pub arcium_program: Program<'info, Arcium>,
 }
 
-#[callback_accounts("check_goal_reached_v4")]
+#[callback_accounts("audit_contributions_10_v1")]
 #[derive(Accounts)]
-pub struct CheckGoalReachedV4Callback<'info> {
+pub struct AuditContributions10V1Callback<'info> {
     pub arcium_program: Program<'info, Arcium>,
-    #[account(address = derive_comp_def_pda!(COMP_DEF_OFFSET_CHECK_GOAL))]
+    #[account(address = derive_comp_def_pda!(COMP_DEF_OFFSET_AUDIT))]
     pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
     #[account(address = ::anchor_lang::solana_program::sysvar::instructions::ID)]
     /// CHECK: instructions_sysvar
     pub instructions_sysvar: AccountInfo<'info>,
+    #[account(
+        mut,
+        seeds = [b"computation", &computation_record.computation_offset.to_le_bytes()],
+        bump = computation_record.bump
+    )]
+    pub computation_record: Box<Account<'info, ComputationRecord>>,
+    #[account(
+        mut,
+        seeds = [b"audit", computation_record.goal.as_ref()],
+        bump = audit.bump
+    )]
+    pub audit: Box<Account<'info, Audit>>,
 }
 
-#[init_computation_definition_accounts("check_goal_reached_v4", payer)]
+#[init_computation_definition_accounts("audit_contributions_10_v1", payer)]
 #[derive(Accounts)]
-pub struct InitCheckGoalCompDef<'info> {
+pub struct InitAuditCompDef<'info> {
     #[account(mut)]
     pub payer: Signer<'info>,
     #[account(mut, address = derive_mxe_pda!())]
@@ -249,27 +3285,109 @@ pub struct InitCheckGoalCompDef<'info> {
     #[account(mut)]
     /// CHECK: Will be initialized
     pub comp_def_account: UncheckedAccount<'info>,
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = config.admin == payer.key() @ ErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, MxeConfig>,
     pub arcium_program: Program<'info, Arcium>,
     pub system_program: Program<'info, System>,
 }
 
-#[init_computation_definition_accounts("reveal_contributions_5_v4", payer)]
+#[queue_computation_accounts("member_progress_v2", payer)]
 #[derive(Accounts)]
-pub struct InitReveal5CompDef<'info> {
+#[instruction(computation_offset: u64)]
+pub struct CheckMemberProgress<'info> {
+    /// The member; only their own contribution can be checked
     #[account(mut)]
     pub payer: Signer<'info>,
-    #[account(mut, address = derive_mxe_pda!())]
-    pub mxe_account: Box<Account<'info, MXEAccount>>,
-    #[account(mut)]
-    /// CHECK: Will be initialized
-    pub comp_def_account: UncheckedAccount<'info>,
-    pub arcium_program: Program<'info, Arcium>,
+    #[account(
+        init_if_needed,
+        space = 9,
+        payer = payer,
+        seeds = [&SIGN_PDA_SEED],
+        bump,
+        address = derive_sign_pda!(),
+    )]
+    pub sign_pda_account: Account<'info, SignerAccount>,
+    #[account(address = derive_mxe_pda!())]
+    pub mxe_account: Account<'info, MXEAccount>,
+    #[account(mut, address = derive_mempool_pda!())]
+    /// CHECK: mempool_account
+    pub mempool_account: UncheckedAccount<'info>,
+    #[account(mut, address = derive_execpool_pda!())]
+    /// CHECK: executing_pool
+    pub executing_pool: UncheckedAccount<'info>,
+    #[account(mut, address = derive_comp_pda!(computation_offset))]
+    /// CHECK: computation_account
+    pub computation_account: UncheckedAccount<'info>,
+    #[account(address = derive_comp_def_pda!(COMP_DEF_OFFSET_MEMBER_PROGRESS))]
+    pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+    pub goal: Account<'info, SavingsGoal>,
+    #[account(seeds = [b"goal_circuits", goal.key().as_ref()], bump = goal_circuits.bump)]
+    pub goal_circuits: Account<'info, GoalCircuits>,
+    #[account(
+        seeds = [b"contribution", goal.key().as_ref(), payer.key().as_ref()],
+        seeds::program = savings_goal::ID,
+        bump
+    )]
+    pub contribution: Account<'info, Contribution>,
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + 32 + 32 + 8 + 32 + 16 + 32 + 9 + 1,
+        seeds = [b"member_progress", goal.key().as_ref(), payer.key().as_ref()],
+        bump
+    )]
+    pub member_progress: Box<Account<'info, MemberProgress>>,
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + 8 + 32 + 1 + 1 + 32 + 8 + 9 + 2 + 9 + COMPUTATION_INPUTS_SPACE + 1,
+        seeds = [b"computation", &computation_offset.to_le_bytes()],
+        bump
+    )]
+    pub computation_record: Box<Account<'info, ComputationRecord>>,
+    #[account(mut, address = derive_cluster_pda!(mxe_account))]
+    pub cluster_account: Account<'info, Cluster>,
+    #[account(mut, address = ARCIUM_FEE_POOL_ACCOUNT_ADDRESS)]
+    pub pool_account: Account<'info, FeePool>,
+    #[account(address = ARCIUM_CLOCK_ACCOUNT_ADDRESS)]
+    pub clock_account: Account<'info, ClockAccount>,
     pub system_program: Program<'info, System>,
+    pub arcium_program: Program<'info, Arcium>,
 }
 
-#[init_computation_definition_accounts("reveal_contributions_10_v4", payer)]
+#[callback_accounts("member_progress_v2")]
 #[derive(Accounts)]
-pub struct InitReveal10CompDef<'info> {
+pub struct MemberProgressV2Callback<'info> {
+    pub arcium_program: Program<'info, Arcium>,
+    #[account(address = derive_comp_def_pda!(COMP_DEF_OFFSET_MEMBER_PROGRESS))]
+    pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+    #[account(address = ::anchor_lang::solana_program::sysvar::instructions::ID)]
+    /// CHECK: instructions_sysvar
+    pub instructions_sysvar: AccountInfo<'info>,
+    #[account(
+        mut,
+        seeds = [b"computation", &computation_record.computation_offset.to_le_bytes()],
+        bump = computation_record.bump
+    )]
+    pub computation_record: Box<Account<'info, ComputationRecord>>,
+    #[account(
+        mut,
+        seeds = [b"member_progress", member_progress.goal.as_ref(), member_progress.member.as_ref()],
+        bump = member_progress.bump,
+        constraint = member_progress.goal == computation_record.goal @ ErrorCode::WrongMemberProgress,
+        constraint = computation_record.inputs.member_progress() == Some(member_progress.member)
+            @ ErrorCode::WrongMemberProgress
+    )]
+    pub member_progress: Account<'info, MemberProgress>,
+}
+
+#[init_computation_definition_accounts("member_progress_v2", payer)]
+#[derive(Accounts)]
+pub struct InitMemberProgressCompDef<'info> {
     #[account(mut)]
     pub payer: Signer<'info>,
     #[account(mut, address = derive_mxe_pda!())]
@@ -277,21 +3395,818 @@ pub struct InitReveal10CompDef<'info> {
     #[account(mut)]
     /// CHECK: Will be initialized
     pub comp_def_account: UncheckedAccount<'info>,
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = config.admin == payer.key() @ ErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, MxeConfig>,
     pub arcium_program: Program<'info, Arcium>,
     pub system_program: Program<'info, System>,
 }
 
+// Data Structures
+#[account]
+pub struct MxeConfig {
+    pub admin: Pubkey,
+    pub pending_admin: Option<Pubkey>,
+    /// Finalize authority handed to Arcium when initializing comp defs
+    pub mxe_authority: Pubkey,
+    pub comp_defs: Vec<CompDefSettings>,
+    /// Circuit versions that `pin_goal_circuits` hands out
+    pub active_versions: Vec<CircuitVersion>,
+    pub bump: u8,
+}
+
+impl MxeConfig {
+    /// Settings for the comp def at `offset`, falling back to the defaults
+    /// used before the config account existed
+    pub fn comp_def_settings(&self, offset: u32) -> CompDefSettings {
+        self.comp_defs
+            .iter()
+            .find(|s| s.offset == offset)
+            .copied()
+            .unwrap_or(CompDefSettings {
+                offset,
+                finalize_during_callback: true,
+                cu_amount: 0,
+            })
+    }
+
+    pub fn set_version(&mut self, circuit: Circuit, version: u16) -> Result<()> {
+        match self.active_versions.iter_mut().find(|v| v.circuit == circuit) {
+            Some(existing) => existing.version = version,
+            None => {
+                require!(
+                    self.active_versions.len() < MAX_CIRCUITS,
+                    ErrorCode::TooManyCircuits
+                );
+                self.active_versions.push(CircuitVersion { circuit, version });
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Logical circuits, independent of the compiled version
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Circuit {
+    AddTwoContributions,
+    CheckGoalReached,
+    RevealContributions10,
+    AddAndCheckGoal,
+    CheckPeriodContribution,
+    MemberProgress,
+    GroupStats,
+    RevealOwnContributions,
+    AuditContributions,
+    RevealFinalTotal,
+    CheckVaultBalance,
+    CheckMilestones,
+    DistributeYield,
+}
+
+impl Circuit {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Circuit::AddTwoContributions => "add_two_contributions",
+            Circuit::CheckGoalReached => "check_goal_reached",
+            Circuit::RevealContributions10 => "reveal_contributions_10",
+            Circuit::AddAndCheckGoal => "add_and_check_goal_10",
+            Circuit::CheckPeriodContribution => "check_period_contribution",
+            Circuit::MemberProgress => "member_progress",
+            Circuit::GroupStats => "group_stats_10",
+            Circuit::RevealOwnContributions => "reveal_own_contributions_5",
+            Circuit::AuditContributions => "audit_contributions_10",
+            Circuit::RevealFinalTotal => "reveal_final_total_10",
+            Circuit::CheckVaultBalance => "check_vault_balance_10",
+            Circuit::CheckMilestones => "check_milestones_10",
+            Circuit::DistributeYield => "distribute_yield_10",
+        }
+    }
+
+    /// Name of the Arcis instruction (and comp def) for a specific version
+    pub fn ix_name(&self, version: u16) -> String {
+        format!("{}_v{}", self.name(), version)
+    }
+
+    /// The versions whose queue/callback instructions are compiled into this program
+    pub fn compiled_versions() -> Vec<CircuitVersion> {
+        vec![
+            CircuitVersion { circuit: Circuit::AddTwoContributions, version: ADD_TWO_VERSION },
+            CircuitVersion { circuit: Circuit::CheckGoalReached, version: CHECK_GOAL_VERSION },
+            CircuitVersion { circuit: Circuit::RevealContributions10, version: REVEAL_10_VERSION },
+            CircuitVersion { circuit: Circuit::AddAndCheckGoal, version: ADD_AND_CHECK_VERSION },
+            CircuitVersion { circuit: Circuit::CheckPeriodContribution, version: CHECK_PERIOD_VERSION },
+            CircuitVersion { circuit: Circuit::MemberProgress, version: MEMBER_PROGRESS_VERSION },
+            CircuitVersion { circuit: Circuit::GroupStats, version: GROUP_STATS_VERSION },
+            CircuitVersion { circuit: Circuit::RevealOwnContributions, version: REVEAL_OWN_VERSION },
+            CircuitVersion { circuit: Circuit::AuditContributions, version: AUDIT_VERSION },
+            CircuitVersion { circuit: Circuit::RevealFinalTotal, version: FINAL_TOTAL_VERSION },
+            CircuitVersion { circuit: Circuit::CheckVaultBalance, version: VAULT_CHECK_VERSION },
+            CircuitVersion { circuit: Circuit::CheckMilestones, version: MILESTONES_VERSION },
+            CircuitVersion { circuit: Circuit::DistributeYield, version: YIELD_VERSION },
+        ]
+    }
+
+    /// The version of this circuit compiled into this program
+    pub fn compiled_version(self) -> u16 {
+        Self::compiled_versions()
+            .into_iter()
+            .find(|compiled| compiled.circuit == self)
+            .map(|compiled| compiled.version)
+            .unwrap_or_default()
+    }
+
+    /// Offset of the comp def of the compiled version
+    pub fn comp_def_offset(self) -> u32 {
+        comp_def_offset(&self.ix_name(self.compiled_version()))
+    }
+
+    /// Whether `version` of this circuit is the compiled one or an earlier one
+    pub fn is_known(self, version: u16) -> bool {
+        (1..=self.compiled_version()).contains(&version)
+    }
+
+    /// Whether `version` of this circuit is the one compiled into this program
+    pub fn is_compiled(self, version: u16) -> bool {
+        Self::compiled_versions().contains(&CircuitVersion {
+            circuit: self,
+            version,
+        })
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct CircuitVersion {
+    pub circuit: Circuit,
+    pub version: u16,
+}
+
+#[account]
+pub struct RegisteredCircuitVersion {
+    pub circuit: Circuit,
+    pub version: u16,
+    pub comp_def_offset: u32,
+    pub registered_at: i64,
+    pub bump: u8,
+}
+
+/// Circuit versions a goal's computations run against
+#[account]
+pub struct GoalCircuits {
+    pub goal: Pubkey,
+    pub versions: Vec<CircuitVersion>,
+    pub pinned_at: i64,
+    pub bump: u8,
+}
+
+impl GoalCircuits {
+    pub fn version_of(&self, circuit: Circuit) -> Option<u16> {
+        self.versions
+            .iter()
+            .find(|v| v.circuit == circuit)
+            .map(|v| v.version)
+    }
+
+    pub fn uses(&self, circuit: Circuit, version: u16) -> bool {
+        self.version_of(circuit) == Some(version)
+    }
+}
+
+/// Links a queued computation to the goal it was queued for
+#[account]
+pub struct ComputationRecord {
+    pub computation_offset: u64,
+    pub goal: Pubkey,
+    pub kind: Circuit,
+    pub status: ComputationStatus,
+    pub requester: Pubkey,
+    pub queued_at: i64,
+    /// When the callback arrived, whether the computation succeeded or not
+    pub completed_at: Option<i64>,
+    pub failure: Option<FailureReason>,
+    /// Offset of the computation that re-queued these inputs after a failure
+    pub retried_by: Option<u64>,
+    pub inputs: ComputationInputs,
+    pub bump: u8,
+}
+
+impl ComputationRecord {
+    /// Mark the computation finished; called from its callback
+    pub fn complete(&mut self) -> Result<()> {
+        require!(
+            self.status == ComputationStatus::Queued,
+            ErrorCode::ComputationNotQueued
+        );
+        self.status = ComputationStatus::Completed;
+        self.completed_at = Some(Clock::get()?.unix_timestamp);
+        Ok(())
+    }
+
+    pub fn fail(&mut self, reason: FailureReason) -> Result<()> {
+        require!(
+            self.status == ComputationStatus::Queued,
+            ErrorCode::ComputationNotQueued
+        );
+        self.status = ComputationStatus::Failed;
+        self.failure = Some(reason);
+        self.completed_at = Some(Clock::get()?.unix_timestamp);
+        Ok(())
+    }
+
+    /// Failed and not retried yet
+    pub fn can_retry(&self) -> bool {
+        self.status == ComputationStatus::Failed && self.retried_by.is_none()
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ComputationStatus {
+    Queued,
+    Completed,
+    Failed,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum FailureReason {
+    /// The cluster returned no output for the computation
+    Aborted,
+}
+
+/// The circuit inputs a computation was queued with, kept so a failed
+/// computation can be retried without the client resubmitting them
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ComputationInputs {
+    AddTwoContributions {
+        ciphertext_0: [u8; 32],
+        ciphertext_1: [u8; 32],
+        pub_key: [u8; 32],
+        nonce: u128,
+    },
+    CheckGoalReached {
+        ciphertext_0: [u8; 32],
+        target: u64,
+        pub_key: [u8; 32],
+        nonce: u128,
+    },
+    /// Every member's contribution in the first `count` slots
+    AddAndCheckGoal {
+        contributions: [StoredContributionInput; MAX_GOAL_CHECK_CONTRIBUTIONS],
+        count: u8,
+        target: u64,
+    },
+    /// `member` and `period` identify the `PeriodContribution` to settle
+    CheckPeriodContribution {
+        contribution: StoredContributionInput,
+        expected: u64,
+        member: Pubkey,
+        period: u32,
+    },
+    /// The commitment is encrypted with `recipient_pubkey` under
+    /// `commitment_nonce`; the shortfall is re-encrypted to the same key
+    MemberProgress {
+        contribution: StoredContributionInput,
+        commitment: [u8; 32],
+        commitment_nonce: u128,
+        member: Pubkey,
+        recipient_pubkey: [u8; 32],
+        recipient_nonce: u128,
+    },
+    /// Only the first `count` slots are used; the rest repeat the first
+    GroupStats {
+        contributions: [StoredContributionInput; MAX_STATS_CONTRIBUTIONS],
+        count: u8,
+    },
+    /// Each of the first `count` slots goes back to its own contributor
+    RevealOwnContributions {
+        contributions: [StoredContributionInput; MAX_OWN_REVEAL_CONTRIBUTIONS],
+        count: u8,
+    },
+    /// The first `count` slots are re-encrypted to the goal owner's
+    /// `recipient_pubkey`
+    RevealToOwner {
+        contributions: [StoredContributionInput; MAX_OWNER_REVEAL_CONTRIBUTIONS],
+        count: u8,
+        recipient_pubkey: [u8; 32],
+        recipient_nonce: u128,
+    },
+    /// The first `count` slots and their total are re-encrypted to the
+    /// goal's `auditor_pubkey`; `contributors` labels the slots
+    Audit {
+        contributions: [StoredContributionInput; MAX_AUDIT_CONTRIBUTIONS],
+        contributors: [Pubkey; MAX_AUDIT_CONTRIBUTIONS],
+        count: u8,
+        auditor_pubkey: [u8; 32],
+        auditor_nonce: u128,
+    },
+    /// Every member's contribution in the first `count` slots
+    RevealFinalTotal {
+        contributions: [StoredContributionInput; MAX_FINAL_TOTAL_CONTRIBUTIONS],
+        count: u8,
+        target: u64,
+    },
+    /// Every member's contribution in the first `count` slots, and what the
+    /// goal recorded as deposited at `read_at`, when the check was first
+    /// queued
+    CheckVaultBalance {
+        contributions: [StoredContributionInput; MAX_VAULT_CHECK_CONTRIBUTIONS],
+        count: u8,
+        vault_balance: u64,
+        read_at: i64,
+    },
+    /// Every member's contribution in the first `count` slots, against the
+    /// first `milestones` thresholds
+    CheckMilestones {
+        contributions: [StoredContributionInput; MAX_MILESTONE_CHECK_CONTRIBUTIONS],
+        count: u8,
+        milestones: u8,
+        thresholds: [u64; MAX_MILESTONES],
+    },
+    /// Every member's contribution in the first `count` slots; bit `i` of
+    /// `contributors` is set if member `i` has one of them
+    DistributeYield {
+        contributions: [StoredContributionInput; MAX_YIELD_CONTRIBUTIONS],
+        count: u8,
+        contributors: u16,
+        yield_amount: u64,
+    },
+}
+
+impl ComputationInputs {
+    pub fn circuit(&self) -> Circuit {
+        match self {
+            ComputationInputs::AddTwoContributions { .. } => Circuit::AddTwoContributions,
+            ComputationInputs::CheckGoalReached { .. } => Circuit::CheckGoalReached,
+            ComputationInputs::AddAndCheckGoal { .. } => Circuit::AddAndCheckGoal,
+            ComputationInputs::CheckPeriodContribution { .. } => Circuit::CheckPeriodContribution,
+            ComputationInputs::MemberProgress { .. } => Circuit::MemberProgress,
+            ComputationInputs::GroupStats { .. } => Circuit::GroupStats,
+            ComputationInputs::RevealOwnContributions { .. } => Circuit::RevealOwnContributions,
+            ComputationInputs::RevealToOwner { .. } => Circuit::RevealContributions10,
+            ComputationInputs::Audit { .. } => Circuit::AuditContributions,
+            ComputationInputs::RevealFinalTotal { .. } => Circuit::RevealFinalTotal,
+            ComputationInputs::CheckVaultBalance { .. } => Circuit::CheckVaultBalance,
+            ComputationInputs::CheckMilestones { .. } => Circuit::CheckMilestones,
+            ComputationInputs::DistributeYield { .. } => Circuit::DistributeYield,
+        }
+    }
+
+    /// Member and period of the `PeriodContribution` a period check settles
+    pub fn period_contribution(&self) -> Option<(Pubkey, u32)> {
+        match *self {
+            ComputationInputs::CheckPeriodContribution { member, period, .. } => {
+                Some((member, period))
+            }
+            _ => None,
+        }
+    }
+
+    /// Member whose `MemberProgress` a progress check updates
+    pub fn member_progress(&self) -> Option<Pubkey> {
+        match *self {
+            ComputationInputs::MemberProgress { member, .. } => Some(member),
+            _ => None,
+        }
+    }
+
+    fn args(&self) -> Vec<Argument> {
+        match *self {
+            ComputationInputs::AddTwoContributions {
+                ciphertext_0,
+                ciphertext_1,
+                pub_key,
+                nonce,
+            } => vec![
+                Argument::ArcisPubkey(pub_key),
+                Argument::PlaintextU128(nonce),
+                Argument::EncryptedU8(ciphertext_0),
+                Argument::EncryptedU8(ciphertext_1),
+            ],
+            ComputationInputs::CheckGoalReached {
+                ciphertext_0,
+                target,
+                pub_key,
+                nonce,
+            } => vec![
+                Argument::ArcisPubkey(pub_key),
+                Argument::PlaintextU128(nonce),
+                Argument::EncryptedU8(ciphertext_0),
+                Argument::PlaintextU64(target),
+            ],
+            ComputationInputs::AddAndCheckGoal {
+                contributions,
+                count,
+                target,
+            } => {
+                let mut args: Vec<Argument> =
+                    contributions.iter().flat_map(StoredContributionInput::args).collect();
+                args.push(Argument::PlaintextU64(u64::from(count)));
+                args.push(Argument::PlaintextU64(target));
+                args
+            }
+            ComputationInputs::CheckPeriodContribution {
+                contribution,
+                expected,
+                ..
+            } => {
+                let mut args = contribution.args();
+                args.push(Argument::PlaintextU64(expected));
+                args
+            }
+            ComputationInputs::MemberProgress {
+                contribution,
+                commitment,
+                commitment_nonce,
+                recipient_pubkey,
+                recipient_nonce,
+                ..
+            } => {
+                let mut args = contribution.args();
+                args.push(Argument::ArcisPubkey(recipient_pubkey));
+                args.push(Argument::PlaintextU128(commitment_nonce));
+                args.push(Argument::EncryptedU64(commitment));
+                args.push(Argument::ArcisPubkey(recipient_pubkey));
+                args.push(Argument::PlaintextU128(recipient_nonce));
+                args
+            }
+            ComputationInputs::GroupStats {
+                contributions,
+                count,
+            } => {
+                let mut args: Vec<Argument> =
+                    contributions.iter().flat_map(StoredContributionInput::args).collect();
+                args.push(Argument::PlaintextU64(u64::from(count)));
+                args
+            }
+            ComputationInputs::RevealOwnContributions { contributions, .. } => {
+                contributions.iter().flat_map(StoredContributionInput::args).collect()
+            }
+            ComputationInputs::RevealToOwner {
+                contributions,
+                recipient_pubkey,
+                recipient_nonce,
+                ..
+            } => {
+                let mut args: Vec<Argument> =
+                    contributions.iter().flat_map(StoredContributionInput::args).collect();
+                args.push(Argument::ArcisPubkey(recipient_pubkey));
+                args.push(Argument::PlaintextU128(recipient_nonce));
+                args
+            }
+            ComputationInputs::Audit {
+                contributions,
+                count,
+                auditor_pubkey,
+                auditor_nonce,
+                ..
+            } => {
+                let mut args: Vec<Argument> =
+                    contributions.iter().flat_map(StoredContributionInput::args).collect();
+                args.push(Argument::PlaintextU64(u64::from(count)));
+                args.push(Argument::ArcisPubkey(auditor_pubkey));
+                args.push(Argument::PlaintextU128(auditor_nonce));
+                args
+            }
+            ComputationInputs::RevealFinalTotal {
+                contributions,
+                count,
+                target,
+            } => {
+                let mut args: Vec<Argument> =
+                    contributions.iter().flat_map(StoredContributionInput::args).collect();
+                args.push(Argument::PlaintextU64(u64::from(count)));
+                args.push(Argument::PlaintextU64(target));
+                args
+            }
+            ComputationInputs::CheckVaultBalance {
+                contributions,
+                count,
+                vault_balance,
+                ..
+            } => {
+                let mut args: Vec<Argument> =
+                    contributions.iter().flat_map(StoredContributionInput::args).collect();
+                args.push(Argument::PlaintextU64(u64::from(count)));
+                args.push(Argument::PlaintextU64(vault_balance));
+                args
+            }
+            ComputationInputs::CheckMilestones {
+                contributions,
+                count,
+                milestones,
+                thresholds,
+            } => {
+                let mut args: Vec<Argument> =
+                    contributions.iter().flat_map(StoredContributionInput::args).collect();
+                args.push(Argument::PlaintextU64(u64::from(count)));
+                args.push(Argument::PlaintextU64(u64::from(milestones)));
+                args.extend(thresholds.map(Argument::PlaintextU64));
+                args
+            }
+            ComputationInputs::DistributeYield {
+                contributions,
+                count,
+                yield_amount,
+                ..
+            } => {
+                let mut args: Vec<Argument> =
+                    contributions.iter().flat_map(StoredContributionInput::args).collect();
+                args.push(Argument::PlaintextU64(u64::from(count)));
+                args.push(Argument::PlaintextU64(yield_amount));
+                args
+            }
+        }
+    }
+}
+
+/// How a goal's vault balance compares with its members' declared
+/// contributions
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum VaultBalance {
+    Matches,
+    /// The vault holds more than was declared
+    Surplus,
+    /// Members declared more than they deposited
+    Shortfall,
+}
+
+impl VaultBalance {
+    /// Decode the sign revealed by `check_vault_balance_10`
+    pub fn from_sign(sign: u8) -> Self {
+        match sign {
+            0 => VaultBalance::Matches,
+            1 => VaultBalance::Surplus,
+            _ => VaultBalance::Shortfall,
+        }
+    }
+}
+
+/// Latest aggregate statistics over a goal's contributions
+#[account]
+pub struct GroupStats {
+    pub goal: Pubkey,
+    /// Computation that produced these figures
+    pub computation_offset: u64,
+    /// Number of contributions the figures cover
+    pub contributors: u8,
+    pub average: u64,
+    pub min: u64,
+    pub max: u64,
+    /// Contributions that were not zero
+    pub active: u8,
+    /// None until the first computation completes
+    pub updated_at: Option<i64>,
+    pub bump: u8,
+}
+
+/// Latest audit of a goal's contributions. Everything is encrypted to the
+/// goal's auditor key; the goal owner who queued it cannot read it either.
+#[account]
+pub struct Audit {
+    pub goal: Pubkey,
+    /// Computation that produced the stored ciphertexts
+    pub computation_offset: u64,
+    /// Contributor of each entry of `encrypted_amounts`, in the same order
+    pub contributors: Vec<Pubkey>,
+    pub encryption_key: [u8; 32],
+    pub nonce: u128,
+    /// Encrypted `u64` per audited contribution
+    pub encrypted_amounts: Vec<[u8; 32]>,
+    /// Encrypted `u64` sum of the audited contributions
+    pub encrypted_total: [u8; 32],
+    /// None until the first audit completes
+    pub updated_at: Option<i64>,
+    pub bump: u8,
+}
+
+/// A member's latest progress check. The result is encrypted to the member's
+/// own key; nobody else, the goal owner included, can read it.
+#[account]
+pub struct MemberProgress {
+    pub goal: Pubkey,
+    pub member: Pubkey,
+    /// Computation that produced the stored result
+    pub computation_offset: u64,
+    pub encryption_key: [u8; 32],
+    pub nonce: u128,
+    /// Encrypted `u64`: how much is missing to reach `commitment`, zero when
+    /// on track
+    pub encrypted_shortfall: [u8; 32],
+    /// None until the first check completes
+    pub updated_at: Option<i64>,
+    pub bump: u8,
+}
+
+/// The encrypted amount of a stored `Contribution`, as queued
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct StoredContributionInput {
+    pub encryption_pubkey: [u8; 32],
+    pub nonce: u128,
+    pub encrypted_amount: [u8; 32],
+}
+
+impl StoredContributionInput {
+    /// Arguments for one `Enc<Shared, u64>` circuit input
+    fn args(&self) -> Vec<Argument> {
+        vec![
+            Argument::ArcisPubkey(self.encryption_pubkey),
+            Argument::PlaintextU128(self.nonce),
+            Argument::EncryptedU64(self.encrypted_amount),
+        ]
+    }
+}
+
+impl From<&Contribution> for StoredContributionInput {
+    fn from(contribution: &Contribution) -> Self {
+        Self {
+            encryption_pubkey: contribution.encryption_pubkey,
+            nonce: contribution.nonce,
+            encrypted_amount: contribution.encrypted_amount,
+        }
+    }
+}
+
+impl From<&PeriodContribution> for StoredContributionInput {
+    fn from(contribution: &PeriodContribution) -> Self {
+        Self {
+            encryption_pubkey: contribution.encryption_pubkey,
+            nonce: contribution.nonce,
+            encrypted_amount: contribution.encrypted_amount,
+        }
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub struct CompDefSettings {
+    pub offset: u32,
+    pub finalize_during_callback: bool,
+    pub cu_amount: u64,
+}
+
+/// Location of a compiled circuit hosted off-chain
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct OffChainSource {
+    pub url: String,
+    /// SHA-256 of the compiled `.arcis` file served at `url`
+    pub hash: [u8; 32],
+}
+
 // Events
+#[event]
+pub struct ConfigInitialized {
+    pub admin: Pubkey,
+    pub mxe_authority: Pubkey,
+}
+
+#[event]
+pub struct AdminRotated {
+    pub previous_admin: Pubkey,
+    pub new_admin: Pubkey,
+}
+
+#[event]
+pub struct CircuitVersionRegistered {
+    pub circuit: Circuit,
+    pub version: u16,
+    pub comp_def_offset: u32,
+}
+
+#[event]
+pub struct GoalCircuitsPinned {
+    pub goal: Pubkey,
+    pub versions: Vec<CircuitVersion>,
+}
+
+#[event]
+pub struct ComputationQueued {
+    pub goal: Pubkey,
+    pub computation_offset: u64,
+    pub kind: Circuit,
+    pub requester: Pubkey,
+}
+
+#[event]
+pub struct ComputationFailed {
+    pub goal: Pubkey,
+    pub computation_offset: u64,
+    pub kind: Circuit,
+    pub reason: FailureReason,
+}
+
+#[event]
+pub struct ComputationRetried {
+    pub goal: Pubkey,
+    pub failed_offset: u64,
+    pub computation_offset: u64,
+}
+
 #[event]
 pub struct AggregationEvent {
+    pub goal: Pubkey,
+    pub computation_offset: u64,
     pub total: u64,
 }
 
 #[event]
 pub struct GoalCheckEvent {
+    pub goal: Pubkey,
+    pub computation_offset: u64,
     pub reached: bool,
 }
 
+#[event]
+pub struct PeriodCheckEvent {
+    pub goal: Pubkey,
+    pub computation_offset: u64,
+    pub member: Pubkey,
+    pub period: u32,
+    pub met: bool,
+}
+
+#[event]
+pub struct GroupStatsEvent {
+    pub goal: Pubkey,
+    pub computation_offset: u64,
+    pub contributors: u8,
+    pub average: u64,
+    pub min: u64,
+    pub max: u64,
+    pub active: u8,
+}
+
+/// A member's progress result is ready in their `MemberProgress`
+#[event]
+pub struct MemberProgressEvent {
+    pub goal: Pubkey,
+    pub computation_offset: u64,
+    pub member: Pubkey,
+}
+
+/// One contribution re-encrypted to the key it was made with; only its
+/// contributor can decrypt it
+#[event]
+pub struct ContributionRevealed {
+    pub goal: Pubkey,
+    pub computation_offset: u64,
+    pub encryption_key: [u8; 32],
+    pub nonce: u128,
+    pub encrypted_amount: [u8; 32],
+}
+
+/// Result of a goal's final total reveal: the total if it met the target,
+/// `None` otherwise
+#[event]
+pub struct FinalTotalEvent {
+    pub goal: Pubkey,
+    pub computation_offset: u64,
+    pub total: Option<u64>,
+}
+
+/// Result of a vault balance check against the deposits it was queued with
+#[event]
+pub struct VaultCheckEvent {
+    pub goal: Pubkey,
+    pub computation_offset: u64,
+    pub vault_balance: u64,
+    pub balance: VaultBalance,
+}
+
+/// Number of the goal's milestones its total has crossed
+#[event]
+pub struct MilestoneCheckEvent {
+    pub goal: Pubkey,
+    pub computation_offset: u64,
+    pub reached: u8,
+}
+
+/// Each member's share of the goal's yield, in member order
+#[event]
+pub struct YieldDistributionEvent {
+    pub goal: Pubkey,
+    pub computation_offset: u64,
+    pub shares: Vec<u64>,
+}
+
+/// A goal's `Audit` was updated for its auditor
+#[event]
+pub struct AuditEvent {
+    pub goal: Pubkey,
+    pub computation_offset: u64,
+    pub contributors: u8,
+}
+
+/// Contributions re-encrypted to the goal owner, in the order they were
+/// passed to `reveal_to_owner`
+#[event]
+pub struct OwnerRevealEvent {
+    pub goal: Pubkey,
+    pub computation_offset: u64,
+    pub encryption_key: [u8; 32],
+    pub nonce: u128,
+    pub encrypted_amounts: Vec<[u8; 32]>,
+}
+
 // Error Codes
 #[error_code]
 pub enum ErrorCode {
@@ -299,4 +4214,74 @@ pub enum ErrorCode {
     AbortedComputation,
     #[msg("Cluster not set")]
     ClusterNotSet,
+    #[msg("Only the config admin or goal owner can perform this action")]
+    Unauthorized,
+    #[msg("Signer is not the pending admin")]
+    NotPendingAdmin,
+    #[msg("Too many comp def settings entries")]
+    TooManyCompDefSettings,
+    #[msg("Circuit source URL is empty or too long (max 200 characters)")]
+    InvalidCircuitSource,
+    #[msg("Too many circuits tracked")]
+    TooManyCircuits,
+    #[msg("No version of this circuit is pinned for the goal")]
+    CircuitVersionNotSet,
+    #[msg("The goal is pinned to a different circuit version")]
+    CircuitVersionMismatch,
+    #[msg("The same contribution was passed twice")]
+    DuplicateContribution,
+    #[msg("The computation is not waiting for a result")]
+    ComputationNotQueued,
+    #[msg("Only failed computations that were not retried yet can be retried")]
+    ComputationNotRetryable,
+    #[msg("The computation ran a different circuit")]
+    WrongComputationKind,
+    #[msg("Only the account that queued the computation can retry it")]
+    NotComputationRequester,
+    #[msg("Goal is not active")]
+    GoalNotActive,
+    #[msg("Goal has no contribution schedule")]
+    NoSchedule,
+    #[msg("Period contribution is not waiting for a check")]
+    PeriodNotPending,
+    #[msg("Period contribution does not belong to this computation")]
+    WrongPeriodContribution,
+    #[msg("Member progress account does not belong to this computation")]
+    WrongMemberProgress,
+    #[msg("Group statistics need between 3 and 10 contributions")]
+    InvalidContributionCount,
+    #[msg("Account is not a contribution to this goal")]
+    WrongContribution,
+    #[msg("Reveals take 1 to 5 contributions, or 1 to 10 when revealing to the owner")]
+    InvalidRevealCount,
+    #[msg("A contributor has not opted in to revealing their amount to the owner")]
+    OwnerRevealNotAllowed,
+    #[msg("Signer is not a member of the goal")]
+    NotAMember,
+    #[msg("Goal has no auditor")]
+    NoAuditor,
+    #[msg("Audits take 1 to 10 contributions")]
+    InvalidAuditCount,
+    #[msg("Pass the contribution address of every goal member, in member order")]
+    MissingContributions,
+    #[msg("No member has contributed to the goal")]
+    NoContributions,
+    #[msg("The goal's total was already revealed")]
+    TotalAlreadyRevealed,
+    #[msg("The goal is finalized and its vault may already have paid out")]
+    GoalFinalized,
+    #[msg("Goal has no milestones")]
+    NoMilestones,
+    #[msg("Goal is not finalized yet")]
+    GoalNotFinalized,
+    #[msg("Goal has no yield in its vault to distribute")]
+    NoYieldToDistribute,
+    #[msg("The goal's yield was already distributed")]
+    YieldAlreadyDistributed,
+    #[msg("This circuit version is not compiled into the program")]
+    CircuitVersionNotCompiled,
+    #[msg("Check the vault balance after finalizing the goal first")]
+    VaultNotChecked,
+    #[msg("The last vault check found a shortfall")]
+    VaultInconsistent,
 }
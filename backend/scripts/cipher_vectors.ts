@@ -0,0 +1,62 @@
+import * as fs from "fs";
+import { RescueCipher, deserializeLE, x25519 } from "@arcium-hq/client";
+
+/**
+ * Known-answer vectors for `crates/savings-client/src/cipher.rs`.
+ *
+ * Encrypts a fixed set of plaintexts with the x25519 + `RescueCipher` of
+ * `@arcium-hq/client` under fixed keys and nonces, and writes them to
+ * `crates/savings-client/tests/vectors/cipher.json` for the Rust cipher to be
+ * checked against. Captured MXE outputs already in the file are kept.
+ *
+ *   npx ts-node scripts/cipher_vectors.ts
+ */
+
+const OUT = "crates/savings-client/tests/vectors/cipher.json";
+
+const U64_MAX = (BigInt(1) << BigInt(64)) - BigInt(1);
+
+// One block, a full block of 5 and a second counter block, with both ends
+// of the u64 range
+const PLAINTEXTS: bigint[][] = [
+  [BigInt(0)],
+  [BigInt(1)],
+  [U64_MAX],
+  [BigInt(7), BigInt(5)],
+  [1, 2, 3, 4, 5].map(BigInt),
+  [...[1, 2, 3, 4, 5, 6, 7, 8, 9, 10].map(BigInt), U64_MAX],
+];
+
+// Deterministic, distinct bytes per seed
+function bytes(len: number, seed: number): Uint8Array {
+  return Uint8Array.from({ length: len }, (_, i) => (seed * 31 + i * 7 + 1) & 0xff);
+}
+
+function hex(value: Uint8Array | number[]): string {
+  return Buffer.from(value).toString("hex");
+}
+
+const cipher = PLAINTEXTS.map((plaintext, i) => {
+  const clientSecret = bytes(32, 2 * i + 1);
+  const mxeSecret = bytes(32, 2 * i + 2);
+  const mxePublicKey = x25519.getPublicKey(mxeSecret);
+  const nonce = bytes(16, 100 + i);
+  const sharedSecret = x25519.getSharedSecret(clientSecret, mxePublicKey);
+  const ciphertexts = new RescueCipher(sharedSecret).encrypt(plaintext, nonce);
+  return {
+    client_secret: hex(clientSecret),
+    mxe_public_key: hex(mxePublicKey),
+    nonce: deserializeLE(nonce).toString(),
+    plaintext: plaintext.map(String),
+    ciphertexts: ciphertexts.map(hex),
+  };
+});
+
+const existing = fs.existsSync(OUT) ? JSON.parse(fs.readFileSync(OUT, "utf8")) : {};
+const vectors = {
+  source: "@arcium-hq/client",
+  cipher,
+  mxe_outputs: existing.mxe_outputs ?? [],
+};
+fs.writeFileSync(OUT, JSON.stringify(vectors, null, 2) + "\n");
+console.log(`Wrote ${cipher.length} vectors to ${OUT}`);
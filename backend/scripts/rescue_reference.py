@@ -0,0 +1,165 @@
+"""A second implementation of the client cipher, for known-answer vectors.
+
+x25519 comes from OpenSSL (via `cryptography`); the Rescue-Prime hash and
+the Rescue block cipher in counter mode are written out here separately
+from `src/cipher.rs`, so a slip in either shows up as a mismatch. Writes
+the same cases as `scripts/cipher_vectors.ts` to
+`crates/savings-client/tests/vectors/cipher.json`, keeping captured MXE
+outputs. Output of `cipher_vectors.ts` takes precedence where
+`@arcium-hq/client` is available.
+
+    python3 scripts/rescue_reference.py
+"""
+
+import hashlib
+import json
+import math
+import os
+
+from cryptography.hazmat.primitives import serialization
+from cryptography.hazmat.primitives.asymmetric.x25519 import X25519PrivateKey, X25519PublicKey
+
+OUT = "crates/savings-client/tests/vectors/cipher.json"
+
+P = 2**255 - 19
+SECURITY = 128
+ALPHA = next(a for a in range(3, 100) if (P - 1) % a)
+ALPHA_INV = pow(ALPHA, -1, P - 1)
+SAMPLE_BYTES = -(-(P.bit_length() + SECURITY) // 8)
+
+def sampler(seed):
+    stream = hashlib.shake_256(seed).digest(SAMPLE_BYTES * 4096)
+    pos = 0
+    def sample():
+        nonlocal pos
+        chunk = stream[pos:pos + SAMPLE_BYTES]
+        pos += SAMPLE_BYTES
+        return int.from_bytes(chunk, "little") % P
+    return sample
+
+def mds(m):
+    return [[pow(i + m + j, P - 2, P) for j in range(m)] for i in range(m)]
+
+def mat_vec(mat, v):
+    return [sum(a * b for a, b in zip(row, v)) % P for row in mat]
+
+def sbox(state, inverse):
+    e = ALPHA_INV if inverse else ALPHA
+    return [pow(x, e, P) for x in state]
+
+# Rescue-Prime hash, m = 12, capacity 5
+HM, HCAP = 12, 5
+HRATE = HM - HCAP
+
+def hash_rounds():
+    def log2_binom(n, k):
+        return sum(math.log2((n - k + i) / i) for i in range(1, int(k) + 1))
+    l1 = 1
+    while l1 < 25:
+        dcon = math.floor(0.5 * (ALPHA - 1) * HM * (l1 - 1) + 2)
+        v = HM * (l1 - 1) + HRATE
+        if 2 * log2_binom(v + dcon, v) > SECURITY:
+            break
+        l1 += 1
+    return math.ceil(1.5 * max(l1, 5))
+
+HR = hash_rounds()
+_s = sampler(f"Rescue-XLIX({P},{HM},{HCAP},{SECURITY})".encode())
+HCONST = [_s() for _ in range(2 * HM * HR)]
+HMDS = mds(HM)
+
+def rescue_prime(inputs):
+    padded = list(inputs) + [1]
+    while len(padded) % HRATE:
+        padded.append(0)
+    state = [0] * HM
+    for c in range(0, len(padded), HRATE):
+        for i in range(HRATE):
+            state[i] = (state[i] + padded[c + i]) % P
+        for r in range(HR):
+            for half in range(2):
+                state = sbox(state, half == 1)
+                off = (2 * r + half) * HM
+                state = [(x + k) % P for x, k in zip(mat_vec(HMDS, state), HCONST[off:off + HM])]
+    return state[:5]
+
+# Rescue block cipher, m = 5
+CM = 5
+def cipher_rounds():
+    l0 = math.ceil(2 * SECURITY / ((CM + 1) * (P.bit_length() - math.log2(ALPHA - 1))))
+    l1 = math.ceil((SECURITY + 3) / (5.5 * CM))
+    return 2 * max(l0, l1, 5)
+
+CR = cipher_rounds()
+CMDS = mds(CM)
+
+def round_keys(key):
+    s = sampler(b"encrypt everything, compute anything")
+    a = [[s() for _ in range(CM)] for _ in range(CM)]
+    c = [s() for _ in range(CM)]
+    b = [s() for _ in range(CM)]
+    state = [(k + x) % P for k, x in zip(key, c)]
+    keys = [state]
+    for step in range(2 * CR):
+        c = [(x + y) % P for x, y in zip(mat_vec(a, c), b)]
+        state = sbox(state, step % 2 == 1)
+        state = [(x + y) % P for x, y in zip(mat_vec(CMDS, state), c)]
+        keys.append(state)
+    return keys
+
+def encrypt_block(keys, block):
+    state = [(x + k) % P for x, k in zip(block, keys[0])]
+    for step, key in enumerate(keys[1:]):
+        state = sbox(state, step % 2 == 1)
+        state = [(x + k) % P for x, k in zip(mat_vec(CMDS, state), key)]
+    return state
+
+def encrypt(shared, values, nonce):
+    keys = round_keys(rescue_prime([int.from_bytes(shared, "little") % P]))
+    out = []
+    for blk in range(0, len(values), CM):
+        ks = encrypt_block(keys, [nonce, blk // CM, 0, 0, 0])
+        out += [(v + k) % P for v, k in zip(values[blk:blk + CM], ks)]
+    return [x.to_bytes(32, "little").hex() for x in out]
+
+# Deterministic, distinct bytes per seed, as in cipher_vectors.ts
+def seeded(n, seed):
+    return bytes((seed * 31 + i * 7 + 1) & 0xFF for i in range(n))
+
+U64_MAX = 2**64 - 1
+# One block, a full block of 5 and a second counter block, with both ends
+# of the u64 range
+PLAINTEXTS = [[0], [1], [U64_MAX], [7, 5], [1, 2, 3, 4, 5], list(range(1, 11)) + [U64_MAX]]
+
+cases = []
+for i, plaintext in enumerate(PLAINTEXTS):
+    client_secret = seeded(32, 2 * i + 1)
+    mxe_public_key = (
+        X25519PrivateKey.from_private_bytes(seeded(32, 2 * i + 2))
+        .public_key()
+        .public_bytes(serialization.Encoding.Raw, serialization.PublicFormat.Raw)
+    )
+    shared = X25519PrivateKey.from_private_bytes(client_secret).exchange(
+        X25519PublicKey.from_public_bytes(mxe_public_key)
+    )
+    nonce = int.from_bytes(seeded(16, 100 + i), "little")
+    cases.append(
+        {
+            "client_secret": client_secret.hex(),
+            "mxe_public_key": mxe_public_key.hex(),
+            "nonce": str(nonce),
+            "plaintext": [str(v) for v in plaintext],
+            "ciphertexts": encrypt(shared, plaintext, nonce),
+        }
+    )
+
+existing = json.load(open(OUT)) if os.path.exists(OUT) else {}
+vectors = {
+    "source": "scripts/rescue_reference.py",
+    "cipher": cases,
+    "mxe_outputs": existing.get("mxe_outputs", []),
+}
+with open(OUT, "w") as f:
+    json.dump(vectors, f, indent=2)
+    f.write("\n")
+print(f"Wrote {len(cases)} vectors to {OUT}")
@@ -0,0 +1,81 @@
+import * as crypto from "crypto";
+import * as fs from "fs";
+import * as http from "http";
+import * as path from "path";
+
+/**
+ * Local stand-in for off-chain circuit hosting.
+ *
+ * Serves the compiled `.arcis` files from `build/` over HTTP so comp defs can
+ * be initialized with an off-chain `CircuitSource` instead of uploading the
+ * whole circuit on-chain. Used by the tests; run directly to serve manually:
+ *
+ *   npx ts-node scripts/serve_circuits.ts [port]
+ */
+
+const BUILD_DIR = path.join(__dirname, "..", "build");
+
+export interface CircuitServer {
+  baseUrl: string;
+  close: () => Promise<void>;
+}
+
+export interface OffChainSource {
+  url: string;
+  hash: number[];
+}
+
+/** SHA-256 of a compiled circuit, as stored in the comp def's circuit source */
+export function circuitHash(circuitName: string, buildDir: string = BUILD_DIR): number[] {
+  const file = fs.readFileSync(path.join(buildDir, `${circuitName}.arcis`));
+  return Array.from(crypto.createHash("sha256").update(file).digest());
+}
+
+/** Build the `circuit_source` argument for an `init_*_comp_def` instruction */
+export function offChainSource(
+  baseUrl: string,
+  circuitName: string,
+  buildDir: string = BUILD_DIR
+): OffChainSource {
+  return {
+    url: `${baseUrl}/${circuitName}.arcis`,
+    hash: circuitHash(circuitName, buildDir),
+  };
+}
+
+export function startCircuitServer(
+  port: number = 8787,
+  buildDir: string = BUILD_DIR
+): Promise<CircuitServer> {
+  const server = http.createServer((req, res) => {
+    const name = path.basename(decodeURIComponent(req.url || ""));
+    const file = path.join(buildDir, name);
+    if (!name.endsWith(".arcis") || !fs.existsSync(file)) {
+      res.writeHead(404);
+      res.end();
+      return;
+    }
+    res.writeHead(200, { "Content-Type": "application/octet-stream" });
+    fs.createReadStream(file).pipe(res);
+  });
+
+  return new Promise((resolve) => {
+    server.listen(port, () => {
+      resolve({
+        baseUrl: `http://127.0.0.1:${port}`,
+        close: () => new Promise((done) => server.close(() => done())),
+      });
+    });
+  });
+}
+
+if (require.main === module) {
+  const port = Number(process.argv[2] || 8787);
+  startCircuitServer(port).then(({ baseUrl }) => {
+    console.log(`📦 Serving circuits from ${BUILD_DIR} at ${baseUrl}`);
+    for (const file of fs.readdirSync(BUILD_DIR).filter((f) => f.endsWith(".arcis"))) {
+      const name = file.replace(/\.arcis$/, "");
+      console.log(`  ${name}: ${Buffer.from(circuitHash(name)).toString("hex")}`);
+    }
+  });
+}
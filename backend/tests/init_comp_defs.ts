@@ -14,6 +14,7 @@ import {
 } from "@arcium-hq/client";
 import * as fs from "fs";
 import * as os from "os";
+import { offChainSource } from "../scripts/serve_circuits";
 
 // Test script to initialize computation definitions on devnet
 // and run a basic computation test
@@ -49,6 +50,30 @@ async function main() {
   console.log("🔐 MXE Account:", mxeAccount.toBase58());
   console.log();
 
+  // Initialize the program config (admin + MXE authority) once
+  const [configAccount] = PublicKey.findProgramAddressSync(
+    [Buffer.from("config")],
+    program.programId
+  );
+  if (!(await connection.getAccountInfo(configAccount))) {
+    const mxeAuthority = process.env.MXE_AUTHORITY
+      ? new PublicKey(process.env.MXE_AUTHORITY)
+      : owner.publicKey;
+    console.log("⏳ Initializing program config...");
+    console.log(`  MXE Authority: ${mxeAuthority.toBase58()}`);
+    // Only the program's upgrade authority may initialize the config
+    const [programData] = PublicKey.findProgramAddressSync(
+      [program.programId.toBuffer()],
+      new PublicKey("BPFLoaderUpgradeab1e11111111111111111111111")
+    );
+    await program.methods
+      .initializeConfig(mxeAuthority)
+      .accountsPartial({ admin: owner.publicKey, programData, config: configAccount })
+      .signers([owner])
+      .rpc({ commitment: "confirmed", skipPreflight: true });
+    console.log("✅ Program config initialized\n");
+  }
+
   // Initialize computation definitions
   try {
     await initCompDef(program, owner, "add_two_contributions_v4");
@@ -57,11 +82,38 @@ async function main() {
     await initCompDef(program, owner, "check_goal_reached_v4");
     console.log("✅ check_goal_reached_v4 initialized\n");
 
-    await initCompDef(program, owner, "reveal_contributions_5_v4");
-    console.log("✅ reveal_contributions_5_v4 initialized\n");
+    await initCompDef(program, owner, "reveal_contributions_10_v5");
+    console.log("✅ reveal_contributions_10_v5 initialized\n");
+
+    await initCompDef(program, owner, "add_and_check_goal_10_v1");
+    console.log("✅ add_and_check_goal_10_v1 initialized\n");
+
+    await initCompDef(program, owner, "check_period_contribution_v1");
+    console.log("✅ check_period_contribution_v1 initialized\n");
+
+    await initCompDef(program, owner, "member_progress_v2");
+    console.log("✅ member_progress_v2 initialized\n");
+
+    await initCompDef(program, owner, "group_stats_10_v1");
+    console.log("✅ group_stats_10_v1 initialized\n");
 
-    await initCompDef(program, owner, "reveal_contributions_10_v4");
-    console.log("✅ reveal_contributions_10_v4 initialized\n");
+    await initCompDef(program, owner, "reveal_own_contributions_5_v1");
+    console.log("✅ reveal_own_contributions_5_v1 initialized\n");
+
+    await initCompDef(program, owner, "audit_contributions_10_v1");
+    console.log("✅ audit_contributions_10_v1 initialized\n");
+
+    await initCompDef(program, owner, "reveal_final_total_10_v1");
+    console.log("✅ reveal_final_total_10_v1 initialized\n");
+
+    await initCompDef(program, owner, "check_vault_balance_10_v1");
+    console.log("✅ check_vault_balance_10_v1 initialized\n");
+
+    await initCompDef(program, owner, "check_milestones_10_v1");
+    console.log("✅ check_milestones_10_v1 initialized\n");
+
+    await initCompDef(program, owner, "distribute_yield_10_v1");
+    console.log("✅ distribute_yield_10_v1 initialized\n");
 
     console.log("\n🎉 All computation definitions initialized successfully!");
     console.log("🚀 Your Arcium MXE is ready for computations on devnet!");
@@ -74,7 +126,7 @@ async function main() {
 async function initCompDef(
   program: Program<SavingsMxe>,
   owner: anchor.web3.Keypair,
-  ixName: "add_two_contributions_v4" | "check_goal_reached_v4" | "reveal_contributions_5_v4" | "reveal_contributions_10_v4"
+  ixName: "add_two_contributions_v4" | "check_goal_reached_v4" | "reveal_contributions_5_v5" | "reveal_contributions_10_v5" | "add_and_check_goal_10_v1" | "check_period_contribution_v1" | "member_progress_v2" | "group_stats_10_v1" | "reveal_own_contributions_5_v1" | "audit_contributions_10_v1" | "reveal_final_total_10_v1" | "check_vault_balance_10_v1" | "check_milestones_10_v1" | "distribute_yield_10_v1"
 ): Promise<string> {
   // ✅ VVI Pattern: Use SDK helpers for account derivation
   const mxeAccount = getMXEAccAddress(program.programId);
@@ -88,11 +140,28 @@ async function initCompDef(
   console.log(`  MXE Account: ${mxeAccount.toBase58()}`);
   console.log(`  Comp Def Account: ${compDefAccount.toBase58()}`);
 
+  // Set CIRCUIT_BASE_URL to reference circuits hosted off-chain instead of uploading them
+  const circuitSource = process.env.CIRCUIT_BASE_URL
+    ? offChainSource(process.env.CIRCUIT_BASE_URL, ixName)
+    : null;
+  if (circuitSource) {
+    console.log(`  Circuit Source: ${circuitSource.url}`);
+  }
+
   const method =
-    ixName === "add_two_contributions_v4" ? program.methods.initAddTwoContributionsCompDef() :
-    ixName === "check_goal_reached_v4" ? program.methods.initCheckGoalReachedCompDef() :
-    ixName === "reveal_contributions_5_v4" ? program.methods.initRevealContributions5CompDef() :
-    program.methods.initRevealContributions10CompDef();
+    ixName === "add_two_contributions_v4" ? program.methods.initAddTwoContributionsCompDef(circuitSource) :
+    ixName === "check_goal_reached_v4" ? program.methods.initCheckGoalReachedCompDef(circuitSource) :
+    ixName === "add_and_check_goal_10_v1" ? program.methods.initAddAndCheckGoalCompDef(circuitSource) :
+    ixName === "check_period_contribution_v1" ? program.methods.initCheckPeriodContributionCompDef(circuitSource) :
+    ixName === "member_progress_v2" ? program.methods.initMemberProgressCompDef(circuitSource) :
+    ixName === "group_stats_10_v1" ? program.methods.initGroupStatsCompDef(circuitSource) :
+    ixName === "reveal_own_contributions_5_v1" ? program.methods.initRevealOwnContributionsCompDef(circuitSource) :
+    ixName === "audit_contributions_10_v1" ? program.methods.initAuditContributionsCompDef(circuitSource) :
+    ixName === "reveal_final_total_10_v1" ? program.methods.initRevealFinalTotalCompDef(circuitSource) :
+    ixName === "check_vault_balance_10_v1" ? program.methods.initCheckVaultBalanceCompDef(circuitSource) :
+    ixName === "check_milestones_10_v1" ? program.methods.initCheckMilestonesCompDef(circuitSource) :
+    ixName === "distribute_yield_10_v1" ? program.methods.initDistributeYieldCompDef(circuitSource) :
+    program.methods.initRevealContributions10CompDef(circuitSource);
 
   // ✅ VVI Pattern: Use .accountsPartial() for better type safety
   const sig = await method
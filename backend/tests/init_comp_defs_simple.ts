@@ -61,10 +61,12 @@ async function main() {
       continue;
     }
 
+    // Circuits are uploaded on-chain here; see scripts/serve_circuits.ts for off-chain hosting
+    const circuitSource = null;
     const method =
       ixName === "add_two_contributions_v4" 
-        ? program.methods.initAddTwoContributionsCompDef()
-        : program.methods.initCheckGoalReachedCompDef();
+        ? program.methods.initAddTwoContributionsCompDef(circuitSource)
+        : program.methods.initCheckGoalReachedCompDef(circuitSource);
 
     try {
       // Send transaction WITHOUT waiting for confirmation
@@ -3,6 +3,7 @@ import { Program } from "@coral-xyz/anchor";
 import { PublicKey, ComputeBudgetProgram } from "@solana/web3.js";
 import BN from "bn.js";
 import { SavingsMxe } from "../target/types/savings_mxe";
+import { SavingsGoal } from "../target/types/savings_goal";
 import * as fs from "fs";
 const idl = JSON.parse(fs.readFileSync("./target/idl/savings_mxe.json", "utf8"));
 import { randomBytes } from "crypto";
@@ -28,6 +29,7 @@ import {
 } from "@arcium-hq/client";
 import * as os from "os";
 import { expect } from "chai";
+import { CircuitServer, offChainSource, startCircuitServer } from "../scripts/serve_circuits";
 
 // This test exercises the current, supported MXE flows:
 // - init computation definitions for add_two_contributions & check_goal_reached
@@ -41,6 +43,7 @@ describe("MXE E2E", () => {
   
   // Use workspace to automatically get correct IDL
   const program = anchor.workspace.SavingsMxe as anchor.Program<SavingsMxe>;
+  const goalProgram = anchor.workspace.SavingsGoal as anchor.Program<SavingsGoal>;
 
   type Event = anchor.IdlEvents<(typeof program)["idl"]>;
   const awaitEvent = async <E extends keyof Event>(eventName: E): Promise<Event[E]> => {
@@ -57,6 +60,17 @@ describe("MXE E2E", () => {
   // Use localnet cluster (cluster_0 from artifacts)
   const arciumClusterPubkey = getClusterAccAddress(0);
 
+  // Set OFFCHAIN_CIRCUITS=1 to initialize comp defs against the local circuit server
+  let circuitServer: CircuitServer | undefined;
+  before(async () => {
+    if (process.env.OFFCHAIN_CIRCUITS) {
+      circuitServer = await startCircuitServer();
+    }
+  });
+  after(async () => {
+    await circuitServer?.close();
+  });
+
   it("initializes MXE and computation definitions", async () => {
     const walletPath = process.env.ANCHOR_WALLET || `${os.homedir()}/.config/solana/id.json`;
     const owner = readKpJson(walletPath);
@@ -72,10 +86,11 @@ describe("MXE E2E", () => {
       throw error;
     }
 
-    // Step 2: Initialize computation definitions (requires MXE to exist)
+    // Step 2: Initialize program config and computation definitions (requires MXE to exist)
     console.log("\n📋 Step 2: Initializing computation definitions...");
-    await initCompDef(program, owner, "add_two_contributions_v4");
-    await initCompDef(program, owner, "check_goal_reached_v4");
+    await initConfig(program, owner);
+    await initCompDef(program, owner, "add_two_contributions_v4", circuitServer?.baseUrl);
+    await initCompDef(program, owner, "check_goal_reached_v4", circuitServer?.baseUrl);
     console.log("✅ Computation definitions initialized\n");
   }).timeout(180000); // 3 minutes for Arcium MPC transactions
 
@@ -95,6 +110,10 @@ describe("MXE E2E", () => {
     const sharedSecret = x25519.getSharedSecret(privateKey, mxePublicKey);
     const cipher = new RescueCipher(sharedSecret);
 
+    // Computations run against the circuit versions pinned for a goal
+    const goalCircuits = await createGoalWithPinnedCircuits(program, goalProgram, provider);
+    const goalAddress = (await program.account.goalCircuits.fetch(goalCircuits)).goal;
+
     const v1 = BigInt(7);
     const v2 = BigInt(5);
     const nonce = randomBytes(16);
@@ -119,6 +138,9 @@ describe("MXE E2E", () => {
           Buffer.from(getCompDefAccOffset("add_two_contributions_v4" as any)).readUInt32LE(0),
         ),
         signPdaAccount: getSignPdaAddress(program.programId),
+        goal: goalAddress,
+        goalCircuits,
+        computationRecord: computationRecordPda(program.programId, computationOffset),
         poolAccount: getStakingPoolAccAddress(), // Fee pool
         clockAccount: getClockAccAddress(),
         systemProgram: anchor.web3.SystemProgram.programId,
@@ -166,6 +188,15 @@ describe("MXE E2E", () => {
     const agg = await sumEventPromise as any;
     const total = (agg.total as BN).toNumber();
     expect(total).to.equal(Number(v1 + v2));
+    expect(agg.goal.toBase58()).to.equal(goalAddress.toBase58());
+    expect((agg.computationOffset as BN).eq(computationOffset)).to.equal(true);
+
+    const record = await program.account.computationRecord.fetch(
+      computationRecordPda(program.programId, computationOffset),
+    );
+    expect(record.goal.toBase58()).to.equal(goalAddress.toBase58());
+    expect(record.status).to.have.property("completed");
+    expect(record.completedAt).to.not.equal(null);
 
     // Now run a goal check against the aggregated total (encrypt single value)
     const nonce2 = randomBytes(16);
@@ -194,6 +225,9 @@ describe("MXE E2E", () => {
             Buffer.from(getCompDefAccOffset("check_goal_reached_v4" as any)).readUInt32LE(0),
           ),
           signPdaAccount: getSignPdaAddress(program.programId),
+          goal: goalAddress,
+          goalCircuits,
+          computationRecord: computationRecordPda(program.programId, compOffset2),
           poolAccount: getStakingPoolAccAddress(),
           clockAccount: getClockAccAddress(),
           systemProgram: anchor.web3.SystemProgram.programId,
@@ -211,13 +245,74 @@ describe("MXE E2E", () => {
     await awaitComputationFinalization(provider, compOffset2, program.programId, "confirmed");
     const goal = await goalEventPromise as any;
     expect(goal.reached).to.equal(true);
+    expect((goal.computationOffset as BN).eq(compOffset2)).to.equal(true);
   });
 });
 
+function computationRecordPda(programId: PublicKey, computationOffset: BN): PublicKey {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("computation"), computationOffset.toArrayLike(Buffer, "le", 8)],
+    programId,
+  )[0];
+}
+
+async function createGoalWithPinnedCircuits(
+  program: Program<SavingsMxe>,
+  goalProgram: Program<SavingsGoal>,
+  provider: anchor.AnchorProvider,
+): Promise<PublicKey> {
+  const owner = provider.wallet.publicKey;
+  const goalId = new BN(randomBytes(8));
+  const [goal] = PublicKey.findProgramAddressSync(
+    [Buffer.from("goal"), owner.toBuffer(), goalId.toArrayLike(Buffer, "le", 8)],
+    goalProgram.programId
+  );
+  await goalProgram.methods
+    .createGoal(goalId, "mxe e2e", new BN(10), null, null, null)
+    .accountsPartial({ owner, goal })
+    .rpc({ commitment: "confirmed" });
+
+  const [goalCircuits] = PublicKey.findProgramAddressSync(
+    [Buffer.from("goal_circuits"), goal.toBuffer()],
+    program.programId
+  );
+  await program.methods
+    .pinGoalCircuits()
+    .accountsPartial({ owner, goal, goalCircuits })
+    .rpc({ commitment: "confirmed" });
+  return goalCircuits;
+}
+
+async function initConfig(
+  program: Program<SavingsMxe>,
+  owner: anchor.web3.Keypair,
+): Promise<void> {
+  const [configAccount] = PublicKey.findProgramAddressSync(
+    [Buffer.from("config")],
+    program.programId
+  );
+  if (await program.provider.connection.getAccountInfo(configAccount)) {
+    console.log("✅ Program config already initialized (skipping)");
+    return;
+  }
+  // Only the program's upgrade authority may initialize the config
+  const [programData] = PublicKey.findProgramAddressSync(
+    [program.programId.toBuffer()],
+    new PublicKey("BPFLoaderUpgradeab1e11111111111111111111111")
+  );
+  const sig = await program.methods
+    .initializeConfig(owner.publicKey)
+    .accountsPartial({ admin: owner.publicKey, programData, config: configAccount })
+    .signers([owner])
+    .rpc({ commitment: "confirmed", skipPreflight: true });
+  console.log(`✅ Initialized program config: ${sig}`);
+}
+
 async function initCompDef(
   program: Program<SavingsMxe>,
   owner: anchor.web3.Keypair,
-  ixName: "add_two_contributions_v4" | "check_goal_reached_v4" | "reveal_contributions_5_v4" | "reveal_contributions_10_v4"
+  ixName: "add_two_contributions_v4" | "check_goal_reached_v4" | "reveal_contributions_10_v5",
+  circuitBaseUrl?: string
 ): Promise<string> {
   // Use SDK helpers for account derivation (like init_comp_defs.ts)
   const mxeAccount = getMXEAccAddress(program.programId);
@@ -234,11 +329,11 @@ async function initCompDef(
     return "already-initialized";
   }
 
+  const circuitSource = circuitBaseUrl ? offChainSource(circuitBaseUrl, ixName) : null;
   const method =
-    ixName === "add_two_contributions_v4" ? program.methods.initAddTwoContributionsCompDef() :
-    ixName === "check_goal_reached_v4" ? program.methods.initCheckGoalReachedCompDef() :
-    ixName === "reveal_contributions_5_v4" ? program.methods.initRevealContributions5CompDef() :
-    program.methods.initRevealContributions10CompDef();
+    ixName === "add_two_contributions_v4" ? program.methods.initAddTwoContributionsCompDef(circuitSource) :
+    ixName === "check_goal_reached_v4" ? program.methods.initCheckGoalReachedCompDef(circuitSource) :
+    program.methods.initRevealContributions10CompDef(circuitSource);
 
   // Use .accountsPartial() pattern like init_comp_defs.ts (CRITICAL for Arcium!)
   let lastError;